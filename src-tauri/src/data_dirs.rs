@@ -0,0 +1,109 @@
+//! 用户数据目录
+//!
+//! 便携模式（exe 目录存在 `portable` 标记文件，或 exe 目录可写）下，配置、缓存和日志都放在
+//! exe 目录；安装模式（exe 目录只读，如 AppImage 或系统安装）下使用用户目录：
+//! Linux 为 XDG config / cache / state 目录，其他平台为对应的应用数据目录。
+
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+/// 强制便携模式的标记文件名（放在 exe 目录）
+pub const PORTABLE_MARKER: &str = "portable";
+
+/// 安装模式下各用户目录中的子目录名
+const APP_DIR_NAME: &str = "mxu";
+
+/// 数据目录模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataMode {
+    Portable,
+    Installed,
+}
+
+/// 各类数据的存放目录
+#[derive(Debug, Clone, Serialize)]
+pub struct DataDirs {
+    pub mode: DataMode,
+    pub exe_dir: PathBuf,
+    /// 用户配置（config）
+    pub config_dir: PathBuf,
+    /// 缓存，含下载的更新包和更新时替换下来的旧文件（cache/old）
+    pub cache_dir: PathBuf,
+    /// 运行数据根目录，MaaFramework 的 user_path 指向这里
+    pub state_dir: PathBuf,
+    /// 日志（debug），MaaFramework 也会把日志写到 state_dir/debug
+    pub logs_dir: PathBuf,
+}
+
+static DATA_DIRS: Lazy<DataDirs> = Lazy::new(detect);
+
+/// 获取数据目录（首次调用时检测模式，之后不变）
+pub fn data_dirs() -> &'static DataDirs {
+    &DATA_DIRS
+}
+
+fn exe_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// 通过创建临时文件检查目录是否可写
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".mxu-write-test-{}", std::process::id()));
+    match OpenOptions::new().write(true).create_new(true).open(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn detect() -> DataDirs {
+    let exe_dir = exe_dir();
+
+    if exe_dir.join(PORTABLE_MARKER).exists() || is_writable(&exe_dir) {
+        return portable(exe_dir);
+    }
+
+    match installed(&exe_dir) {
+        Some(dirs) => dirs,
+        // 无法确定用户目录时仍使用 exe 目录，写入失败会在各自调用处报错
+        None => portable(exe_dir),
+    }
+}
+
+fn portable(exe_dir: PathBuf) -> DataDirs {
+    DataDirs {
+        mode: DataMode::Portable,
+        config_dir: exe_dir.join("config"),
+        cache_dir: exe_dir.join("cache"),
+        state_dir: exe_dir.clone(),
+        logs_dir: exe_dir.join("debug"),
+        exe_dir,
+    }
+}
+
+fn installed(exe_dir: &Path) -> Option<DataDirs> {
+    let config_dir = dirs::config_dir()?.join(APP_DIR_NAME);
+    let cache_dir = dirs::cache_dir()?.join(APP_DIR_NAME);
+    // state_dir 只在 Linux 上有定义，其他平台使用本地应用数据目录
+    let state_dir = dirs::state_dir()
+        .or_else(dirs::data_local_dir)?
+        .join(APP_DIR_NAME);
+
+    Some(DataDirs {
+        mode: DataMode::Installed,
+        exe_dir: exe_dir.to_path_buf(),
+        config_dir,
+        cache_dir,
+        logs_dir: state_dir.join("debug"),
+        state_dir,
+    })
+}
@@ -0,0 +1,405 @@
+//! 无界面模式
+//!
+//! `mxu --headless --instance <名称或 ID> [--run]` 不创建窗口：读取 exe 目录下的
+//! interface.json 和前端保存的用户配置，按实例保存的控制器、设备、资源和任务生成启动参数。
+//! 不带 `--run` 时只输出将要执行的任务和 Pipeline 覆盖；带 `--run` 时按定时执行相同的流程
+//! 准备实例（启动前步骤、连接设备、加载资源）并启动任务，日志输出到标准输出，运行结束后退出。
+//!
+//! 退出码：运行成功 0，有任务失败 1，运行被停止 2，参数、配置或准备实例出错 3。
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use log::{error, info, warn, LevelFilter, Log, Metadata, Record};
+
+use crate::interface_validator;
+use crate::maa_commands::{destroy_instance, get_exe_dir, MaaState, StartRequest, TaskConfig};
+use crate::maa_events::{EventPhase, MaaEvent};
+use crate::maa_ffi::{add_event_observer, current_backend, MaaCallbackEvent};
+use crate::mxu_config::{MxuConfig, SavedDeviceInfo, SavedInstance};
+use crate::pipeline_override::{describe_errors, task_pipeline_override};
+use crate::pre_run::PreRunManager;
+use crate::project_interface::{self, ControllerItem, ControllerType, ProjectInterface};
+use crate::reconnect::ReconnectWatchdog;
+use crate::scheduler::{launch, ScheduleDevice};
+use crate::task_queue::RunOutcome;
+use crate::time_zone::Zone;
+
+const EXIT_SUCCEEDED: i32 = 0;
+const EXIT_FAILED: i32 = 1;
+const EXIT_STOPPED: i32 = 2;
+const EXIT_ERROR: i32 = 3;
+
+const USAGE: &str = "Usage: mxu --headless --instance <name|id> [--run] [--verbose] [--simulate]";
+
+/// 命令行是否请求无界面模式
+pub fn requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--headless")
+}
+
+/// 执行无界面模式，返回进程退出码
+pub fn run() -> i32 {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return EXIT_ERROR;
+        }
+    };
+    init_logger(if args.verbose {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Info
+    });
+
+    match execute(&args) {
+        Ok(None) => EXIT_SUCCEEDED,
+        Ok(Some(outcome)) => match outcome {
+            RunOutcome::Succeeded => EXIT_SUCCEEDED,
+            RunOutcome::Failed => EXIT_FAILED,
+            RunOutcome::Stopped => EXIT_STOPPED,
+        },
+        Err(e) => {
+            error!("{}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+struct Args {
+    instance: String,
+    run: bool,
+    verbose: bool,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut instance = None;
+        let mut run = false;
+        let mut verbose = false;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" | "--simulate" => {}
+                // 由 maafw_search 读取
+                "--maafw-dir" => {
+                    args.next().ok_or("--maafw-dir requires a value")?;
+                }
+                "--run" => run = true,
+                "--verbose" => verbose = true,
+                "--instance" => {
+                    instance = Some(args.next().ok_or("--instance requires a value")?);
+                }
+                _ if arg.starts_with("--maafw-dir=") => {}
+                _ => match arg.strip_prefix("--instance=") {
+                    Some(value) => instance = Some(value.to_string()),
+                    None => return Err(format!("Unknown argument: {}", arg)),
+                },
+            }
+        }
+        Ok(Self {
+            instance: instance
+                .filter(|instance| !instance.is_empty())
+                .ok_or("Missing --instance")?,
+            run,
+            verbose,
+        })
+    }
+}
+
+/// 标准输出日志（无界面模式没有 tauri-plugin-log）
+struct StdoutLogger;
+
+static LOGGER: StdoutLogger = StdoutLogger;
+
+impl Log for StdoutLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(
+            stdout,
+            "{} [{}] {}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            record.level(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stdout().flush();
+    }
+}
+
+fn init_logger(level: LevelFilter) {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// 由保存的配置生成的启动参数
+struct Plan {
+    instance_id: String,
+    device: Option<ScheduleDevice>,
+    resource_paths: Vec<String>,
+    request: StartRequest,
+}
+
+fn execute(args: &Args) -> Result<Option<RunOutcome>, String> {
+    let base_path = get_exe_dir()?;
+    let pi = project_interface::reload()?;
+    for issue in interface_validator::validate(&pi, Path::new(&base_path)) {
+        warn!("interface.json {}: {}", issue.path, issue.message);
+    }
+    let config = MxuConfig::load(&pi.name)?;
+    let instance = config
+        .find_instance(&args.instance)
+        .ok_or_else(|| format!("Instance not found: {}", args.instance))?;
+    let plan = build_plan(&pi, &config, instance, &base_path)?;
+
+    info!(
+        "Instance '{}' ({}): {} task(s)",
+        instance.name,
+        instance.id,
+        plan.request.tasks.len()
+    );
+    for task in &plan.request.tasks {
+        info!(
+            "  {} -> {} {}",
+            task.label.as_deref().unwrap_or(&task.entry),
+            task.entry,
+            task.pipeline_override
+        );
+    }
+    if !args.run {
+        return Ok(None);
+    }
+
+    crate::load_backend();
+    current_backend()?;
+    run_plan(instance, plan).map(Some)
+}
+
+fn build_plan(
+    pi: &ProjectInterface,
+    config: &MxuConfig,
+    instance: &SavedInstance,
+    base_path: &str,
+) -> Result<Plan, String> {
+    let controller = match &instance.controller_name {
+        Some(name) => pi.controller.iter().find(|c| &c.name == name),
+        None => pi.controller.first(),
+    }
+    .ok_or("Controller not found")?;
+    let resource = match &instance.resource_name {
+        Some(name) => pi.resource.iter().find(|r| &r.name == name),
+        None => pi.resource.first(),
+    }
+    .ok_or("Resource not found")?;
+
+    let mut tasks = Vec::new();
+    for saved in instance.tasks.iter().filter(|task| task.enabled) {
+        let Some(task) = pi.find_task(&saved.task_name) else {
+            warn!("Task not found in interface, skipped: {}", saved.task_name);
+            continue;
+        };
+        let policy = saved.run_policy.clone().unwrap_or_default();
+        // 国际化标签需要翻译文件，无界面模式直接使用任务名
+        let label = saved
+            .custom_name
+            .clone()
+            .filter(|name| !name.is_empty())
+            .or_else(|| task.label.clone().filter(|label| !label.starts_with('$')))
+            .unwrap_or_else(|| saved.task_name.clone());
+        tasks.push(TaskConfig {
+            entry: task.entry.clone(),
+            pipeline_override: task_pipeline_override(pi, &saved.task_name, &saved.option_values)
+                .map_err(|errors| {
+                format!("Task {}: {}", saved.task_name, describe_errors(&errors))
+            })?,
+            task_name: Some(saved.task_name.clone()),
+            label: Some(label),
+            source_id: Some(saved.id.clone()),
+            retry_count: policy.retry_count,
+            retry_delay_ms: policy.retry_delay_ms,
+            timeout_ms: policy.timeout_ms,
+            conditions: saved.conditions.clone(),
+        });
+    }
+    if tasks.is_empty() {
+        return Err("No enabled tasks".to_string());
+    }
+
+    let resource_paths = resource
+        .path
+        .iter()
+        .map(|path| {
+            let path = path.strip_prefix("./").unwrap_or(path);
+            let path = path.strip_prefix(".\\").unwrap_or(path);
+            format!("{}/{}", base_path, path)
+        })
+        .collect();
+
+    Ok(Plan {
+        instance_id: instance.id.clone(),
+        device: instance
+            .saved_device
+            .as_ref()
+            .and_then(|saved| saved_device(controller, saved)),
+        resource_paths,
+        request: StartRequest {
+            tasks,
+            agent_config: pi.agent.clone(),
+            cwd: base_path.to_string(),
+            tcp_compat_mode: config.settings.tcp_compat_mode,
+            run_loop: instance.run_loop.clone().unwrap_or_default(),
+            skip_completed: instance.skip_completed,
+            timezone: Zone::parse(instance.schedule_time_zone.as_deref().unwrap_or_default())?,
+        },
+    })
+}
+
+/// 由保存的设备和控制器配置生成查找设备的条件（与前端 buildScheduleDevice 一致）
+fn saved_device(controller: &ControllerItem, saved: &SavedDeviceInfo) -> Option<ScheduleDevice> {
+    match controller.controller_type {
+        ControllerType::Adb => saved
+            .adb_device_name
+            .clone()
+            .map(|name| ScheduleDevice::Adb { name }),
+        ControllerType::Win32 => saved.window_name.clone().map(|window_name| {
+            let win32 = controller.win32.clone().unwrap_or_default();
+            ScheduleDevice::Win32 {
+                window_name,
+                class_regex: win32.class_regex,
+                window_regex: win32.window_regex,
+                screencap_method: win32_screencap_method(win32.screencap.as_deref()),
+                mouse_method: win32_input_method(win32.mouse.as_deref()),
+                keyboard_method: win32_input_method(win32.keyboard.as_deref()),
+            }
+        }),
+        ControllerType::Gamepad => saved.window_name.clone().map(|window_name| {
+            let gamepad = controller.gamepad.clone().unwrap_or_default();
+            ScheduleDevice::Gamepad {
+                window_name,
+                class_regex: gamepad.class_regex,
+                window_regex: gamepad.window_regex,
+            }
+        }),
+        ControllerType::PlayCover => saved
+            .playcover_address
+            .clone()
+            .map(|address| ScheduleDevice::PlayCover { address }),
+        ControllerType::Other(_) => None,
+    }
+}
+
+/// Win32 截图方式名称对应的值，未知名称使用 FramePool
+fn win32_screencap_method(name: Option<&str>) -> u64 {
+    let bit = match name.unwrap_or_default() {
+        "GDI" => 0,
+        "DXGI_DesktopDup" => 2,
+        "DXGI_DesktopDup_Window" => 3,
+        "PrintWindow" => 4,
+        "ScreenDC" => 5,
+        _ => 1,
+    };
+    1 << bit
+}
+
+/// Win32 输入方式名称对应的值，未知名称使用 Seize
+fn win32_input_method(name: Option<&str>) -> u64 {
+    let bit = match name.unwrap_or_default() {
+        "SendMessage" => 1,
+        "PostMessage" => 2,
+        "LegacyEvent" => 3,
+        "PostThreadMessage" => 4,
+        "SendMessageWithCursorPos" => 5,
+        "PostMessageWithCursorPos" => 6,
+        _ => 0,
+    };
+    1 << bit
+}
+
+/// 准备实例并启动任务，阻塞到运行结束
+fn run_plan(instance: &SavedInstance, plan: Plan) -> Result<RunOutcome, String> {
+    let state = crate::start_state();
+    let instance_id = plan.instance_id.as_str();
+
+    let watchdog = ReconnectWatchdog::start(state.clone());
+    if let Some(policy) = &instance.reconnect_policy {
+        watchdog.set_policy(instance_id, policy.clone())?;
+    }
+    if let Some(reset) = &instance.day_reset {
+        state.queues.ledger.set_reset(instance_id, reset.clone())?;
+    }
+    let pre_run = PreRunManager::default();
+    pre_run.set_steps(instance_id, instance.pre_run.clone())?;
+
+    let (sender, receiver) = mpsc::channel();
+    state.queues.set_run_listener(sender);
+    let observer = state.clone();
+    add_event_observer(move |event| log_task_event(&observer, event));
+
+    let result = launch(
+        &state,
+        &pre_run,
+        instance_id,
+        plan.device.as_ref(),
+        &plan.resource_paths,
+        plan.request,
+    )
+    .and_then(|_| {
+        receiver
+            .iter()
+            .find(|event| event.instance_id == instance_id)
+            .ok_or_else(|| "Task queue stopped unexpectedly".to_string())
+    });
+    state.queues.ledger.flush();
+    if let Err(e) = destroy_instance(&state, instance_id) {
+        warn!("Failed to destroy instance: {}", e);
+    }
+
+    let summary = result?.summary;
+    let outcome = summary.outcome.unwrap_or(RunOutcome::Failed);
+    info!(
+        "Run {:?}: {} iteration(s), {} succeeded, {} failed, {} skipped, {} cancelled",
+        outcome,
+        summary.iterations,
+        summary.succeeded,
+        summary.failed,
+        summary.skipped,
+        summary.cancelled
+    );
+    Ok(outcome)
+}
+
+/// 输出任务开始和结束（任务名取自队列中的显示名称）
+fn log_task_event(state: &Arc<MaaState>, event: &MaaCallbackEvent) {
+    let MaaEvent::TaskerTask { phase, detail } = &event.event else {
+        return;
+    };
+    if detail.is_stop() {
+        return;
+    }
+    let label = state
+        .queues
+        .snapshot(&event.instance_id)
+        .and_then(|tasks| {
+            tasks
+                .into_iter()
+                .find(|task| task.task_id == Some(detail.task_id))
+        })
+        .and_then(|task| task.label)
+        .unwrap_or_else(|| detail.entry.clone());
+    match phase {
+        EventPhase::Starting => info!("[task] {} started", label),
+        EventPhase::Succeeded => info!("[task] {} succeeded", label),
+        EventPhase::Failed => warn!("[task] {} failed", label),
+    }
+}
@@ -0,0 +1,371 @@
+//! 实例生命周期状态机
+//!
+//! 状态由命令调用和 MaaFramework 回调共同驱动，每次变化都会向前端发送
+//! `maa-instance-state` 事件。`maa_get_instance_state` / `maa_get_all_states`
+//! 返回同一份模型，保证刷新页面后拉取的快照与推送的状态一致。
+//!
+//! 回调观察者运行在 MaaFramework 工作线程中，本模块的锁只用于短暂的内存操作，
+//! 持锁期间不得调用 MaaFramework API，否则可能与销毁 Tasker 等操作互相等待。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::maa_events::{EventPhase, MaaEvent};
+use crate::maa_ffi::{emit_event, MaaCallbackEvent};
+
+/// 失败发生的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailureStage {
+    Connection,
+    Resource,
+    Tasker,
+}
+
+/// 实例生命周期状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state")]
+pub enum LifecycleState {
+    Idle,
+    Connecting,
+    Connected,
+    LoadingResource,
+    /// 控制器已连接且资源已加载，可以运行任务
+    Ready,
+    Running,
+    /// 任务队列被用户暂停，恢复后继续执行剩余任务
+    Paused,
+    Stopping,
+    Failed {
+        stage: FailureStage,
+        reason: String,
+    },
+}
+
+/// 实例运行时状态（用于前端查询和 maa-instance-state 事件）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceState {
+    /// 生命周期状态
+    pub lifecycle: LifecycleState,
+    /// 控制器是否已连接
+    pub connected: bool,
+    /// 资源是否已加载
+    pub resource_loaded: bool,
+    /// Tasker 是否已初始化
+    pub tasker_inited: bool,
+    /// 是否有任务正在运行（包括正在停止）
+    pub is_running: bool,
+    /// 当前运行的任务 ID 列表
+    pub task_ids: Vec<i64>,
+}
+
+/// maa-instance-state 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceStateEvent {
+    pub instance_id: String,
+    /// 变化前的生命周期状态
+    pub previous: LifecycleState,
+    pub state: InstanceState,
+}
+
+#[derive(Debug, Clone, Default)]
+enum ControllerPhase {
+    #[default]
+    None,
+    Connecting,
+    Connected,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default)]
+enum ResourcePhase {
+    #[default]
+    None,
+    /// 等待加载完成的资源包数量
+    Loading(usize),
+    Loaded,
+    Failed(String),
+}
+
+/// 单个实例的生命周期记录
+#[derive(Debug, Clone, Default)]
+pub struct InstanceLifecycle {
+    controller: ControllerPhase,
+    resource: ResourcePhase,
+    tasker_inited: bool,
+    tasker_error: Option<String>,
+    /// 已提交但尚未结束的任务数
+    pending_tasks: usize,
+    /// 任务队列中尚未提交的任务数
+    queued_tasks: usize,
+    /// 任务队列被用户暂停
+    paused: bool,
+    stopping: bool,
+    task_ids: Vec<i64>,
+}
+
+impl InstanceLifecycle {
+    /// 根据各部分的状态推导生命周期状态
+    pub fn state(&self) -> LifecycleState {
+        if self.stopping {
+            return LifecycleState::Stopping;
+        }
+        if self.pending_tasks > 0 || self.queued_tasks > 0 {
+            return LifecycleState::Running;
+        }
+
+        let failed = |stage, reason: &String| LifecycleState::Failed {
+            stage,
+            reason: reason.clone(),
+        };
+        if let ControllerPhase::Failed(reason) = &self.controller {
+            return failed(FailureStage::Connection, reason);
+        }
+        if let ResourcePhase::Failed(reason) = &self.resource {
+            return failed(FailureStage::Resource, reason);
+        }
+        if let Some(reason) = &self.tasker_error {
+            return failed(FailureStage::Tasker, reason);
+        }
+
+        match (&self.controller, &self.resource) {
+            (ControllerPhase::Connecting, _) => LifecycleState::Connecting,
+            (_, ResourcePhase::Loading(_)) => LifecycleState::LoadingResource,
+            (ControllerPhase::Connected, ResourcePhase::Loaded) if self.paused => {
+                LifecycleState::Paused
+            }
+            (ControllerPhase::Connected, ResourcePhase::Loaded) => LifecycleState::Ready,
+            (ControllerPhase::Connected, _) => LifecycleState::Connected,
+            _ => LifecycleState::Idle,
+        }
+    }
+
+    pub fn snapshot(&self) -> InstanceState {
+        InstanceState {
+            lifecycle: self.state(),
+            connected: matches!(self.controller, ControllerPhase::Connected),
+            resource_loaded: matches!(self.resource, ResourcePhase::Loaded),
+            tasker_inited: self.tasker_inited,
+            is_running: self.pending_tasks > 0 || self.queued_tasks > 0 || self.stopping,
+            task_ids: self.task_ids.clone(),
+        }
+    }
+
+    /// 开始连接控制器（旧控制器会被替换）
+    pub fn connect_started(&mut self) {
+        self.controller = ControllerPhase::Connecting;
+    }
+
+    pub fn connect_failed(&mut self, reason: &str) {
+        self.controller = ControllerPhase::Failed(reason.to_string());
+    }
+
+    /// 即将提交 count 个资源包，需在提交前调用，避免回调先于记录到达
+    pub fn resource_posting(&mut self, count: usize) {
+        self.resource = if count == 0 {
+            ResourcePhase::Failed("No resource to load".to_string())
+        } else {
+            ResourcePhase::Loading(count)
+        };
+    }
+
+    /// 资源包提交完成，rejected 为提交失败的数量
+    pub fn resource_posted(&mut self, rejected: usize) {
+        if rejected == 0 {
+            return;
+        }
+        if let ResourcePhase::Loading(remaining) = self.resource {
+            self.resource = if remaining <= rejected {
+                ResourcePhase::Failed("Failed to post resource bundle".to_string())
+            } else {
+                ResourcePhase::Loading(remaining - rejected)
+            };
+        }
+    }
+
+    /// 资源被销毁（绑定该资源的 Tasker 也随之销毁）
+    pub fn resource_destroyed(&mut self) {
+        self.resource = ResourcePhase::None;
+        self.tasker_inited = false;
+        self.tasker_error = None;
+    }
+
+    pub fn tasker_ready(&mut self) {
+        self.tasker_inited = true;
+        self.tasker_error = None;
+    }
+
+    pub fn tasker_failed(&mut self, reason: &str) {
+        self.tasker_error = Some(reason.to_string());
+    }
+
+    /// 即将提交 count 个任务，需在提交前调用
+    pub fn tasks_posting(&mut self, count: usize) {
+        self.pending_tasks += count;
+        self.tasker_error = None;
+    }
+
+    /// 更新任务队列中尚未提交的任务数（由任务队列维护）
+    pub fn set_queued(&mut self, count: usize) {
+        self.queued_tasks = count;
+    }
+
+    /// 更新任务队列的暂停状态（由任务队列维护）
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// 任务提交完成，replace 为 true 时替换缓存的任务 ID 列表，否则追加
+    pub fn tasks_posted(&mut self, requested: usize, task_ids: &[i64], replace: bool) {
+        let rejected = requested.saturating_sub(task_ids.len());
+        self.pending_tasks = self.pending_tasks.saturating_sub(rejected);
+        if replace {
+            self.task_ids = task_ids.to_vec();
+        } else {
+            self.task_ids.extend_from_slice(task_ids);
+        }
+    }
+
+    /// 实例的所有对象已随 MaaFramework 重新加载销毁，回到初始状态
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn stop_requested(&mut self) {
+        self.task_ids.clear();
+        if self.pending_tasks > 0 {
+            self.stopping = true;
+        }
+    }
+
+    /// 根据回调消息更新状态
+    fn apply_event(&mut self, event: &MaaEvent) {
+        match event {
+            MaaEvent::ControllerAction { phase, detail } if detail.is_connect() => match phase {
+                EventPhase::Starting => self.controller = ControllerPhase::Connecting,
+                EventPhase::Succeeded => self.controller = ControllerPhase::Connected,
+                EventPhase::Failed => {
+                    self.connect_failed("Controller connection failed");
+                }
+            },
+            MaaEvent::ResourceLoading { phase, detail } => match phase {
+                EventPhase::Starting => {}
+                EventPhase::Succeeded => {
+                    if let ResourcePhase::Loading(remaining) = self.resource {
+                        self.resource = if remaining <= 1 {
+                            ResourcePhase::Loaded
+                        } else {
+                            ResourcePhase::Loading(remaining - 1)
+                        };
+                    }
+                }
+                EventPhase::Failed => {
+                    self.resource =
+                        ResourcePhase::Failed(format!("Failed to load resource: {}", detail.path));
+                }
+            },
+            MaaEvent::TaskerTask { phase, detail } if *phase != EventPhase::Starting => {
+                if detail.is_stop() {
+                    // 停止任务结束，之前提交的任务均已中止
+                    self.pending_tasks = 0;
+                    self.stopping = false;
+                } else {
+                    self.pending_tasks = self.pending_tasks.saturating_sub(1);
+                    if self.pending_tasks == 0 {
+                        self.stopping = false;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 所有实例的生命周期记录
+#[derive(Default)]
+pub struct LifecycleRegistry {
+    instances: Mutex<HashMap<String, InstanceLifecycle>>,
+}
+
+impl LifecycleRegistry {
+    /// 注册新实例（已存在时保持原状态）
+    pub fn insert(&self, instance_id: &str) {
+        let Ok(mut instances) = self.instances.lock() else {
+            return;
+        };
+        if instances.contains_key(instance_id) {
+            return;
+        }
+        let lifecycle = InstanceLifecycle::default();
+        emit_state(instance_id, LifecycleState::Idle, lifecycle.snapshot());
+        instances.insert(instance_id.to_string(), lifecycle);
+    }
+
+    pub fn remove(&self, instance_id: &str) {
+        if let Ok(mut instances) = self.instances.lock() {
+            instances.remove(instance_id);
+        }
+    }
+
+    /// 修改实例状态，状态有变化时发送 maa-instance-state 事件
+    pub fn update<F>(&self, instance_id: &str, f: F)
+    where
+        F: FnOnce(&mut InstanceLifecycle),
+    {
+        let Ok(mut instances) = self.instances.lock() else {
+            warn!("[lifecycle] Failed to lock lifecycle registry");
+            return;
+        };
+        let Some(lifecycle) = instances.get_mut(instance_id) else {
+            return;
+        };
+
+        let before = lifecycle.snapshot();
+        f(lifecycle);
+        let after = lifecycle.snapshot();
+
+        if before != after {
+            debug!(
+                "[lifecycle] {}: {:?} -> {:?}",
+                instance_id, before.lifecycle, after.lifecycle
+            );
+            // 持锁发送，保证事件顺序与状态变化顺序一致
+            emit_state(instance_id, before.lifecycle, after);
+        }
+    }
+
+    pub fn snapshot(&self, instance_id: &str) -> Option<InstanceState> {
+        let instances = self.instances.lock().ok()?;
+        instances.get(instance_id).map(InstanceLifecycle::snapshot)
+    }
+
+    pub fn snapshots(&self) -> HashMap<String, InstanceState> {
+        match self.instances.lock() {
+            Ok(instances) => instances
+                .iter()
+                .map(|(id, lifecycle)| (id.clone(), lifecycle.snapshot()))
+                .collect(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    /// 回调观察者入口
+    pub fn handle_event(&self, event: &MaaCallbackEvent) {
+        self.update(&event.instance_id, |lifecycle| {
+            lifecycle.apply_event(&event.event)
+        });
+    }
+}
+
+fn emit_state(instance_id: &str, previous: LifecycleState, state: InstanceState) {
+    emit_event(
+        "maa-instance-state",
+        InstanceStateEvent {
+            instance_id: instance_id.to_string(),
+            previous,
+            state,
+        },
+    );
+}
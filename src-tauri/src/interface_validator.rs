@@ -0,0 +1,463 @@
+//! interface.json 校验
+//!
+//! 检查解析后的 ProjectInterface 中前端和后端会出错或静默忽略的问题，每条结果带 JSON 路径
+//! （如 `$.task[2].option[0]`）：引用不存在的选项、控制器或资源，资源目录不存在，
+//! MXU 不支持的控制器类型，重复的名称，无效的正则，选项互相嵌套成环等。
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::project_interface::{
+    ControllerType, OptionDefinition, OptionType, PipelineType, ProjectInterface,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// 相关的任务、选项或控制器无法正常使用
+    Error,
+    /// 可以使用，但可能不是作者的本意
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    UnknownOption,
+    UnknownController,
+    UnknownResource,
+    UnknownCase,
+    MissingResourceDir,
+    MissingFile,
+    UnsupportedController,
+    UnsupportedOptionType,
+    UnsupportedPipelineType,
+    InvalidValue,
+    DuplicateName,
+    InvalidRegex,
+    InvalidSwitch,
+    EmptyOption,
+    CyclicOption,
+}
+
+/// 一条校验结果
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceIssue {
+    pub severity: IssueSeverity,
+    pub kind: IssueKind,
+    /// 问题所在的 JSON 路径
+    pub path: String,
+    pub message: String,
+}
+
+/// 校验 interface，base_dir 为 interface.json 所在目录（检查资源目录和翻译文件）
+pub fn validate(pi: &ProjectInterface, base_dir: &Path) -> Vec<InterfaceIssue> {
+    let mut validator = Validator {
+        pi,
+        base_dir,
+        issues: Vec::new(),
+    };
+    validator.check_languages();
+    validator.check_controllers();
+    validator.check_resources();
+    validator.check_tasks();
+    validator.check_options();
+    validator.issues
+}
+
+/// 对象键的 JSON 路径（非标识符的键使用 ["..."]）
+fn key_path(parent: &str, key: &str) -> String {
+    let is_ident = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_ident {
+        format!("{}.{}", parent, key)
+    } else {
+        format!("{}[{}]", parent, serde_json::Value::from(key))
+    }
+}
+
+struct Validator<'a> {
+    pi: &'a ProjectInterface,
+    base_dir: &'a Path,
+    issues: Vec<InterfaceIssue>,
+}
+
+impl<'a> Validator<'a> {
+    fn push(&mut self, severity: IssueSeverity, kind: IssueKind, path: String, message: String) {
+        self.issues.push(InterfaceIssue {
+            severity,
+            kind,
+            path,
+            message,
+        });
+    }
+
+    fn error(&mut self, kind: IssueKind, path: String, message: String) {
+        self.push(IssueSeverity::Error, kind, path, message);
+    }
+
+    fn warning(&mut self, kind: IssueKind, path: String, message: String) {
+        self.push(IssueSeverity::Warning, kind, path, message);
+    }
+
+    /// 同一列表中重复的名称（从第二次出现开始报告）
+    fn check_duplicates<'n>(&mut self, path: &str, names: impl Iterator<Item = &'n str>) {
+        let mut seen = HashSet::new();
+        for (index, name) in names.enumerate() {
+            if !seen.insert(name) {
+                self.error(
+                    IssueKind::DuplicateName,
+                    format!("{}[{}].name", path, index),
+                    format!("Duplicate name: {}", name),
+                );
+            }
+        }
+    }
+
+    fn check_regex(&mut self, path: String, regex: Option<&str>) {
+        if let Some(Err(e)) = regex.filter(|r| !r.is_empty()).map(Regex::new) {
+            self.error(
+                IssueKind::InvalidRegex,
+                path,
+                format!("Invalid regex: {}", e),
+            );
+        }
+    }
+
+    /// 名称列表中引用的选项、控制器或资源是否存在
+    fn check_refs<'n>(
+        &mut self,
+        path: &str,
+        refs: impl Iterator<Item = &'n String>,
+        known: &HashSet<&str>,
+        kind: IssueKind,
+    ) {
+        let what = match kind {
+            IssueKind::UnknownOption => "option",
+            IssueKind::UnknownController => "controller",
+            _ => "resource",
+        };
+        for (index, name) in refs.enumerate() {
+            if !known.contains(name.as_str()) {
+                self.error(
+                    kind,
+                    format!("{}[{}]", path, index),
+                    format!("Unknown {}: {}", what, name),
+                );
+            }
+        }
+    }
+
+    fn option_names(&self) -> HashSet<&'a str> {
+        self.pi.option.keys().map(String::as_str).collect()
+    }
+
+    fn check_languages(&mut self) {
+        let mut languages: Vec<(&String, &String)> = self.pi.languages.iter().collect();
+        languages.sort();
+        for (lang, file) in languages {
+            if !self.base_dir.join(file).is_file() {
+                self.warning(
+                    IssueKind::MissingFile,
+                    key_path("$.languages", lang),
+                    format!("Translation file not found: {}", file),
+                );
+            }
+        }
+    }
+
+    fn check_controllers(&mut self) {
+        let pi = self.pi;
+        self.check_duplicates(
+            "$.controller",
+            pi.controller.iter().map(|c| c.name.as_str()),
+        );
+        for (index, controller) in pi.controller.iter().enumerate() {
+            let path = format!("$.controller[{}]", index);
+            let supported_here = match &controller.controller_type {
+                ControllerType::Adb => true,
+                ControllerType::Win32 | ControllerType::Gamepad => cfg!(windows),
+                ControllerType::PlayCover => cfg!(target_os = "macos"),
+                ControllerType::Other(other) => {
+                    self.error(
+                        IssueKind::UnsupportedController,
+                        format!("{}.type", path),
+                        format!("Unsupported controller type: {}", other),
+                    );
+                    true
+                }
+            };
+            if !supported_here {
+                self.warning(
+                    IssueKind::UnsupportedController,
+                    format!("{}.type", path),
+                    format!(
+                        "{:?} controller is not available on {}",
+                        controller.controller_type,
+                        std::env::consts::OS
+                    ),
+                );
+            }
+            for (field, value) in [
+                ("display_short_side", &controller.display_short_side),
+                ("display_long_side", &controller.display_long_side),
+            ] {
+                let valid = value
+                    .as_ref()
+                    .map_or(true, |v| v.as_u64().is_some_and(|n| n <= u32::MAX as u64));
+                if !valid {
+                    self.error(
+                        IssueKind::InvalidValue,
+                        format!("{}.{}", path, field),
+                        format!("{} must be a non-negative integer", field),
+                    );
+                }
+            }
+            if let Some(win32) = &controller.win32 {
+                self.check_regex(
+                    format!("{}.win32.class_regex", path),
+                    win32.class_regex.as_deref(),
+                );
+                self.check_regex(
+                    format!("{}.win32.window_regex", path),
+                    win32.window_regex.as_deref(),
+                );
+            }
+            if let Some(gamepad) = &controller.gamepad {
+                self.check_regex(
+                    format!("{}.gamepad.class_regex", path),
+                    gamepad.class_regex.as_deref(),
+                );
+                self.check_regex(
+                    format!("{}.gamepad.window_regex", path),
+                    gamepad.window_regex.as_deref(),
+                );
+            }
+        }
+    }
+
+    fn check_resources(&mut self) {
+        let pi = self.pi;
+        let controllers: HashSet<&str> = pi.controller.iter().map(|c| c.name.as_str()).collect();
+        let options = self.option_names();
+        self.check_duplicates("$.resource", pi.resource.iter().map(|r| r.name.as_str()));
+        for (index, resource) in pi.resource.iter().enumerate() {
+            let path = format!("$.resource[{}]", index);
+            if resource.path.is_empty() {
+                self.error(
+                    IssueKind::MissingResourceDir,
+                    format!("{}.path", path),
+                    "Resource has no path".to_string(),
+                );
+            }
+            for (i, dir) in resource.path.iter().enumerate() {
+                if !self.base_dir.join(dir).is_dir() {
+                    self.error(
+                        IssueKind::MissingResourceDir,
+                        format!("{}.path[{}]", path, i),
+                        format!("Resource directory not found: {}", dir),
+                    );
+                }
+            }
+            if let Some(refs) = &resource.controller {
+                self.check_refs(
+                    &format!("{}.controller", path),
+                    refs.iter(),
+                    &controllers,
+                    IssueKind::UnknownController,
+                );
+            }
+            self.check_refs(
+                &format!("{}.option", path),
+                resource.option.iter(),
+                &options,
+                IssueKind::UnknownOption,
+            );
+        }
+    }
+
+    fn check_tasks(&mut self) {
+        let pi = self.pi;
+        let controllers: HashSet<&str> = pi.controller.iter().map(|c| c.name.as_str()).collect();
+        let resources: HashSet<&str> = pi.resource.iter().map(|r| r.name.as_str()).collect();
+        let options = self.option_names();
+        self.check_duplicates("$.task", pi.task.iter().map(|t| t.name.as_str()));
+        for (index, task) in pi.task.iter().enumerate() {
+            let path = format!("$.task[{}]", index);
+            if let Some(refs) = &task.controller {
+                self.check_refs(
+                    &format!("{}.controller", path),
+                    refs.iter(),
+                    &controllers,
+                    IssueKind::UnknownController,
+                );
+            }
+            if let Some(refs) = &task.resource {
+                self.check_refs(
+                    &format!("{}.resource", path),
+                    refs.iter(),
+                    &resources,
+                    IssueKind::UnknownResource,
+                );
+            }
+            self.check_refs(
+                &format!("{}.option", path),
+                task.option.iter(),
+                &options,
+                IssueKind::UnknownOption,
+            );
+        }
+    }
+
+    fn check_options(&mut self) {
+        let pi = self.pi;
+        let options = self.option_names();
+        let mut keys: Vec<&String> = pi.option.keys().collect();
+        keys.sort();
+        for key in keys {
+            let option = &pi.option[key];
+            let path = key_path("$.option", key);
+            match option.option_type {
+                OptionType::Select | OptionType::Switch => self.check_cases(&path, option),
+                OptionType::Input => {
+                    if option.inputs.is_empty() {
+                        self.error(
+                            IssueKind::EmptyOption,
+                            format!("{}.inputs", path),
+                            "Input option has no inputs".to_string(),
+                        );
+                    }
+                    self.check_duplicates(
+                        &format!("{}.inputs", path),
+                        option.inputs.iter().map(|i| i.name.as_str()),
+                    );
+                    for (index, input) in option.inputs.iter().enumerate() {
+                        let input_path = format!("{}.inputs[{}]", path, index);
+                        self.check_regex(format!("{}.verify", input_path), input.verify.as_deref());
+                        if input.pipeline_type == PipelineType::Unknown {
+                            self.warning(
+                                IssueKind::UnsupportedPipelineType,
+                                format!("{}.pipeline_type", input_path),
+                                "Unsupported pipeline_type, treated as string".to_string(),
+                            );
+                        }
+                    }
+                }
+                OptionType::Unknown => self.error(
+                    IssueKind::UnsupportedOptionType,
+                    format!("{}.type", path),
+                    "Unsupported option type".to_string(),
+                ),
+            }
+            for (index, case) in option.cases.iter().enumerate() {
+                self.check_refs(
+                    &format!("{}.cases[{}].option", path, index),
+                    case.option.iter(),
+                    &options,
+                    IssueKind::UnknownOption,
+                );
+            }
+        }
+        self.check_cycles();
+    }
+
+    fn check_cases(&mut self, path: &str, option: &OptionDefinition) {
+        if option.cases.is_empty() {
+            self.error(
+                IssueKind::EmptyOption,
+                format!("{}.cases", path),
+                "Option has no cases".to_string(),
+            );
+            return;
+        }
+        self.check_duplicates(
+            &format!("{}.cases", path),
+            option.cases.iter().map(|c| c.name.as_str()),
+        );
+        if let Some(default_case) = &option.default_case {
+            if !option.cases.iter().any(|c| &c.name == default_case) {
+                self.error(
+                    IssueKind::UnknownCase,
+                    format!("{}.default_case", path),
+                    format!("Unknown case: {}", default_case),
+                );
+            }
+        }
+        if option.option_type == OptionType::Switch {
+            // 与前端 findSwitchCase 一致：开关按 Yes / No 等名称查找 case
+            let has = |names: [&str; 4]| {
+                option
+                    .cases
+                    .iter()
+                    .any(|c| names.contains(&c.name.as_str()))
+            };
+            if option.cases.len() != 2
+                || !has(["Yes", "yes", "Y", "y"])
+                || !has(["No", "no", "N", "n"])
+            {
+                self.error(
+                    IssueKind::InvalidSwitch,
+                    format!("{}.cases", path),
+                    "Switch option needs exactly two cases named Yes and No".to_string(),
+                );
+            }
+        }
+    }
+
+    /// 选项通过 case 的嵌套选项引用自身（前端展开和生成覆盖时会无限递归）
+    fn check_cycles(&mut self) {
+        let pi = self.pi;
+        let edges: HashMap<&str, Vec<&str>> = pi
+            .option
+            .iter()
+            .map(|(key, option)| {
+                let nested = option
+                    .cases
+                    .iter()
+                    .flat_map(|case| case.option.iter().map(String::as_str))
+                    .collect();
+                (key.as_str(), nested)
+            })
+            .collect();
+
+        let mut keys: Vec<&str> = edges.keys().copied().collect();
+        keys.sort();
+        let mut reported = HashSet::new();
+        for start in keys {
+            // 从 start 出发的深度优先搜索，栈中保存 (选项, 下一个要访问的子节点)
+            let mut stack: Vec<(&str, usize)> = vec![(start, 0)];
+            let mut on_path: Vec<&str> = vec![start];
+            let mut visited = HashSet::new();
+            while let Some((node, next)) = stack.last_mut() {
+                let children = edges.get(*node).map(Vec::as_slice).unwrap_or_default();
+                let Some(&child) = children.get(*next) else {
+                    stack.pop();
+                    on_path.pop();
+                    continue;
+                };
+                *next += 1;
+                if child == start {
+                    let mut cycle: Vec<&str> = on_path.clone();
+                    cycle.sort();
+                    if reported.insert(cycle) {
+                        let chain: Vec<&str> = on_path.iter().copied().chain([start]).collect();
+                        self.error(
+                            IssueKind::CyclicOption,
+                            key_path("$.option", start),
+                            format!("Option references itself: {}", chain.join(" -> ")),
+                        );
+                    }
+                } else if edges.contains_key(child) && visited.insert(child) {
+                    stack.push((child, 0));
+                    on_path.push(child);
+                }
+            }
+        }
+    }
+}
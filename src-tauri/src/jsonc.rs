@@ -0,0 +1,82 @@
+//! JSONC 解析（支持注释和尾逗号），与前端 utils/jsonc.ts 接受的格式一致
+//!
+//! interface.json、翻译文件和用户配置都可能带注释，无界面模式等后端功能读取时使用。
+
+use serde::de::DeserializeOwned;
+
+/// 解析 JSONC 字符串
+pub fn parse_jsonc<T: DeserializeOwned>(content: &str, source: &str) -> Result<T, String> {
+    serde_json::from_str(&strip_jsonc(content))
+        .map_err(|e| format!("解析 JSONC 失败 [{}]: {}", source, e))
+}
+
+/// 把注释和对象、数组末尾的逗号替换为空白（保留换行，错误位置的行列号不变）
+fn strip_jsonc(content: &str) -> String {
+    let chars: Vec<char> = content.trim_start_matches('\u{feff}').chars().collect();
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    // 最近一个尚未确认是否为尾逗号的逗号在 out 中的位置
+    let mut pending_comma: Option<usize> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                pending_comma = None;
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        out.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if chars[i - 1] == '"' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(' ');
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                out.extend([' ', ' ']);
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                    i += 1;
+                }
+                if i < chars.len() {
+                    out.extend([' ', ' ']);
+                    i += 2;
+                }
+            }
+            ',' => {
+                pending_comma = Some(out.len());
+                out.push(c);
+                i += 1;
+            }
+            '}' | ']' => {
+                if let Some(pos) = pending_comma.take() {
+                    out[pos] = ' ';
+                }
+                out.push(c);
+                i += 1;
+            }
+            _ => {
+                if !c.is_whitespace() {
+                    pending_comma = None;
+                }
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out.into_iter().collect()
+}
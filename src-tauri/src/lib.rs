@@ -1,24 +1,44 @@
+mod data_dirs;
+pub mod headless;
+mod instance_state;
+mod interface_validator;
+mod jsonc;
+mod maa_backend;
 pub mod maa_commands;
+mod maa_events;
 mod maa_ffi;
+mod maa_simulator;
+mod maafw_search;
+mod mxu_config;
+mod pipeline_inspect;
+mod pipeline_override;
+mod post_run;
+mod pre_run;
+mod project_interface;
+mod reconnect;
+mod scheduler;
+mod task_condition;
+mod task_ledger;
+mod task_queue;
+mod time_zone;
+mod user_command;
 
 use maa_commands::MaaState;
 use maa_ffi::MaaLibraryError;
+use maa_simulator::{SimulatedBackend, SimulatorConfig};
+use post_run::PostRunManager;
+use pre_run::PreRunManager;
+use reconnect::ReconnectWatchdog;
+use scheduler::Scheduler;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::Manager;
 use tauri_plugin_log::{Target, TargetKind, TimezoneStrategy};
 
-/// 获取 exe 所在目录下的 debug/logs 子目录
-fn get_logs_dir() -> PathBuf {
-    let exe_path = std::env::current_exe().unwrap_or_default();
-    let exe_dir = exe_path.parent().unwrap_or(std::path::Path::new("."));
-    exe_dir.join("debug")
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // 日志目录：exe 目录/debug/logs（与前端日志同目录）
-    let logs_dir = get_logs_dir();
+    // 日志目录：便携模式为 exe 目录/debug，安装模式为用户状态目录（与前端日志同目录）
+    let logs_dir = data_dirs::data_dirs().logs_dir.clone();
 
     // 确保日志目录存在
     let _ = std::fs::create_dir_all(&logs_dir);
@@ -34,7 +54,7 @@ pub fn run() {
                 .targets([
                     // 输出到控制台
                     Target::new(TargetKind::Stdout),
-                    // 输出到 exe/debug/logs 目录（与前端日志同目录，文件名用 mxu-tauri 区分）
+                    // 输出到日志目录（与前端日志同目录，文件名用 mxu-tauri 区分）
                     Target::new(TargetKind::Folder {
                         path: logs_dir,
                         file_name: Some("mxu-tauri".into()),
@@ -46,7 +66,24 @@ pub fn run() {
         )
         .setup(|app| {
             // 创建 MaaState 并注册为 Tauri 管理状态
-            let maa_state = Arc::new(MaaState::default());
+            let maa_state = start_state();
+
+            // 控制器断开后按实例策略自动重连
+            app.manage(ReconnectWatchdog::start(maa_state.clone()));
+
+            // 运行结束后按实例设置执行动作（关机、启动其他实例等）
+            app.manage(PostRunManager::start(
+                app.handle().clone(),
+                maa_state.clone(),
+            ));
+
+            // 启动前的准备步骤（启动模拟器或游戏并等待就绪）
+            let pre_run = Arc::new(PreRunManager::default());
+            app.manage(pre_run.clone());
+
+            // 定时执行：按实例策略在后台准备实例并启动任务
+            app.manage(Scheduler::start(maa_state.clone(), pre_run));
+
             app.manage(maa_state);
 
             // 存储 AppHandle 供 MaaFramework 回调使用（发送事件到前端）
@@ -62,61 +99,41 @@ pub fn run() {
             }
 
             // 启动时异步清理 cache/old 目录（更新残留的旧文件），不阻塞应用启动
-            if let Ok(exe_dir) = maa_commands::get_exe_dir() {
-                let old_dir = std::path::Path::new(&exe_dir).join("cache").join("old");
-                if old_dir.exists() {
-                    std::thread::spawn(move || {
-                        let (deleted, failed) = maa_commands::cleanup_dir_contents(&old_dir);
-                        if deleted > 0 || failed > 0 {
-                            if failed == 0 {
-                                log::info!("Cleaned up cache/old: {} items deleted", deleted);
-                            } else {
-                                log::warn!(
-                                    "Cleaned up cache/old: {} deleted, {} failed",
-                                    deleted,
-                                    failed
-                                );
-                            }
-                        }
-                    });
-                }
-            }
-
-            // 启动时自动加载 MaaFramework DLL
-            if let Ok(maafw_dir) = maa_commands::get_maafw_dir() {
-                if maafw_dir.exists() {
-                    match maa_ffi::init_maa_library(&maafw_dir) {
-                        Ok(()) => log::info!("MaaFramework loaded from {:?}", maafw_dir),
-                        Err(e) => {
-                            log::error!("Failed to load MaaFramework: {}", e);
-                            // 检查是否是 DLL 存在但加载失败的情况（可能是运行库缺失）
-                            if let MaaLibraryError::LoadFailed { dlls_exist: true, error, .. } = &e {
-                                log::warn!(
-                                    "DLLs exist but failed to load, possibly missing VC++ runtime: {}",
-                                    error
-                                );
-                                // 设置标记，前端加载完成后会查询此标记
-                                maa_ffi::set_vcredist_missing(true);
-                            }
+            let old_dir = data_dirs::data_dirs().cache_dir.join("old");
+            if old_dir.exists() {
+                std::thread::spawn(move || {
+                    let (deleted, failed) = maa_commands::cleanup_dir_contents(&old_dir);
+                    if deleted > 0 || failed > 0 {
+                        if failed == 0 {
+                            log::info!("Cleaned up cache/old: {} items deleted", deleted);
+                        } else {
+                            log::warn!(
+                                "Cleaned up cache/old: {} deleted, {} failed",
+                                deleted,
+                                failed
+                            );
                         }
                     }
-                } else {
-                    log::warn!("MaaFramework directory not found: {:?}", maafw_dir);
-                }
+                });
             }
 
+            load_backend();
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             maa_commands::maa_init,
+            maa_commands::maa_reload_library,
             maa_commands::maa_set_resource_dir,
             maa_commands::maa_get_version,
             maa_commands::maa_check_version,
+            maa_commands::maa_get_capabilities,
             maa_commands::maa_find_adb_devices,
             maa_commands::maa_find_win32_windows,
             maa_commands::maa_create_instance,
             maa_commands::maa_destroy_instance,
             maa_commands::maa_connect_controller,
+            maa_commands::maa_set_reconnect_policy,
             maa_commands::maa_get_connection_status,
             maa_commands::maa_load_resource,
             maa_commands::maa_is_resource_loaded,
@@ -124,16 +141,37 @@ pub fn run() {
             maa_commands::maa_run_task,
             maa_commands::maa_get_task_status,
             maa_commands::maa_stop_task,
+            maa_commands::maa_pause_tasks,
+            maa_commands::maa_resume_tasks,
             maa_commands::maa_override_pipeline,
+            maa_commands::maa_get_task_queue,
+            maa_commands::maa_queue_insert,
+            maa_commands::maa_queue_remove,
+            maa_commands::maa_queue_reorder,
+            maa_commands::maa_queue_update_override,
+            maa_commands::maa_set_day_reset,
+            maa_commands::maa_get_task_ledger,
+            maa_commands::maa_clear_task_ledger,
             maa_commands::maa_is_running,
             maa_commands::maa_post_screencap,
             maa_commands::maa_get_cached_image,
             maa_commands::maa_start_tasks,
             maa_commands::maa_stop_agent,
+            maa_commands::maa_set_pre_run_steps,
+            maa_commands::maa_run_pre_run,
+            maa_commands::maa_cancel_pre_run,
+            maa_commands::maa_set_post_run_actions,
+            maa_commands::maa_cancel_post_run,
+            maa_commands::maa_set_schedule,
+            maa_commands::maa_preview_schedule,
+            maa_commands::maa_validate_interface,
+            maa_commands::maa_build_pipeline_override,
+            maa_commands::maa_inspect_pipeline,
             maa_commands::read_local_file,
             maa_commands::read_local_file_base64,
             maa_commands::local_file_exists,
             maa_commands::get_exe_dir,
+            maa_commands::get_data_dirs,
             maa_commands::get_cwd,
             maa_commands::check_exe_path,
             // 状态查询命令
@@ -168,3 +206,64 @@ pub fn run() {
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// 创建 MaaState，并注册生命周期状态机和任务队列的回调观察者
+fn start_state() -> Arc<MaaState> {
+    let maa_state = Arc::new(MaaState::default());
+
+    // 由 MaaFramework 回调驱动实例生命周期状态机
+    let observer_state = maa_state.clone();
+    maa_ffi::add_event_observer(move |event| observer_state.lifecycles.handle_event(event));
+
+    // 任务队列：逐个提交任务，任务结束后提交下一个
+    task_queue::start_dispatcher(maa_state.clone());
+
+    maa_state
+}
+
+/// 加载 MaaFramework（或模拟后端），失败时只记录日志
+fn load_backend() {
+    // --simulate 或 MXU_SIMULATE=1 时使用模拟后端，不加载 MaaFramework
+    if maa_simulator::simulation_requested() {
+        let exe_dir = maa_commands::get_exe_dir()
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let backend = SimulatedBackend::new(SimulatorConfig::discover(&exe_dir));
+        match maa_ffi::install_backend(Arc::new(backend)) {
+            Ok(()) => log::info!("Using simulated MaaFramework backend"),
+            Err(e) => log::error!("Failed to install simulated backend: {}", e),
+        }
+    } else if let Ok(search) = maa_commands::search_maafw_dir() {
+        // 启动时按搜索顺序查找并自动加载 MaaFramework DLL
+        if let Some(chosen) = &search.chosen {
+            let maafw_dir = &chosen.path;
+            log::info!(
+                "MaaFramework directory {:?} ({:?})",
+                maafw_dir,
+                chosen.source
+            );
+            match maa_ffi::init_maa_library(maafw_dir) {
+                Ok(()) => log::info!("MaaFramework loaded from {:?}", maafw_dir),
+                Err(e) => {
+                    log::error!("Failed to load MaaFramework: {}", e);
+                    // 检查是否是 DLL 存在但加载失败的情况（可能是运行库缺失）
+                    if let MaaLibraryError::LoadFailed {
+                        dlls_exist: true,
+                        error,
+                        ..
+                    } = &e
+                    {
+                        log::warn!(
+                            "DLLs exist but failed to load, possibly missing VC++ runtime: {}",
+                            error
+                        );
+                        // 设置标记，前端加载完成后会查询此标记
+                        maa_ffi::set_vcredist_missing(true);
+                    }
+                }
+            }
+        } else {
+            log::warn!("{}", search.not_found_message());
+        }
+    }
+}
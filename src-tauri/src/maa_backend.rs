@@ -0,0 +1,612 @@
+//! MaaFramework 后端抽象
+//!
+//! 命令层只通过 `MaaBackend` 调用 MaaFramework，动态库（`MaaLibrary`）和进程内模拟器
+//! （`maa_simulator::SimulatedBackend`）是它的两个实现。
+//!
+//! 句柄仍以 MaaFramework 的不透明指针表示，只能传回创建它的同一个后端。接收句柄的方法
+//! 直接把指针交给 FFI，传入已销毁的句柄是未定义行为，因此这些方法都是 `unsafe fn`，
+//! 只由 maa_ffi 中的句柄封装类型（Resource、Tasker 等）调用，由它们保证句柄有效；
+//! 命令层只使用不接收句柄的方法（设备搜索、创建控制器等）。
+
+use std::os::raw::c_void;
+
+use serde::{Deserialize, Serialize};
+
+use crate::maa_ffi::{
+    from_cstr, to_cstring, MaaAdbInputMethod, MaaAdbScreencapMethod, MaaAgentClient, MaaController,
+    MaaCtrlOption, MaaEventCallback, MaaGamepadType, MaaGlobalOption, MaaId, MaaImageBuffer,
+    MaaLibrary, MaaResource, MaaSize, MaaStatus, MaaStringBuffer, MaaTasker, MaaWin32InputMethod,
+    MaaWin32ScreencapMethod,
+};
+
+/// 后端类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// 动态加载的 MaaFramework
+    Library,
+    /// 进程内模拟器
+    Simulator,
+}
+
+/// 依赖可选函数的功能，旧版本 MaaFramework 缺少对应函数时不可用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionalFeature {
+    /// 修改已提交但未开始的任务的选项
+    OverridePipeline,
+    /// Agent 通信兼容模式（TCP）
+    TcpAgent,
+}
+
+impl OptionalFeature {
+    pub const ALL: [Self; 2] = [Self::OverridePipeline, Self::TcpAgent];
+
+    /// 功能依赖的 MaaFramework 函数
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::OverridePipeline => "MaaTaskerOverridePipeline",
+            Self::TcpAgent => "MaaAgentClientCreateTcp",
+        }
+    }
+}
+
+/// Toolkit 搜索到的 ADB 设备
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdbDeviceInfo {
+    pub name: String,
+    pub adb_path: String,
+    pub address: String,
+    pub screencap_methods: MaaAdbScreencapMethod,
+    pub input_methods: MaaAdbInputMethod,
+    #[serde(default)]
+    pub config: String,
+}
+
+/// Toolkit 搜索到的桌面窗口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopWindowInfo {
+    pub handle: u64,
+    pub class_name: String,
+    pub window_name: String,
+}
+
+/// MaaFramework 操作集合
+///
+/// # Safety
+/// `unsafe` 方法接收的句柄必须由同一个后端创建且尚未销毁；`*_destroy` 之后不得再使用该句柄
+pub(crate) trait MaaBackend: Send + Sync {
+    fn kind(&self) -> BackendKind;
+    fn version(&self) -> String;
+    /// 是否支持依赖可选函数的功能
+    fn supports(&self, feature: OptionalFeature) -> bool;
+    fn set_global_option(&self, option: MaaGlobalOption, value: &[u8]) -> bool;
+
+    // Toolkit
+    fn toolkit_init_option(&self, user_path: &str, default_json: &str) -> bool;
+    fn find_adb_devices(&self) -> Result<Vec<AdbDeviceInfo>, String>;
+    fn find_desktop_windows(&self) -> Result<Vec<DesktopWindowInfo>, String>;
+
+    // StringBuffer
+    fn string_buffer_create(&self) -> *mut MaaStringBuffer;
+    unsafe fn string_buffer_destroy(&self, buffer: *mut MaaStringBuffer);
+    unsafe fn string_buffer_get(&self, buffer: *mut MaaStringBuffer) -> String;
+
+    // ImageBuffer
+    fn image_buffer_create(&self) -> *mut MaaImageBuffer;
+    unsafe fn image_buffer_destroy(&self, buffer: *mut MaaImageBuffer);
+    /// 编码后的图像数据（PNG），无数据时为空
+    unsafe fn image_buffer_encoded(&self, buffer: *mut MaaImageBuffer) -> Vec<u8>;
+
+    // Resource
+    fn resource_create(&self) -> *mut MaaResource;
+    unsafe fn resource_destroy(&self, res: *mut MaaResource);
+    unsafe fn resource_add_sink(
+        &self,
+        res: *mut MaaResource,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId;
+    unsafe fn resource_post_bundle(&self, res: *mut MaaResource, path: &str) -> MaaId;
+    unsafe fn resource_loaded(&self, res: *mut MaaResource) -> bool;
+
+    // Controller
+    fn adb_controller_create(
+        &self,
+        adb_path: &str,
+        address: &str,
+        screencap_methods: MaaAdbScreencapMethod,
+        input_methods: MaaAdbInputMethod,
+        config: &str,
+        agent_path: &str,
+    ) -> *mut MaaController;
+    fn win32_controller_create(
+        &self,
+        hwnd: *mut c_void,
+        screencap_method: MaaWin32ScreencapMethod,
+        mouse_method: MaaWin32InputMethod,
+        keyboard_method: MaaWin32InputMethod,
+    ) -> *mut MaaController;
+    fn gamepad_controller_create(
+        &self,
+        hwnd: *mut c_void,
+        gamepad_type: MaaGamepadType,
+        screencap_method: MaaWin32ScreencapMethod,
+    ) -> *mut MaaController;
+    unsafe fn controller_destroy(&self, ctrl: *mut MaaController);
+    unsafe fn controller_add_sink(
+        &self,
+        ctrl: *mut MaaController,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId;
+    unsafe fn controller_set_option(
+        &self,
+        ctrl: *mut MaaController,
+        option: MaaCtrlOption,
+        value: &[u8],
+    ) -> bool;
+    unsafe fn controller_post_connection(&self, ctrl: *mut MaaController) -> MaaId;
+    unsafe fn controller_connected(&self, ctrl: *mut MaaController) -> bool;
+    unsafe fn controller_post_screencap(&self, ctrl: *mut MaaController) -> MaaId;
+    unsafe fn controller_cached_image(
+        &self,
+        ctrl: *mut MaaController,
+        buffer: *mut MaaImageBuffer,
+    ) -> bool;
+
+    // Tasker
+    fn tasker_create(&self) -> *mut MaaTasker;
+    unsafe fn tasker_destroy(&self, tasker: *mut MaaTasker);
+    unsafe fn tasker_add_sink(
+        &self,
+        tasker: *mut MaaTasker,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId;
+    unsafe fn tasker_add_context_sink(
+        &self,
+        tasker: *mut MaaTasker,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId;
+    unsafe fn tasker_bind_resource(&self, tasker: *mut MaaTasker, res: *mut MaaResource) -> bool;
+    unsafe fn tasker_bind_controller(
+        &self,
+        tasker: *mut MaaTasker,
+        ctrl: *mut MaaController,
+    ) -> bool;
+    unsafe fn tasker_inited(&self, tasker: *mut MaaTasker) -> bool;
+    unsafe fn tasker_post_task(
+        &self,
+        tasker: *mut MaaTasker,
+        entry: &str,
+        pipeline_override: &str,
+    ) -> MaaId;
+    unsafe fn tasker_status(&self, tasker: *mut MaaTasker, task_id: MaaId) -> MaaStatus;
+    unsafe fn tasker_running(&self, tasker: *mut MaaTasker) -> bool;
+    unsafe fn tasker_post_stop(&self, tasker: *mut MaaTasker) -> MaaId;
+    /// 覆盖已提交任务的 Pipeline，后端不支持时返回 None
+    unsafe fn tasker_override_pipeline(
+        &self,
+        tasker: *mut MaaTasker,
+        task_id: MaaId,
+        pipeline_override: &str,
+    ) -> Option<bool>;
+
+    // AgentClient
+    fn agent_client_create(&self) -> *mut MaaAgentClient;
+    /// 以 TCP 模式创建，后端不支持时返回 None
+    fn agent_client_create_tcp(&self, port: u16) -> Option<*mut MaaAgentClient>;
+    unsafe fn agent_client_destroy(&self, agent: *mut MaaAgentClient);
+    unsafe fn agent_client_bind_resource(
+        &self,
+        agent: *mut MaaAgentClient,
+        res: *mut MaaResource,
+    ) -> bool;
+    unsafe fn agent_client_identifier(
+        &self,
+        agent: *mut MaaAgentClient,
+        buffer: *mut MaaStringBuffer,
+    ) -> bool;
+    unsafe fn agent_client_set_timeout(&self, agent: *mut MaaAgentClient, timeout_ms: i64) -> bool;
+    /// 等待 Agent 连接（阻塞）
+    unsafe fn agent_client_connect(&self, agent: *mut MaaAgentClient) -> bool;
+    unsafe fn agent_client_disconnect(&self, agent: *mut MaaAgentClient) -> bool;
+}
+
+// ============================================================================
+// 动态库实现
+// ============================================================================
+
+impl MaaBackend for MaaLibrary {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Library
+    }
+
+    fn version(&self) -> String {
+        MaaLibrary::version(self)
+    }
+
+    fn supports(&self, feature: OptionalFeature) -> bool {
+        match feature {
+            OptionalFeature::OverridePipeline => self.maa_tasker_override_pipeline.is_some(),
+            OptionalFeature::TcpAgent => self.maa_agent_client_create_tcp.is_some(),
+        }
+    }
+
+    fn set_global_option(&self, option: MaaGlobalOption, value: &[u8]) -> bool {
+        unsafe {
+            (self.maa_set_global_option)(
+                option,
+                value.as_ptr() as *const c_void,
+                value.len() as MaaSize,
+            ) != 0
+        }
+    }
+
+    fn toolkit_init_option(&self, user_path: &str, default_json: &str) -> bool {
+        let user_path_c = to_cstring(user_path);
+        let default_json_c = to_cstring(default_json);
+        unsafe {
+            (self.maa_toolkit_config_init_option)(user_path_c.as_ptr(), default_json_c.as_ptr())
+                != 0
+        }
+    }
+
+    fn find_adb_devices(&self) -> Result<Vec<AdbDeviceInfo>, String> {
+        unsafe {
+            log::debug!("Creating ADB device list...");
+            let list = (self.maa_toolkit_adb_device_list_create)();
+            if list.is_null() {
+                log::error!("Failed to create device list (null pointer)");
+                return Err("Failed to create device list".to_string());
+            }
+
+            log::debug!("Calling MaaToolkitAdbDeviceFind...");
+            let found = (self.maa_toolkit_adb_device_find)(list);
+            // MaaToolkitAdbDeviceFind 只在 buffer 为 null 时返回 false
+            // 即使没找到设备也会返回 true，所以不应该用返回值判断是否找到设备
+            if found == 0 {
+                log::warn!("MaaToolkitAdbDeviceFind returned false (unexpected)");
+            }
+
+            let size = (self.maa_toolkit_adb_device_list_size)(list);
+            let mut devices = Vec::with_capacity(size as usize);
+            for i in 0..size {
+                let device = (self.maa_toolkit_adb_device_list_at)(list, i);
+                if device.is_null() {
+                    log::warn!("Device at index {} is null, skipping", i);
+                    continue;
+                }
+
+                devices.push(AdbDeviceInfo {
+                    name: from_cstr((self.maa_toolkit_adb_device_get_name)(device)),
+                    adb_path: from_cstr((self.maa_toolkit_adb_device_get_adb_path)(device)),
+                    address: from_cstr((self.maa_toolkit_adb_device_get_address)(device)),
+                    screencap_methods: (self.maa_toolkit_adb_device_get_screencap_methods)(device),
+                    input_methods: (self.maa_toolkit_adb_device_get_input_methods)(device),
+                    config: from_cstr((self.maa_toolkit_adb_device_get_config)(device)),
+                });
+            }
+
+            log::debug!("Destroying ADB device list...");
+            (self.maa_toolkit_adb_device_list_destroy)(list);
+            Ok(devices)
+        }
+    }
+
+    fn find_desktop_windows(&self) -> Result<Vec<DesktopWindowInfo>, String> {
+        unsafe {
+            log::debug!("Creating desktop window list...");
+            let list = (self.maa_toolkit_desktop_window_list_create)();
+            if list.is_null() {
+                log::error!("Failed to create window list (null pointer)");
+                return Err("Failed to create window list".to_string());
+            }
+
+            log::debug!("Calling MaaToolkitDesktopWindowFindAll...");
+            let found = (self.maa_toolkit_desktop_window_find_all)(list);
+            let mut windows = Vec::new();
+            if found != 0 {
+                let size = (self.maa_toolkit_desktop_window_list_size)(list);
+                windows.reserve(size as usize);
+                for i in 0..size {
+                    let window = (self.maa_toolkit_desktop_window_list_at)(list, i);
+                    if window.is_null() {
+                        continue;
+                    }
+
+                    windows.push(DesktopWindowInfo {
+                        handle: (self.maa_toolkit_desktop_window_get_handle)(window) as u64,
+                        class_name: from_cstr((self.maa_toolkit_desktop_window_get_class_name)(
+                            window,
+                        )),
+                        window_name: from_cstr((self.maa_toolkit_desktop_window_get_window_name)(
+                            window,
+                        )),
+                    });
+                }
+            }
+
+            log::debug!("Destroying desktop window list...");
+            (self.maa_toolkit_desktop_window_list_destroy)(list);
+            Ok(windows)
+        }
+    }
+
+    fn string_buffer_create(&self) -> *mut MaaStringBuffer {
+        unsafe { (self.maa_string_buffer_create)() }
+    }
+
+    unsafe fn string_buffer_destroy(&self, buffer: *mut MaaStringBuffer) {
+        unsafe { (self.maa_string_buffer_destroy)(buffer) }
+    }
+
+    unsafe fn string_buffer_get(&self, buffer: *mut MaaStringBuffer) -> String {
+        unsafe { from_cstr((self.maa_string_buffer_get)(buffer)) }
+    }
+
+    fn image_buffer_create(&self) -> *mut MaaImageBuffer {
+        unsafe { (self.maa_image_buffer_create)() }
+    }
+
+    unsafe fn image_buffer_destroy(&self, buffer: *mut MaaImageBuffer) {
+        unsafe { (self.maa_image_buffer_destroy)(buffer) }
+    }
+
+    unsafe fn image_buffer_encoded(&self, buffer: *mut MaaImageBuffer) -> Vec<u8> {
+        unsafe {
+            let ptr = (self.maa_image_buffer_get_encoded)(buffer);
+            let size = (self.maa_image_buffer_get_encoded_size)(buffer);
+            if ptr.is_null() || size == 0 {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(ptr, size as usize).to_vec()
+            }
+        }
+    }
+
+    fn resource_create(&self) -> *mut MaaResource {
+        unsafe { (self.maa_resource_create)() }
+    }
+
+    unsafe fn resource_destroy(&self, res: *mut MaaResource) {
+        unsafe { (self.maa_resource_destroy)(res) }
+    }
+
+    unsafe fn resource_add_sink(
+        &self,
+        res: *mut MaaResource,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        unsafe { (self.maa_resource_add_sink)(res, callback, trans_arg) }
+    }
+
+    unsafe fn resource_post_bundle(&self, res: *mut MaaResource, path: &str) -> MaaId {
+        let path_c = to_cstring(path);
+        unsafe { (self.maa_resource_post_bundle)(res, path_c.as_ptr()) }
+    }
+
+    unsafe fn resource_loaded(&self, res: *mut MaaResource) -> bool {
+        unsafe { (self.maa_resource_loaded)(res) != 0 }
+    }
+
+    fn adb_controller_create(
+        &self,
+        adb_path: &str,
+        address: &str,
+        screencap_methods: MaaAdbScreencapMethod,
+        input_methods: MaaAdbInputMethod,
+        config: &str,
+        agent_path: &str,
+    ) -> *mut MaaController {
+        let adb_path_c = to_cstring(adb_path);
+        let address_c = to_cstring(address);
+        let config_c = to_cstring(config);
+        let agent_path_c = to_cstring(agent_path);
+        unsafe {
+            (self.maa_adb_controller_create)(
+                adb_path_c.as_ptr(),
+                address_c.as_ptr(),
+                screencap_methods,
+                input_methods,
+                config_c.as_ptr(),
+                agent_path_c.as_ptr(),
+            )
+        }
+    }
+
+    fn win32_controller_create(
+        &self,
+        hwnd: *mut c_void,
+        screencap_method: MaaWin32ScreencapMethod,
+        mouse_method: MaaWin32InputMethod,
+        keyboard_method: MaaWin32InputMethod,
+    ) -> *mut MaaController {
+        unsafe {
+            (self.maa_win32_controller_create)(
+                hwnd,
+                screencap_method,
+                mouse_method,
+                keyboard_method,
+            )
+        }
+    }
+
+    fn gamepad_controller_create(
+        &self,
+        hwnd: *mut c_void,
+        gamepad_type: MaaGamepadType,
+        screencap_method: MaaWin32ScreencapMethod,
+    ) -> *mut MaaController {
+        unsafe { (self.maa_gamepad_controller_create)(hwnd, gamepad_type, screencap_method) }
+    }
+
+    unsafe fn controller_destroy(&self, ctrl: *mut MaaController) {
+        unsafe { (self.maa_controller_destroy)(ctrl) }
+    }
+
+    unsafe fn controller_add_sink(
+        &self,
+        ctrl: *mut MaaController,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        unsafe { (self.maa_controller_add_sink)(ctrl, callback, trans_arg) }
+    }
+
+    unsafe fn controller_set_option(
+        &self,
+        ctrl: *mut MaaController,
+        option: MaaCtrlOption,
+        value: &[u8],
+    ) -> bool {
+        unsafe {
+            (self.maa_controller_set_option)(
+                ctrl,
+                option,
+                value.as_ptr() as *const c_void,
+                value.len() as MaaSize,
+            ) != 0
+        }
+    }
+
+    unsafe fn controller_post_connection(&self, ctrl: *mut MaaController) -> MaaId {
+        unsafe { (self.maa_controller_post_connection)(ctrl) }
+    }
+
+    unsafe fn controller_connected(&self, ctrl: *mut MaaController) -> bool {
+        unsafe { (self.maa_controller_connected)(ctrl) != 0 }
+    }
+
+    unsafe fn controller_post_screencap(&self, ctrl: *mut MaaController) -> MaaId {
+        unsafe { (self.maa_controller_post_screencap)(ctrl) }
+    }
+
+    unsafe fn controller_cached_image(
+        &self,
+        ctrl: *mut MaaController,
+        buffer: *mut MaaImageBuffer,
+    ) -> bool {
+        unsafe { (self.maa_controller_cached_image)(ctrl, buffer) != 0 }
+    }
+
+    fn tasker_create(&self) -> *mut MaaTasker {
+        unsafe { (self.maa_tasker_create)() }
+    }
+
+    unsafe fn tasker_destroy(&self, tasker: *mut MaaTasker) {
+        unsafe { (self.maa_tasker_destroy)(tasker) }
+    }
+
+    unsafe fn tasker_add_sink(
+        &self,
+        tasker: *mut MaaTasker,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        unsafe { (self.maa_tasker_add_sink)(tasker, callback, trans_arg) }
+    }
+
+    unsafe fn tasker_add_context_sink(
+        &self,
+        tasker: *mut MaaTasker,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        unsafe { (self.maa_tasker_add_context_sink)(tasker, callback, trans_arg) }
+    }
+
+    unsafe fn tasker_bind_resource(&self, tasker: *mut MaaTasker, res: *mut MaaResource) -> bool {
+        unsafe { (self.maa_tasker_bind_resource)(tasker, res) != 0 }
+    }
+
+    unsafe fn tasker_bind_controller(
+        &self,
+        tasker: *mut MaaTasker,
+        ctrl: *mut MaaController,
+    ) -> bool {
+        unsafe { (self.maa_tasker_bind_controller)(tasker, ctrl) != 0 }
+    }
+
+    unsafe fn tasker_inited(&self, tasker: *mut MaaTasker) -> bool {
+        unsafe { (self.maa_tasker_inited)(tasker) != 0 }
+    }
+
+    unsafe fn tasker_post_task(
+        &self,
+        tasker: *mut MaaTasker,
+        entry: &str,
+        pipeline_override: &str,
+    ) -> MaaId {
+        let entry_c = to_cstring(entry);
+        let override_c = to_cstring(pipeline_override);
+        unsafe { (self.maa_tasker_post_task)(tasker, entry_c.as_ptr(), override_c.as_ptr()) }
+    }
+
+    unsafe fn tasker_status(&self, tasker: *mut MaaTasker, task_id: MaaId) -> MaaStatus {
+        unsafe { (self.maa_tasker_status)(tasker, task_id) }
+    }
+
+    unsafe fn tasker_running(&self, tasker: *mut MaaTasker) -> bool {
+        unsafe { (self.maa_tasker_running)(tasker) != 0 }
+    }
+
+    unsafe fn tasker_post_stop(&self, tasker: *mut MaaTasker) -> MaaId {
+        unsafe { (self.maa_tasker_post_stop)(tasker) }
+    }
+
+    unsafe fn tasker_override_pipeline(
+        &self,
+        tasker: *mut MaaTasker,
+        task_id: MaaId,
+        pipeline_override: &str,
+    ) -> Option<bool> {
+        let override_fn = self.maa_tasker_override_pipeline?;
+        let override_c = to_cstring(pipeline_override);
+        Some(unsafe { override_fn(tasker, task_id, override_c.as_ptr()) } != 0)
+    }
+
+    fn agent_client_create(&self) -> *mut MaaAgentClient {
+        unsafe { (self.maa_agent_client_create_v2)(std::ptr::null()) }
+    }
+
+    fn agent_client_create_tcp(&self, port: u16) -> Option<*mut MaaAgentClient> {
+        let create_tcp_fn = self.maa_agent_client_create_tcp?;
+        Some(unsafe { create_tcp_fn(port) })
+    }
+
+    unsafe fn agent_client_destroy(&self, agent: *mut MaaAgentClient) {
+        unsafe { (self.maa_agent_client_destroy)(agent) }
+    }
+
+    unsafe fn agent_client_bind_resource(
+        &self,
+        agent: *mut MaaAgentClient,
+        res: *mut MaaResource,
+    ) -> bool {
+        unsafe { (self.maa_agent_client_bind_resource)(agent, res) != 0 }
+    }
+
+    unsafe fn agent_client_identifier(
+        &self,
+        agent: *mut MaaAgentClient,
+        buffer: *mut MaaStringBuffer,
+    ) -> bool {
+        unsafe { (self.maa_agent_client_identifier)(agent, buffer) != 0 }
+    }
+
+    unsafe fn agent_client_set_timeout(&self, agent: *mut MaaAgentClient, timeout_ms: i64) -> bool {
+        unsafe { (self.maa_agent_client_set_timeout)(agent, timeout_ms) != 0 }
+    }
+
+    unsafe fn agent_client_connect(&self, agent: *mut MaaAgentClient) -> bool {
+        unsafe { (self.maa_agent_client_connect)(agent) != 0 }
+    }
+
+    unsafe fn agent_client_disconnect(&self, agent: *mut MaaAgentClient) -> bool {
+        unsafe { (self.maa_agent_client_disconnect)(agent) != 0 }
+    }
+}
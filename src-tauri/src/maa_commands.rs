@@ -6,23 +6,38 @@ use log::{debug, error, info, warn};
 use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::{BufRead, BufReader, Write};
-use std::os::raw::c_void;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use tauri::{Emitter, State};
 
+use crate::data_dirs::{data_dirs, DataDirs, DataMode};
+use crate::instance_state::{FailureStage, InstanceState, LifecycleRegistry, LifecycleState};
+use crate::interface_validator::{self, InterfaceIssue};
+use crate::maa_backend::{BackendKind, MaaBackend, OptionalFeature};
 use crate::maa_ffi::{
-    emit_agent_output, from_cstr, get_event_callback, get_maa_version, get_maa_version_standalone,
-    init_maa_library, to_cstring, MaaAgentClient, MaaController, MaaImageBuffer, MaaLibrary,
-    MaaResource, MaaTasker, MaaToolkitAdbDeviceList, MaaToolkitDesktopWindowList, SendPtr,
-    MAA_CTRL_OPTION_SCREENSHOT_TARGET_SHORT_SIDE, MAA_GAMEPAD_TYPE_DUALSHOCK4,
-    MAA_GAMEPAD_TYPE_XBOX360, MAA_INVALID_ID, MAA_LIBRARY, MAA_STATUS_PENDING, MAA_STATUS_RUNNING,
-    MAA_STATUS_SUCCEEDED, MAA_WIN32_SCREENCAP_DXGI_DESKTOPDUP,
+    current_backend, emit_agent_output, emit_event, get_maa_version, get_maa_version_standalone,
+    init_maa_library, last_load_report, unload_backend, AgentClient, Controller, Resource,
+    SinkContexts, Tasker, MAA_CTRL_OPTION_SCREENSHOT_TARGET_SHORT_SIDE,
+    MAA_GAMEPAD_TYPE_DUALSHOCK4, MAA_GAMEPAD_TYPE_XBOX360, MAA_INVALID_ID, MAA_STATUS_PENDING,
+    MAA_STATUS_RUNNING, MAA_STATUS_SUCCEEDED, MAA_WIN32_SCREENCAP_DXGI_DESKTOPDUP,
 };
+use crate::maafw_search::{self, MaafwSearch};
+use crate::pipeline_inspect::{self, PipelineInspection};
+use crate::pipeline_override::{self, OverrideError};
+use crate::post_run::{PostRunConfig, PostRunManager};
+use crate::pre_run::{PreRunManager, PreRunStep};
+use crate::project_interface::{self, OptionValue};
+use crate::reconnect::{ReconnectPolicy, ReconnectWatchdog};
+use crate::scheduler::{self, InstanceSchedule, SchedulePolicy, Scheduler};
+use crate::task_condition::TaskCondition;
+use crate::task_ledger::{DayReset, TaskLedgerSnapshot};
+use crate::task_queue::{QueuedTask, RunLoop, RunProgress, TaskQueue, TaskQueueRegistry};
+use crate::time_zone::Zone;
 
 // ============================================================================
 // 辅助函数
@@ -57,11 +72,9 @@ fn normalize_path(path: &str) -> PathBuf {
     components.iter().collect()
 }
 
-/// 获取 exe 所在目录下的 debug/logs 子目录
+/// 获取日志目录（便携模式为 exe 目录/debug，见 data_dirs）
 fn get_logs_dir() -> PathBuf {
-    let exe_path = std::env::current_exe().unwrap_or_default();
-    let exe_dir = exe_path.parent().unwrap_or(std::path::Path::new("."));
-    exe_dir.join("debug")
+    data_dirs().logs_dir.clone()
 }
 
 // ============================================================================
@@ -156,92 +169,102 @@ pub enum TaskStatus {
     Failed,
 }
 
-/// 实例运行时状态（用于前端查询）
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct InstanceState {
-    /// 控制器是否已连接（通过 MaaControllerConnected API 查询）
-    pub connected: bool,
-    /// 资源是否已加载（通过 MaaResourceLoaded API 查询）
-    pub resource_loaded: bool,
-    /// Tasker 是否已初始化
-    pub tasker_inited: bool,
-    /// 是否有任务正在运行（通过 MaaTaskerRunning API 查询）
-    pub is_running: bool,
-    /// 当前运行的任务 ID 列表
-    pub task_ids: Vec<i64>,
-}
-
 /// 所有实例状态的快照
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AllInstanceStates {
     pub instances: HashMap<String, InstanceState>,
+    /// 各实例的任务队列
+    pub task_queues: HashMap<String, Vec<QueuedTask>>,
+    /// 正在循环执行的实例的循环进度
+    pub task_runs: HashMap<String, RunProgress>,
     pub cached_adb_devices: Vec<AdbDevice>,
     pub cached_win32_windows: Vec<Win32Window>,
 }
 
 /// 实例运行时状态（持有 MaaFramework 对象句柄）
 pub struct InstanceRuntime {
-    pub resource: Option<*mut MaaResource>,
-    pub controller: Option<*mut MaaController>,
-    pub tasker: Option<*mut MaaTasker>,
-    pub agent_client: Option<*mut MaaAgentClient>,
+    pub resource: Option<Arc<Resource>>,
+    pub controller: Option<Arc<Controller>>,
+    /// Tasker 持有所绑定资源和控制器的引用，替换或销毁它们时不会提前释放
+    pub tasker: Option<Arc<Tasker>>,
+    pub agent_client: Option<Arc<AgentClient>>,
     pub agent_child: Option<Child>,
-    /// 当前运行的任务 ID 列表（用于刷新后恢复状态）
-    pub task_ids: Vec<i64>,
+    /// 各 Sink 的回调上下文（标记事件所属实例和来源），注册时由句柄封装额外持有一份
+    pub sink_contexts: SinkContexts,
+    /// 最近一次连接使用的控制器配置，自动重连时复用
+    pub controller_config: Option<ControllerConfig>,
 }
 
-// 为原始指针实现 Send 和 Sync
-// MaaFramework 的 API 是线程安全的
-unsafe impl Send for InstanceRuntime {}
-unsafe impl Sync for InstanceRuntime {}
-
-impl Default for InstanceRuntime {
-    fn default() -> Self {
+impl InstanceRuntime {
+    pub fn new(instance_id: &str) -> Self {
         Self {
             resource: None,
             controller: None,
             tasker: None,
             agent_client: None,
             agent_child: None,
-            task_ids: Vec::new(),
+            sink_contexts: SinkContexts::new(instance_id),
+            controller_config: None,
         }
     }
+
+    /// 获取 Tasker，不存在时创建并绑定当前的资源和控制器
+    fn ensure_tasker(&mut self, backend: &Arc<dyn MaaBackend>) -> Result<Arc<Tasker>, String> {
+        let resource = self.resource.clone().ok_or("Resource not loaded")?;
+        let controller = self.controller.clone().ok_or("Controller not connected")?;
+
+        if let Some(tasker) = &self.tasker {
+            debug!("Using existing tasker: {:?}", tasker.handle());
+            return Ok(tasker.clone());
+        }
+
+        debug!("Creating new tasker...");
+        let mut tasker = Tasker::create(backend).ok_or("Failed to create tasker")?;
+        // 任务状态通知和 Node 级别的通知（包含 focus 消息）
+        tasker.add_sink(self.sink_contexts.tasker.clone());
+        tasker.add_context_sink(self.sink_contexts.context.clone());
+        tasker.bind_resource(&resource);
+        tasker.bind_controller(&controller);
+        debug!("Tasker created and bound: {:?}", tasker.handle());
+
+        let tasker = Arc::new(tasker);
+        self.tasker = Some(tasker.clone());
+        Ok(tasker)
+    }
 }
 
 impl Drop for InstanceRuntime {
     fn drop(&mut self) {
-        if let Ok(guard) = MAA_LIBRARY.lock() {
-            if let Some(lib) = guard.as_ref() {
-                unsafe {
-                    // 断开并销毁 agent
-                    if let Some(agent) = self.agent_client.take() {
-                        (lib.maa_agent_client_disconnect)(agent);
-                        (lib.maa_agent_client_destroy)(agent);
-                    }
-                    // 终止 agent 子进程
-                    if let Some(mut child) = self.agent_child.take() {
-                        let _ = child.kill();
-                    }
-                    if let Some(tasker) = self.tasker.take() {
-                        (lib.maa_tasker_destroy)(tasker);
-                    }
-                    if let Some(controller) = self.controller.take() {
-                        (lib.maa_controller_destroy)(controller);
-                    }
-                    if let Some(resource) = self.resource.take() {
-                        (lib.maa_resource_destroy)(resource);
-                    }
-                }
-            }
+        // 断开 agent，销毁由 AgentClient 自身完成
+        if let Some(agent) = self.agent_client.take() {
+            agent.disconnect();
+        }
+        // 终止 agent 子进程
+        if let Some(mut child) = self.agent_child.take() {
+            let _ = child.kill();
         }
+        // 先释放 Tasker，再释放其绑定的控制器和资源
+        self.tasker.take();
+        self.controller.take();
+        self.resource.take();
     }
 }
 
+/// 单个实例的运行时，各实例独立加锁
+pub type SharedInstance = Arc<Mutex<InstanceRuntime>>;
+
 /// MaaFramework 运行时状态
 pub struct MaaState {
     pub lib_dir: Mutex<Option<PathBuf>>,
     pub resource_dir: Mutex<Option<PathBuf>>,
-    pub instances: Mutex<HashMap<String, InstanceRuntime>>,
+    /// 实例表只在查找、增删实例时短暂加锁，操作实例时只锁该实例
+    pub instances: Mutex<HashMap<String, SharedInstance>>,
+    /// 各实例的生命周期状态（独立加锁，回调线程只访问这里）
+    pub lifecycles: LifecycleRegistry,
+    /// 各实例的任务队列（独立加锁，可在持有队列锁时更新生命周期状态）
+    pub queues: TaskQueueRegistry,
+    /// 各实例最近一次成功启动任务的参数
+    pub last_starts: Mutex<HashMap<String, StartRequest>>,
     /// 缓存的 ADB 设备列表（全局共享，避免重复搜索）
     pub cached_adb_devices: Mutex<Vec<AdbDevice>>,
     /// 缓存的 Win32 窗口列表（全局共享）
@@ -254,65 +277,143 @@ impl Default for MaaState {
             lib_dir: Mutex::new(None),
             resource_dir: Mutex::new(None),
             instances: Mutex::new(HashMap::new()),
+            lifecycles: LifecycleRegistry::default(),
+            queues: TaskQueueRegistry::default(),
+            last_starts: Mutex::new(HashMap::new()),
             cached_adb_devices: Mutex::new(Vec::new()),
             cached_win32_windows: Mutex::new(Vec::new()),
         }
     }
 }
 
+impl MaaState {
+    /// 获取实例（只短暂持有实例表的锁）
+    pub fn instance(&self, instance_id: &str) -> Result<SharedInstance, String> {
+        let instances = self.instances.lock().map_err(|e| e.to_string())?;
+        instances
+            .get(instance_id)
+            .cloned()
+            .ok_or_else(|| "Instance not found".to_string())
+    }
+
+    /// 修改实例的任务队列（见 TaskQueueRegistry::update）
+    pub fn update_queue<F, R>(&self, instance_id: &str, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut TaskQueue) -> R,
+    {
+        self.queues
+            .update(&self.lifecycles, instance_id, f)
+            .ok_or_else(|| "Instance not found".to_string())
+    }
+}
+
 // ============================================================================
 // Tauri 命令
 // ============================================================================
 
-/// 获取可执行文件所在目录下的 maafw 子目录
-pub fn get_maafw_dir() -> Result<PathBuf, String> {
-    let exe_path =
-        std::env::current_exe().map_err(|e| format!("Failed to get executable path: {}", e))?;
-    let exe_dir = exe_path
-        .parent()
-        .ok_or_else(|| "Failed to get executable directory".to_string())?;
+/// 按搜索顺序查找 MaaFramework 运行库目录（见 maafw_search）
+pub fn search_maafw_dir() -> Result<MaafwSearch, String> {
+    let exe_dir = get_exe_directory()?;
+    Ok(maafw_search::search(&exe_dir))
+}
 
-    // macOS app bundle 需要特殊处理：exe 在 Contents/MacOS 下，maafw 应在 Contents/Resources 下
-    #[cfg(target_os = "macos")]
-    {
-        if exe_dir.ends_with("Contents/MacOS") {
-            let resources_dir = exe_dir.parent().unwrap().join("Resources").join("maafw");
-            if resources_dir.exists() {
-                return Ok(resources_dir);
-            }
-        }
+/// 获取 MaaFramework 运行库目录
+/// 所有候选目录都无效时返回 exe 目录下的 maafw，作为提示用户放置的默认位置
+pub fn get_maafw_dir() -> Result<PathBuf, String> {
+    match search_maafw_dir()?.chosen {
+        Some(candidate) => Ok(candidate.path),
+        None => Ok(get_exe_directory()?.join("maafw")),
     }
+}
 
-    Ok(exe_dir.join("maafw"))
+/// maa_init 返回结果
+#[derive(Serialize)]
+pub struct MaaInitResult {
+    pub version: String,
+    /// 实际使用的库目录
+    pub lib_dir: String,
+    /// 自动搜索的结果，调用方指定 lib_dir 时为 None
+    pub search: Option<MaafwSearch>,
 }
 
 /// 初始化 MaaFramework
-/// 如果提供 lib_dir 则使用该路径，否则自动从 exe 目录/maafw 加载
+/// 如果提供 lib_dir 则使用该路径，否则按搜索顺序查找运行库目录
 #[tauri::command]
-pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<String, String> {
+pub fn maa_init(
+    state: State<Arc<MaaState>>,
+    lib_dir: Option<String>,
+) -> Result<MaaInitResult, String> {
     info!("maa_init called, lib_dir: {:?}", lib_dir);
 
-    let lib_path = match lib_dir {
-        Some(dir) if !dir.is_empty() => PathBuf::from(&dir),
-        _ => get_maafw_dir()?,
+    let (lib_path, search) = match lib_dir {
+        Some(dir) if !dir.is_empty() => (PathBuf::from(&dir), None),
+        _ => {
+            let search = search_maafw_dir()?;
+            for candidate in &search.rejected {
+                debug!(
+                    "maa_init skipped {:?} ({:?}): {}",
+                    candidate.path,
+                    candidate.source,
+                    candidate.reason.as_deref().unwrap_or_default()
+                );
+            }
+            let path = match &search.chosen {
+                Some(candidate) => candidate.path.clone(),
+                None => get_exe_directory()?.join("maafw"),
+            };
+            (path, Some(search))
+        }
     };
 
     info!("maa_init using path: {:?}", lib_path);
 
-    if !lib_path.exists() {
-        let err = format!(
-            "MaaFramework library directory not found: {}",
-            lib_path.display()
-        );
+    // 模拟模式下后端已在启动时安装，不加载 MaaFramework
+    let simulated = current_backend().is_ok_and(|b| b.kind() == BackendKind::Simulator);
+    if !simulated && !lib_path.exists() {
+        let err = match &search {
+            Some(search) => search.not_found_message(),
+            None => format!(
+                "MaaFramework library directory not found: {}",
+                lib_path.display()
+            ),
+        };
         error!("{}", err);
         return Err(err);
     }
 
+    let version = init_from_dir(&state, &lib_path)?;
+    Ok(MaaInitResult {
+        version,
+        lib_dir: lib_path.to_string_lossy().into_owned(),
+        search,
+    })
+}
+
+/// 从指定目录加载 MaaFramework，返回版本号
+fn init_from_dir(state: &MaaState, lib_path: &Path) -> Result<String, String> {
+    if let Ok(backend) = current_backend() {
+        if backend.kind() == BackendKind::Simulator {
+            *state.lib_dir.lock().map_err(|e| e.to_string())? = Some(lib_path.to_path_buf());
+            let version = backend.version();
+            info!("maa_init using simulated backend, version: {}", version);
+            return Ok(version);
+        }
+
+        // 已加载时：同一目录直接复用，其他目录走重新加载流程，避免新旧库的对象混用
+        let loaded_dir = last_load_report().and_then(|report| report.lib_dir);
+        if loaded_dir.as_deref() == Some(lib_path) {
+            let version = backend.version();
+            info!("maa_init library already loaded, version: {}", version);
+            return Ok(version);
+        }
+        return reload_maa_library(state, lib_path);
+    }
+
     // 先设置 lib_dir，即使后续加载失败也能用于版本检查
-    *state.lib_dir.lock().map_err(|e| e.to_string())? = Some(lib_path.clone());
+    *state.lib_dir.lock().map_err(|e| e.to_string())? = Some(lib_path.to_path_buf());
 
     info!("maa_init loading library...");
-    init_maa_library(&lib_path).map_err(|e| e.to_string())?;
+    init_maa_library(lib_path).map_err(|e| e.to_string())?;
 
     let version = get_maa_version().unwrap_or_default();
     info!("maa_init success, version: {}", version);
@@ -320,6 +421,123 @@ pub fn maa_init(state: State<Arc<MaaState>>, lib_dir: Option<String>) -> Result<
     Ok(version)
 }
 
+/// 重新加载时等待旧库对象释放的最长时间
+const RELOAD_RELEASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// maa-library-reloaded 事件载荷
+#[derive(Clone, Serialize)]
+pub struct LibraryReloadedEvent {
+    pub lib_dir: String,
+    pub version: String,
+}
+
+/// 重新加载 MaaFramework：停止任务、销毁所有实例持有的对象、卸载旧库后加载新目录
+/// 实例本身保留并回到 Idle 状态，由前端重新连接
+fn reload_maa_library(state: &MaaState, lib_path: &Path) -> Result<String, String> {
+    info!("Reloading MaaFramework from {:?}", lib_path);
+
+    if let Ok(backend) = current_backend() {
+        if backend.kind() == BackendKind::Simulator {
+            return Err("MaaFramework cannot be reloaded in simulator mode".to_string());
+        }
+    }
+    if !lib_path.exists() {
+        return Err(format!(
+            "MaaFramework library directory not found: {}",
+            lib_path.display()
+        ));
+    }
+
+    // 先卸载全局后端，重新加载期间不会再创建旧库的对象
+    let old_backend = unload_backend()?;
+
+    // 换出所有实例的运行时，在锁外销毁（销毁 Tasker 会等待任务结束）
+    let instances: Vec<(String, SharedInstance)> = {
+        let instances = state.instances.lock().map_err(|e| e.to_string())?;
+        instances
+            .iter()
+            .map(|(id, instance)| (id.clone(), instance.clone()))
+            .collect()
+    };
+    for (instance_id, instance) in instances {
+        let runtime = {
+            let mut runtime = instance.lock().map_err(|e| e.to_string())?;
+            std::mem::replace(&mut *runtime, InstanceRuntime::new(&instance_id))
+        };
+        // 队列中未完成的任务不会再执行
+        state
+            .queues
+            .update(&state.lifecycles, &instance_id, |queue| {
+                queue.abort("MaaFramework reloaded")
+            });
+        if let Some(tasker) = &runtime.tasker {
+            if tasker.running() {
+                info!("Stopping tasks of instance {} before reload", instance_id);
+                state
+                    .lifecycles
+                    .update(&instance_id, |lc| lc.stop_requested());
+                tasker.post_stop();
+            }
+        }
+        drop(runtime);
+        state.lifecycles.update(&instance_id, |lc| lc.reset());
+    }
+
+    // 等待其他线程释放旧库的对象；超时后仍继续加载，旧库在最后一个对象销毁后卸载
+    if let Some(old_backend) = old_backend {
+        let deadline = Instant::now() + RELOAD_RELEASE_TIMEOUT;
+        while old_backend.strong_count() > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(50));
+        }
+        match old_backend.strong_count() {
+            0 => info!("Old MaaFramework library unloaded"),
+            count => warn!(
+                "Old MaaFramework library still referenced by {} handle(s), loading new library anyway",
+                count
+            ),
+        }
+    }
+
+    *state.lib_dir.lock().map_err(|e| e.to_string())? = Some(lib_path.to_path_buf());
+    init_maa_library(lib_path).map_err(|e| e.to_string())?;
+
+    let version = get_maa_version().unwrap_or_default();
+    info!("MaaFramework reloaded, version: {}", version);
+    emit_event(
+        "maa-library-reloaded",
+        LibraryReloadedEvent {
+            lib_dir: lib_path.to_string_lossy().into_owned(),
+            version: version.clone(),
+        },
+    );
+
+    Ok(version)
+}
+
+/// 重新加载 MaaFramework（无需重启 MXU）
+/// lib_dir 为空时重新加载当前目录
+#[tauri::command]
+pub async fn maa_reload_library(
+    state: State<'_, Arc<MaaState>>,
+    lib_dir: Option<String>,
+) -> Result<String, String> {
+    info!("maa_reload_library called, lib_dir: {:?}", lib_dir);
+
+    let lib_path = match lib_dir {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => match state.lib_dir.lock().map_err(|e| e.to_string())?.clone() {
+            Some(dir) => dir,
+            None => get_maafw_dir()?,
+        },
+    };
+
+    // 销毁 Tasker 和等待旧库释放可能阻塞较久，放到阻塞线程执行
+    let state = state.inner().clone();
+    tokio::task::spawn_blocking(move || reload_maa_library(&state, &lib_path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
 /// 设置资源目录
 #[tauri::command]
 pub fn maa_set_resource_dir(
@@ -413,91 +631,141 @@ pub fn maa_check_version(state: State<Arc<MaaState>>) -> Result<VersionCheckResu
     })
 }
 
-/// 查找 ADB 设备（结果会缓存到 MaaState）
-#[tauri::command]
-pub fn maa_find_adb_devices(state: State<Arc<MaaState>>) -> Result<Vec<AdbDevice>, String> {
-    info!("maa_find_adb_devices called");
-
-    let guard = MAA_LIBRARY.lock().map_err(|e| {
-        error!("Failed to lock MAA_LIBRARY: {}", e);
-        e.to_string()
-    })?;
-
-    let lib = guard.as_ref().ok_or_else(|| {
-        error!("MaaFramework not initialized");
-        "MaaFramework not initialized".to_string()
-    })?;
+/// 解析后的版本号
+#[derive(Serialize)]
+pub struct ParsedVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// 预发布标识，如 "beta.1"，正式版为空
+    pub pre: String,
+}
 
-    debug!("MaaFramework library loaded");
+/// 可选函数的解析情况
+#[derive(Serialize)]
+pub struct SymbolStatus {
+    pub name: &'static str,
+    pub feature: OptionalFeature,
+    pub available: bool,
+}
 
-    let devices = unsafe {
-        debug!("Creating ADB device list...");
-        let list = (lib.maa_toolkit_adb_device_list_create)();
-        if list.is_null() {
-            error!("Failed to create device list (null pointer)");
-            return Err("Failed to create device list".to_string());
-        }
-        debug!("Device list created successfully");
+/// MaaFramework 运行时能力报告
+#[derive(Serialize)]
+pub struct MaaCapabilities {
+    /// 当前后端，未加载时为 None
+    pub backend: Option<BackendKind>,
+    pub lib_dir: Option<String>,
+    /// 最近一次加载的动态库路径
+    pub library_paths: Vec<String>,
+    pub version: Option<String>,
+    pub parsed_version: Option<ParsedVersion>,
+    pub optional_symbols: Vec<SymbolStatus>,
+    /// 加载失败时缺失的必需函数
+    pub missing_symbols: Vec<String>,
+    pub load_error: Option<String>,
+    /// 因后端未加载或缺少可选函数而禁用的功能
+    pub disabled_features: Vec<OptionalFeature>,
+}
+
+/// 获取 MaaFramework 运行时能力（加载路径、版本、可选函数、禁用的功能）
+#[tauri::command]
+pub fn maa_get_capabilities(state: State<Arc<MaaState>>) -> Result<MaaCapabilities, String> {
+    debug!("maa_get_capabilities called");
 
-        // 确保清理
-        struct ListGuard<'a> {
-            list: *mut MaaToolkitAdbDeviceList,
-            lib: &'a MaaLibrary,
-        }
-        impl Drop for ListGuard<'_> {
-            fn drop(&mut self) {
-                log::debug!("Destroying ADB device list...");
-                unsafe {
-                    (self.lib.maa_toolkit_adb_device_list_destroy)(self.list);
-                }
-            }
-        }
-        let _guard = ListGuard { list, lib };
+    let backend = current_backend().ok();
+    let report = last_load_report().unwrap_or_default();
+    let lib_dir = state
+        .lib_dir
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clone()
+        .or(report.lib_dir.clone());
 
-        debug!("Calling MaaToolkitAdbDeviceFind...");
-        let found = (lib.maa_toolkit_adb_device_find)(list);
-        debug!("MaaToolkitAdbDeviceFind returned: {}", found);
+    // 后端未加载时仍尝试独立获取版本，便于提示版本过旧
+    let version = match &backend {
+        Some(backend) => Some(backend.version()),
+        None => lib_dir.as_deref().and_then(get_maa_version_standalone),
+    };
+    let parsed_version = version
+        .as_deref()
+        .and_then(|v| semver::Version::parse(v.trim_start_matches('v')).ok())
+        .map(|v| ParsedVersion {
+            major: v.major,
+            minor: v.minor,
+            patch: v.patch,
+            pre: v.pre.to_string(),
+        });
 
-        // MaaToolkitAdbDeviceFind 只在 buffer 为 null 时返回 false
-        // 即使没找到设备也会返回 true，所以不应该用返回值判断是否找到设备
-        if found == 0 {
-            warn!("MaaToolkitAdbDeviceFind returned false (unexpected)");
-            // 继续执行而不是直接返回，检查 list size
-        }
+    let optional_symbols: Vec<SymbolStatus> = OptionalFeature::ALL
+        .iter()
+        .map(|&feature| SymbolStatus {
+            name: feature.symbol(),
+            feature,
+            available: backend.as_ref().is_some_and(|b| b.supports(feature)),
+        })
+        .collect();
+    let disabled_features = optional_symbols
+        .iter()
+        .filter(|symbol| !symbol.available)
+        .map(|symbol| symbol.feature)
+        .collect();
 
-        let size = (lib.maa_toolkit_adb_device_list_size)(list);
-        info!("Found {} ADB device(s)", size);
+    let capabilities = MaaCapabilities {
+        backend: backend.as_ref().map(|b| b.kind()),
+        lib_dir: lib_dir.map(|p| p.to_string_lossy().to_string()),
+        library_paths: report
+            .library_paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+        version,
+        parsed_version,
+        optional_symbols,
+        missing_symbols: report.missing_symbols,
+        load_error: report.error,
+        disabled_features,
+    };
+    info!(
+        "maa_get_capabilities: backend={:?}, version={:?}, disabled={:?}",
+        capabilities.backend, capabilities.version, capabilities.disabled_features
+    );
+    Ok(capabilities)
+}
 
-        let mut devices = Vec::with_capacity(size as usize);
+/// 查找 ADB 设备（结果会缓存到 MaaState）
+#[tauri::command]
+pub fn maa_find_adb_devices(state: State<Arc<MaaState>>) -> Result<Vec<AdbDevice>, String> {
+    info!("maa_find_adb_devices called");
 
-        for i in 0..size {
-            let device = (lib.maa_toolkit_adb_device_list_at)(list, i);
-            if device.is_null() {
-                warn!("Device at index {} is null, skipping", i);
-                continue;
-            }
+    let backend = current_backend().map_err(|e| {
+        error!("{}", e);
+        e
+    })?;
 
-            let name = from_cstr((lib.maa_toolkit_adb_device_get_name)(device));
-            let adb_path = from_cstr((lib.maa_toolkit_adb_device_get_adb_path)(device));
-            let address = from_cstr((lib.maa_toolkit_adb_device_get_address)(device));
+    let found = backend.find_adb_devices().map_err(|e| {
+        error!("{}", e);
+        e
+    })?;
+    info!("Found {} ADB device(s)", found.len());
 
+    let devices: Vec<AdbDevice> = found
+        .into_iter()
+        .enumerate()
+        .map(|(i, device)| {
             debug!(
                 "Device {}: name='{}', adb_path='{}', address='{}'",
-                i, name, adb_path, address
+                i, device.name, device.adb_path, device.address
             );
-
-            devices.push(AdbDevice {
-                name,
-                adb_path,
-                address,
-                screencap_methods: (lib.maa_toolkit_adb_device_get_screencap_methods)(device),
-                input_methods: (lib.maa_toolkit_adb_device_get_input_methods)(device),
-                config: from_cstr((lib.maa_toolkit_adb_device_get_config)(device)),
-            });
-        }
-
-        devices
-    };
+            AdbDevice {
+                name: device.name,
+                adb_path: device.adb_path,
+                address: device.address,
+                screencap_methods: device.screencap_methods,
+                input_methods: device.input_methods,
+                config: device.config,
+            }
+        })
+        .collect();
 
     // 缓存搜索结果
     if let Ok(mut cached) = state.cached_adb_devices.lock() {
@@ -520,95 +788,51 @@ pub fn maa_find_win32_windows(
         class_regex, window_regex
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| {
-        error!("Failed to lock MAA_LIBRARY: {}", e);
-        e.to_string()
+    let backend = current_backend().map_err(|e| {
+        error!("{}", e);
+        e
     })?;
-    let lib = guard.as_ref().ok_or_else(|| {
-        error!("MaaFramework not initialized");
-        "MaaFramework not initialized".to_string()
-    })?;
-
-    let windows = unsafe {
-        debug!("Creating desktop window list...");
-        let list = (lib.maa_toolkit_desktop_window_list_create)();
-        if list.is_null() {
-            error!("Failed to create window list (null pointer)");
-            return Err("Failed to create window list".to_string());
-        }
 
-        struct ListGuard<'a> {
-            list: *mut MaaToolkitDesktopWindowList,
-            lib: &'a MaaLibrary,
-        }
-        impl Drop for ListGuard<'_> {
-            fn drop(&mut self) {
-                log::debug!("Destroying desktop window list...");
-                unsafe {
-                    (self.lib.maa_toolkit_desktop_window_list_destroy)(self.list);
+    let found = backend.find_desktop_windows().map_err(|e| {
+        error!("{}", e);
+        e
+    })?;
+    debug!("Found {} total window(s)", found.len());
+
+    // 编译正则表达式
+    let class_re = class_regex.as_ref().and_then(|r| regex::Regex::new(r).ok());
+    let window_re = window_regex
+        .as_ref()
+        .and_then(|r| regex::Regex::new(r).ok());
+
+    let windows: Vec<Win32Window> = found
+        .into_iter()
+        .filter(|window| {
+            // 过滤
+            if let Some(re) = &class_re {
+                if !re.is_match(&window.class_name) {
+                    return false;
                 }
             }
-        }
-        let _guard = ListGuard { list, lib };
-
-        debug!("Calling MaaToolkitDesktopWindowFindAll...");
-        let found = (lib.maa_toolkit_desktop_window_find_all)(list);
-        debug!("MaaToolkitDesktopWindowFindAll returned: {}", found);
-
-        if found == 0 {
-            info!("No windows found");
-            Vec::new()
-        } else {
-            let size = (lib.maa_toolkit_desktop_window_list_size)(list);
-            debug!("Found {} total window(s)", size);
-
-            let mut windows = Vec::with_capacity(size as usize);
-
-            // 编译正则表达式
-            let class_re = class_regex.as_ref().and_then(|r| regex::Regex::new(r).ok());
-            let window_re = window_regex
-                .as_ref()
-                .and_then(|r| regex::Regex::new(r).ok());
-
-            for i in 0..size {
-                let window = (lib.maa_toolkit_desktop_window_list_at)(list, i);
-                if window.is_null() {
-                    continue;
+            if let Some(re) = &window_re {
+                if !re.is_match(&window.window_name) {
+                    return false;
                 }
-
-                let class_name = from_cstr((lib.maa_toolkit_desktop_window_get_class_name)(window));
-                let window_name =
-                    from_cstr((lib.maa_toolkit_desktop_window_get_window_name)(window));
-
-                // 过滤
-                if let Some(re) = &class_re {
-                    if !re.is_match(&class_name) {
-                        continue;
-                    }
-                }
-                if let Some(re) = &window_re {
-                    if !re.is_match(&window_name) {
-                        continue;
-                    }
-                }
-
-                let handle = (lib.maa_toolkit_desktop_window_get_handle)(window);
-
-                debug!(
-                    "Window {}: handle={}, class='{}', name='{}'",
-                    i, handle as u64, class_name, window_name
-                );
-
-                windows.push(Win32Window {
-                    handle: handle as u64,
-                    class_name,
-                    window_name,
-                });
             }
-
-            windows
-        }
-    };
+            true
+        })
+        .map(|window| {
+            debug!(
+                "Window: handle={}, class='{}', name='{}'",
+                window.handle, window.class_name, window.window_name
+            );
+            Win32Window {
+                handle: window.handle,
+                class_name: window.class_name,
+                window_name: window.window_name,
+            }
+        })
+        .collect();
 
     // 缓存搜索结果
     if let Ok(mut cached) = state.cached_win32_windows.lock() {
@@ -623,15 +847,24 @@ pub fn maa_find_win32_windows(
 #[tauri::command]
 pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
     info!("maa_create_instance called, instance_id: {}", instance_id);
+    create_instance(&state, &instance_id)
+}
 
+/// 创建实例，已存在时直接返回（定时执行也会调用）
+pub(crate) fn create_instance(state: &MaaState, instance_id: &str) -> Result<(), String> {
     let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
 
-    if instances.contains_key(&instance_id) {
+    if instances.contains_key(instance_id) {
         debug!("maa_create_instance: instance already exists, returning success");
         return Ok(());
     }
 
-    instances.insert(instance_id.clone(), InstanceRuntime::default());
+    instances.insert(
+        instance_id.to_string(),
+        Arc::new(Mutex::new(InstanceRuntime::new(instance_id))),
+    );
+    state.lifecycles.insert(instance_id);
+    state.queues.insert(instance_id);
     info!("maa_create_instance success, instance_id: {}", instance_id);
     Ok(())
 }
@@ -643,11 +876,22 @@ pub fn maa_destroy_instance(
     instance_id: String,
 ) -> Result<(), String> {
     info!("maa_destroy_instance called, instance_id: {}", instance_id);
+    destroy_instance(&state, &instance_id)
+}
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let removed = instances.remove(&instance_id).is_some();
+/// maa_destroy_instance 的实现，无界面模式退出前也通过这里释放实例
+pub(crate) fn destroy_instance(state: &MaaState, instance_id: &str) -> Result<(), String> {
+    let removed = state
+        .instances
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(instance_id);
+    state.lifecycles.remove(instance_id);
+    state.queues.remove(instance_id);
 
-    if removed {
+    // 在实例表的锁外销毁，断开 Agent 和销毁 Tasker 可能阻塞
+    if let Some(instance) = removed {
+        drop(instance);
         info!("maa_destroy_instance success, instance_id: {}", instance_id);
     } else {
         warn!(
@@ -670,19 +914,33 @@ pub fn maa_connect_controller(
     info!("instance_id: {}", instance_id);
     info!("config: {:?}", config);
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| {
-        error!("Failed to lock MAA_LIBRARY: {}", e);
-        e.to_string()
-    })?;
-    let lib = guard.as_ref().ok_or_else(|| {
-        error!("MaaFramework not initialized");
-        "MaaFramework not initialized".to_string()
+    connect_controller(&state, &instance_id, &config)
+}
+
+/// 创建控制器并发起连接，替换实例原有的控制器（自动重连也使用此函数）
+pub(crate) fn connect_controller(
+    state: &MaaState,
+    instance_id: &str,
+    config: &ControllerConfig,
+) -> Result<i64, String> {
+    // 先取出控制器 Sink 上下文，持有引用直到控制器存入实例或被销毁
+    let instance = state.instance(instance_id)?;
+    let sink_context = instance
+        .lock()
+        .map_err(|e| e.to_string())?
+        .sink_contexts
+        .controller
+        .clone();
+
+    let backend = current_backend().map_err(|e| {
+        error!("Failed to get MaaFramework backend: {}", e);
+        e
     })?;
 
-    debug!("MaaFramework library loaded, creating controller...");
+    debug!("MaaFramework backend ready, creating controller...");
 
-    let controller = unsafe {
-        match &config {
+    let raw_controller = {
+        match config {
             ControllerConfig::Adb {
                 adb_path,
                 address,
@@ -711,22 +969,18 @@ pub fn maa_connect_controller(
                 );
                 debug!("  config: {}", config);
 
-                let adb_path_c = to_cstring(adb_path);
-                let address_c = to_cstring(address);
-                let config_c = to_cstring(config);
                 let agent_path = get_maafw_dir()
                     .map(|p| p.join("MaaAgentBinary").to_string_lossy().to_string())
                     .unwrap_or_default();
-                let agent_path_c = to_cstring(&agent_path);
 
                 debug!("Calling MaaAdbControllerCreate...");
-                let ctrl = (lib.maa_adb_controller_create)(
-                    adb_path_c.as_ptr(),
-                    address_c.as_ptr(),
+                let ctrl = backend.adb_controller_create(
+                    adb_path,
+                    address,
                     screencap_methods_u64,
                     input_methods_u64,
-                    config_c.as_ptr(),
-                    agent_path_c.as_ptr(),
+                    config,
+                    &agent_path,
                 );
                 debug!("MaaAdbControllerCreate returned: {:?}", ctrl);
                 ctrl
@@ -736,7 +990,7 @@ pub fn maa_connect_controller(
                 screencap_method,
                 mouse_method,
                 keyboard_method,
-            } => (lib.maa_win32_controller_create)(
+            } => backend.win32_controller_create(
                 *handle as *mut std::ffi::c_void,
                 *screencap_method,
                 *mouse_method,
@@ -755,7 +1009,7 @@ pub fn maa_connect_controller(
                 // 截图方法，默认为 DXGI_DesktopDup
                 let screencap = screencap_method.unwrap_or(MAA_WIN32_SCREENCAP_DXGI_DESKTOPDUP);
 
-                (lib.maa_gamepad_controller_create)(
+                backend.gamepad_controller_create(
                     *handle as *mut std::ffi::c_void,
                     gp_type,
                     screencap,
@@ -768,64 +1022,138 @@ pub fn maa_connect_controller(
         }
     };
 
-    if controller.is_null() {
+    let Some(mut controller) = (unsafe { Controller::from_raw(&backend, raw_controller) }) else {
         error!("Controller creation failed (null pointer)");
+        state.lifecycles.update(instance_id, |lc| {
+            lc.connect_failed("Failed to create controller")
+        });
         return Err("Failed to create controller".to_string());
-    }
+    };
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.connect_started());
 
-    debug!("Controller created successfully: {:?}", controller);
+    debug!("Controller created successfully: {:?}", controller.handle());
 
     // 添加回调 Sink，用于接收连接状态通知
     debug!("Adding controller sink...");
-    unsafe {
-        (lib.maa_controller_add_sink)(controller, get_event_callback(), std::ptr::null_mut());
-    }
+    controller.add_sink(sink_context);
 
     // 设置默认截图分辨率
     debug!("Setting screenshot target short side to 720...");
-    unsafe {
-        let short_side: i32 = 720;
-        (lib.maa_controller_set_option)(
-            controller,
-            MAA_CTRL_OPTION_SCREENSHOT_TARGET_SHORT_SIDE,
-            &short_side as *const i32 as *const std::ffi::c_void,
-            std::mem::size_of::<i32>() as u64,
-        );
-    }
+    controller.set_option_i32(MAA_CTRL_OPTION_SCREENSHOT_TARGET_SHORT_SIDE, 720);
 
     // 发起连接（不等待，通过回调通知完成）
     debug!("Calling MaaControllerPostConnection...");
-    let conn_id = unsafe { (lib.maa_controller_post_connection)(controller) };
+    let conn_id = controller.post_connection();
     info!("MaaControllerPostConnection returned conn_id: {}", conn_id);
 
     if conn_id == MAA_INVALID_ID {
         error!("Failed to post connection");
-        unsafe {
-            (lib.maa_controller_destroy)(controller);
-        }
+        state.lifecycles.update(instance_id, |lc| {
+            lc.connect_failed("Failed to post connection")
+        });
         return Err("Failed to post connection".to_string());
     }
 
     // 更新实例状态
     debug!("Updating instance state...");
-    {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
-
-        // 清理旧的控制器
-        if let Some(old_controller) = instance.controller.take() {
-            debug!("Destroying old controller...");
-            unsafe {
-                (lib.maa_controller_destroy)(old_controller);
+    let old_controller = {
+        let controller = Arc::new(controller);
+        // 实例在连接过程中被销毁时，持有的 Arc 仍可用，新控制器随实例一起释放
+        let mut instance = instance.lock().map_err(|e| e.to_string())?;
+
+        // 已有 Tasker 时重新绑定，旧控制器在解绑后才会被销毁
+        if let Some(tasker) = &instance.tasker {
+            debug!("Rebinding tasker to new controller...");
+            tasker.bind_controller(&controller);
+        }
+        instance.controller_config = Some(config.clone());
+        instance.controller.replace(controller)
+    };
+    // 在实例锁外销毁旧控制器
+    drop(old_controller);
+
+    Ok(conn_id)
+}
+
+/// 发起连接并阻塞等待连接结果（自动重连和定时执行使用）
+pub(crate) fn connect_controller_blocking(
+    state: &MaaState,
+    instance_id: &str,
+    config: &ControllerConfig,
+    timeout: Duration,
+) -> Result<(), String> {
+    connect_controller(state, instance_id, config)?;
+    if wait_lifecycle(state, instance_id, FailureStage::Connection, timeout, |s| {
+        s.connected
+    })? {
+        return Ok(());
+    }
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.connect_failed("Connection timed out"));
+    Err("Connection timed out".to_string())
+}
+
+/// 提交资源加载并阻塞等待加载完成（定时执行使用）
+pub(crate) fn load_resource_blocking(
+    state: &MaaState,
+    instance_id: &str,
+    paths: &[String],
+    timeout: Duration,
+) -> Result<(), String> {
+    load_resource(state, instance_id, paths)?;
+    if wait_lifecycle(state, instance_id, FailureStage::Resource, timeout, |s| {
+        s.resource_loaded
+    })? {
+        return Ok(());
+    }
+    Err("Resource loading timed out".to_string())
+}
+
+/// 轮询实例状态直到满足条件；对应阶段失败时返回失败原因，超时返回 Ok(false)
+fn wait_lifecycle(
+    state: &MaaState,
+    instance_id: &str,
+    stage: FailureStage,
+    timeout: Duration,
+    done: impl Fn(&InstanceState) -> bool,
+) -> Result<bool, String> {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        match state.lifecycles.snapshot(instance_id) {
+            None => return Err("Instance not found".to_string()),
+            Some(snapshot) if done(&snapshot) => return Ok(true),
+            Some(snapshot) => {
+                if let LifecycleState::Failed {
+                    stage: failed,
+                    reason,
+                } = snapshot.lifecycle
+                {
+                    if failed == stage {
+                        return Err(reason);
+                    }
+                }
             }
         }
-
-        instance.controller = Some(controller);
+        thread::sleep(Duration::from_millis(200));
     }
+    Ok(false)
+}
 
-    Ok(conn_id)
+/// 设置实例的自动重连策略（实例尚未创建时也可设置）
+#[tauri::command]
+pub fn maa_set_reconnect_policy(
+    watchdog: State<Arc<ReconnectWatchdog>>,
+    instance_id: String,
+    policy: ReconnectPolicy,
+) -> Result<(), String> {
+    info!(
+        "maa_set_reconnect_policy called, instance_id: {}, policy: {:?}",
+        instance_id, policy
+    );
+    watchdog.set_policy(&instance_id, policy)
 }
 
 /// 获取连接状态（通过 MaaControllerConnected API 查询）
@@ -839,22 +1167,15 @@ pub fn maa_get_connection_status(
         instance_id
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+    let controller = {
+        let instance = state.instance(&instance_id)?;
+        let instance = instance.lock().map_err(|e| e.to_string())?;
+        instance.controller.clone()
+    };
 
-    let status = match instance.controller {
-        Some(ctrl) => {
-            let connected = unsafe { (lib.maa_controller_connected)(ctrl) != 0 };
-            if connected {
-                ConnectionStatus::Connected
-            } else {
-                ConnectionStatus::Disconnected
-            }
-        }
-        None => ConnectionStatus::Disconnected,
+    let status = match controller {
+        Some(ctrl) if ctrl.connected() => ConnectionStatus::Connected,
+        _ => ConnectionStatus::Disconnected,
     };
 
     debug!("maa_get_connection_status result: {:?}", status);
@@ -873,42 +1194,48 @@ pub fn maa_load_resource(
         "maa_load_resource called, instance: {}, paths: {:?}",
         instance_id, paths
     );
+    load_resource(&state, &instance_id, &paths)
+}
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
+/// 创建或获取实例的资源并提交加载（定时执行也会调用）
+pub(crate) fn load_resource(
+    state: &MaaState,
+    instance_id: &str,
+    paths: &[String],
+) -> Result<Vec<i64>, String> {
+    let backend = current_backend()?;
 
     // 创建或获取资源
     let resource = {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
-
-        if instance.resource.is_none() {
-            let res = unsafe { (lib.maa_resource_create)() };
-            if res.is_null() {
-                return Err("Failed to create resource".to_string());
-            }
+        let instance = state.instance(instance_id)?;
+        let mut instance = instance.lock().map_err(|e| e.to_string())?;
 
-            // 添加回调 Sink，用于接收资源加载状态通知
-            debug!("Adding resource sink...");
-            unsafe {
-                (lib.maa_resource_add_sink)(res, get_event_callback(), std::ptr::null_mut());
-            }
+        match &instance.resource {
+            Some(resource) => resource.clone(),
+            None => {
+                let mut res = Resource::create(&backend).ok_or("Failed to create resource")?;
 
-            instance.resource = Some(res);
-        }
+                // 添加回调 Sink，用于接收资源加载状态通知
+                debug!("Adding resource sink...");
+                res.add_sink(instance.sink_contexts.resource.clone());
 
-        instance.resource.unwrap()
+                let res = Arc::new(res);
+                instance.resource = Some(res.clone());
+                res
+            }
+        }
     };
 
     // 加载资源（不等待，通过回调通知完成）
+    // 先记录待加载数量，避免回调先于记录到达
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.resource_posting(paths.len()));
     let mut res_ids = Vec::new();
-    for path in &paths {
+    for path in paths {
         let normalized = normalize_path(path);
         let normalized_str = normalized.to_string_lossy();
-        let path_c = to_cstring(&normalized_str);
-        let res_id = unsafe { (lib.maa_resource_post_bundle)(resource, path_c.as_ptr()) };
+        let res_id = resource.post_bundle(&normalized_str);
         info!(
             "Posted resource bundle: {} -> id: {}",
             normalized_str, res_id
@@ -922,6 +1249,11 @@ pub fn maa_load_resource(
         res_ids.push(res_id);
     }
 
+    let rejected = paths.len() - res_ids.len();
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.resource_posted(rejected));
+
     Ok(res_ids)
 }
 
@@ -936,15 +1268,13 @@ pub fn maa_is_resource_loaded(
         instance_id
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
+    let resource = {
+        let instance = state.instance(&instance_id)?;
+        let instance = instance.lock().map_err(|e| e.to_string())?;
+        instance.resource.clone()
+    };
 
-    let loaded = instance
-        .resource
-        .map_or(false, |res| unsafe { (lib.maa_resource_loaded)(res) != 0 });
+    let loaded = resource.is_some_and(|res| res.loaded());
 
     debug!("maa_is_resource_loaded result: {}", loaded);
     Ok(loaded)
@@ -958,129 +1288,88 @@ pub fn maa_destroy_resource(
 ) -> Result<(), String> {
     info!("maa_destroy_resource called, instance_id: {}", instance_id);
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances
-        .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
+    let (old_tasker, old_resource) = {
+        let instance = state.instance(&instance_id)?;
+        let mut instance = instance.lock().map_err(|e| e.to_string())?;
+        (instance.tasker.take(), instance.resource.take())
+    };
 
-    // 销毁旧的资源
-    if let Some(resource) = instance.resource.take() {
-        debug!("Destroying old resource...");
-        unsafe {
-            (lib.maa_resource_destroy)(resource);
-        }
-    }
+    // tasker 绑定了旧的 resource，需要一起释放（在实例锁外，销毁 Tasker 会等待任务结束）
+    // Tasker 先于其绑定的资源销毁；Agent 仍绑定旧资源时，旧资源随 Agent 一起释放
+    debug!("Releasing old tasker and resource...");
+    drop(old_tasker);
+    drop(old_resource);
 
-    // 如果有 tasker，也需要销毁（因为 tasker 绑定了旧的 resource）
-    if let Some(tasker) = instance.tasker.take() {
-        debug!("Destroying old tasker (bound to old resource)...");
-        unsafe {
-            (lib.maa_tasker_destroy)(tasker);
-        }
-    }
+    state
+        .lifecycles
+        .update(&instance_id, |lc| lc.resource_destroyed());
 
     info!("maa_destroy_resource success, instance_id: {}", instance_id);
     Ok(())
 }
 
-/// 运行任务（异步，通过回调通知完成状态）
-/// 返回任务 ID，前端通过监听 maa-callback 事件获取完成状态
+/// 运行单个任务：追加到实例任务队列的末尾（异步，通过回调通知完成状态）
+/// 返回队列项 ID，前端通过监听 maa-task-queue 事件获取任务状态
 #[tauri::command]
 pub fn maa_run_task(
     state: State<Arc<MaaState>>,
     instance_id: String,
     entry: String,
     pipeline_override: String,
-) -> Result<i64, String> {
+    label: Option<String>,
+    source_id: Option<String>,
+) -> Result<u64, String> {
     info!(
         "maa_run_task called, instance_id: {}, entry: {}, pipeline_override: {}",
         instance_id, entry, pipeline_override
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let (_resource, _controller, tasker) = {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
-
-        let resource = instance.resource.ok_or("Resource not loaded")?;
-        let controller = instance.controller.ok_or("Controller not connected")?;
-
-        // 创建或获取 tasker
-        if instance.tasker.is_none() {
-            let tasker = unsafe { (lib.maa_tasker_create)() };
-            if tasker.is_null() {
-                return Err("Failed to create tasker".to_string());
-            }
-
-            // 添加回调 Sink，用于接收任务状态通知
-            debug!("Adding tasker sink...");
-            unsafe {
-                (lib.maa_tasker_add_sink)(tasker, get_event_callback(), std::ptr::null_mut());
-            }
-
-            // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
-            debug!("Adding tasker context sink...");
-            unsafe {
-                (lib.maa_tasker_add_context_sink)(
-                    tasker,
-                    get_event_callback(),
-                    std::ptr::null_mut(),
-                );
-            }
-
-            // 绑定资源和控制器
-            unsafe {
-                (lib.maa_tasker_bind_resource)(tasker, resource);
-                (lib.maa_tasker_bind_controller)(tasker, controller);
-            }
-
-            instance.tasker = Some(tasker);
-        }
+    let backend = current_backend()?;
 
-        (resource, controller, instance.tasker.unwrap())
+    let tasker = {
+        let instance = state.instance(&instance_id)?;
+        let mut instance = instance.lock().map_err(|e| e.to_string())?;
+        instance.ensure_tasker(&backend)?
     };
 
     // 检查初始化状态
-    let inited = unsafe { (lib.maa_tasker_inited)(tasker) };
+    let inited = tasker.inited();
     info!("Tasker inited status: {}", inited);
-    if inited == 0 {
-        error!("Tasker not properly initialized, inited: {}", inited);
+    if !inited {
+        error!("Tasker not properly initialized");
+        state.lifecycles.update(&instance_id, |lc| {
+            lc.tasker_failed("Tasker not properly initialized")
+        });
         return Err("Tasker not properly initialized".to_string());
     }
+    state
+        .lifecycles
+        .update(&instance_id, |lc| lc.tasker_ready());
+
+    // 加入队列（不等待，由派发线程提交，通过回调通知完成）
+    let task = TaskConfig {
+        entry,
+        pipeline_override,
+        task_name: None,
+        label,
+        source_id,
+        retry_count: None,
+        retry_delay_ms: None,
+        timeout_ms: None,
+        conditions: Vec::new(),
+    };
+    state
+        .update_queue(&instance_id, |queue| queue.enqueue(&[task], false))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Failed to queue task".to_string())
+}
 
-    // 提交任务（不等待，通过回调通知完成）
-    let entry_c = to_cstring(&entry);
-    let override_c = to_cstring(&pipeline_override);
-
-    info!(
-        "Calling MaaTaskerPostTask: entry={}, override={}",
-        entry, pipeline_override
-    );
-    let task_id =
-        unsafe { (lib.maa_tasker_post_task)(tasker, entry_c.as_ptr(), override_c.as_ptr()) };
-
-    info!("MaaTaskerPostTask returned task_id: {}", task_id);
-
-    if task_id == MAA_INVALID_ID {
-        return Err("Failed to post task".to_string());
-    }
-
-    // 缓存 task_id，用于刷新后恢复状态
-    {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        if let Some(instance) = instances.get_mut(&instance_id) {
-            instance.task_ids.push(task_id);
-        }
-    }
-
-    Ok(task_id)
+/// 获取实例的 Tasker
+fn get_tasker(state: &MaaState, instance_id: &str) -> Result<Option<Arc<Tasker>>, String> {
+    let instance = state.instance(instance_id)?;
+    let instance = instance.lock().map_err(|e| e.to_string())?;
+    Ok(instance.tasker.clone())
 }
 
 /// 获取任务状态
@@ -1095,16 +1384,8 @@ pub fn maa_get_task_status(
         instance_id, task_id
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let tasker = {
-        let instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-        instance.tasker.ok_or("Tasker not created")?
-    };
-
-    let status = unsafe { (lib.maa_tasker_status)(tasker, task_id) };
+    let tasker = get_tasker(&state, &instance_id)?.ok_or("Tasker not created")?;
+    let status = tasker.status(task_id);
 
     let result = match status {
         MAA_STATUS_PENDING => TaskStatus::Pending,
@@ -1121,27 +1402,75 @@ pub fn maa_get_task_status(
 #[tauri::command]
 pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
     info!("maa_stop_task called, instance_id: {}", instance_id);
+    stop_tasks(&state, &instance_id)
+}
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
+/// maa_stop_task 的实现：取消等待中的任务并停止 Tasker
+pub(crate) fn stop_tasks(state: &MaaState, instance_id: &str) -> Result<(), String> {
     let tasker = {
-        let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
-        // 清空缓存的 task_ids
-        instance.task_ids.clear();
-        instance.tasker.ok_or("Tasker not created")?
+        let instance = state.instance(instance_id)?;
+        let instance = instance.lock().map_err(|e| e.to_string())?;
+        instance.tasker.clone().ok_or("Tasker not created")?
     };
 
+    // 取消队列中尚未开始的任务（自动重连后也不再继续）
+    state.update_queue(instance_id, TaskQueue::stop)?;
+
+    // 清除生命周期中记录的 MaaFramework 任务 ID，进入停止中状态
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.stop_requested());
+
     debug!("Calling MaaTaskerPostStop...");
-    let stop_id = unsafe { (lib.maa_tasker_post_stop)(tasker) };
+    let stop_id = tasker.post_stop();
     info!("MaaTaskerPostStop returned: {}", stop_id);
 
     Ok(())
 }
 
+/// 暂停任务队列
+/// immediate 为 false 时等当前任务结束后暂停，为 true 时立即停止当前任务，恢复后可重新执行
+#[tauri::command]
+pub fn maa_pause_tasks(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    immediate: bool,
+) -> Result<(), String> {
+    info!(
+        "maa_pause_tasks called, instance_id: {}, immediate: {}",
+        instance_id, immediate
+    );
+
+    let interrupted = state.update_queue(&instance_id, |queue| queue.pause(immediate))??;
+    if let Some(task_id) = interrupted {
+        info!("Interrupting task {} for pause", task_id);
+        let tasker = get_tasker(&state, &instance_id)?.ok_or("Tasker not created")?;
+        state
+            .lifecycles
+            .update(&instance_id, |lc| lc.stop_requested());
+        let stop_id = tasker.post_stop();
+        info!("MaaTaskerPostStop returned: {}", stop_id);
+    }
+
+    Ok(())
+}
+
+/// 恢复暂停的任务队列，按顺序继续执行剩余任务
+/// rerun_interrupted 为 false 时不再执行暂停时被中断的任务；返回将执行的任务的队列项 ID
+#[tauri::command]
+pub fn maa_resume_tasks(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    rerun_interrupted: bool,
+) -> Result<Vec<u64>, String> {
+    info!(
+        "maa_resume_tasks called, instance_id: {}, rerun_interrupted: {}",
+        instance_id, rerun_interrupted
+    );
+
+    state.update_queue(&instance_id, |queue| queue.resume(rerun_interrupted))?
+}
+
 /// 覆盖已提交任务的 Pipeline 配置（用于运行中修改尚未执行的任务选项）
 #[tauri::command]
 pub fn maa_override_pipeline(
@@ -1155,24 +1484,142 @@ pub fn maa_override_pipeline(
         instance_id, task_id, pipeline_override
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
+    let tasker = get_tasker(&state, &instance_id)?.ok_or("Tasker not created")?;
+    let success = tasker.override_pipeline(task_id, &pipeline_override)?;
 
-    let tasker = {
-        let instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-        instance.tasker.ok_or("Tasker not created")?
-    };
+    info!("MaaTaskerOverridePipeline returned: {}", success);
+    Ok(success)
+}
 
-    let override_fn = lib
-        .maa_tasker_override_pipeline
-        .ok_or("MaaTaskerOverridePipeline not available in this MaaFramework version")?;
+/// 获取实例的任务队列
+#[tauri::command]
+pub fn maa_get_task_queue(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<Vec<QueuedTask>, String> {
+    state
+        .queues
+        .snapshot(&instance_id)
+        .ok_or_else(|| "Instance not found".to_string())
+}
 
-    let override_c = to_cstring(&pipeline_override);
-    let success = unsafe { (override_fn)(tasker, task_id, override_c.as_ptr()) };
+/// 在任务队列的 index 处插入任务（只能插入到尚未开始的任务之间，index 为空时追加到末尾）
+/// 返回新任务的队列项 ID
+#[tauri::command]
+pub fn maa_queue_insert(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    index: Option<usize>,
+    tasks: Vec<TaskConfig>,
+) -> Result<Vec<u64>, String> {
+    info!(
+        "maa_queue_insert called, instance_id: {}, index: {:?}, tasks: {}",
+        instance_id,
+        index,
+        tasks.len()
+    );
+    validate_conditions(&tasks)?;
+    state.update_queue(&instance_id, |queue| {
+        queue.insert(index.unwrap_or(usize::MAX), &tasks)
+    })
+}
 
-    info!("MaaTaskerOverridePipeline returned: {}", success);
-    Ok(success != 0)
+/// 从任务队列中删除尚未开始的任务
+#[tauri::command]
+pub fn maa_queue_remove(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    item_id: u64,
+) -> Result<(), String> {
+    info!(
+        "maa_queue_remove called, instance_id: {}, item_id: {}",
+        instance_id, item_id
+    );
+    state.update_queue(&instance_id, |queue| queue.remove(item_id))?
+}
+
+/// 调整尚未开始的任务的顺序，item_ids 需包含所有尚未开始的任务
+#[tauri::command]
+pub fn maa_queue_reorder(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    item_ids: Vec<u64>,
+) -> Result<(), String> {
+    info!(
+        "maa_queue_reorder called, instance_id: {}, item_ids: {:?}",
+        instance_id, item_ids
+    );
+    state.update_queue(&instance_id, |queue| queue.reorder(&item_ids))?
+}
+
+/// 修改队列中任务的 Pipeline 覆盖（尚未开始或正在运行的任务）
+#[tauri::command]
+pub fn maa_queue_update_override(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    item_id: u64,
+    pipeline_override: String,
+) -> Result<(), String> {
+    info!(
+        "maa_queue_update_override called, instance_id: {}, item_id: {}, pipeline_override: {}",
+        instance_id, item_id, pipeline_override
+    );
+
+    let task_id = state.update_queue(&instance_id, |queue| {
+        queue.update_override(item_id, &pipeline_override)
+    })??;
+    // 正在运行的任务同时覆盖到 Tasker，影响尚未执行的节点
+    if let Some(task_id) = task_id {
+        let tasker = get_tasker(&state, &instance_id)?.ok_or("Tasker not created")?;
+        if !tasker.override_pipeline(task_id, &pipeline_override)? {
+            return Err("Failed to override pipeline".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// 设置实例每日完成记录的重置时间和时区（实例尚未创建时也可设置）
+#[tauri::command]
+pub fn maa_set_day_reset(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    reset: DayReset,
+) -> Result<(), String> {
+    info!(
+        "maa_set_day_reset called, instance_id: {}, reset: {:?}",
+        instance_id, reset
+    );
+    state.queues.ledger.set_reset(&instance_id, reset)?;
+    state.queues.ledger.flush();
+    Ok(())
+}
+
+/// 获取实例的每日完成记录
+#[tauri::command]
+pub fn maa_get_task_ledger(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+) -> Result<TaskLedgerSnapshot, String> {
+    state.queues.ledger.snapshot(&instance_id)
+}
+
+/// 清除实例中指定任务（为空时清除全部任务）的完成记录
+#[tauri::command]
+pub fn maa_clear_task_ledger(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    task_name: Option<String>,
+) -> Result<(), String> {
+    info!(
+        "maa_clear_task_ledger called, instance_id: {}, task_name: {:?}",
+        instance_id, task_name
+    );
+    state
+        .queues
+        .ledger
+        .clear(&instance_id, task_name.as_deref())?;
+    state.queues.ledger.flush();
+    Ok(())
 }
 
 /// 检查是否正在运行
@@ -1180,40 +1627,31 @@ pub fn maa_override_pipeline(
 pub fn maa_is_running(state: State<Arc<MaaState>>, instance_id: String) -> Result<bool, String> {
     // debug!("maa_is_running called, instance_id: {}", instance_id);
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let tasker = {
-        let instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-        match instance.tasker {
-            Some(t) => t,
-            None => {
-                // debug!("maa_is_running: no tasker, returning false");
-                return Ok(false);
-            }
-        }
+    let Some(tasker) = get_tasker(&state, &instance_id)? else {
+        // debug!("maa_is_running: no tasker, returning false");
+        return Ok(false);
     };
 
-    let running = unsafe { (lib.maa_tasker_running)(tasker) };
-    let result = running != 0;
-    // debug!("maa_is_running result: {} (raw: {})", result, running);
+    let result = tasker.running();
+    // debug!("maa_is_running result: {}", result);
     Ok(result)
 }
 
+/// 获取实例的控制器
+fn get_controller(state: &MaaState, instance_id: &str) -> Result<Arc<Controller>, String> {
+    let instance = state.instance(instance_id)?;
+    let instance = instance.lock().map_err(|e| e.to_string())?;
+    instance
+        .controller
+        .clone()
+        .ok_or_else(|| "Controller not connected".to_string())
+}
+
 /// 发起截图请求
 #[tauri::command]
 pub fn maa_post_screencap(state: State<Arc<MaaState>>, instance_id: String) -> Result<i64, String> {
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let controller = {
-        let instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-        instance.controller.ok_or("Controller not connected")?
-    };
-
-    let screencap_id = unsafe { (lib.maa_controller_post_screencap)(controller) };
+    let controller = get_controller(&state, &instance_id)?;
+    let screencap_id = controller.post_screencap();
 
     if screencap_id == MAA_INVALID_ID {
         return Err("Failed to post screencap".to_string());
@@ -1228,61 +1666,14 @@ pub fn maa_get_cached_image(
     state: State<Arc<MaaState>>,
     instance_id: String,
 ) -> Result<String, String> {
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let controller = {
-        let instances = state.instances.lock().map_err(|e| e.to_string())?;
-        let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-        instance.controller.ok_or("Controller not connected")?
-    };
-
-    unsafe {
-        // 创建图像缓冲区
-        let image_buffer = (lib.maa_image_buffer_create)();
-        if image_buffer.is_null() {
-            return Err("Failed to create image buffer".to_string());
-        }
-
-        // 确保缓冲区被释放
-        struct ImageBufferGuard<'a> {
-            buffer: *mut MaaImageBuffer,
-            lib: &'a MaaLibrary,
-        }
-        impl Drop for ImageBufferGuard<'_> {
-            fn drop(&mut self) {
-                unsafe {
-                    (self.lib.maa_image_buffer_destroy)(self.buffer);
-                }
-            }
-        }
-        let _guard = ImageBufferGuard {
-            buffer: image_buffer,
-            lib,
-        };
+    let controller = get_controller(&state, &instance_id)?;
+    let data = controller.cached_image()?;
 
-        // 获取缓存的图像
-        let success = (lib.maa_controller_cached_image)(controller, image_buffer);
-        if success == 0 {
-            return Err("Failed to get cached image".to_string());
-        }
-
-        // 获取编码后的图像数据
-        let encoded_ptr = (lib.maa_image_buffer_get_encoded)(image_buffer);
-        let encoded_size = (lib.maa_image_buffer_get_encoded_size)(image_buffer);
-
-        if encoded_ptr.is_null() || encoded_size == 0 {
-            return Err("No image data available".to_string());
-        }
-
-        // 复制数据并转换为 base64
-        let data = std::slice::from_raw_parts(encoded_ptr, encoded_size as usize);
-        use base64::{engine::general_purpose::STANDARD, Engine as _};
-        let base64_str = STANDARD.encode(data);
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    let base64_str = STANDARD.encode(data);
 
-        // 返回带 data URL 前缀的 base64 字符串
-        Ok(format!("data:image/png;base64,{}", base64_str))
-    }
+    // 返回带 data URL 前缀的 base64 字符串
+    Ok(format!("data:image/png;base64,{}", base64_str))
 }
 
 /// Agent 配置
@@ -1300,10 +1691,65 @@ pub struct AgentConfig {
 pub struct TaskConfig {
     pub entry: String,
     pub pipeline_override: String,
+    /// interface 中的任务名，每日完成记录以此为键
+    #[serde(default)]
+    pub task_name: Option<String>,
+    /// 显示名称
+    #[serde(default)]
+    pub label: Option<String>,
+    /// 前端任务列表中对应的任务 ID
+    #[serde(default)]
+    pub source_id: Option<String>,
+    /// 失败后最多重试的次数
+    #[serde(default)]
+    pub retry_count: Option<u32>,
+    /// 重试前的等待时间（毫秒）
+    #[serde(default)]
+    pub retry_delay_ms: Option<u64>,
+    /// 单次执行的最长时间（毫秒），超时后停止任务
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 执行条件，提交前检查，不满足时跳过
+    #[serde(default)]
+    pub conditions: Vec<TaskCondition>,
+}
+
+/// 启动任务的参数（运行结束后的动作启动其他实例时，复用该实例上一次的参数；
+/// 定时执行使用前端同步的参数）
+#[derive(Debug, Clone, Deserialize)]
+pub struct StartRequest {
+    pub tasks: Vec<TaskConfig>,
+    #[serde(default)]
+    pub agent_config: Option<AgentConfig>,
+    pub cwd: String,
+    #[serde(default)]
+    pub tcp_compat_mode: bool,
+    #[serde(default)]
+    pub run_loop: RunLoop,
+    /// 跳过本周期内已完成的任务
+    #[serde(default)]
+    pub skip_completed: bool,
+    /// 执行条件中时间段和星期几所在的时区（定时执行时为策略的时区）
+    #[serde(default)]
+    pub timezone: Zone,
+}
+
+/// 检查任务的执行条件是否有效，避免无效的条件在派发时才作为跳过原因出现
+fn validate_conditions(tasks: &[TaskConfig]) -> Result<(), String> {
+    for task in tasks {
+        for condition in &task.conditions {
+            condition
+                .validate()
+                .map_err(|e| format!("Invalid condition of task {}: {}", task.entry, e))?;
+        }
+    }
+    Ok(())
 }
 
 /// 启动任务（支持 Agent）
+/// 替换实例的任务队列，返回各任务的队列项 ID
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn maa_start_tasks(
     state: State<'_, Arc<MaaState>>,
     instance_id: String,
@@ -1311,8 +1757,39 @@ pub async fn maa_start_tasks(
     agent_config: Option<AgentConfig>,
     cwd: String,
     tcp_compat_mode: bool,
-) -> Result<Vec<i64>, String> {
+    run_loop: Option<RunLoop>,
+    skip_completed: Option<bool>,
+    timezone: Option<String>,
+) -> Result<Vec<u64>, String> {
     info!("maa_start_tasks called");
+    let request = StartRequest {
+        tasks,
+        agent_config,
+        cwd,
+        tcp_compat_mode,
+        run_loop: run_loop.unwrap_or_default(),
+        skip_completed: skip_completed.unwrap_or(false),
+        timezone: Zone::parse(timezone.as_deref().unwrap_or_default())?,
+    };
+    start_tasks(&state, instance_id, request).await
+}
+
+/// maa_start_tasks 的实现，运行结束后的动作也通过这里启动其他实例
+pub(crate) async fn start_tasks(
+    state: &MaaState,
+    instance_id: String,
+    request: StartRequest,
+) -> Result<Vec<u64>, String> {
+    let saved_request = request.clone();
+    let StartRequest {
+        mut tasks,
+        agent_config,
+        cwd,
+        tcp_compat_mode,
+        run_loop,
+        skip_completed,
+        timezone,
+    } = request;
     info!(
         "instance_id: {}, tasks: {}, cwd: {}, tcp_compat_mode: {}",
         instance_id,
@@ -1320,169 +1797,63 @@ pub async fn maa_start_tasks(
         cwd,
         tcp_compat_mode
     );
+    validate_conditions(&tasks)?;
 
-    // 使用 SendPtr 包装原始指针，以便跨越 await 边界
+    // 克隆出库和句柄的引用，跨越 await 边界时保证对象存活
+    let backend = current_backend()?;
+    let instance = state.instance(&instance_id)?;
     let (resource, tasker) = {
-        debug!("[start_tasks] Acquiring MAA_LIBRARY lock...");
-        let guard = MAA_LIBRARY
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        debug!("[start_tasks] MAA_LIBRARY lock acquired");
-        let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-        debug!("[start_tasks] Acquiring instances lock...");
-        let mut instances = state
-            .instances
+        debug!("[start_tasks] Acquiring instance lock...");
+        let mut instance = instance
             .lock()
             .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        debug!("[start_tasks] Instances lock acquired");
-        let instance = instances
-            .get_mut(&instance_id)
-            .ok_or("Instance not found")?;
-        debug!("[start_tasks] Instance found: {}", instance_id);
-
-        let resource = instance.resource.ok_or("Resource not loaded")?;
-        debug!("[start_tasks] Resource pointer: {:?}", resource);
-        let controller = instance.controller.ok_or("Controller not connected")?;
-        debug!("[start_tasks] Controller pointer: {:?}", controller);
-
-        // 创建或获取 tasker
-        if instance.tasker.is_none() {
-            debug!("[start_tasks] Creating new tasker...");
-            let tasker = unsafe { (lib.maa_tasker_create)() };
-            debug!("[start_tasks] maa_tasker_create returned: {:?}", tasker);
-            if tasker.is_null() {
-                return Err("Failed to create tasker".to_string());
-            }
-
-            // 添加回调 Sink，用于接收任务状态通知
-            debug!("[start_tasks] Adding tasker sink...");
-            unsafe {
-                (lib.maa_tasker_add_sink)(tasker, get_event_callback(), std::ptr::null_mut());
-            }
-            debug!("[start_tasks] Tasker sink added");
-
-            // 添加 Context Sink，用于接收 Node 级别的通知（包含 focus 消息）
-            debug!("[start_tasks] Adding tasker context sink...");
-            unsafe {
-                (lib.maa_tasker_add_context_sink)(
-                    tasker,
-                    get_event_callback(),
-                    std::ptr::null_mut(),
-                );
-            }
-            debug!("[start_tasks] Tasker context sink added");
-
-            // 绑定资源和控制器
-            debug!("[start_tasks] Binding resource...");
-            unsafe {
-                (lib.maa_tasker_bind_resource)(tasker, resource);
-            }
-            debug!("[start_tasks] Resource bound");
-            debug!("[start_tasks] Binding controller...");
-            unsafe {
-                (lib.maa_tasker_bind_controller)(tasker, controller);
-            }
-            debug!("[start_tasks] Controller bound");
+        debug!("[start_tasks] Instance lock acquired: {}", instance_id);
 
-            instance.tasker = Some(tasker);
-            debug!("[start_tasks] Tasker created and stored");
-        } else {
-            debug!("[start_tasks] Using existing tasker: {:?}", instance.tasker);
-        }
-
-        let tasker_ptr = instance.tasker.unwrap();
-        debug!("[start_tasks] Tasker pointer for SendPtr: {:?}", tasker_ptr);
-        (SendPtr::new(resource), SendPtr::new(tasker_ptr))
+        let tasker = instance.ensure_tasker(&backend)?;
+        let resource = instance.resource.clone().ok_or("Resource not loaded")?;
+        (resource, tasker)
     };
     debug!("[start_tasks] Resource and tasker acquired, proceeding...");
 
     // 启动 Agent（如果配置了）
-    // agent_client 用 SendPtr 包装，可跨 await 边界
     debug!("[start_tasks] Checking agent config...");
-    let agent_client: Option<SendPtr<MaaAgentClient>> = if let Some(agent) = &agent_config {
+    let agent_client: Option<Arc<AgentClient>> = if let Some(agent) = &agent_config {
         info!("[start_tasks] Starting agent: {:?}", agent);
 
-        // 创建 AgentClient 并获取 socket_id（在 guard 作用域内完成同步操作）
-        debug!("[agent] Acquiring MAA_LIBRARY lock for agent creation...");
-        let (agent_client, socket_id) = {
-            let guard = MAA_LIBRARY
-                .lock()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            debug!("[agent] MAA_LIBRARY lock acquired");
-            let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-            // 根据 tcp_compat_mode 选择创建方式
-            // TCP 模式用于不支持 AF_UNIX 的旧版 Windows（Build 17063 之前）
-            let agent_client = if tcp_compat_mode {
-                // 检查 TCP 模式是否可用（旧版本 MaaFramework 可能不支持）
-                if let Some(create_tcp_fn) = lib.maa_agent_client_create_tcp {
-                    debug!("[agent] Using TCP compat mode, calling maa_agent_client_create_tcp...");
-                    let client = unsafe { create_tcp_fn(0) }; // port=0 自动选择端口
-                    debug!("[agent] maa_agent_client_create_tcp returned: {:?}", client);
-                    client
-                } else {
-                    warn!("[agent] TCP compat mode requested but MaaAgentClientCreateTcp not available, falling back to V2");
-                    let client = unsafe { (lib.maa_agent_client_create_v2)(std::ptr::null()) };
-                    debug!("[agent] maa_agent_client_create_v2 (fallback) returned: {:?}", client);
-                    client
-                }
-            } else {
-                debug!("[agent] Calling maa_agent_client_create_v2...");
-                let client = unsafe { (lib.maa_agent_client_create_v2)(std::ptr::null()) };
-                debug!("[agent] maa_agent_client_create_v2 returned: {:?}", client);
-                client
-            };
-
-            if agent_client.is_null() {
-                error!("[agent] Failed to create agent client (null pointer)");
-                return Err("Failed to create agent client".to_string());
-            }
-
-            // 绑定资源
-            debug!(
-                "[agent] Binding resource to agent client, resource ptr: {:?}",
-                resource.as_ptr()
-            );
-            unsafe {
-                (lib.maa_agent_client_bind_resource)(agent_client, resource.as_ptr());
+        // 根据 tcp_compat_mode 选择创建方式
+        // TCP 模式用于不支持 AF_UNIX 的旧版 Windows（Build 17063 之前）
+        let tcp_client = if tcp_compat_mode {
+            debug!("[agent] Using TCP compat mode...");
+            AgentClient::create_tcp(&backend, 0) // port=0 自动选择端口
+        } else {
+            None
+        };
+        let client = tcp_client.or_else(|| {
+            if tcp_compat_mode {
+                // 旧版本 MaaFramework 可能不支持 TCP 模式
+                warn!("[agent] TCP compat mode requested but MaaAgentClientCreateTcp not available, falling back to V2");
             }
-            debug!("[agent] Resource bound to agent client");
-
-            // 获取 socket identifier
-            debug!("[agent] Getting socket identifier...");
-            let socket_id = unsafe {
-                debug!("[agent] Creating string buffer...");
-                let id_buffer = (lib.maa_string_buffer_create)();
-                debug!("[agent] String buffer created: {:?}", id_buffer);
-                if id_buffer.is_null() {
-                    error!("[agent] Failed to create string buffer (null pointer)");
-                    (lib.maa_agent_client_destroy)(agent_client);
-                    return Err("Failed to create string buffer".to_string());
-                }
-
-                debug!("[agent] Calling maa_agent_client_identifier...");
-                let success = (lib.maa_agent_client_identifier)(agent_client, id_buffer);
-                debug!("[agent] maa_agent_client_identifier returned: {}", success);
-                if success == 0 {
-                    error!("[agent] Failed to get agent identifier");
-                    (lib.maa_string_buffer_destroy)(id_buffer);
-                    (lib.maa_agent_client_destroy)(agent_client);
-                    return Err("Failed to get agent identifier".to_string());
-                }
+            AgentClient::create(&backend)
+        });
+        let Some(mut client) = client else {
+            error!("[agent] Failed to create agent client (null pointer)");
+            return Err("Failed to create agent client".to_string());
+        };
 
-                debug!("[agent] Getting string from buffer...");
-                let id = from_cstr((lib.maa_string_buffer_get)(id_buffer));
-                debug!("[agent] Got socket_id: {}", id);
-                (lib.maa_string_buffer_destroy)(id_buffer);
-                debug!("[agent] String buffer destroyed");
-                id
-            };
+        // 绑定资源
+        debug!(
+            "[agent] Binding resource to agent client, resource ptr: {:?}",
+            resource.handle()
+        );
+        client.bind_resource(&resource);
 
-            debug!("[agent] AgentClient created successfully, wrapping in SendPtr");
-            (SendPtr::new(agent_client), socket_id)
+        // 获取 socket identifier
+        debug!("[agent] Getting socket identifier...");
+        let Some(socket_id) = client.identifier() else {
+            error!("[agent] Failed to get agent identifier");
+            return Err("Failed to get agent identifier".to_string());
         };
-        debug!("[agent] MAA_LIBRARY lock released");
+        debug!("[agent] AgentClient created successfully");
 
         info!("[agent] Agent socket_id: {}", socket_id);
 
@@ -1645,74 +2016,30 @@ pub async fn maa_start_tasks(
             });
         }
 
-        // 设置连接超时并获取 connect 函数指针（在 guard 作用域内）
+        // 设置连接超时
         let timeout_ms = agent.timeout.unwrap_or(-1);
-        debug!("[agent] Setting up connection timeout and getting connect_fn...");
-        let connect_fn = {
-            debug!("[agent] Acquiring MAA_LIBRARY lock for timeout setup...");
-            let guard = MAA_LIBRARY
-                .lock()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            debug!("[agent] MAA_LIBRARY lock acquired for timeout setup");
-            let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-            info!("[agent] Setting agent connect timeout: {} ms", timeout_ms);
-            debug!(
-                "[agent] Calling maa_agent_client_set_timeout, agent_client ptr: {:?}",
-                agent_client.as_ptr()
-            );
-            unsafe {
-                (lib.maa_agent_client_set_timeout)(agent_client.as_ptr(), timeout_ms);
-            }
-            debug!("[agent] Timeout set, getting connect function pointer");
-            lib.maa_agent_client_connect
-        };
-        debug!("[agent] MAA_LIBRARY lock released after timeout setup");
+        info!("[agent] Setting agent connect timeout: {} ms", timeout_ms);
+        client.set_timeout(timeout_ms);
 
         // 等待连接（在独立线程池中执行，避免阻塞 UI 线程）
-        let agent_ptr = agent_client.as_ptr() as usize;
-        debug!("[agent] Agent pointer for connect: 0x{:x}", agent_ptr);
-
+        let client = Arc::new(client);
+        let connecting = client.clone();
         info!("[agent] Waiting for agent connection (non-blocking)...");
-        debug!("[agent] Spawning blocking task for maa_agent_client_connect...");
-        let connected = tokio::task::spawn_blocking(move || {
-            debug!(
-                "[agent] Inside spawn_blocking: calling connect_fn with ptr 0x{:x}",
-                agent_ptr
-            );
-            let result = unsafe { connect_fn(agent_ptr as *mut MaaAgentClient) };
-            debug!(
-                "[agent] Inside spawn_blocking: connect_fn returned {}",
-                result
-            );
-            result
-        })
-        .await
-        .map_err(|e| format!("Agent connect task panicked: {}", e))?;
+        let connected = tokio::task::spawn_blocking(move || connecting.connect())
+            .await
+            .map_err(|e| format!("Agent connect task panicked: {}", e))?;
         debug!("[agent] spawn_blocking completed, connected: {}", connected);
 
-        if connected == 0 {
-            // 连接失败，清理资源
-            error!("[agent] Agent connection failed (connected=0), cleaning up...");
-            debug!("[agent] Acquiring MAA_LIBRARY lock for cleanup...");
-            let guard = MAA_LIBRARY
-                .lock()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-            debug!("[agent] Acquiring instances lock for cleanup...");
-            let mut instances = state
-                .instances
+        if !connected {
+            // 连接失败，agent_client 随作用域结束销毁
+            error!("[agent] Agent connection failed, cleaning up...");
+            instance
                 .lock()
-                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            if let Some(instance) = instances.get_mut(&instance_id) {
-                instance.agent_child = Some(child);
-            }
-            debug!("[agent] Destroying agent_client...");
-            unsafe {
-                (lib.maa_agent_client_destroy)(agent_client.as_ptr());
-            }
-            debug!("[agent] Agent cleanup complete");
+                .map_err(|e: std::sync::PoisonError<_>| e.to_string())?
+                .agent_child = Some(child);
+            state.lifecycles.update(&instance_id, |lc| {
+                lc.tasker_failed("Failed to connect to agent")
+            });
             return Err("Failed to connect to agent".to_string());
         }
 
@@ -1721,101 +2048,57 @@ pub async fn maa_start_tasks(
         // 保存 agent 状态
         debug!("[agent] Saving agent state to instance...");
         {
-            let mut instances = state
-                .instances
+            let mut instance = instance
                 .lock()
                 .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-            if let Some(instance) = instances.get_mut(&instance_id) {
-                instance.agent_client = Some(agent_client.as_ptr());
-                instance.agent_child = Some(child);
-            }
+            instance.agent_client = Some(client.clone());
+            instance.agent_child = Some(child);
         }
         debug!("[agent] Agent state saved");
 
-        debug!("[start_tasks] Agent setup complete, returning agent_client");
-        Some(agent_client)
+        Some(client)
     } else {
         debug!("[start_tasks] No agent config, skipping agent setup");
         None
     };
 
-    // 检查初始化状态并提交任务（重新获取 guard）
-    debug!("[start_tasks] Re-acquiring MAA_LIBRARY lock for task submission...");
-    let guard = MAA_LIBRARY
-        .lock()
-        .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-    debug!("[start_tasks] MAA_LIBRARY lock re-acquired");
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    debug!(
-        "[start_tasks] Checking tasker inited status, tasker ptr: {:?}",
-        tasker.as_ptr()
-    );
-    let inited = unsafe { (lib.maa_tasker_inited)(tasker.as_ptr()) };
+    // 检查初始化状态并提交任务
+    let inited = tasker.inited();
     info!("[start_tasks] Tasker inited status: {}", inited);
-    if inited == 0 {
-        error!(
-            "[start_tasks] Tasker not properly initialized, inited: {}",
-            inited
-        );
+    if !inited {
+        error!("[start_tasks] Tasker not properly initialized");
+        state.lifecycles.update(&instance_id, |lc| {
+            lc.tasker_failed("Tasker not properly initialized")
+        });
         return Err("Tasker not properly initialized".to_string());
     }
+    // 新一轮任务：Tasker 就绪，清除生命周期中上一轮的任务 ID（排队的任务由任务队列管理）
+    state.lifecycles.update(&instance_id, |lc| {
+        lc.tasker_ready();
+        lc.tasks_posted(0, &[], true);
+    });
 
-    // 提交所有任务
-    debug!("[start_tasks] Submitting {} tasks...", tasks.len());
-    let mut task_ids = Vec::new();
-    for (idx, task) in tasks.iter().enumerate() {
-        debug!("[start_tasks] Preparing task {}: entry={}", idx, task.entry);
-        let entry_c = to_cstring(&task.entry);
-        let override_c = to_cstring(&task.pipeline_override);
-        debug!("[start_tasks] CStrings created for task {}", idx);
-
-        info!(
-            "[start_tasks] Calling MaaTaskerPostTask: entry={}, override={}",
-            task.entry, task.pipeline_override
-        );
-        let task_id = unsafe {
-            (lib.maa_tasker_post_task)(tasker.as_ptr(), entry_c.as_ptr(), override_c.as_ptr())
-        };
-
-        info!(
-            "[start_tasks] MaaTaskerPostTask returned task_id: {}",
-            task_id
-        );
-
-        if task_id == MAA_INVALID_ID {
-            warn!("[start_tasks] Failed to post task: {}", task.entry);
-            continue;
+    // 跳过本周期内已完成的任务：提交前按每日完成记录检查
+    if skip_completed {
+        for task in &mut tasks {
+            if !task.conditions.contains(&TaskCondition::NotSucceededToday) {
+                task.conditions.push(TaskCondition::NotSucceededToday);
+            }
         }
-
-        task_ids.push(task_id);
-        debug!(
-            "[start_tasks] Task {} submitted successfully, task_id: {}",
-            idx, task_id
-        );
     }
 
+    // 替换任务队列，由派发线程逐个提交，按循环设置自动开始下一轮
     debug!(
-        "[start_tasks] All tasks submitted, total: {} task_ids",
-        task_ids.len()
+        "[start_tasks] Queueing {} tasks, run loop: {:?}",
+        tasks.len(),
+        run_loop
     );
-
-    // 释放 guard 后再访问 instances
-    debug!("[start_tasks] Releasing MAA_LIBRARY lock...");
-    drop(guard);
-
-    // 缓存 task_ids，用于刷新后恢复状态
-    debug!("[start_tasks] Caching task_ids...");
-    {
-        let mut instances = state
-            .instances
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        if let Some(instance) = instances.get_mut(&instance_id) {
-            instance.task_ids = task_ids.clone();
-        }
+    let item_ids = state.update_queue(&instance_id, |queue| {
+        queue.start_run(&tasks, run_loop, timezone)
+    })?;
+    if let Ok(mut last_starts) = state.last_starts.lock() {
+        last_starts.insert(instance_id.clone(), saved_request);
     }
-    debug!("[start_tasks] Task_ids cached");
 
     // agent_client 用于表示是否启动了 agent（用于调试日志）
     if agent_client.is_some() {
@@ -1823,10 +2106,10 @@ pub async fn maa_start_tasks(
     }
 
     info!(
-        "[start_tasks] maa_start_tasks completed successfully, returning {} task_ids",
-        task_ids.len()
+        "[start_tasks] maa_start_tasks completed successfully, {} tasks queued",
+        item_ids.len()
     );
-    Ok(task_ids)
+    Ok(item_ids)
 }
 
 /// 停止 Agent 并断开连接（异步执行，避免阻塞 UI）
@@ -1834,34 +2117,28 @@ pub async fn maa_start_tasks(
 #[tauri::command]
 pub fn maa_stop_agent(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
     info!("maa_stop_agent called for instance: {}", instance_id);
+    stop_agent(&state, &instance_id)
+}
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances
-        .get_mut(&instance_id)
-        .ok_or("Instance not found")?;
-
+/// 断开并清理实例的 Agent（运行结束后的动作也会调用）
+pub(crate) fn stop_agent(state: &MaaState, instance_id: &str) -> Result<(), String> {
     // 取出 agent 和 child，准备在后台线程清理
-    let agent_opt = instance.agent_client.take();
-    let child_opt = instance.agent_child.take();
+    let (agent_opt, child_opt) = {
+        let instance = state.instance(instance_id)?;
+        let mut instance = instance.lock().map_err(|e| e.to_string())?;
+        (instance.agent_client.take(), instance.agent_child.take())
+    };
 
     // 在后台线程执行阻塞的清理操作（disconnect 和 wait 可能阻塞）
     // 不 kill 子进程，依赖 MaaTaskerPostStop 让子进程自行结束
     if agent_opt.is_some() || child_opt.is_some() {
-        let agent_ptr = agent_opt.map(SendPtr::new);
         thread::spawn(move || {
             // 断开并销毁 agent（disconnect 会发送 ShutDown 请求，等待子进程响应）
-            if let Some(agent) = agent_ptr {
-                let guard = MAA_LIBRARY.lock();
-                if let Ok(guard) = guard {
-                    if let Some(lib) = guard.as_ref() {
-                        info!("Background: Disconnecting agent...");
-                        unsafe {
-                            (lib.maa_agent_client_disconnect)(agent.as_ptr());
-                            (lib.maa_agent_client_destroy)(agent.as_ptr());
-                        }
-                        info!("Background: Agent disconnected and destroyed");
-                    }
-                }
+            if let Some(agent) = agent_opt {
+                info!("Background: Disconnecting agent...");
+                agent.disconnect();
+                drop(agent);
+                info!("Background: Agent disconnected and destroyed");
             }
 
             // 等待子进程自行退出，避免僵尸进程
@@ -1876,6 +2153,144 @@ pub fn maa_stop_agent(state: State<Arc<MaaState>>, instance_id: String) -> Resul
     Ok(())
 }
 
+/// 设置实例启动前的准备步骤（启动模拟器或游戏并等待就绪）
+#[tauri::command]
+pub fn maa_set_pre_run_steps(
+    pre_run: State<Arc<PreRunManager>>,
+    instance_id: String,
+    steps: Vec<PreRunStep>,
+) -> Result<(), String> {
+    info!(
+        "maa_set_pre_run_steps called, instance_id: {}, steps: {:?}",
+        instance_id, steps
+    );
+    pre_run.set_steps(&instance_id, steps)
+}
+
+/// 执行实例启动前的准备步骤，全部就绪后返回（未设置步骤时直接返回）
+#[tauri::command]
+pub async fn maa_run_pre_run(
+    pre_run: State<'_, Arc<PreRunManager>>,
+    instance_id: String,
+) -> Result<(), String> {
+    info!("maa_run_pre_run called, instance_id: {}", instance_id);
+
+    // 等待模拟器启动可能需要几分钟，放到阻塞线程执行
+    let pre_run = pre_run.inner().clone();
+    tokio::task::spawn_blocking(move || pre_run.run(&instance_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// 中止实例正在执行的启动前准备步骤，返回是否有正在执行的步骤
+#[tauri::command]
+pub fn maa_cancel_pre_run(
+    pre_run: State<Arc<PreRunManager>>,
+    instance_id: String,
+) -> Result<bool, String> {
+    info!("maa_cancel_pre_run called, instance_id: {}", instance_id);
+    pre_run.cancel(&instance_id)
+}
+
+/// 设置实例运行结束后的动作
+#[tauri::command]
+pub fn maa_set_post_run_actions(
+    post_run: State<Arc<PostRunManager>>,
+    instance_id: String,
+    config: PostRunConfig,
+) -> Result<(), String> {
+    info!(
+        "maa_set_post_run_actions called, instance_id: {}, config: {:?}",
+        instance_id, config
+    );
+    post_run.set_config(&instance_id, config)
+}
+
+/// 取消实例正在倒计时的运行结束后动作，返回是否有需要取消的动作
+#[tauri::command]
+pub fn maa_cancel_post_run(
+    post_run: State<Arc<PostRunManager>>,
+    instance_id: String,
+) -> Result<bool, String> {
+    info!("maa_cancel_post_run called, instance_id: {}", instance_id);
+    post_run.cancel(&instance_id)
+}
+
+/// 设置实例的定时执行（策略、启动参数、设备和资源由前端同步）
+#[tauri::command]
+pub fn maa_set_schedule(
+    scheduler: State<Arc<Scheduler>>,
+    instance_id: String,
+    schedule: InstanceSchedule,
+) -> Result<(), String> {
+    info!(
+        "maa_set_schedule called, instance_id: {}, policies: {}",
+        instance_id,
+        schedule.policies.len()
+    );
+    scheduler.set_schedule(&instance_id, schedule)
+}
+
+/// 预览策略接下来的执行时间（Unix 毫秒），策略或时区无效时返回错误
+/// timezone 为 IANA 时区名，未设置时为本机时区
+#[tauri::command]
+pub fn maa_preview_schedule(
+    policy: SchedulePolicy,
+    timezone: Option<String>,
+    count: Option<usize>,
+) -> Result<Vec<i64>, String> {
+    let zone = Zone::parse(timezone.as_deref().unwrap_or_default())?;
+    scheduler::preview(&policy, zone, count.unwrap_or(3))
+}
+
+// ============================================================================
+// interface.json
+// ============================================================================
+
+/// 重新加载 exe 目录下的 interface.json 并校验，返回发现的问题（解析失败时返回错误）
+#[tauri::command]
+pub fn maa_validate_interface() -> Result<Vec<InterfaceIssue>, String> {
+    info!("maa_validate_interface called");
+    let pi = project_interface::reload()?;
+    let issues = interface_validator::validate(&pi, &get_exe_directory()?);
+    info!("maa_validate_interface found {} issue(s)", issues.len());
+    Ok(issues)
+}
+
+/// 根据 interface 中的任务和选项定义生成任务的 pipeline override（JSON 数组字符串）
+#[tauri::command]
+pub fn maa_build_pipeline_override(
+    task: String,
+    option_values: HashMap<String, OptionValue>,
+) -> Result<String, Vec<OverrideError>> {
+    info!("maa_build_pipeline_override called, task: {}", task);
+    let pi = project_interface::current().map_err(|e| vec![OverrideError::interface(e)])?;
+    let result = pipeline_override::task_pipeline_override(&pi, &task, &option_values);
+    if let Err(errors) = &result {
+        warn!(
+            "maa_build_pipeline_override failed [{}]: {}",
+            task,
+            pipeline_override::describe_errors(errors)
+        );
+    }
+    result
+}
+
+/// 查看合并 pipeline_override 后入口及其可达节点的最终定义
+/// resource_paths 为资源目录（按加载顺序），pipeline_override 为单个对象或对象数组
+#[tauri::command]
+pub fn maa_inspect_pipeline(
+    entry: String,
+    pipeline_override: String,
+    resource_paths: Vec<String>,
+) -> Result<PipelineInspection, String> {
+    info!(
+        "maa_inspect_pipeline called, entry: {}, resource_paths: {:?}",
+        entry, resource_paths
+    );
+    pipeline_inspect::inspect(&entry, &pipeline_override, &resource_paths)
+}
+
 // ============================================================================
 // 文件读取
 // ============================================================================
@@ -1930,6 +2345,12 @@ pub fn get_exe_dir() -> Result<String, String> {
     Ok(exe_dir.to_string_lossy().to_string())
 }
 
+/// 获取配置、缓存和日志目录（便携模式下位于 exe 目录，安装模式下位于用户目录）
+#[tauri::command]
+pub fn get_data_dirs() -> DataDirs {
+    data_dirs().clone()
+}
+
 /// 获取当前工作目录
 #[tauri::command]
 pub fn get_cwd() -> Result<String, String> {
@@ -2011,7 +2432,7 @@ pub fn check_exe_path() -> Option<String> {
 // 状态查询命令
 // ============================================================================
 
-/// 获取单个实例的运行时状态
+/// 获取单个实例的运行时状态（与 maa-instance-state 事件使用同一模型）
 #[tauri::command]
 pub fn maa_get_instance_state(
     state: State<Arc<MaaState>>,
@@ -2022,36 +2443,10 @@ pub fn maa_get_instance_state(
         instance_id
     );
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let instance = instances.get(&instance_id).ok_or("Instance not found")?;
-
-    // 通过 Maa API 查询真实状态
-    let connected = instance.controller.map_or(false, |ctrl| unsafe {
-        (lib.maa_controller_connected)(ctrl) != 0
-    });
-
-    let resource_loaded = instance
-        .resource
-        .map_or(false, |res| unsafe { (lib.maa_resource_loaded)(res) != 0 });
-
-    let tasker_inited = instance.tasker.map_or(false, |tasker| unsafe {
-        (lib.maa_tasker_inited)(tasker) != 0
-    });
-
-    let is_running = instance.tasker.map_or(false, |tasker| unsafe {
-        (lib.maa_tasker_running)(tasker) != 0
-    });
-
-    Ok(InstanceState {
-        connected,
-        resource_loaded,
-        tasker_inited,
-        is_running,
-        task_ids: instance.task_ids.clone(),
-    })
+    state
+        .lifecycles
+        .snapshot(&instance_id)
+        .ok_or_else(|| "Instance not found".to_string())
 }
 
 /// 获取所有实例的状态快照（用于前端启动时恢复状态）
@@ -2059,53 +2454,16 @@ pub fn maa_get_instance_state(
 pub fn maa_get_all_states(state: State<Arc<MaaState>>) -> Result<AllInstanceStates, String> {
     debug!("maa_get_all_states called");
 
-    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
-    let lib = guard.as_ref();
-
-    let instances = state.instances.lock().map_err(|e| e.to_string())?;
     let cached_adb = state.cached_adb_devices.lock().map_err(|e| e.to_string())?;
     let cached_win32 = state
         .cached_win32_windows
         .lock()
         .map_err(|e| e.to_string())?;
 
-    let mut instance_states = HashMap::new();
-
-    // 如果 MaaFramework 未初始化，返回空状态
-    if let Some(lib) = lib {
-        for (id, instance) in instances.iter() {
-            // 通过 Maa API 查询真实状态
-            let connected = instance.controller.map_or(false, |ctrl| unsafe {
-                (lib.maa_controller_connected)(ctrl) != 0
-            });
-
-            let resource_loaded = instance
-                .resource
-                .map_or(false, |res| unsafe { (lib.maa_resource_loaded)(res) != 0 });
-
-            let tasker_inited = instance.tasker.map_or(false, |tasker| unsafe {
-                (lib.maa_tasker_inited)(tasker) != 0
-            });
-
-            let is_running = instance.tasker.map_or(false, |tasker| unsafe {
-                (lib.maa_tasker_running)(tasker) != 0
-            });
-
-            instance_states.insert(
-                id.clone(),
-                InstanceState {
-                    connected,
-                    resource_loaded,
-                    tasker_inited,
-                    is_running,
-                    task_ids: instance.task_ids.clone(),
-                },
-            );
-        }
-    }
-
     Ok(AllInstanceStates {
-        instances: instance_states,
+        instances: state.lifecycles.snapshots(),
+        task_queues: state.queues.snapshots(),
+        task_runs: state.queues.run_progresses(),
         cached_adb_devices: cached_adb.clone(),
         cached_win32_windows: cached_win32.clone(),
     })
@@ -2287,15 +2645,14 @@ pub fn move_file_to_old(file_path: String) -> Result<(), String> {
     move_to_old_folder(path)
 }
 
-/// 将文件或目录移动到程序目录下的 cache/old 文件夹，处理重名冲突（内部函数）
+/// 将文件或目录移动到缓存目录下的 cache/old 文件夹，处理重名冲突（内部函数）
 fn move_to_old_folder(source: &std::path::Path) -> Result<(), String> {
     if !source.exists() {
         return Ok(());
     }
 
-    // 统一移动到 exe_dir/cache/old
-    let exe_dir = get_exe_dir()?;
-    let old_dir = std::path::Path::new(&exe_dir).join("cache").join("old");
+    // 统一移动到缓存目录的 old 子目录
+    let old_dir = data_dirs().cache_dir.join("old");
 
     // 在移动前先尝试清理 old 目录，避免同名文件冲突
     if old_dir.exists() {
@@ -2575,8 +2932,9 @@ pub fn fallback_update(
     )?;
 
     // 复制 config 文件夹（如果存在）
+    // 安装模式下配置在用户目录，新版本直接共用，无需复制
     let config_src = target_path.join("config");
-    if config_src.exists() {
+    if data_dirs().mode == DataMode::Portable && config_src.exists() {
         let config_dst = final_fallback_dir.join("config");
         if let Err(e) = copy_dir_recursive(&config_src, &config_dst) {
             warn!("复制 config 文件夹失败: {}", e);
@@ -2952,25 +3310,14 @@ pub fn restart_as_admin(app_handle: tauri::AppHandle) -> Result<(), String> {
 /// 设置全局选项 - 保存调试图像
 #[tauri::command]
 pub fn maa_set_save_draw(enabled: bool) -> Result<bool, String> {
-    let lib = MAA_LIBRARY
-        .lock()
-        .map_err(|e| format!("Failed to lock library: {}", e))?;
+    let backend = current_backend()?;
 
-    if lib.is_none() {
-        return Err("MaaFramework not initialized".to_string());
-    }
-
-    let lib = lib.as_ref().unwrap();
-
-    let result = unsafe {
-        (lib.maa_set_global_option)(
-            crate::maa_ffi::MAA_GLOBAL_OPTION_SAVE_DRAW,
-            &enabled as *const bool as *const c_void,
-            std::mem::size_of::<bool>() as u64,
-        )
-    };
+    let result = backend.set_global_option(
+        crate::maa_ffi::MAA_GLOBAL_OPTION_SAVE_DRAW,
+        &[enabled as u8],
+    );
 
-    if result != 0 {
+    if result {
         info!("保存调试图像: {}", if enabled { "启用" } else { "禁用" });
         Ok(true)
     } else {
@@ -3039,7 +3386,7 @@ pub async fn run_and_wait(file_path: String) -> Result<i32, String> {
 
 /// 重新尝试加载 MaaFramework 库
 #[tauri::command]
-pub async fn retry_load_maa_library() -> Result<String, String> {
+pub async fn retry_load_maa_library(state: State<'_, Arc<MaaState>>) -> Result<String, String> {
     info!("retry_load_maa_library");
 
     let maafw_dir = get_maafw_dir()?;
@@ -3047,6 +3394,14 @@ pub async fn retry_load_maa_library() -> Result<String, String> {
         return Err("MaaFramework directory not found".to_string());
     }
 
+    // 已有库加载时按重新加载处理，先销毁旧库的对象
+    if current_backend().is_ok() {
+        let state = state.inner().clone();
+        return tokio::task::spawn_blocking(move || reload_maa_library(&state, &maafw_dir))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
     crate::maa_ffi::init_maa_library(&maafw_dir).map_err(|e| e.to_string())?;
 
     let version = crate::maa_ffi::get_maa_version().unwrap_or_default();
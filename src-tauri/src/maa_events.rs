@@ -0,0 +1,236 @@
+//! MaaFramework 回调消息的强类型模型
+//!
+//! 回调消息在 maa_ffi 中只解析一次，后端各功能（状态机、任务队列、通知等）
+//! 直接匹配枚举变体即可，不再需要比较 "Tasker.Task.Succeeded" 这类字符串。
+//! 无法识别的消息保留为 `Unknown`，兼容新版本 MaaFramework 新增的消息类型。
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 消息阶段（消息名的最后一段）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventPhase {
+    Starting,
+    Succeeded,
+    Failed,
+}
+
+impl EventPhase {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Starting" => Some(Self::Starting),
+            "Succeeded" => Some(Self::Succeeded),
+            "Failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Resource.Loading.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceLoadingDetail {
+    pub res_id: i64,
+    #[serde(default)]
+    pub path: String,
+    /// 加载类型，如 "Bundle"
+    #[serde(default, rename = "type")]
+    pub load_type: String,
+    #[serde(default)]
+    pub hash: String,
+}
+
+/// Controller.Action.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControllerActionDetail {
+    pub ctrl_id: i64,
+    #[serde(default)]
+    pub uuid: String,
+    /// 动作名称，如 "connect"、"screencap"、"click"
+    #[serde(default)]
+    pub action: String,
+    #[serde(default)]
+    pub param: Value,
+}
+
+impl ControllerActionDetail {
+    /// 是否为连接动作
+    pub fn is_connect(&self) -> bool {
+        self.action.eq_ignore_ascii_case("connect")
+    }
+}
+
+/// Tasker.Task.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskerTaskDetail {
+    pub task_id: i64,
+    #[serde(default)]
+    pub entry: String,
+    #[serde(default)]
+    pub uuid: String,
+    #[serde(default)]
+    pub hash: String,
+}
+
+impl TaskerTaskDetail {
+    /// 是否为 MaaTaskerPostStop 产生的停止任务
+    pub fn is_stop(&self) -> bool {
+        self.entry == "MaaTaskerPostStop"
+    }
+}
+
+/// Node.PipelineNode.* / Node.RecognitionNode.* / Node.ActionNode.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDetail {
+    pub task_id: i64,
+    #[serde(default)]
+    pub node_id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub focus: Option<Value>,
+}
+
+/// Node.NextList 中的单个候选节点
+/// 新版本为带属性的对象，旧版本仅为节点名字符串
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum NextListItem {
+    Detailed {
+        name: String,
+        #[serde(default)]
+        jump_back: bool,
+        #[serde(default)]
+        anchor: bool,
+    },
+    Name(String),
+}
+
+/// Node.NextList.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextListDetail {
+    pub task_id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub list: Vec<NextListItem>,
+    #[serde(default)]
+    pub focus: Option<Value>,
+}
+
+/// Node.Recognition.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecognitionDetail {
+    pub task_id: i64,
+    #[serde(default)]
+    pub reco_id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub focus: Option<Value>,
+}
+
+/// Node.Action.* 详情
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionDetail {
+    pub task_id: i64,
+    #[serde(default)]
+    pub action_id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub focus: Option<Value>,
+}
+
+/// MaaFramework 回调消息
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind")]
+pub enum MaaEvent {
+    ResourceLoading {
+        phase: EventPhase,
+        detail: ResourceLoadingDetail,
+    },
+    ControllerAction {
+        phase: EventPhase,
+        detail: ControllerActionDetail,
+    },
+    TaskerTask {
+        phase: EventPhase,
+        detail: TaskerTaskDetail,
+    },
+    PipelineNode {
+        phase: EventPhase,
+        detail: NodeDetail,
+    },
+    RecognitionNode {
+        phase: EventPhase,
+        detail: NodeDetail,
+    },
+    ActionNode {
+        phase: EventPhase,
+        detail: NodeDetail,
+    },
+    NextList {
+        phase: EventPhase,
+        detail: NextListDetail,
+    },
+    Recognition {
+        phase: EventPhase,
+        detail: RecognitionDetail,
+    },
+    Action {
+        phase: EventPhase,
+        detail: ActionDetail,
+    },
+    /// 未识别的消息或详情结构不匹配的消息，原样保留
+    Unknown { message: String, details: Value },
+}
+
+/// 将 details 反序列化为指定的详情结构
+fn typed<T: DeserializeOwned>(details: &Value) -> Option<T> {
+    match T::deserialize(details) {
+        Ok(detail) => Some(detail),
+        Err(e) => {
+            log::debug!("[event] Failed to parse callback details: {}", e);
+            None
+        }
+    }
+}
+
+impl MaaEvent {
+    /// 根据消息名和 details 解析回调消息
+    pub fn parse(message: &str, details: &Value) -> Self {
+        let parsed = message.rsplit_once('.').and_then(|(family, phase)| {
+            let phase = EventPhase::parse(phase)?;
+            match family {
+                "Resource.Loading" => {
+                    typed(details).map(|detail| Self::ResourceLoading { phase, detail })
+                }
+                "Controller.Action" => {
+                    typed(details).map(|detail| Self::ControllerAction { phase, detail })
+                }
+                "Tasker.Task" => typed(details).map(|detail| Self::TaskerTask { phase, detail }),
+                "Node.PipelineNode" => {
+                    typed(details).map(|detail| Self::PipelineNode { phase, detail })
+                }
+                "Node.RecognitionNode" => {
+                    typed(details).map(|detail| Self::RecognitionNode { phase, detail })
+                }
+                "Node.ActionNode" => {
+                    typed(details).map(|detail| Self::ActionNode { phase, detail })
+                }
+                "Node.NextList" => typed(details).map(|detail| Self::NextList { phase, detail }),
+                "Node.Recognition" => {
+                    typed(details).map(|detail| Self::Recognition { phase, detail })
+                }
+                "Node.Action" => typed(details).map(|detail| Self::Action { phase, detail }),
+                _ => None,
+            }
+        });
+
+        parsed.unwrap_or_else(|| Self::Unknown {
+            message: message.to_string(),
+            details: details.clone(),
+        })
+    }
+}
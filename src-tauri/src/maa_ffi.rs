@@ -13,7 +13,7 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_void};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, RwLock, Weak};
 
 use libloading::Library;
 use log::{debug, info, warn};
@@ -21,6 +21,10 @@ use once_cell::sync::Lazy;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
+use crate::data_dirs::data_dirs;
+use crate::maa_backend::MaaBackend;
+use crate::maa_events::MaaEvent;
+
 // 类型定义 (对应 MaaDef.h)
 pub type MaaBool = u8;
 pub type MaaSize = u64;
@@ -82,28 +86,6 @@ pub enum MaaToolkitDesktopWindowList {}
 pub enum MaaToolkitDesktopWindow {}
 pub enum MaaAgentClient {}
 
-/// MaaFramework 原始指针的 Send 包装器
-/// MaaFramework API 是线程安全的，可以安全地在线程间传递
-#[derive(Clone, Copy)]
-pub struct SendPtr<T>(pub *mut T);
-
-unsafe impl<T> Send for SendPtr<T> {}
-unsafe impl<T> Sync for SendPtr<T> {}
-
-impl<T> SendPtr<T> {
-    pub fn new(ptr: *mut T) -> Self {
-        SendPtr(ptr)
-    }
-
-    pub fn as_ptr(&self) -> *mut T {
-        self.0
-    }
-
-    pub fn is_null(&self) -> bool {
-        self.0.is_null()
-    }
-}
-
 // 回调类型
 pub type MaaEventCallback = Option<
     extern "C" fn(
@@ -343,8 +325,27 @@ pub struct MaaLibrary {
 unsafe impl Send for MaaLibrary {}
 unsafe impl Sync for MaaLibrary {}
 
+/// 一次加载 MaaFramework 的过程记录，供 maa_get_capabilities 查询
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LoadReport {
+    pub lib_dir: Option<PathBuf>,
+    /// 尝试加载的动态库路径
+    pub library_paths: Vec<PathBuf>,
+    /// 缺失的必需函数
+    pub missing_symbols: Vec<String>,
+    /// 加载失败原因
+    pub error: Option<String>,
+}
+
+/// 缺失必需函数时的占位指针，存在缺失时整个 MaaLibrary 会被丢弃，不会被调用
+extern "C" fn missing_symbol() {}
+
 impl MaaLibrary {
-    pub fn load(lib_dir: &Path) -> Result<Self, String> {
+    /// 加载 lib_dir 下的 MaaFramework，加载过程记录到 report
+    /// 必需函数缺失时会检查完全部函数后再返回错误，以便一次报告所有缺失的函数
+    pub fn load(lib_dir: &Path, report: &mut LoadReport) -> Result<Self, String> {
+        report.lib_dir = Some(lib_dir.to_path_buf());
+
         // Windows: 将 lib_dir 添加到 DLL 搜索路径，确保依赖 DLL 能被找到
         #[cfg(windows)]
         {
@@ -389,6 +390,12 @@ impl MaaLibrary {
             #[cfg(target_os = "linux")]
             let agent_client_path = lib_dir.join("libMaaAgentClient.so");
 
+            report.library_paths = vec![
+                framework_path.clone(),
+                toolkit_path.clone(),
+                agent_client_path.clone(),
+            ];
+
             info!("Loading MaaFramework from {:?}...", framework_path);
             let framework_lib = Library::new(&framework_path).map_err(|e| {
                 format!(
@@ -416,13 +423,18 @@ impl MaaLibrary {
             })?;
             info!("MaaAgentClient loaded successfully");
 
-            // 加载函数宏 - 使用 transmute 进行类型转换
+            // 加载函数宏 - 使用 transmute 进行类型转换，缺失时记录并使用占位指针
             macro_rules! load_fn {
                 ($lib:expr, $name:literal) => {{
-                    let sym = $lib
-                        .get::<*const ()>($name.as_bytes())
-                        .map_err(|e| format!("Failed to load {}: {}", $name, e))?;
-                    std::mem::transmute(*sym)
+                    let ptr = match $lib.get::<*const ()>($name.as_bytes()) {
+                        Ok(sym) => *sym,
+                        Err(e) => {
+                            warn!("Required function {} not available: {}", $name, e);
+                            report.missing_symbols.push($name.to_string());
+                            missing_symbol as *const ()
+                        }
+                    };
+                    std::mem::transmute(ptr)
                 }};
             }
 
@@ -439,7 +451,7 @@ impl MaaLibrary {
                 }};
             }
 
-            Ok(Self {
+            let lib = Self {
                 // Version
                 maa_version: load_fn!(framework_lib, "MaaVersion"),
                 maa_set_global_option: load_fn!(framework_lib, "MaaGlobalSetOption"),
@@ -604,7 +616,15 @@ impl MaaLibrary {
                 _framework_lib: framework_lib,
                 _toolkit_lib: toolkit_lib,
                 _agent_client_lib: agent_client_lib,
-            })
+            };
+
+            if !report.missing_symbols.is_empty() {
+                return Err(format!(
+                    "Missing required functions: {}",
+                    report.missing_symbols.join(", ")
+                ));
+            }
+            Ok(lib)
         }
     }
 
@@ -619,8 +639,32 @@ impl MaaLibrary {
     }
 }
 
-/// 全局 MaaLibrary 实例
-pub static MAA_LIBRARY: Lazy<Mutex<Option<MaaLibrary>>> = Lazy::new(|| Mutex::new(None));
+/// 全局 MaaFramework 后端（动态库或模拟器）
+/// 句柄封装类型各自持有一份 Arc，库在所有对象销毁后才会卸载
+pub static MAA_BACKEND: Lazy<Mutex<Option<Arc<dyn MaaBackend>>>> = Lazy::new(|| Mutex::new(None));
+
+/// 安装后端，替换之前的后端（已创建的对象仍持有旧后端直到销毁）
+pub fn install_backend(backend: Arc<dyn MaaBackend>) -> Result<(), String> {
+    let mut guard = MAA_BACKEND.lock().map_err(|e| e.to_string())?;
+    *guard = Some(backend);
+    Ok(())
+}
+
+/// 卸载当前后端并清空版本缓存，之后创建对象会返回未初始化错误
+/// 返回旧后端的弱引用：仍有对象持有它时动态库不会真正卸载
+pub fn unload_backend() -> Result<Option<Weak<dyn MaaBackend>>, String> {
+    let old = MAA_BACKEND.lock().map_err(|e| e.to_string())?.take();
+    invalidate_version_cache();
+    Ok(old.as_ref().map(Arc::downgrade))
+}
+
+/// 最近一次加载 MaaFramework 的记录
+static LAST_LOAD_REPORT: Lazy<Mutex<Option<LoadReport>>> = Lazy::new(|| Mutex::new(None));
+
+/// 获取最近一次加载 MaaFramework 的记录（从未加载时为 None）
+pub fn last_load_report() -> Option<LoadReport> {
+    LAST_LOAD_REPORT.lock().ok()?.clone()
+}
 
 /// 标记是否检测到可能缺少 VC++ 运行库（DLL 存在但加载失败）
 static VCREDIST_MISSING_DETECTED: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
@@ -653,6 +697,8 @@ pub enum MaaLibraryError {
         error: String,
         dlls_exist: bool,
     },
+    /// DLL 加载成功但缺少必需函数（通常是 MaaFramework 版本过旧）
+    MissingSymbols { path: String, symbols: Vec<String> },
     /// 其他错误
     Other(String),
 }
@@ -664,6 +710,9 @@ impl std::fmt::Display for MaaLibraryError {
             MaaLibraryError::LoadFailed { path, error, .. } => {
                 write!(f, "Failed to load DLL: {} ({})", path, error)
             }
+            MaaLibraryError::MissingSymbols { path, symbols } => {
+                write!(f, "Missing functions in {}: {}", path, symbols.join(", "))
+            }
             MaaLibraryError::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -687,7 +736,21 @@ pub fn check_dlls_exist(lib_dir: &Path) -> bool {
 
 /// 初始化 MaaFramework 库
 pub fn init_maa_library(lib_dir: &Path) -> Result<(), MaaLibraryError> {
-    let lib = MaaLibrary::load(lib_dir).map_err(|e| {
+    let mut report = LoadReport::default();
+    let result = MaaLibrary::load(lib_dir, &mut report);
+    report.error = result.as_ref().err().cloned();
+    let missing_symbols = report.missing_symbols.clone();
+    if let Ok(mut guard) = LAST_LOAD_REPORT.lock() {
+        *guard = Some(report);
+    }
+
+    let lib = result.map_err(|e| {
+        if !missing_symbols.is_empty() {
+            return MaaLibraryError::MissingSymbols {
+                path: lib_dir.to_string_lossy().into_owned(),
+                symbols: missing_symbols,
+            };
+        }
         // 检查 DLL 文件是否存在
         let dlls_exist = check_dlls_exist(lib_dir);
         if dlls_exist {
@@ -702,35 +765,34 @@ pub fn init_maa_library(lib_dir: &Path) -> Result<(), MaaLibraryError> {
         }
     })?;
 
-    // 初始化 Toolkit 配置，user_path 指向 exe 目录，避免 MaaFramework 日志落在 maafw 目录
-    let exe_dir = std::env::current_exe()
-        .ok()
-        .and_then(|path| path.parent().map(|p| p.to_path_buf()))
-        .unwrap_or_else(|| PathBuf::from("."));
-    let user_path_str = exe_dir.to_string_lossy();
-    let user_path = to_cstring(&user_path_str);
-    let default_json = to_cstring("{}");
+    // 初始化 Toolkit 配置，user_path 指向数据目录（便携模式为 exe 目录），避免日志落在 maafw 目录
+    let user_path_str = data_dirs().state_dir.to_string_lossy();
     debug!("MaaToolkitConfigInitOption user_path: {}", user_path_str);
-    let result =
-        unsafe { (lib.maa_toolkit_config_init_option)(user_path.as_ptr(), default_json.as_ptr()) };
+    let result = lib.toolkit_init_option(&user_path_str, "{}");
     debug!("MaaToolkitConfigInitOption result: {}", result);
 
-    let mut guard = MAA_LIBRARY
-        .lock()
-        .map_err(|e| MaaLibraryError::Other(e.to_string()))?;
-    *guard = Some(lib);
+    install_backend(Arc::new(lib)).map_err(MaaLibraryError::Other)?;
+    // 缓存可能来自之前加载的其他目录
+    invalidate_version_cache();
     Ok(())
 }
 
 /// 获取 MaaFramework 版本
 pub fn get_maa_version() -> Option<String> {
-    let guard = MAA_LIBRARY.lock().ok()?;
-    guard.as_ref().map(|lib| lib.version())
+    let guard = MAA_BACKEND.lock().ok()?;
+    guard.as_ref().map(|backend| backend.version())
 }
 
 /// 缓存的版本号（从独立加载获取）
 static CACHED_VERSION: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
 
+/// 清空版本缓存（更换或卸载 MaaFramework 后调用）
+pub fn invalidate_version_cache() {
+    if let Ok(mut guard) = CACHED_VERSION.lock() {
+        *guard = None;
+    }
+}
+
 /// 独立获取 MaaFramework 版本（不依赖完整库加载）
 /// 只加载 MaaVersion 函数，用于版本检查
 pub fn get_maa_version_standalone(lib_dir: &Path) -> Option<String> {
@@ -809,13 +871,104 @@ pub fn set_app_handle(handle: AppHandle) {
     }
 }
 
+/// 发送任意事件到前端（AppHandle 未设置时忽略）
+pub fn emit_event<S: Serialize + Clone>(event: &str, payload: S) {
+    // 快速克隆 AppHandle 后立即释放锁
+    let handle = match APP_HANDLE.lock() {
+        Ok(guard) => guard.clone(),
+        Err(e) => {
+            log::error!("[emit] Failed to lock APP_HANDLE: {}", e);
+            return;
+        }
+    };
+
+    if let Some(handle) = handle {
+        if let Err(e) = handle.emit(event, payload) {
+            log::error!("[emit] Failed to emit {}: {}", event, e);
+        }
+    }
+}
+
+/// 后端回调观察者，在 MaaFramework 工作线程中同步调用
+/// 观察者必须尽快返回，且不得在持有锁期间调用 MaaFramework API
+pub type EventObserver = Box<dyn Fn(&MaaCallbackEvent) + Send + Sync>;
+
+static EVENT_OBSERVERS: Lazy<RwLock<Vec<EventObserver>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// 注册后端回调观察者（状态机、任务队列等后端功能通过此接口订阅回调）
+pub fn add_event_observer<F>(observer: F)
+where
+    F: Fn(&MaaCallbackEvent) + Send + Sync + 'static,
+{
+    if let Ok(mut observers) = EVENT_OBSERVERS.write() {
+        observers.push(Box::new(observer));
+    }
+}
+
+/// 回调来源对象类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallbackSource {
+    Resource,
+    Controller,
+    Tasker,
+    /// Tasker 的 Context Sink（Node 级别消息）
+    Context,
+}
+
+/// 回调上下文，作为 trans_arg 注册到 MaaFramework 的 Sink 上
+/// 注意：必须保证上下文存活到对应的 MaaFramework 对象销毁之后
+pub struct CallbackContext {
+    pub instance_id: String,
+    pub source: CallbackSource,
+}
+
+impl CallbackContext {
+    pub fn new(instance_id: &str, source: CallbackSource) -> Arc<Self> {
+        Arc::new(Self {
+            instance_id: instance_id.to_string(),
+            source,
+        })
+    }
+
+    /// 获取传给 MaaFramework 的 trans_arg 指针（不转移所有权）
+    pub fn as_trans_arg(self: &Arc<Self>) -> *mut c_void {
+        Arc::as_ptr(self) as *mut c_void
+    }
+}
+
+/// 单个实例的全部 Sink 上下文
+pub struct SinkContexts {
+    pub resource: Arc<CallbackContext>,
+    pub controller: Arc<CallbackContext>,
+    pub tasker: Arc<CallbackContext>,
+    pub context: Arc<CallbackContext>,
+}
+
+impl SinkContexts {
+    pub fn new(instance_id: &str) -> Self {
+        Self {
+            resource: CallbackContext::new(instance_id, CallbackSource::Resource),
+            controller: CallbackContext::new(instance_id, CallbackSource::Controller),
+            tasker: CallbackContext::new(instance_id, CallbackSource::Tasker),
+            context: CallbackContext::new(instance_id, CallbackSource::Context),
+        }
+    }
+}
+
 /// MaaFramework 回调事件载荷
 #[derive(Clone, Serialize)]
 pub struct MaaCallbackEvent {
+    /// 事件所属实例 ID
+    pub instance_id: String,
+    /// 事件来源对象类型
+    pub source: CallbackSource,
     /// 消息类型，如 "Resource.Loading.Succeeded", "Controller.Action.Succeeded", "Tasker.Task.Succeeded"
     pub message: String,
-    /// 详细数据 JSON 字符串
-    pub details: String,
+    /// 详细数据（已解析的 JSON，保留全部原始字段，供 focus 占位符等使用）
+    pub details: serde_json::Value,
+    /// 强类型消息
+    pub event: MaaEvent,
 }
 
 /// Agent 输出事件载荷
@@ -877,10 +1030,19 @@ extern "C" fn maa_event_callback(
     _handle: *mut c_void,
     message: *const c_char,
     details_json: *const c_char,
-    _trans_arg: *mut c_void,
+    trans_arg: *mut c_void,
 ) {
     // 使用 catch_unwind 捕获潜在的 panic，避免回调中的 panic 导致整个程序崩溃
     let result = std::panic::catch_unwind(|| {
+        // 所有 Sink 都以 CallbackContext 作为 trans_arg 注册，为空说明注册方式有误
+        if trans_arg.is_null() {
+            log::warn!("[callback] Received null trans_arg, event dropped");
+            return;
+        }
+        // SAFETY: trans_arg 指向的 CallbackContext 由 InstanceRuntime 持有，
+        // 在对应的 MaaFramework 对象销毁之前不会被释放
+        let context = unsafe { &*(trans_arg as *const CallbackContext) };
+
         // 安全地读取 C 字符串
         let message_str = if message.is_null() {
             log::warn!("[callback] Received null message pointer");
@@ -897,32 +1059,40 @@ extern "C" fn maa_event_callback(
         };
 
         log::debug!(
-            "[callback] Received: message={}, details={}",
+            "[callback] Received: instance={}, source={:?}, message={}, details={}",
+            context.instance_id,
+            context.source,
             message_str,
             details_str
         );
 
-        // 快速克隆 AppHandle 后立即释放锁，避免阻塞 MaaFramework 工作线程
-        let handle = match APP_HANDLE.lock() {
-            Ok(guard) => guard.clone(),
-            Err(e) => {
-                log::error!("[callback] Failed to lock APP_HANDLE: {}", e);
-                return;
-            }
+        // 解析一次 details，无法解析时保留为 null
+        let details = if details_str.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_str(&details_str).unwrap_or_else(|e| {
+                log::warn!("[callback] Failed to parse details_json: {}", e);
+                serde_json::Value::Null
+            })
+        };
+        let event = MaaEvent::parse(&message_str, &details);
+
+        let event = MaaCallbackEvent {
+            instance_id: context.instance_id.clone(),
+            source: context.source,
+            message: message_str,
+            details,
+            event,
         };
 
-        // 使用克隆的 handle 发送事件（锁已释放）
-        if let Some(handle) = handle {
-            let event = MaaCallbackEvent {
-                message: message_str,
-                details: details_str,
-            };
-            if let Err(e) = handle.emit("maa-callback", event) {
-                log::error!("[callback] Failed to emit event: {}", e);
+        // 先通知后端观察者，再转发到前端
+        if let Ok(observers) = EVENT_OBSERVERS.read() {
+            for observer in observers.iter() {
+                observer(&event);
             }
-        } else {
-            log::warn!("[callback] APP_HANDLE is None, cannot emit event");
         }
+
+        emit_event("maa-callback", event);
     });
 
     if let Err(e) = result {
@@ -934,3 +1104,436 @@ extern "C" fn maa_event_callback(
 pub fn get_event_callback() -> MaaEventCallback {
     Some(maa_event_callback)
 }
+
+// ============================================================================
+// 句柄封装
+// ============================================================================
+//
+// 以下类型独占 MaaFramework 对象指针，Drop 时通过创建它的后端销毁。
+// 调用后端接收句柄的 unsafe 方法时，传入的都是自身持有、尚未销毁的句柄。
+// 持有 Arc<dyn MaaBackend> 保证动态库在对象销毁前不会被卸载；
+// Tasker / AgentClient 持有所绑定对象的 Arc，保证先销毁 Tasker 再销毁其绑定的资源和控制器。
+// MaaFramework 的 API 是线程安全的，因此这些类型实现 Send 和 Sync。
+
+/// 获取当前后端（只短暂持有全局锁）
+pub fn current_backend() -> Result<Arc<dyn MaaBackend>, String> {
+    let guard = MAA_BACKEND.lock().map_err(|e| e.to_string())?;
+    guard
+        .clone()
+        .ok_or_else(|| "MaaFramework not initialized".to_string())
+}
+
+/// MaaStringBuffer
+pub struct StringBuffer {
+    backend: Arc<dyn MaaBackend>,
+    handle: *mut MaaStringBuffer,
+}
+
+unsafe impl Send for StringBuffer {}
+unsafe impl Sync for StringBuffer {}
+
+impl StringBuffer {
+    pub fn create(backend: &Arc<dyn MaaBackend>) -> Option<Self> {
+        let handle = backend.string_buffer_create();
+        (!handle.is_null()).then(|| Self {
+            backend: backend.clone(),
+            handle,
+        })
+    }
+
+    pub fn handle(&self) -> *mut MaaStringBuffer {
+        self.handle
+    }
+
+    pub fn get(&self) -> String {
+        unsafe { self.backend.string_buffer_get(self.handle) }
+    }
+}
+
+impl Drop for StringBuffer {
+    fn drop(&mut self) {
+        unsafe { self.backend.string_buffer_destroy(self.handle) }
+    }
+}
+
+/// MaaImageBuffer
+pub struct ImageBuffer {
+    backend: Arc<dyn MaaBackend>,
+    handle: *mut MaaImageBuffer,
+}
+
+unsafe impl Send for ImageBuffer {}
+unsafe impl Sync for ImageBuffer {}
+
+impl ImageBuffer {
+    pub fn create(backend: &Arc<dyn MaaBackend>) -> Option<Self> {
+        let handle = backend.image_buffer_create();
+        (!handle.is_null()).then(|| Self {
+            backend: backend.clone(),
+            handle,
+        })
+    }
+
+    pub fn handle(&self) -> *mut MaaImageBuffer {
+        self.handle
+    }
+
+    /// 编码后的图像数据（PNG），无数据时为空
+    pub fn encoded(&self) -> Vec<u8> {
+        unsafe { self.backend.image_buffer_encoded(self.handle) }
+    }
+}
+
+impl Drop for ImageBuffer {
+    fn drop(&mut self) {
+        unsafe { self.backend.image_buffer_destroy(self.handle) }
+    }
+}
+
+/// MaaResource
+pub struct Resource {
+    backend: Arc<dyn MaaBackend>,
+    handle: *mut MaaResource,
+    /// 回调上下文，在 Drop 销毁资源之后才释放
+    sinks: Vec<Arc<CallbackContext>>,
+}
+
+unsafe impl Send for Resource {}
+unsafe impl Sync for Resource {}
+
+impl Resource {
+    pub(crate) fn create(backend: &Arc<dyn MaaBackend>) -> Option<Self> {
+        let handle = backend.resource_create();
+        (!handle.is_null()).then(|| Self {
+            backend: backend.clone(),
+            handle,
+            sinks: Vec::new(),
+        })
+    }
+
+    pub fn handle(&self) -> *mut MaaResource {
+        self.handle
+    }
+
+    pub fn add_sink(&mut self, context: Arc<CallbackContext>) {
+        unsafe {
+            self.backend.resource_add_sink(
+                self.handle,
+                get_event_callback(),
+                context.as_trans_arg(),
+            )
+        };
+        self.sinks.push(context);
+    }
+
+    pub fn post_bundle(&self, path: &str) -> MaaId {
+        unsafe { self.backend.resource_post_bundle(self.handle, path) }
+    }
+
+    pub fn loaded(&self) -> bool {
+        unsafe { self.backend.resource_loaded(self.handle) }
+    }
+}
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        debug!("Destroying resource {:?}", self.handle);
+        unsafe { self.backend.resource_destroy(self.handle) }
+    }
+}
+
+/// MaaController
+pub struct Controller {
+    backend: Arc<dyn MaaBackend>,
+    handle: *mut MaaController,
+    sinks: Vec<Arc<CallbackContext>>,
+}
+
+unsafe impl Send for Controller {}
+unsafe impl Sync for Controller {}
+
+impl Controller {
+    /// 接管 `MaaBackend::xxx_controller_create` 返回的指针，空指针返回 None
+    ///
+    /// # Safety
+    /// handle 必须由 backend 创建，且之后不再由其他地方销毁
+    pub(crate) unsafe fn from_raw(
+        backend: &Arc<dyn MaaBackend>,
+        handle: *mut MaaController,
+    ) -> Option<Self> {
+        (!handle.is_null()).then(|| Self {
+            backend: backend.clone(),
+            handle,
+            sinks: Vec::new(),
+        })
+    }
+
+    pub fn handle(&self) -> *mut MaaController {
+        self.handle
+    }
+
+    pub fn add_sink(&mut self, context: Arc<CallbackContext>) {
+        unsafe {
+            self.backend.controller_add_sink(
+                self.handle,
+                get_event_callback(),
+                context.as_trans_arg(),
+            )
+        };
+        self.sinks.push(context);
+    }
+
+    pub fn set_option_i32(&self, option: MaaCtrlOption, value: i32) -> bool {
+        unsafe {
+            self.backend
+                .controller_set_option(self.handle, option, &value.to_ne_bytes())
+        }
+    }
+
+    pub fn post_connection(&self) -> MaaId {
+        unsafe { self.backend.controller_post_connection(self.handle) }
+    }
+
+    pub fn connected(&self) -> bool {
+        unsafe { self.backend.controller_connected(self.handle) }
+    }
+
+    pub fn post_screencap(&self) -> MaaId {
+        unsafe { self.backend.controller_post_screencap(self.handle) }
+    }
+
+    /// 获取最近一次截图的编码数据（PNG）
+    pub fn cached_image(&self) -> Result<Vec<u8>, String> {
+        let buffer = ImageBuffer::create(&self.backend).ok_or("Failed to create image buffer")?;
+        let cached = unsafe {
+            self.backend
+                .controller_cached_image(self.handle, buffer.handle())
+        };
+        if !cached {
+            return Err("Failed to get cached image".to_string());
+        }
+
+        let data = buffer.encoded();
+        if data.is_empty() {
+            return Err("No image data available".to_string());
+        }
+        Ok(data)
+    }
+}
+
+impl Drop for Controller {
+    fn drop(&mut self) {
+        debug!("Destroying controller {:?}", self.handle);
+        unsafe { self.backend.controller_destroy(self.handle) }
+    }
+}
+
+/// Tasker 当前绑定的对象
+#[derive(Default)]
+struct TaskerBindings {
+    resource: Option<Arc<Resource>>,
+    controller: Option<Arc<Controller>>,
+}
+
+/// MaaTasker
+pub struct Tasker {
+    backend: Arc<dyn MaaBackend>,
+    handle: *mut MaaTasker,
+    sinks: Vec<Arc<CallbackContext>>,
+    /// 所绑定的资源和控制器，在 Drop 销毁 Tasker 之后才释放
+    bindings: Mutex<TaskerBindings>,
+}
+
+unsafe impl Send for Tasker {}
+unsafe impl Sync for Tasker {}
+
+impl Tasker {
+    pub(crate) fn create(backend: &Arc<dyn MaaBackend>) -> Option<Self> {
+        let handle = backend.tasker_create();
+        (!handle.is_null()).then(|| Self {
+            backend: backend.clone(),
+            handle,
+            sinks: Vec::new(),
+            bindings: Mutex::new(TaskerBindings::default()),
+        })
+    }
+
+    pub fn handle(&self) -> *mut MaaTasker {
+        self.handle
+    }
+
+    pub fn add_sink(&mut self, context: Arc<CallbackContext>) {
+        unsafe {
+            self.backend
+                .tasker_add_sink(self.handle, get_event_callback(), context.as_trans_arg())
+        };
+        self.sinks.push(context);
+    }
+
+    /// 添加 Context Sink，用于接收 Node 级别的通知
+    pub fn add_context_sink(&mut self, context: Arc<CallbackContext>) {
+        unsafe {
+            self.backend.tasker_add_context_sink(
+                self.handle,
+                get_event_callback(),
+                context.as_trans_arg(),
+            )
+        };
+        self.sinks.push(context);
+    }
+
+    /// 绑定资源，之前绑定的资源在重新绑定成功后才释放
+    pub fn bind_resource(&self, resource: &Arc<Resource>) -> bool {
+        let success = unsafe {
+            self.backend
+                .tasker_bind_resource(self.handle, resource.handle)
+        };
+        // 绑定失败时 MaaFramework 仍使用之前的资源，不能释放
+        if success {
+            if let Ok(mut bindings) = self.bindings.lock() {
+                bindings.resource = Some(resource.clone());
+            }
+        }
+        success
+    }
+
+    /// 绑定控制器，之前绑定的控制器在重新绑定成功后才释放
+    pub fn bind_controller(&self, controller: &Arc<Controller>) -> bool {
+        let success = unsafe {
+            self.backend
+                .tasker_bind_controller(self.handle, controller.handle)
+        };
+        // 绑定失败时 MaaFramework 仍使用之前的控制器，不能释放
+        if success {
+            if let Ok(mut bindings) = self.bindings.lock() {
+                bindings.controller = Some(controller.clone());
+            }
+        }
+        success
+    }
+
+    pub fn inited(&self) -> bool {
+        unsafe { self.backend.tasker_inited(self.handle) }
+    }
+
+    pub fn post_task(&self, entry: &str, pipeline_override: &str) -> MaaId {
+        unsafe {
+            self.backend
+                .tasker_post_task(self.handle, entry, pipeline_override)
+        }
+    }
+
+    pub fn status(&self, task_id: MaaId) -> MaaStatus {
+        unsafe { self.backend.tasker_status(self.handle, task_id) }
+    }
+
+    pub fn running(&self) -> bool {
+        unsafe { self.backend.tasker_running(self.handle) }
+    }
+
+    pub fn post_stop(&self) -> MaaId {
+        unsafe { self.backend.tasker_post_stop(self.handle) }
+    }
+
+    /// 覆盖已提交任务的 Pipeline（旧版本 MaaFramework 不支持时返回错误）
+    pub fn override_pipeline(
+        &self,
+        task_id: MaaId,
+        pipeline_override: &str,
+    ) -> Result<bool, String> {
+        unsafe {
+            self.backend
+                .tasker_override_pipeline(self.handle, task_id, pipeline_override)
+        }
+        .ok_or_else(|| {
+            "MaaTaskerOverridePipeline not available in this MaaFramework version".to_string()
+        })
+    }
+}
+
+impl Drop for Tasker {
+    fn drop(&mut self) {
+        debug!("Destroying tasker {:?}", self.handle);
+        unsafe { self.backend.tasker_destroy(self.handle) }
+    }
+}
+
+/// MaaAgentClient
+pub struct AgentClient {
+    backend: Arc<dyn MaaBackend>,
+    handle: *mut MaaAgentClient,
+    /// 所绑定的资源，在 Drop 销毁 AgentClient 之后才释放
+    resource: Option<Arc<Resource>>,
+}
+
+unsafe impl Send for AgentClient {}
+unsafe impl Sync for AgentClient {}
+
+impl AgentClient {
+    pub(crate) fn create(backend: &Arc<dyn MaaBackend>) -> Option<Self> {
+        Self::wrap(backend, backend.agent_client_create())
+    }
+
+    /// 以 TCP 模式创建（port 为 0 时自动选择端口），后端不支持或创建失败时返回 None
+    pub(crate) fn create_tcp(backend: &Arc<dyn MaaBackend>, port: u16) -> Option<Self> {
+        let handle = backend.agent_client_create_tcp(port)?;
+        Self::wrap(backend, handle)
+    }
+
+    fn wrap(backend: &Arc<dyn MaaBackend>, handle: *mut MaaAgentClient) -> Option<Self> {
+        (!handle.is_null()).then(|| Self {
+            backend: backend.clone(),
+            handle,
+            resource: None,
+        })
+    }
+
+    pub fn handle(&self) -> *mut MaaAgentClient {
+        self.handle
+    }
+
+    pub fn bind_resource(&mut self, resource: &Arc<Resource>) -> bool {
+        let success = unsafe {
+            self.backend
+                .agent_client_bind_resource(self.handle, resource.handle)
+        };
+        if success {
+            self.resource = Some(resource.clone());
+        }
+        success
+    }
+
+    /// 获取 socket identifier，用于传给 Agent 子进程
+    pub fn identifier(&self) -> Option<String> {
+        let buffer = StringBuffer::create(&self.backend)?;
+        unsafe {
+            self.backend
+                .agent_client_identifier(self.handle, buffer.handle())
+        }
+        .then(|| buffer.get())
+    }
+
+    pub fn set_timeout(&self, timeout_ms: i64) -> bool {
+        unsafe {
+            self.backend
+                .agent_client_set_timeout(self.handle, timeout_ms)
+        }
+    }
+
+    /// 等待 Agent 连接（阻塞，应在独立线程中调用）
+    pub fn connect(&self) -> bool {
+        unsafe { self.backend.agent_client_connect(self.handle) }
+    }
+
+    /// 断开连接（会发送 ShutDown 请求并等待子进程响应，可能阻塞）
+    pub fn disconnect(&self) -> bool {
+        unsafe { self.backend.agent_client_disconnect(self.handle) }
+    }
+}
+
+impl Drop for AgentClient {
+    fn drop(&mut self) {
+        debug!("Destroying agent client {:?}", self.handle);
+        unsafe { self.backend.agent_client_destroy(self.handle) }
+    }
+}
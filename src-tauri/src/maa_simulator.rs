@@ -0,0 +1,1234 @@
+//! 进程内模拟的 MaaFramework 后端
+//!
+//! 不依赖 MaaFramework 二进制，用于在 CI 中运行命令层和无模拟器环境下演示界面。
+//! 对象句柄是 `Arc::into_raw` 得到的指针，销毁时还原并释放。
+//! 每个对象有一个工作线程按提交顺序执行异步操作，并像 MaaFramework 一样在工作线程中触发回调；
+//! 销毁对象时等待工作线程退出，保证销毁后不再有回调。
+//!
+//! 启动参数 `--simulate` 或环境变量 `MXU_SIMULATE=1` 启用，配置从 `MXU_SIMULATE_CONFIG`
+//! 指定的文件或 exe 目录下的 `simulator.json` 读取，不存在时使用默认配置。
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io::Write;
+use std::os::raw::c_void;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Crc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::maa_backend::{
+    AdbDeviceInfo, BackendKind, DesktopWindowInfo, MaaBackend, OptionalFeature,
+};
+use crate::maa_ffi::{
+    MaaAdbInputMethod, MaaAdbScreencapMethod, MaaAgentClient, MaaController, MaaCtrlOption,
+    MaaEventCallback, MaaGamepadType, MaaGlobalOption, MaaId, MaaImageBuffer, MaaResource,
+    MaaStatus, MaaStringBuffer, MaaTasker, MaaWin32InputMethod, MaaWin32ScreencapMethod,
+    MAA_ADB_INPUT_DEFAULT, MAA_ADB_SCREENCAP_DEFAULT, MAA_INVALID_ID, MAA_STATUS_FAILED,
+    MAA_STATUS_INVALID, MAA_STATUS_PENDING, MAA_STATUS_RUNNING, MAA_STATUS_SUCCEEDED,
+};
+
+/// 模拟器配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulatorConfig {
+    /// 上报的 MaaFramework 版本
+    pub version: String,
+    pub adb_devices: Vec<AdbDeviceInfo>,
+    pub desktop_windows: Vec<DesktopWindowInfo>,
+    /// 控制器连接耗时
+    pub connect_delay_ms: u64,
+    /// 连接失败的目标（ADB 地址或窗口句柄）
+    pub failing_targets: Vec<String>,
+    /// 每个资源包的加载耗时
+    pub resource_load_delay_ms: u64,
+    /// 路径包含其中任一子串的资源包加载失败
+    pub failing_resources: Vec<String>,
+    /// 每个任务的执行耗时，平均分配到各节点
+    pub task_duration_ms: u64,
+    /// 按任务入口配置的执行脚本，未配置的入口只执行入口节点本身并成功
+    pub tasks: HashMap<String, TaskScript>,
+    /// 截图目录，按文件名顺序循环返回其中的 PNG，未配置或为空时返回占位图
+    pub screenshot_dir: Option<PathBuf>,
+}
+
+/// 单个任务入口的执行脚本
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskScript {
+    /// 依次命中的节点，为空时只有入口节点
+    pub nodes: Vec<String>,
+    /// 是否在最后一个节点识别失败
+    pub fail: bool,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        Self {
+            version: "v5.5.0+simulated".to_string(),
+            adb_devices: vec![
+                AdbDeviceInfo {
+                    name: "Simulated Emulator".to_string(),
+                    adb_path: "adb".to_string(),
+                    address: "127.0.0.1:5555".to_string(),
+                    screencap_methods: MAA_ADB_SCREENCAP_DEFAULT,
+                    input_methods: MAA_ADB_INPUT_DEFAULT,
+                    config: "{}".to_string(),
+                },
+                AdbDeviceInfo {
+                    name: "Simulated Phone".to_string(),
+                    adb_path: "adb".to_string(),
+                    address: "emulator-5554".to_string(),
+                    screencap_methods: MAA_ADB_SCREENCAP_DEFAULT,
+                    input_methods: MAA_ADB_INPUT_DEFAULT,
+                    config: "{}".to_string(),
+                },
+            ],
+            desktop_windows: vec![DesktopWindowInfo {
+                handle: 0x10001,
+                class_name: "SimulatedGameWindow".to_string(),
+                window_name: "Simulated Game".to_string(),
+            }],
+            connect_delay_ms: 500,
+            failing_targets: Vec::new(),
+            resource_load_delay_ms: 800,
+            failing_resources: Vec::new(),
+            task_duration_ms: 2000,
+            tasks: HashMap::new(),
+            screenshot_dir: None,
+        }
+    }
+}
+
+impl SimulatorConfig {
+    /// 从 JSON 文件读取配置，缺省字段使用默认值
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// 按 MXU_SIMULATE_CONFIG 或 exe 目录下的 simulator.json 读取配置
+    pub fn discover(exe_dir: &Path) -> Self {
+        let path = std::env::var_os("MXU_SIMULATE_CONFIG")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| exe_dir.join("simulator.json"));
+        if !path.exists() {
+            info!(
+                "[simulator] {} not found, using default config",
+                path.display()
+            );
+            return Self::default();
+        }
+
+        match Self::load(&path) {
+            Ok(config) => {
+                info!("[simulator] Loaded config from {}", path.display());
+                config
+            }
+            Err(e) => {
+                warn!("[simulator] {}, using default config", e);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// 是否通过启动参数或环境变量请求了模拟模式
+pub fn simulation_requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--simulate")
+        || std::env::var("MXU_SIMULATE").is_ok_and(|v| v == "1")
+}
+
+/// 模拟的 MaaFramework 后端
+pub struct SimulatedBackend {
+    config: Arc<SimulatorConfig>,
+}
+
+impl SimulatedBackend {
+    pub fn new(config: SimulatorConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+}
+
+// ============================================================================
+// 内部工具
+// ============================================================================
+
+/// 资源、控制器、任务等共用的 ID 序列
+static NEXT_ID: AtomicI64 = AtomicI64::new(1);
+
+fn next_id() -> MaaId {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// 将 Arc 转为句柄指针
+fn into_handle<T, H>(object: Arc<T>) -> *mut H {
+    Arc::into_raw(object) as *mut H
+}
+
+/// 借用句柄指向的对象
+///
+/// # Safety
+/// handle 必须由 into_handle::<T, _> 创建且尚未销毁
+unsafe fn borrow_handle<'a, T, H>(handle: *mut H) -> &'a T {
+    &*(handle as *const T)
+}
+
+/// 获取句柄指向对象的一份新 Arc
+///
+/// # Safety
+/// 同 borrow_handle
+unsafe fn clone_handle<T, H>(handle: *mut H) -> Arc<T> {
+    Arc::increment_strong_count(handle as *const T);
+    Arc::from_raw(handle as *const T)
+}
+
+/// 取回句柄的所有权
+///
+/// # Safety
+/// 同 borrow_handle，且之后不得再使用该句柄
+unsafe fn take_handle<T, H>(handle: *mut H) -> Arc<T> {
+    Arc::from_raw(handle as *const T)
+}
+
+/// 已注册的回调
+#[derive(Default)]
+struct Sinks {
+    entries: Mutex<Vec<(MaaEventCallback, usize)>>,
+}
+
+impl Sinks {
+    fn add(&self, callback: MaaEventCallback, trans_arg: *mut c_void) -> MaaId {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push((callback, trans_arg as usize));
+        }
+        next_id()
+    }
+
+    fn emit(&self, handle: *const c_void, message: &str, details: &Value) {
+        // 复制后释放锁，回调中可能再次访问本对象
+        let entries = match self.entries.lock() {
+            Ok(entries) => entries.clone(),
+            Err(_) => return,
+        };
+        let message_c = CString::new(message).unwrap_or_default();
+        let details_c = CString::new(details.to_string()).unwrap_or_default();
+        for (callback, trans_arg) in entries {
+            if let Some(callback) = callback {
+                callback(
+                    handle as *mut c_void,
+                    message_c.as_ptr(),
+                    details_c.as_ptr(),
+                    trans_arg as *mut c_void,
+                );
+            }
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// 按提交顺序执行异步操作的工作线程
+struct Worker {
+    sender: Mutex<Option<Sender<Job>>>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Worker {
+    fn spawn(name: &str) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let thread = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                for job in receiver {
+                    job();
+                }
+            })
+            .map_err(|e| warn!("[simulator] Failed to spawn {} worker: {}", name, e))
+            .ok();
+        Self {
+            sender: Mutex::new(thread.as_ref().map(|_| sender)),
+            thread: Mutex::new(thread),
+        }
+    }
+
+    fn post(&self, job: impl FnOnce() + Send + 'static) -> bool {
+        match self.sender.lock() {
+            Ok(sender) => sender
+                .as_ref()
+                .is_some_and(|sender| sender.send(Box::new(job)).is_ok()),
+            Err(_) => false,
+        }
+    }
+
+    /// 执行完已提交的操作后退出
+    fn shutdown(&self) {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+        let thread = self.thread.lock().ok().and_then(|mut thread| thread.take());
+        if let Some(thread) = thread {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 等待 duration，cancel 置位时提前返回 false
+fn sleep_unless(duration: Duration, cancel: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        thread::sleep((deadline - now).min(Duration::from_millis(50)));
+    }
+}
+
+fn phase_message(family: &str, success: bool) -> String {
+    format!(
+        "{}.{}",
+        family,
+        if success { "Succeeded" } else { "Failed" }
+    )
+}
+
+/// 从 pipeline_override 中取节点的 focus 配置（支持对象或对象数组，后者优先）
+fn node_focus(overrides: &Value, name: &str) -> Value {
+    let focus = |value: &Value| value.get(name).and_then(|node| node.get("focus")).cloned();
+    match overrides {
+        Value::Array(items) => items.iter().rev().find_map(focus),
+        value => focus(value),
+    }
+    .unwrap_or(Value::Null)
+}
+
+// ============================================================================
+// 占位截图
+// ============================================================================
+
+/// 控制器截图使用的占位图（1280x720 渐变）
+static PLACEHOLDER_PNG: Lazy<Vec<u8>> = Lazy::new(|| {
+    let (width, height) = (1280u32, 720u32);
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            rgb.push((x * 255 / width) as u8);
+            rgb.push((y * 255 / height) as u8);
+            rgb.push(160);
+        }
+    }
+    encode_png(width, height, &rgb)
+});
+
+/// 将 RGB8 像素编码为 PNG
+fn encode_png(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 3;
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in rgb.chunks(stride) {
+        // 每行以滤波类型开头，0 表示不滤波
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+    let _ = encoder.write_all(&raw);
+    let data = encoder.finish().unwrap_or_default();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 位深 8，RGB，默认压缩/滤波，无隔行
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_png_chunk(&mut png, b"IHDR", &header);
+    write_png_chunk(&mut png, b"IDAT", &data);
+    write_png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    let mut crc = Crc::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&crc.sum().to_be_bytes());
+}
+
+// ============================================================================
+// 模拟对象
+// ============================================================================
+
+struct SimResource {
+    config: Arc<SimulatorConfig>,
+    sinks: Sinks,
+    worker: Worker,
+    loaded: AtomicBool,
+}
+
+impl SimResource {
+    fn post_bundle(self: &Arc<Self>, path: &str) -> MaaId {
+        let res_id = next_id();
+        let path = path.to_string();
+        let this = self.clone();
+        let posted = self.worker.post(move || {
+            let handle = Arc::as_ptr(&this) as *const c_void;
+            let details = json!({ "res_id": res_id, "path": path, "type": "Bundle", "hash": "" });
+            this.sinks
+                .emit(handle, "Resource.Loading.Starting", &details);
+            thread::sleep(Duration::from_millis(this.config.resource_load_delay_ms));
+
+            let success = !this
+                .config
+                .failing_resources
+                .iter()
+                .any(|pattern| path.contains(pattern.as_str()));
+            if success {
+                this.loaded.store(true, Ordering::SeqCst);
+            }
+            this.sinks.emit(
+                handle,
+                &phase_message("Resource.Loading", success),
+                &details,
+            );
+        });
+        if posted {
+            res_id
+        } else {
+            MAA_INVALID_ID
+        }
+    }
+}
+
+struct SimController {
+    config: Arc<SimulatorConfig>,
+    sinks: Sinks,
+    worker: Worker,
+    /// 连接目标（ADB 地址或窗口句柄），用于匹配 failing_targets
+    target: String,
+    connected: AtomicBool,
+    image: Mutex<Vec<u8>>,
+    screenshot_index: AtomicUsize,
+}
+
+impl SimController {
+    fn new(config: &Arc<SimulatorConfig>, target: String) -> Arc<Self> {
+        Arc::new(Self {
+            config: config.clone(),
+            sinks: Sinks::default(),
+            worker: Worker::spawn("sim-controller"),
+            target,
+            connected: AtomicBool::new(false),
+            image: Mutex::new(Vec::new()),
+            screenshot_index: AtomicUsize::new(0),
+        })
+    }
+
+    /// 下一张截图：截图目录中的 PNG 按文件名循环，没有时使用占位图
+    fn next_screenshot(&self) -> Vec<u8> {
+        let Some(dir) = &self.config.screenshot_dir else {
+            return PLACEHOLDER_PNG.clone();
+        };
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| {
+                        path.extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if files.is_empty() {
+            return PLACEHOLDER_PNG.clone();
+        }
+        files.sort();
+
+        let index = self.screenshot_index.fetch_add(1, Ordering::Relaxed) % files.len();
+        std::fs::read(&files[index]).unwrap_or_else(|e| {
+            warn!(
+                "[simulator] Failed to read screenshot {}: {}",
+                files[index].display(),
+                e
+            );
+            PLACEHOLDER_PNG.clone()
+        })
+    }
+
+    /// 提交控制器动作，run 返回动作是否成功
+    fn post_action(
+        self: &Arc<Self>,
+        action: &'static str,
+        run: impl FnOnce(&SimController) -> bool + Send + 'static,
+    ) -> MaaId {
+        let ctrl_id = next_id();
+        let this = self.clone();
+        let posted = self.worker.post(move || {
+            let handle = Arc::as_ptr(&this) as *const c_void;
+            let details = json!({
+                "ctrl_id": ctrl_id,
+                "uuid": this.target,
+                "action": action,
+                "param": {},
+            });
+            this.sinks
+                .emit(handle, "Controller.Action.Starting", &details);
+            let success = run(&this);
+            this.sinks.emit(
+                handle,
+                &phase_message("Controller.Action", success),
+                &details,
+            );
+        });
+        if posted {
+            ctrl_id
+        } else {
+            MAA_INVALID_ID
+        }
+    }
+}
+
+struct SimTasker {
+    config: Arc<SimulatorConfig>,
+    sinks: Sinks,
+    context_sinks: Sinks,
+    worker: Worker,
+    resource: Mutex<Option<Arc<SimResource>>>,
+    controller: Mutex<Option<Arc<SimController>>>,
+    statuses: Mutex<HashMap<MaaId, MaaStatus>>,
+    /// 已提交但尚未结束的任务数（包括停止任务）
+    pending: AtomicUsize,
+    /// 正在停止，未执行完的任务均以失败结束
+    stopping: AtomicBool,
+}
+
+impl SimTasker {
+    fn set_status(&self, task_id: MaaId, status: MaaStatus) {
+        if let Ok(mut statuses) = self.statuses.lock() {
+            statuses.insert(task_id, status);
+        }
+    }
+
+    fn controller_uuid(&self) -> String {
+        self.controller
+            .lock()
+            .ok()
+            .and_then(|controller| controller.as_ref().map(|c| c.target.clone()))
+            .unwrap_or_default()
+    }
+
+    fn inited(&self) -> bool {
+        let resource_loaded = self
+            .resource
+            .lock()
+            .ok()
+            .and_then(|r| r.as_ref().map(|r| r.loaded.load(Ordering::SeqCst)))
+            .unwrap_or(false);
+        let connected = self
+            .controller
+            .lock()
+            .ok()
+            .and_then(|c| c.as_ref().map(|c| c.connected.load(Ordering::SeqCst)))
+            .unwrap_or(false);
+        resource_loaded && connected
+    }
+
+    /// 提交任务，run 返回任务是否成功
+    fn post(
+        self: &Arc<Self>,
+        entry: &str,
+        run: impl FnOnce(&SimTasker, MaaId) -> bool + Send + 'static,
+    ) -> MaaId {
+        let task_id = next_id();
+        let entry = entry.to_string();
+        let this = self.clone();
+        self.set_status(task_id, MAA_STATUS_PENDING);
+        self.pending.fetch_add(1, Ordering::SeqCst);
+
+        let posted = self.worker.post(move || {
+            let handle = Arc::as_ptr(&this) as *const c_void;
+            let details = json!({
+                "task_id": task_id,
+                "entry": entry,
+                "uuid": this.controller_uuid(),
+                "hash": "",
+            });
+            this.set_status(task_id, MAA_STATUS_RUNNING);
+            this.sinks.emit(handle, "Tasker.Task.Starting", &details);
+
+            let success = run(&this, task_id);
+            this.set_status(
+                task_id,
+                if success {
+                    MAA_STATUS_SUCCEEDED
+                } else {
+                    MAA_STATUS_FAILED
+                },
+            );
+            this.pending.fetch_sub(1, Ordering::SeqCst);
+            this.sinks
+                .emit(handle, &phase_message("Tasker.Task", success), &details);
+        });
+
+        if posted {
+            task_id
+        } else {
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+            self.set_status(task_id, MAA_STATUS_FAILED);
+            MAA_INVALID_ID
+        }
+    }
+
+    fn post_task(self: &Arc<Self>, entry: &str, pipeline_override: &str) -> MaaId {
+        if !self.inited() {
+            warn!("[simulator] Tasker not inited, rejecting task {}", entry);
+            return MAA_INVALID_ID;
+        }
+
+        let script = self.config.tasks.get(entry).cloned().unwrap_or_default();
+        let nodes = if script.nodes.is_empty() {
+            vec![entry.to_string()]
+        } else {
+            script.nodes.clone()
+        };
+        let overrides: Value = serde_json::from_str(pipeline_override).unwrap_or(Value::Null);
+        let entry_name = entry.to_string();
+        self.post(entry, move |this, task_id| {
+            debug!("[simulator] Running task {} ({})", task_id, entry_name);
+            this.run_nodes(task_id, &entry_name, &nodes, script.fail, &overrides)
+        })
+    }
+
+    /// 依次执行节点，发送与 MaaFramework 一致的 Node.* 回调序列
+    fn run_nodes(
+        &self,
+        task_id: MaaId,
+        entry: &str,
+        nodes: &[String],
+        fail_last: bool,
+        overrides: &Value,
+    ) -> bool {
+        let handle = self as *const Self as *const c_void;
+        let step = Duration::from_millis(self.config.task_duration_ms / nodes.len() as u64 / 2);
+        let emit = |message: &str, details: &Value| {
+            self.context_sinks.emit(handle, message, details);
+        };
+        let mut current = entry.to_string();
+
+        for (index, name) in nodes.iter().enumerate() {
+            let focus = node_focus(overrides, name);
+            let next_list = json!({
+                "task_id": task_id,
+                "name": current,
+                "list": [{ "name": name, "jump_back": false, "anchor": false }],
+                "focus": node_focus(overrides, &current),
+            });
+            let recognition = json!({
+                "task_id": task_id,
+                "reco_id": next_id(),
+                "name": name,
+                "focus": focus,
+            });
+
+            emit("Node.NextList.Starting", &next_list);
+            emit("Node.Recognition.Starting", &recognition);
+            let hit =
+                sleep_unless(step, &self.stopping) && !(fail_last && index + 1 == nodes.len());
+            emit(&phase_message("Node.Recognition", hit), &recognition);
+            emit(&phase_message("Node.NextList", hit), &next_list);
+            if !hit {
+                return false;
+            }
+
+            let node = json!({
+                "task_id": task_id,
+                "node_id": next_id(),
+                "name": name,
+                "focus": focus,
+            });
+            let action = json!({
+                "task_id": task_id,
+                "action_id": next_id(),
+                "name": name,
+                "focus": focus,
+            });
+            emit("Node.PipelineNode.Starting", &node);
+            emit("Node.Action.Starting", &action);
+            let done = sleep_unless(step, &self.stopping);
+            emit(&phase_message("Node.Action", done), &action);
+            emit(&phase_message("Node.PipelineNode", done), &node);
+            if !done {
+                return false;
+            }
+            current = name.clone();
+        }
+        true
+    }
+
+    fn post_stop(self: &Arc<Self>) -> MaaId {
+        self.stopping.store(true, Ordering::SeqCst);
+        // 排在所有已提交任务之后执行，执行时之前的任务均已中止
+        self.post("MaaTaskerPostStop", |this, _| {
+            this.stopping.store(false, Ordering::SeqCst);
+            true
+        })
+    }
+}
+
+// ============================================================================
+// MaaBackend 实现
+// ============================================================================
+
+impl MaaBackend for SimulatedBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Simulator
+    }
+
+    fn version(&self) -> String {
+        self.config.version.clone()
+    }
+
+    fn supports(&self, _feature: OptionalFeature) -> bool {
+        true
+    }
+
+    fn set_global_option(&self, option: MaaGlobalOption, _value: &[u8]) -> bool {
+        debug!("[simulator] set_global_option({})", option);
+        true
+    }
+
+    fn toolkit_init_option(&self, _user_path: &str, _default_json: &str) -> bool {
+        true
+    }
+
+    fn find_adb_devices(&self) -> Result<Vec<AdbDeviceInfo>, String> {
+        Ok(self.config.adb_devices.clone())
+    }
+
+    fn find_desktop_windows(&self) -> Result<Vec<DesktopWindowInfo>, String> {
+        Ok(self.config.desktop_windows.clone())
+    }
+
+    fn string_buffer_create(&self) -> *mut MaaStringBuffer {
+        into_handle(Arc::new(Mutex::new(String::new())))
+    }
+
+    unsafe fn string_buffer_destroy(&self, buffer: *mut MaaStringBuffer) {
+        drop(unsafe { take_handle::<Mutex<String>, _>(buffer) });
+    }
+
+    unsafe fn string_buffer_get(&self, buffer: *mut MaaStringBuffer) -> String {
+        let buffer = unsafe { borrow_handle::<Mutex<String>, _>(buffer) };
+        buffer.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    fn image_buffer_create(&self) -> *mut MaaImageBuffer {
+        into_handle(Arc::new(Mutex::new(Vec::<u8>::new())))
+    }
+
+    unsafe fn image_buffer_destroy(&self, buffer: *mut MaaImageBuffer) {
+        drop(unsafe { take_handle::<Mutex<Vec<u8>>, _>(buffer) });
+    }
+
+    unsafe fn image_buffer_encoded(&self, buffer: *mut MaaImageBuffer) -> Vec<u8> {
+        let buffer = unsafe { borrow_handle::<Mutex<Vec<u8>>, _>(buffer) };
+        buffer.lock().map(|data| data.clone()).unwrap_or_default()
+    }
+
+    fn resource_create(&self) -> *mut MaaResource {
+        into_handle(Arc::new(SimResource {
+            config: self.config.clone(),
+            sinks: Sinks::default(),
+            worker: Worker::spawn("sim-resource"),
+            loaded: AtomicBool::new(false),
+        }))
+    }
+
+    unsafe fn resource_destroy(&self, res: *mut MaaResource) {
+        let resource = unsafe { take_handle::<SimResource, _>(res) };
+        resource.worker.shutdown();
+    }
+
+    unsafe fn resource_add_sink(
+        &self,
+        res: *mut MaaResource,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        let resource = unsafe { borrow_handle::<SimResource, _>(res) };
+        resource.sinks.add(callback, trans_arg)
+    }
+
+    unsafe fn resource_post_bundle(&self, res: *mut MaaResource, path: &str) -> MaaId {
+        let resource = unsafe { clone_handle::<SimResource, _>(res) };
+        resource.post_bundle(path)
+    }
+
+    unsafe fn resource_loaded(&self, res: *mut MaaResource) -> bool {
+        let resource = unsafe { borrow_handle::<SimResource, _>(res) };
+        resource.loaded.load(Ordering::SeqCst)
+    }
+
+    fn adb_controller_create(
+        &self,
+        _adb_path: &str,
+        address: &str,
+        _screencap_methods: MaaAdbScreencapMethod,
+        _input_methods: MaaAdbInputMethod,
+        _config: &str,
+        _agent_path: &str,
+    ) -> *mut MaaController {
+        into_handle(SimController::new(&self.config, address.to_string()))
+    }
+
+    fn win32_controller_create(
+        &self,
+        hwnd: *mut c_void,
+        _screencap_method: MaaWin32ScreencapMethod,
+        _mouse_method: MaaWin32InputMethod,
+        _keyboard_method: MaaWin32InputMethod,
+    ) -> *mut MaaController {
+        into_handle(SimController::new(&self.config, (hwnd as u64).to_string()))
+    }
+
+    fn gamepad_controller_create(
+        &self,
+        hwnd: *mut c_void,
+        _gamepad_type: MaaGamepadType,
+        _screencap_method: MaaWin32ScreencapMethod,
+    ) -> *mut MaaController {
+        into_handle(SimController::new(&self.config, (hwnd as u64).to_string()))
+    }
+
+    unsafe fn controller_destroy(&self, ctrl: *mut MaaController) {
+        let controller = unsafe { take_handle::<SimController, _>(ctrl) };
+        controller.worker.shutdown();
+    }
+
+    unsafe fn controller_add_sink(
+        &self,
+        ctrl: *mut MaaController,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        let controller = unsafe { borrow_handle::<SimController, _>(ctrl) };
+        controller.sinks.add(callback, trans_arg)
+    }
+
+    unsafe fn controller_set_option(
+        &self,
+        _ctrl: *mut MaaController,
+        option: MaaCtrlOption,
+        _value: &[u8],
+    ) -> bool {
+        debug!("[simulator] controller_set_option({})", option);
+        true
+    }
+
+    unsafe fn controller_post_connection(&self, ctrl: *mut MaaController) -> MaaId {
+        let controller = unsafe { clone_handle::<SimController, _>(ctrl) };
+        controller.post_action("connect", |this| {
+            thread::sleep(Duration::from_millis(this.config.connect_delay_ms));
+            let success = !this.config.failing_targets.contains(&this.target);
+            this.connected.store(success, Ordering::SeqCst);
+            success
+        })
+    }
+
+    unsafe fn controller_connected(&self, ctrl: *mut MaaController) -> bool {
+        let controller = unsafe { borrow_handle::<SimController, _>(ctrl) };
+        controller.connected.load(Ordering::SeqCst)
+    }
+
+    unsafe fn controller_post_screencap(&self, ctrl: *mut MaaController) -> MaaId {
+        let controller = unsafe { clone_handle::<SimController, _>(ctrl) };
+        controller.post_action("screencap", |this| {
+            if !this.connected.load(Ordering::SeqCst) {
+                return false;
+            }
+            let screenshot = this.next_screenshot();
+            if let Ok(mut image) = this.image.lock() {
+                *image = screenshot;
+            }
+            true
+        })
+    }
+
+    unsafe fn controller_cached_image(
+        &self,
+        ctrl: *mut MaaController,
+        buffer: *mut MaaImageBuffer,
+    ) -> bool {
+        let controller = unsafe { borrow_handle::<SimController, _>(ctrl) };
+        let buffer = unsafe { borrow_handle::<Mutex<Vec<u8>>, _>(buffer) };
+        let image = match controller.image.lock() {
+            Ok(image) if !image.is_empty() => image.clone(),
+            _ => return false,
+        };
+        if let Ok(mut data) = buffer.lock() {
+            *data = image;
+        }
+        true
+    }
+
+    fn tasker_create(&self) -> *mut MaaTasker {
+        into_handle(Arc::new(SimTasker {
+            config: self.config.clone(),
+            sinks: Sinks::default(),
+            context_sinks: Sinks::default(),
+            worker: Worker::spawn("sim-tasker"),
+            resource: Mutex::new(None),
+            controller: Mutex::new(None),
+            statuses: Mutex::new(HashMap::new()),
+            pending: AtomicUsize::new(0),
+            stopping: AtomicBool::new(false),
+        }))
+    }
+
+    unsafe fn tasker_destroy(&self, tasker: *mut MaaTasker) {
+        let tasker = unsafe { take_handle::<SimTasker, _>(tasker) };
+        // 中止未完成的任务，等待工作线程退出
+        tasker.stopping.store(true, Ordering::SeqCst);
+        tasker.worker.shutdown();
+    }
+
+    unsafe fn tasker_add_sink(
+        &self,
+        tasker: *mut MaaTasker,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        tasker.sinks.add(callback, trans_arg)
+    }
+
+    unsafe fn tasker_add_context_sink(
+        &self,
+        tasker: *mut MaaTasker,
+        callback: MaaEventCallback,
+        trans_arg: *mut c_void,
+    ) -> MaaId {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        tasker.context_sinks.add(callback, trans_arg)
+    }
+
+    unsafe fn tasker_bind_resource(&self, tasker: *mut MaaTasker, res: *mut MaaResource) -> bool {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        let resource = unsafe { clone_handle::<SimResource, _>(res) };
+        match tasker.resource.lock() {
+            Ok(mut bound) => {
+                *bound = Some(resource);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    unsafe fn tasker_bind_controller(
+        &self,
+        tasker: *mut MaaTasker,
+        ctrl: *mut MaaController,
+    ) -> bool {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        let controller = unsafe { clone_handle::<SimController, _>(ctrl) };
+        match tasker.controller.lock() {
+            Ok(mut bound) => {
+                *bound = Some(controller);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    unsafe fn tasker_inited(&self, tasker: *mut MaaTasker) -> bool {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        tasker.inited()
+    }
+
+    unsafe fn tasker_post_task(
+        &self,
+        tasker: *mut MaaTasker,
+        entry: &str,
+        pipeline_override: &str,
+    ) -> MaaId {
+        let tasker = unsafe { clone_handle::<SimTasker, _>(tasker) };
+        tasker.post_task(entry, pipeline_override)
+    }
+
+    unsafe fn tasker_status(&self, tasker: *mut MaaTasker, task_id: MaaId) -> MaaStatus {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        tasker
+            .statuses
+            .lock()
+            .ok()
+            .and_then(|statuses| statuses.get(&task_id).copied())
+            .unwrap_or(MAA_STATUS_INVALID)
+    }
+
+    unsafe fn tasker_running(&self, tasker: *mut MaaTasker) -> bool {
+        let tasker = unsafe { borrow_handle::<SimTasker, _>(tasker) };
+        tasker.pending.load(Ordering::SeqCst) > 0
+    }
+
+    unsafe fn tasker_post_stop(&self, tasker: *mut MaaTasker) -> MaaId {
+        let tasker = unsafe { clone_handle::<SimTasker, _>(tasker) };
+        tasker.post_stop()
+    }
+
+    unsafe fn tasker_override_pipeline(
+        &self,
+        tasker: *mut MaaTasker,
+        task_id: MaaId,
+        _pipeline_override: &str,
+    ) -> Option<bool> {
+        let status = self.tasker_status(tasker, task_id);
+        Some(status == MAA_STATUS_PENDING || status == MAA_STATUS_RUNNING)
+    }
+
+    fn agent_client_create(&self) -> *mut MaaAgentClient {
+        into_handle(Arc::new(format!("sim-agent-{}", next_id())))
+    }
+
+    fn agent_client_create_tcp(&self, _port: u16) -> Option<*mut MaaAgentClient> {
+        Some(self.agent_client_create())
+    }
+
+    unsafe fn agent_client_destroy(&self, agent: *mut MaaAgentClient) {
+        drop(unsafe { take_handle::<String, _>(agent) });
+    }
+
+    unsafe fn agent_client_bind_resource(
+        &self,
+        _agent: *mut MaaAgentClient,
+        _res: *mut MaaResource,
+    ) -> bool {
+        true
+    }
+
+    unsafe fn agent_client_identifier(
+        &self,
+        agent: *mut MaaAgentClient,
+        buffer: *mut MaaStringBuffer,
+    ) -> bool {
+        let identifier = unsafe { borrow_handle::<String, _>(agent) };
+        let buffer = unsafe { borrow_handle::<Mutex<String>, _>(buffer) };
+        match buffer.lock() {
+            Ok(mut data) => {
+                *data = identifier.clone();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    unsafe fn agent_client_set_timeout(
+        &self,
+        _agent: *mut MaaAgentClient,
+        _timeout_ms: i64,
+    ) -> bool {
+        true
+    }
+
+    unsafe fn agent_client_connect(&self, _agent: *mut MaaAgentClient) -> bool {
+        true
+    }
+
+    unsafe fn agent_client_disconnect(&self, _agent: *mut MaaAgentClient) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maa_commands::{
+        connect_controller_blocking, create_instance, destroy_instance, load_resource_blocking,
+        start_tasks, stop_tasks, ControllerConfig, StartRequest, TaskConfig,
+    };
+    use crate::maa_ffi::{install_backend, Controller, Resource, Tasker};
+    use crate::task_queue::{QueuedTaskStatus, RunLoop, RunOutcome};
+    use crate::time_zone::Zone;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// 两个节点后成功的 Collect 和在节点上识别失败的 Broken
+    fn test_config() -> SimulatorConfig {
+        let mut tasks = HashMap::new();
+        tasks.insert(
+            "Collect".to_string(),
+            TaskScript {
+                nodes: vec!["OpenMenu".to_string(), "Claim".to_string()],
+                fail: false,
+            },
+        );
+        tasks.insert(
+            "Broken".to_string(),
+            TaskScript {
+                nodes: vec!["OpenMenu".to_string()],
+                fail: true,
+            },
+        );
+        SimulatorConfig {
+            connect_delay_ms: 10,
+            resource_load_delay_ms: 10,
+            task_duration_ms: 300,
+            tasks,
+            ..SimulatorConfig::default()
+        }
+    }
+
+    /// 轮询直到条件满足，超时返回 false
+    fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + TIMEOUT;
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+
+    fn wait_finished(tasker: &Tasker, task_id: MaaId) -> MaaStatus {
+        let mut status = MAA_STATUS_INVALID;
+        wait_until(|| {
+            status = tasker.status(task_id);
+            status == MAA_STATUS_SUCCEEDED || status == MAA_STATUS_FAILED
+        });
+        status
+    }
+
+    #[test]
+    fn wrappers_connect_load_and_run_tasks() {
+        let backend: Arc<dyn MaaBackend> = Arc::new(SimulatedBackend::new(test_config()));
+
+        let raw = backend.adb_controller_create(
+            "adb",
+            "127.0.0.1:5555",
+            MAA_ADB_SCREENCAP_DEFAULT,
+            MAA_ADB_INPUT_DEFAULT,
+            "{}",
+            "",
+        );
+        let controller = Arc::new(unsafe { Controller::from_raw(&backend, raw) }.unwrap());
+        assert_ne!(controller.post_connection(), MAA_INVALID_ID);
+        assert!(wait_until(|| controller.connected()));
+
+        let resource = Arc::new(Resource::create(&backend).unwrap());
+        assert_ne!(resource.post_bundle("resource/base"), MAA_INVALID_ID);
+        assert!(wait_until(|| resource.loaded()));
+
+        let tasker = Tasker::create(&backend).unwrap();
+        assert_eq!(tasker.post_task("Collect", "{}"), MAA_INVALID_ID);
+        assert!(tasker.bind_resource(&resource));
+        assert!(tasker.bind_controller(&controller));
+        assert!(tasker.inited());
+
+        let task_id = tasker.post_task("Collect", "{}");
+        assert_eq!(wait_finished(&tasker, task_id), MAA_STATUS_SUCCEEDED);
+        let task_id = tasker.post_task("Broken", "{}");
+        assert_eq!(wait_finished(&tasker, task_id), MAA_STATUS_FAILED);
+
+        // 停止后正在执行的任务失败，之后提交的任务照常执行
+        let task_id = tasker.post_task("Collect", "{}");
+        assert!(wait_until(|| tasker.status(task_id) == MAA_STATUS_RUNNING));
+        assert_ne!(tasker.post_stop(), MAA_INVALID_ID);
+        assert_eq!(wait_finished(&tasker, task_id), MAA_STATUS_FAILED);
+        assert!(wait_until(|| !tasker.running()));
+        let task_id = tasker.post_task("Collect", "{}");
+        assert_eq!(wait_finished(&tasker, task_id), MAA_STATUS_SUCCEEDED);
+    }
+
+    #[test]
+    fn commands_run_and_stop_tasks() {
+        install_backend(Arc::new(SimulatedBackend::new(test_config()))).unwrap();
+        let state = crate::start_state();
+        let (sender, runs) = mpsc::channel();
+        state.queues.set_run_listener(sender);
+
+        let id = "simulator-test";
+        create_instance(&state, id).unwrap();
+        let controller = ControllerConfig::Adb {
+            adb_path: "adb".to_string(),
+            address: "127.0.0.1:5555".to_string(),
+            screencap_methods: MAA_ADB_SCREENCAP_DEFAULT.to_string(),
+            input_methods: MAA_ADB_INPUT_DEFAULT.to_string(),
+            config: "{}".to_string(),
+        };
+        connect_controller_blocking(&state, id, &controller, TIMEOUT).unwrap();
+        load_resource_blocking(&state, id, &["resource/base".to_string()], TIMEOUT).unwrap();
+
+        let request = |entries: &[&str]| StartRequest {
+            tasks: entries
+                .iter()
+                .map(|entry| TaskConfig {
+                    entry: entry.to_string(),
+                    pipeline_override: "{}".to_string(),
+                    task_name: None,
+                    label: None,
+                    source_id: None,
+                    retry_count: None,
+                    retry_delay_ms: None,
+                    timeout_ms: None,
+                    conditions: Vec::new(),
+                })
+                .collect(),
+            agent_config: None,
+            cwd: ".".to_string(),
+            tcp_compat_mode: false,
+            run_loop: RunLoop::default(),
+            skip_completed: false,
+            timezone: Zone::Local,
+        };
+
+        let run = tauri::async_runtime::block_on(start_tasks(
+            &state,
+            id.to_string(),
+            request(&["Collect", "Broken"]),
+        ));
+        assert_eq!(run.unwrap().len(), 2);
+        let summary = runs.recv_timeout(TIMEOUT).unwrap().summary;
+        assert_eq!(summary.outcome, Some(RunOutcome::Failed));
+        assert_eq!((summary.succeeded, summary.failed), (1, 1));
+
+        // 停止时取消等待中的任务，运行以停止结束
+        let run = tauri::async_runtime::block_on(start_tasks(
+            &state,
+            id.to_string(),
+            request(&["Collect", "Collect", "Collect"]),
+        ));
+        assert!(run.is_ok());
+        assert!(wait_until(|| {
+            state.queues.snapshot(id).is_some_and(|tasks| {
+                tasks
+                    .iter()
+                    .any(|task| task.status == QueuedTaskStatus::Running)
+            })
+        }));
+        stop_tasks(&state, id).unwrap();
+        let summary = runs.recv_timeout(TIMEOUT).unwrap().summary;
+        assert_eq!(summary.outcome, Some(RunOutcome::Stopped));
+        assert_eq!(summary.succeeded, 0);
+
+        destroy_instance(&state, id).unwrap();
+    }
+}
@@ -0,0 +1,230 @@
+//! MaaFramework 运行库目录搜索
+//!
+//! 按顺序检查候选目录，第一个包含 MaaFramework 动态库的目录即为结果：
+//! 启动参数 `--maafw-dir`、环境变量 `MXU_MAAFW_DIR`、exe 相对目录、
+//! XDG 数据目录、系统库目录。被跳过的候选目录连同原因一起返回，便于打包时排查。
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// 启动参数名，支持 `--maafw-dir <path>` 和 `--maafw-dir=<path>`
+const CLI_FLAG: &str = "--maafw-dir";
+
+/// 指定运行库目录的环境变量
+const ENV_VAR: &str = "MXU_MAAFW_DIR";
+
+#[cfg(windows)]
+const FRAMEWORK_LIB_NAME: &str = "MaaFramework.dll";
+#[cfg(target_os = "macos")]
+const FRAMEWORK_LIB_NAME: &str = "libMaaFramework.dylib";
+#[cfg(all(unix, not(target_os = "macos")))]
+const FRAMEWORK_LIB_NAME: &str = "libMaaFramework.so";
+
+/// 候选目录的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateSource {
+    /// 启动参数 --maafw-dir
+    CliFlag,
+    /// 环境变量 MXU_MAAFW_DIR
+    EnvVar,
+    /// exe 所在目录（含 macOS app bundle 和 AppImage 的 usr/lib）
+    ExeDir,
+    /// $XDG_DATA_HOME / $XDG_DATA_DIRS
+    XdgData,
+    /// 系统库目录，如 /usr/lib/maafw
+    System,
+}
+
+/// 搜索过的候选目录
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    pub path: PathBuf,
+    pub source: CandidateSource,
+    /// 被跳过的原因，选中的目录为 None
+    pub reason: Option<String>,
+}
+
+/// 搜索结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MaafwSearch {
+    pub chosen: Option<Candidate>,
+    /// 在选中目录之前被跳过的候选目录（按搜索顺序）
+    pub rejected: Vec<Candidate>,
+}
+
+impl MaafwSearch {
+    /// 找不到运行库时的错误信息，列出所有检查过的目录
+    pub fn not_found_message(&self) -> String {
+        let tried: Vec<String> = self
+            .rejected
+            .iter()
+            .map(|c| {
+                format!(
+                    "{} ({})",
+                    c.path.display(),
+                    c.reason.as_deref().unwrap_or_default()
+                )
+            })
+            .collect();
+        format!(
+            "MaaFramework library directory not found, searched: {}",
+            tried.join("; ")
+        )
+    }
+}
+
+/// 启动参数中的 --maafw-dir
+fn cli_dir() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == CLI_FLAG {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(value) = arg.strip_prefix(CLI_FLAG).and_then(|s| s.strip_prefix('=')) {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+/// 非空环境变量
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// exe 相对的候选目录
+fn exe_candidates(exe_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    // macOS app bundle：exe 在 Contents/MacOS 下，maafw 在 Contents/Resources 下
+    #[cfg(target_os = "macos")]
+    {
+        if exe_dir.ends_with("Contents/MacOS") {
+            if let Some(contents) = exe_dir.parent() {
+                dirs.push(contents.join("Resources").join("maafw"));
+            }
+        }
+    }
+
+    dirs.push(exe_dir.join("maafw"));
+
+    // AppImage / 发行版包：exe 在 usr/bin 下，maafw 在 usr/lib 下
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        if let Some(prefix) = exe_dir.parent() {
+            dirs.push(prefix.join("lib").join("maafw"));
+        }
+    }
+
+    dirs
+}
+
+/// XDG 数据目录下的候选目录
+#[cfg(all(unix, not(target_os = "macos")))]
+fn xdg_candidates() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    let data_home = env_path("XDG_DATA_HOME")
+        .or_else(|| env_path("HOME").map(|home| home.join(".local").join("share")));
+    if let Some(data_home) = data_home {
+        dirs.push(data_home.join("maafw"));
+    }
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(
+        data_dirs
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| Path::new(dir).join("maafw")),
+    );
+
+    dirs
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn xdg_candidates() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// 系统库目录下的候选目录
+fn system_candidates() -> Vec<PathBuf> {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        ["/usr/local/lib/maafw", "/usr/lib/maafw", "/usr/lib64/maafw"]
+            .iter()
+            .map(PathBuf::from)
+            .collect()
+    }
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        Vec::new()
+    }
+}
+
+/// 按搜索顺序列出所有候选目录
+fn candidates(exe_dir: &Path) -> Vec<(PathBuf, CandidateSource)> {
+    let mut list = Vec::new();
+    if let Some(dir) = cli_dir() {
+        list.push((dir, CandidateSource::CliFlag));
+    }
+    if let Some(dir) = env_path(ENV_VAR) {
+        list.push((dir, CandidateSource::EnvVar));
+    }
+    list.extend(
+        exe_candidates(exe_dir)
+            .into_iter()
+            .map(|dir| (dir, CandidateSource::ExeDir)),
+    );
+    list.extend(
+        xdg_candidates()
+            .into_iter()
+            .map(|dir| (dir, CandidateSource::XdgData)),
+    );
+    list.extend(
+        system_candidates()
+            .into_iter()
+            .map(|dir| (dir, CandidateSource::System)),
+    );
+    list
+}
+
+/// 检查目录是否包含 MaaFramework 动态库，不包含时返回原因
+fn check_dir(dir: &Path) -> Option<String> {
+    if !dir.is_dir() {
+        return Some("directory does not exist".to_string());
+    }
+    if !dir.join(FRAMEWORK_LIB_NAME).is_file() {
+        return Some(format!("{} not found", FRAMEWORK_LIB_NAME));
+    }
+    None
+}
+
+/// 按顺序搜索 MaaFramework 运行库目录
+pub fn search(exe_dir: &Path) -> MaafwSearch {
+    let mut result = MaafwSearch::default();
+    for (path, source) in candidates(exe_dir) {
+        match check_dir(&path) {
+            None => {
+                result.chosen = Some(Candidate {
+                    path,
+                    source,
+                    reason: None,
+                });
+                break;
+            }
+            Some(reason) => result.rejected.push(Candidate {
+                path,
+                source,
+                reason: Some(reason),
+            }),
+        }
+    }
+    result
+}
@@ -5,6 +5,11 @@
 mod webview2;
 
 fn main() {
+    // --headless 时不创建窗口，在命令行执行保存的实例后按运行结果退出
+    if mxu_lib::headless::requested() {
+        std::process::exit(mxu_lib::headless::run());
+    }
+
     #[cfg(target_os = "windows")]
     {
         // 设置 WebView2 数据目录为程序所在目录下的 webview_data 文件夹
@@ -0,0 +1,127 @@
+//! 前端保存的用户配置（config_dir/mxu-{项目名}.json）
+//!
+//! 字段与前端 types/config.ts 一致，只声明后端用到的字段。无界面模式从这里读取实例的
+//! 控制器、设备、任务和各项运行设置。
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::data_dirs::data_dirs;
+use crate::jsonc::parse_jsonc;
+use crate::pre_run::PreRunStep;
+use crate::project_interface::OptionValue;
+use crate::reconnect::ReconnectPolicy;
+use crate::task_condition::TaskCondition;
+use crate::task_ledger::DayReset;
+use crate::task_queue::RunLoop;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MxuConfig {
+    #[serde(default)]
+    pub instances: Vec<SavedInstance>,
+    #[serde(default)]
+    pub settings: AppSettings,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    /// 通信兼容模式，强制使用 TCP 而非 IPC
+    #[serde(default)]
+    pub tcp_compat_mode: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedInstance {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub controller_name: Option<String>,
+    #[serde(default)]
+    pub resource_name: Option<String>,
+    #[serde(default)]
+    pub saved_device: Option<SavedDeviceInfo>,
+    #[serde(default)]
+    pub tasks: Vec<SavedTask>,
+    #[serde(default)]
+    pub reconnect_policy: Option<ReconnectPolicy>,
+    #[serde(default)]
+    pub run_loop: Option<RunLoop>,
+    #[serde(default)]
+    pub day_reset: Option<DayReset>,
+    #[serde(default)]
+    pub skip_completed: bool,
+    /// 定时执行的时区（IANA 名称），执行条件也按该时区判断
+    #[serde(default)]
+    pub schedule_time_zone: Option<String>,
+    #[serde(default)]
+    pub pre_run: Vec<PreRunStep>,
+}
+
+/// 保存的设备信息
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedDeviceInfo {
+    /// ADB 设备名称
+    pub adb_device_name: Option<String>,
+    /// Win32 / Gamepad 窗口名称
+    pub window_name: Option<String>,
+    /// PlayCover 地址
+    pub playcover_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedTask {
+    pub id: String,
+    /// 对应 interface 中的 task.name
+    pub task_name: String,
+    #[serde(default)]
+    pub custom_name: Option<String>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub option_values: HashMap<String, OptionValue>,
+    #[serde(default)]
+    pub run_policy: Option<TaskRunPolicy>,
+    #[serde(default)]
+    pub conditions: Vec<TaskCondition>,
+}
+
+/// 任务的重试与超时设置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TaskRunPolicy {
+    pub retry_count: Option<u32>,
+    pub retry_delay_ms: Option<u64>,
+    pub timeout_ms: Option<u64>,
+}
+
+/// 配置文件路径（项目名为空时为 mxu.json）
+pub fn config_path(project_name: &str) -> PathBuf {
+    let file_name = if project_name.is_empty() {
+        "mxu.json".to_string()
+    } else {
+        format!("mxu-{}.json", project_name)
+    };
+    data_dirs().config_dir.join(file_name)
+}
+
+impl MxuConfig {
+    /// 读取项目的用户配置
+    pub fn load(project_name: &str) -> Result<Self, String> {
+        let path = config_path(project_name);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("读取文件失败 [{}]: {}", path.display(), e))?;
+        parse_jsonc(&content, &path.to_string_lossy())
+    }
+
+    /// 按 ID 或名称查找实例（ID 优先）
+    pub fn find_instance(&self, key: &str) -> Option<&SavedInstance> {
+        self.instances
+            .iter()
+            .find(|instance| instance.id == key)
+            .or_else(|| self.instances.iter().find(|instance| instance.name == key))
+    }
+}
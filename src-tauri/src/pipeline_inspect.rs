@@ -0,0 +1,281 @@
+//! Pipeline 合并结果查看
+//!
+//! 按 MaaFramework 的规则依次合并资源目录中的 pipeline 和 pipeline_override 数组的每一层，
+//! 返回入口节点及其通过 next / interrupt / on_error 可达的所有节点的最终定义，
+//! 并记录每个字段最后由哪一层设置，用于排查选项覆盖不符合预期的问题。
+//!
+//! 合并规则：同名节点逐字段覆盖；recognition / action 为对象且类型相同（或覆盖层未写类型）时，
+//! param 逐字段覆盖，类型不同时整体替换。不包含 default_pipeline.json 中的默认值，
+//! `[Anchor]` 引用在运行时才能确定，不会展开。
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::jsonc::parse_jsonc;
+
+/// 会跳转到其他节点的字段
+const LINK_FIELDS: [&str; 3] = ["next", "interrupt", "on_error"];
+/// param 可以逐字段合并的字段
+const TYPED_FIELDS: [&str; 2] = ["recognition", "action"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerKind {
+    /// 资源目录中的 pipeline
+    Resource,
+    /// pipeline_override 数组中的一项
+    Override,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineLayer {
+    pub kind: LayerKind,
+    /// 资源目录，或覆盖项在数组中的位置（如 `$[2]`）
+    pub source: String,
+}
+
+/// 合并后的节点
+#[derive(Debug, Clone, Serialize)]
+pub struct InspectedNode {
+    pub name: String,
+    pub definition: Value,
+    /// 字段 -> 最后设置该字段的层（layers 的下标），param 中的字段为 `recognition.param.xxx`
+    pub field_layers: BTreeMap<String, usize>,
+    /// 最后定义该节点的 pipeline 文件（只在覆盖中出现的节点为 None）
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineInspection {
+    pub entry: String,
+    pub layers: Vec<PipelineLayer>,
+    /// 按从入口开始的广度优先顺序
+    pub nodes: Vec<InspectedNode>,
+    /// 被引用但没有定义的节点
+    pub missing: Vec<String>,
+}
+
+/// 覆盖数组中的一项：节点名和节点定义
+type OverrideLayer = Vec<(String, Map<String, Value>)>;
+
+#[derive(Default)]
+struct MergedNode {
+    definition: Map<String, Value>,
+    field_layers: BTreeMap<String, usize>,
+    file: Option<String>,
+}
+
+/// 合并资源和覆盖后，从 entry 开始收集可达节点
+pub fn inspect(
+    entry: &str,
+    pipeline_override: &str,
+    resource_paths: &[String],
+) -> Result<PipelineInspection, String> {
+    let overrides = parse_overrides(pipeline_override)?;
+
+    let mut layers = Vec::new();
+    let mut nodes: HashMap<String, MergedNode> = HashMap::new();
+    for resource_path in resource_paths {
+        let layer = layers.len();
+        layers.push(PipelineLayer {
+            kind: LayerKind::Resource,
+            source: resource_path.clone(),
+        });
+        for file in pipeline_files(&Path::new(resource_path).join("pipeline")) {
+            let content = std::fs::read_to_string(&file)
+                .map_err(|e| format!("读取文件失败 [{}]: {}", file.display(), e))?;
+            let file_name = file.to_string_lossy().to_string();
+            let definitions: Map<String, Value> = parse_jsonc(&content, &file_name)?;
+            for (name, definition) in definitions {
+                // MaaFramework 忽略 $ 开头的键（如 $schema）
+                if name.starts_with('$') {
+                    continue;
+                }
+                let Value::Object(definition) = definition else {
+                    return Err(format!("节点定义必须是对象 [{}]: {}", file_name, name));
+                };
+                let node = nodes.entry(name).or_default();
+                merge_node(node, definition, layer);
+                node.file = Some(file_name.clone());
+            }
+        }
+    }
+    for (index, layer_nodes) in overrides.into_iter().enumerate() {
+        let layer = layers.len();
+        layers.push(PipelineLayer {
+            kind: LayerKind::Override,
+            source: format!("$[{}]", index),
+        });
+        for (name, definition) in layer_nodes {
+            merge_node(nodes.entry(name).or_default(), definition, layer);
+        }
+    }
+
+    let mut inspected = Vec::new();
+    let mut missing = Vec::new();
+    let mut visited = HashSet::from([entry.to_string()]);
+    let mut queue = VecDeque::from([entry.to_string()]);
+    while let Some(name) = queue.pop_front() {
+        let Some(node) = nodes.remove(&name) else {
+            missing.push(name);
+            continue;
+        };
+        for field in LINK_FIELDS {
+            for target in node
+                .definition
+                .get(field)
+                .map(linked_nodes)
+                .unwrap_or_default()
+            {
+                if visited.insert(target.clone()) {
+                    queue.push_back(target);
+                }
+            }
+        }
+        inspected.push(InspectedNode {
+            name,
+            definition: Value::Object(node.definition),
+            field_layers: node.field_layers,
+            file: node.file,
+        });
+    }
+
+    Ok(PipelineInspection {
+        entry: entry.to_string(),
+        layers,
+        nodes: inspected,
+        missing,
+    })
+}
+
+/// 解析 pipeline_override（单个对象或对象数组），每个节点定义都必须是对象
+fn parse_overrides(pipeline_override: &str) -> Result<Vec<OverrideLayer>, String> {
+    let value: Value = serde_json::from_str(pipeline_override)
+        .map_err(|e| format!("pipeline_override 不是有效的 JSON: {}", e))?;
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(_) => vec![value],
+        _ => return Err("pipeline_override 必须是对象或对象数组".to_string()),
+    };
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let Value::Object(layer) = item else {
+                return Err(format!("$[{}] 必须是对象", index));
+            };
+            layer
+                .into_iter()
+                .map(|(name, definition)| match definition {
+                    Value::Object(definition) => Ok((name, definition)),
+                    _ => Err(format!("$[{}].{} 节点定义必须是对象", index, name)),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// pipeline 目录下的所有 json / jsonc 文件（按路径排序，目录不存在时为空）
+fn pipeline_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("json") || ext.eq_ignore_ascii_case("jsonc")
+            }) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// 把一层的节点定义合并到已有节点上，记录各字段的来源
+fn merge_node(node: &mut MergedNode, definition: Map<String, Value>, layer: usize) {
+    for (field, value) in definition {
+        if TYPED_FIELDS.contains(&field.as_str()) {
+            if let (Some(Value::Object(current)), Value::Object(update)) =
+                (node.definition.get_mut(&field), &value)
+            {
+                let same_type = match update.get("type") {
+                    Some(update_type) => current.get("type") == Some(update_type),
+                    None => true,
+                };
+                if same_type {
+                    merge_typed(current, &mut node.field_layers, &field, update, layer);
+                    continue;
+                }
+            }
+        }
+        let prefix = format!("{}.", field);
+        node.field_layers.retain(|key, _| !key.starts_with(&prefix));
+        node.field_layers.insert(field.clone(), layer);
+        node.definition.insert(field, value);
+    }
+}
+
+/// 类型相同的 recognition / action：param 逐字段覆盖，其他字段直接替换
+fn merge_typed(
+    current: &mut Map<String, Value>,
+    field_layers: &mut BTreeMap<String, usize>,
+    field: &str,
+    update: &Map<String, Value>,
+    layer: usize,
+) {
+    for (key, value) in update {
+        match (key.as_str(), current.get_mut(key), value) {
+            ("param", Some(Value::Object(param)), Value::Object(update_param)) => {
+                for (param_key, param_value) in update_param {
+                    field_layers.insert(format!("{}.param.{}", field, param_key), layer);
+                    param.insert(param_key.clone(), param_value.clone());
+                }
+            }
+            _ => {
+                let prefix = format!("{}.{}.", field, key);
+                field_layers.retain(|k, _| !k.starts_with(&prefix));
+                field_layers.insert(format!("{}.{}", field, key), layer);
+                current.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// next / interrupt / on_error 中引用的节点名（去掉 `[JumpBack]` 等前缀，跳过 `[Anchor]` 引用）
+fn linked_nodes(value: &Value) -> Vec<String> {
+    let items = match value {
+        Value::Array(items) => items.iter().collect(),
+        value => vec![value],
+    };
+    items
+        .into_iter()
+        .filter_map(|item| match item {
+            Value::String(name) => parse_link(name),
+            Value::Object(link) if link.get("anchor").and_then(Value::as_bool) != Some(true) => {
+                link.get("name").and_then(Value::as_str).map(str::to_string)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_link(text: &str) -> Option<String> {
+    let mut name = text.trim();
+    while let Some(rest) = name.strip_prefix('[') {
+        let (attribute, rest) = rest.split_once(']')?;
+        if attribute.eq_ignore_ascii_case("Anchor") {
+            return None;
+        }
+        name = rest.trim_start();
+    }
+    Some(name.to_string())
+}
@@ -0,0 +1,301 @@
+//! Pipeline 覆盖生成（与前端 utils/pipelineOverride.ts 的规则一致）
+//!
+//! 依次收集任务自身和所选选项（含嵌套选项）的 pipeline_override，生成数组格式的 JSON，
+//! MaaFramework 会按顺序依次合并。input 选项把 `{输入名}` 占位符替换为填写的值，
+//! int / bool 类型的输入整体作为占位符时替换为数字或布尔值。填写的值不匹配 `verify`
+//! 或不是有效的数字时返回结构化的错误，不会生成无效的覆盖交给 MaaFramework。
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+use crate::project_interface::{
+    CaseItem, InputItem, OptionDefinition, OptionValue, PipelineType, ProjectInterface,
+};
+
+const YES_CASE_NAMES: [&str; 4] = ["Yes", "yes", "Y", "y"];
+const NO_CASE_NAMES: [&str; 4] = ["No", "no", "N", "n"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverrideErrorKind {
+    /// interface.json 无法加载
+    Interface,
+    /// interface 中不存在该任务
+    UnknownTask,
+    /// 输入值不匹配 verify 正则
+    VerifyFailed,
+    /// int 类型的输入不是有效的数字
+    InvalidNumber,
+    /// 选项通过嵌套选项引用了自身
+    CyclicOption,
+}
+
+/// 生成 pipeline override 时的错误
+#[derive(Debug, Clone, Serialize)]
+pub struct OverrideError {
+    pub kind: OverrideErrorKind,
+    /// 出错的选项名
+    pub option: Option<String>,
+    /// 出错的输入名
+    pub input: Option<String>,
+    pub message: String,
+}
+
+impl OverrideError {
+    pub fn interface(message: String) -> Self {
+        Self {
+            kind: OverrideErrorKind::Interface,
+            option: None,
+            input: None,
+            message,
+        }
+    }
+
+    fn input(kind: OverrideErrorKind, option: &str, input: &str, message: String) -> Self {
+        Self {
+            kind,
+            option: Some(option.to_string()),
+            input: Some(input.to_string()),
+            message,
+        }
+    }
+}
+
+/// 多个错误合并为一行文本（无界面模式等只需要字符串错误的场景）
+pub fn describe_errors(errors: &[OverrideError]) -> String {
+    errors
+        .iter()
+        .map(|e| e.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// 根据 switch 的选中状态查找对应的 case
+fn find_switch_case(cases: &[CaseItem], checked: bool) -> Option<&CaseItem> {
+    let names = if checked {
+        &YES_CASE_NAMES
+    } else {
+        &NO_CASE_NAMES
+    };
+    cases
+        .iter()
+        .find(|case| names.contains(&case.name.as_str()))
+}
+
+/// 为单个任务生成 pipeline override，返回所有选项中发现的错误
+pub fn task_pipeline_override(
+    pi: &ProjectInterface,
+    task_name: &str,
+    option_values: &HashMap<String, OptionValue>,
+) -> Result<String, Vec<OverrideError>> {
+    let Some(task) = pi.find_task(task_name) else {
+        return Err(vec![OverrideError {
+            kind: OverrideErrorKind::UnknownTask,
+            option: None,
+            input: None,
+            message: format!("任务不存在: {}", task_name),
+        }]);
+    };
+
+    let mut overrides = Vec::new();
+    let mut errors = Vec::new();
+    if let Some(task_override) = &task.pipeline_override {
+        overrides.push(task_override.clone());
+    }
+    for option_key in &task.option {
+        collect_option_overrides(
+            option_key,
+            option_values,
+            &pi.option,
+            &mut HashSet::new(),
+            &mut overrides,
+            &mut errors,
+        );
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(Value::Array(overrides).to_string())
+}
+
+/// 递归收集选项及其嵌套选项的 pipeline_override，path 为当前路径上的选项，
+/// 嵌套选项构成循环时记录错误，不再继续展开
+fn collect_option_overrides<'a>(
+    option_key: &'a str,
+    option_values: &HashMap<String, OptionValue>,
+    all_options: &'a HashMap<String, OptionDefinition>,
+    path: &mut HashSet<&'a str>,
+    overrides: &mut Vec<Value>,
+    errors: &mut Vec<OverrideError>,
+) {
+    let (Some(option_def), Some(option_value)) =
+        (all_options.get(option_key), option_values.get(option_key))
+    else {
+        return;
+    };
+    if path.contains(option_key) {
+        errors.push(OverrideError {
+            kind: OverrideErrorKind::CyclicOption,
+            option: Some(option_key.to_string()),
+            input: None,
+            message: format!("选项嵌套构成循环: {}", option_key),
+        });
+        return;
+    }
+
+    let case_name = match option_value {
+        OptionValue::Select { case_name } => case_name.as_str(),
+        OptionValue::Switch { value } => find_switch_case(&option_def.cases, *value)
+            .map(|case| case.name.as_str())
+            .unwrap_or(if *value { "Yes" } else { "No" }),
+        OptionValue::Input { values } => {
+            let input_errors = verify_inputs(option_key, &option_def.inputs, values);
+            if !input_errors.is_empty() {
+                errors.extend(input_errors);
+                return;
+            }
+            if let Some(template) = &option_def.pipeline_override {
+                match fill_inputs(option_key, template.clone(), &option_def.inputs, values) {
+                    Ok(filled) => overrides.push(filled),
+                    Err(e) => errors.push(e),
+                }
+            }
+            return;
+        }
+    };
+
+    let Some(case_def) = option_def.cases.iter().find(|case| case.name == case_name) else {
+        return;
+    };
+    if let Some(case_override) = &case_def.pipeline_override {
+        overrides.push(case_override.clone());
+    }
+    path.insert(option_key);
+    for nested_key in &case_def.option {
+        collect_option_overrides(
+            nested_key,
+            option_values,
+            all_options,
+            path,
+            overrides,
+            errors,
+        );
+    }
+    path.remove(option_key);
+}
+
+/// 检查填写的值是否匹配输入的 verify 正则（空值和无效的正则不检查，与前端一致）
+fn verify_inputs(
+    option_key: &str,
+    inputs: &[InputItem],
+    values: &HashMap<String, String>,
+) -> Vec<OverrideError> {
+    let mut errors = Vec::new();
+    for input in inputs {
+        let (Some(verify), Some(value)) = (&input.verify, values.get(&input.name)) else {
+            continue;
+        };
+        if value.is_empty() {
+            continue;
+        }
+        let Ok(regex) = Regex::new(verify) else {
+            continue;
+        };
+        if !regex.is_match(value) {
+            let message = match &input.pattern_msg {
+                Some(msg) => format!("{}.{}: {}", option_key, input.name, msg),
+                None => format!(
+                    "{}.{} 不符合格式要求 ({}): {}",
+                    option_key, input.name, verify, value
+                ),
+            };
+            errors.push(OverrideError::input(
+                OverrideErrorKind::VerifyFailed,
+                option_key,
+                &input.name,
+                message,
+            ));
+        }
+    }
+    errors
+}
+
+/// 按填写的值依次替换各输入的占位符
+fn fill_inputs(
+    option_key: &str,
+    mut template: Value,
+    inputs: &[InputItem],
+    values: &HashMap<String, String>,
+) -> Result<Value, OverrideError> {
+    for (input_name, input_value) in values {
+        let pipeline_type = inputs
+            .iter()
+            .find(|input| &input.name == input_name)
+            .map(|input| input.pipeline_type)
+            .unwrap_or_default();
+        let (text, typed) = match pipeline_type {
+            PipelineType::Int => {
+                let text = if input_value.is_empty() {
+                    "0"
+                } else {
+                    input_value.as_str()
+                };
+                let number: Number = serde_json::from_str(text).map_err(|_| {
+                    OverrideError::input(
+                        OverrideErrorKind::InvalidNumber,
+                        option_key,
+                        input_name,
+                        format!(
+                            "{}.{} 不是有效的数字: {}",
+                            option_key, input_name, input_value
+                        ),
+                    )
+                })?;
+                (text.to_string(), Some(Value::Number(number)))
+            }
+            PipelineType::Bool => {
+                let checked =
+                    ["true", "1", "yes", "y"].contains(&input_value.to_lowercase().as_str());
+                (checked.to_string(), Some(Value::Bool(checked)))
+            }
+            PipelineType::String | PipelineType::Unknown => (input_value.clone(), None),
+        };
+        template = replace_placeholder(template, &format!("{{{}}}", input_name), &text, &typed);
+    }
+    Ok(template)
+}
+
+/// 替换键和字符串中的占位符；字符串恰好是占位符且有类型化的值时整体替换
+fn replace_placeholder(
+    value: Value,
+    placeholder: &str,
+    text: &str,
+    typed: &Option<Value>,
+) -> Value {
+    match value {
+        Value::String(s) => match typed {
+            Some(typed) if s == placeholder => typed.clone(),
+            _ => Value::String(s.replace(placeholder, text)),
+        },
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| replace_placeholder(item, placeholder, text, typed))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, item)| {
+                    (
+                        key.replace(placeholder, text),
+                        replace_placeholder(item, placeholder, text, typed),
+                    )
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
@@ -0,0 +1,384 @@
+//! 运行结束后的动作
+//!
+//! 实例的一次运行（`maa_start_tasks` 启动，含所有循环轮次）结束后，按实例配置依次执行：
+//! 停止 Agent、运行用户命令（如通过模拟器命令行关闭模拟器）、启动另一个实例的任务、
+//! 关机或睡眠、退出 MXU。用户停止的运行不触发；有任务失败时按配置决定是否执行。
+//!
+//! 执行前先倒计时，通过 `maa-post-run` 事件通知前端，用户可调用 `maa_cancel_post_run` 取消；
+//! 倒计时期间该实例开始了新的运行时也会取消。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::maa_commands::{start_tasks, stop_agent, MaaState};
+use crate::maa_ffi::emit_event;
+use crate::task_queue::{QueuedTaskStatus, RunEvent, RunOutcome};
+use crate::user_command::user_command;
+
+/// 倒计时期间检查取消的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 等待用户命令结束的最长时间，超时后不再等待，继续执行后面的动作
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 运行结束后的动作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PostRunAction {
+    /// 退出 MXU
+    ExitApp,
+    /// 运行用户命令
+    RunCommand {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+        /// 工作目录，为空时使用 MXU 的工作目录
+        #[serde(default)]
+        cwd: Option<String>,
+    },
+    /// 停止本实例的 Agent
+    StopAgent,
+    /// 关机
+    Shutdown,
+    /// 睡眠
+    Suspend,
+    /// 按上一次的参数启动另一个实例的任务
+    StartInstance { instance_id: String },
+}
+
+/// 实例的运行结束后动作设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostRunConfig {
+    /// 按顺序执行的动作
+    pub actions: Vec<PostRunAction>,
+    /// 执行前的倒计时（秒）
+    pub countdown_secs: u32,
+    /// 有任务失败时也执行
+    pub on_failure: bool,
+}
+
+impl Default for PostRunConfig {
+    fn default() -> Self {
+        Self {
+            actions: Vec::new(),
+            countdown_secs: 30,
+            on_failure: false,
+        }
+    }
+}
+
+/// 动作执行阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostRunPhase {
+    /// 开始倒计时，到 deadline 时执行
+    Countdown,
+    /// 倒计时被取消，error 为原因
+    Cancelled,
+    /// 开始执行第 action_index 个动作
+    Executing,
+    /// 第 action_index 个动作执行失败
+    ActionFailed,
+    /// 所有动作执行完毕
+    Completed,
+}
+
+/// maa-post-run 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct PostRunEvent {
+    pub instance_id: String,
+    pub phase: PostRunPhase,
+    pub actions: Vec<PostRunAction>,
+    /// 触发动作的运行结果
+    pub outcome: RunOutcome,
+    /// 倒计时结束的时间（Unix 毫秒）
+    pub deadline: i64,
+    pub action_index: Option<usize>,
+    pub error: Option<String>,
+}
+
+impl PostRunEvent {
+    fn emit(&self, phase: PostRunPhase, action_index: Option<usize>, error: Option<String>) {
+        let mut event = self.clone();
+        event.phase = phase;
+        event.action_index = action_index;
+        event.error = error;
+        emit_event("maa-post-run", event);
+    }
+}
+
+/// 运行结束后动作的执行者
+pub struct PostRunManager {
+    state: Arc<MaaState>,
+    app: AppHandle,
+    /// 各实例的设置，未设置的实例不执行任何动作
+    configs: Mutex<HashMap<String, PostRunConfig>>,
+    /// 正在倒计时的实例及其取消标记
+    pending: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl PostRunManager {
+    /// 接收任务队列的运行结束通知并启动处理线程
+    pub fn start(app: AppHandle, state: Arc<MaaState>) -> Arc<Self> {
+        let (sender, receiver) = mpsc::channel();
+        state.queues.set_run_listener(sender);
+        let manager = Arc::new(Self {
+            state,
+            app,
+            configs: Mutex::new(HashMap::new()),
+            pending: Mutex::new(HashMap::new()),
+        });
+
+        let worker = manager.clone();
+        thread::spawn(move || worker.run(receiver));
+
+        manager
+    }
+
+    pub fn set_config(&self, instance_id: &str, config: PostRunConfig) -> Result<(), String> {
+        self.configs
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(instance_id.to_string(), config);
+        Ok(())
+    }
+
+    /// 取消实例的倒计时，返回是否有正在倒计时的动作
+    pub fn cancel(&self, instance_id: &str) -> Result<bool, String> {
+        let cancel = self
+            .pending
+            .lock()
+            .map_err(|e| e.to_string())?
+            .remove(instance_id);
+        match cancel {
+            Some(cancel) => {
+                cancel.store(true, Ordering::Release);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn run(self: Arc<Self>, receiver: Receiver<RunEvent>) {
+        for event in receiver {
+            self.on_run_finished(event);
+        }
+    }
+
+    fn on_run_finished(self: &Arc<Self>, event: RunEvent) {
+        let Some(outcome) = event.summary.outcome else {
+            return;
+        };
+        let config = self
+            .configs
+            .lock()
+            .ok()
+            .and_then(|configs| configs.get(&event.instance_id).cloned())
+            .unwrap_or_default();
+        let triggered = match outcome {
+            RunOutcome::Succeeded => true,
+            RunOutcome::Failed => config.on_failure,
+            RunOutcome::Stopped => false,
+        };
+        if config.actions.is_empty() || !triggered {
+            return;
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Ok(mut pending) = self.pending.lock() {
+            // 同一实例已有倒计时时以新的为准
+            if let Some(old) = pending.insert(event.instance_id.clone(), cancel.clone()) {
+                old.store(true, Ordering::Release);
+            }
+        }
+        let manager = self.clone();
+        thread::spawn(move || manager.execute_all(&event.instance_id, config, outcome, cancel));
+    }
+
+    /// 倒计时结束后依次执行动作
+    fn execute_all(
+        &self,
+        instance_id: &str,
+        config: PostRunConfig,
+        outcome: RunOutcome,
+        cancel: Arc<AtomicBool>,
+    ) {
+        let countdown = Duration::from_secs(config.countdown_secs as u64);
+        let event = PostRunEvent {
+            instance_id: instance_id.to_string(),
+            phase: PostRunPhase::Countdown,
+            actions: config.actions.clone(),
+            outcome,
+            deadline: chrono::Utc::now().timestamp_millis() + countdown.as_millis() as i64,
+            action_index: None,
+            error: None,
+        };
+        info!(
+            "[post_run] {}: run {:?}, {} action(s) in {:?}",
+            instance_id,
+            outcome,
+            config.actions.len(),
+            countdown
+        );
+        event.emit(PostRunPhase::Countdown, None, None);
+
+        let started = Instant::now();
+        while started.elapsed() < countdown {
+            thread::sleep(POLL_INTERVAL);
+            if cancel.load(Ordering::Acquire) {
+                info!("[post_run] {}: cancelled", instance_id);
+                event.emit(PostRunPhase::Cancelled, None, Some("Cancelled".to_string()));
+                return;
+            }
+            if self.is_running(instance_id) {
+                info!("[post_run] {}: new run started, cancelled", instance_id);
+                self.clear_pending(instance_id, &cancel);
+                event.emit(
+                    PostRunPhase::Cancelled,
+                    None,
+                    Some("New run started".to_string()),
+                );
+                return;
+            }
+        }
+        self.clear_pending(instance_id, &cancel);
+
+        for (index, action) in config.actions.iter().enumerate() {
+            info!("[post_run] {}: executing {:?}", instance_id, action);
+            event.emit(PostRunPhase::Executing, Some(index), None);
+            if let Err(e) = self.execute(instance_id, action) {
+                warn!("[post_run] {}: {:?} failed: {}", instance_id, action, e);
+                event.emit(PostRunPhase::ActionFailed, Some(index), Some(e));
+            }
+        }
+        event.emit(PostRunPhase::Completed, None, None);
+    }
+
+    /// 倒计时结束，移除取消标记（已被新的倒计时替换时保留）
+    fn clear_pending(&self, instance_id: &str, cancel: &Arc<AtomicBool>) {
+        if let Ok(mut pending) = self.pending.lock() {
+            if pending
+                .get(instance_id)
+                .is_some_and(|current| Arc::ptr_eq(current, cancel))
+            {
+                pending.remove(instance_id);
+            }
+        }
+    }
+
+    /// 实例的任务队列中有未结束的任务
+    fn is_running(&self, instance_id: &str) -> bool {
+        self.state
+            .queues
+            .snapshot(instance_id)
+            .is_some_and(|tasks| {
+                tasks.iter().any(|task| {
+                    task.status == QueuedTaskStatus::Pending
+                        || task.status == QueuedTaskStatus::Running
+                })
+            })
+    }
+
+    fn execute(&self, instance_id: &str, action: &PostRunAction) -> Result<(), String> {
+        match action {
+            PostRunAction::ExitApp => {
+                self.app.exit(0);
+                Ok(())
+            }
+            PostRunAction::RunCommand { program, args, cwd } => {
+                run_command(program, args, cwd.as_deref())
+            }
+            PostRunAction::StopAgent => stop_agent(&self.state, instance_id),
+            PostRunAction::Shutdown => {
+                let (program, args) = power_command(PostRunAction::Shutdown);
+                run_command(program, &args, None)
+            }
+            PostRunAction::Suspend => {
+                let (program, args) = power_command(PostRunAction::Suspend);
+                run_command(program, &args, None)
+            }
+            PostRunAction::StartInstance { instance_id } => self.start_instance(instance_id),
+        }
+    }
+
+    /// 按上一次的参数启动实例的任务
+    fn start_instance(&self, instance_id: &str) -> Result<(), String> {
+        if self.is_running(instance_id) {
+            return Err(format!("Instance {} is already running", instance_id));
+        }
+        let request = self
+            .state
+            .last_starts
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(instance_id)
+            .cloned()
+            .ok_or_else(|| format!("Instance {} has no previous run", instance_id))?;
+        let item_ids = tauri::async_runtime::block_on(start_tasks(
+            &self.state,
+            instance_id.to_string(),
+            request,
+        ))?;
+        info!(
+            "[post_run] Started instance {}: {} task(s)",
+            instance_id,
+            item_ids.len()
+        );
+        Ok(())
+    }
+}
+
+/// 关机、睡眠使用的系统命令
+fn power_command(action: PostRunAction) -> (&'static str, Vec<String>) {
+    let shutdown = action == PostRunAction::Shutdown;
+    let (program, args): (&'static str, &[&str]) = if cfg!(windows) {
+        if shutdown {
+            ("shutdown", &["/s", "/t", "0"])
+        } else {
+            ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"])
+        }
+    } else if cfg!(target_os = "macos") {
+        if shutdown {
+            (
+                "osascript",
+                &["-e", "tell application \"System Events\" to shut down"],
+            )
+        } else {
+            ("pmset", &["sleepnow"])
+        }
+    } else if shutdown {
+        ("systemctl", &["poweroff"])
+    } else {
+        ("systemctl", &["suspend"])
+    };
+    (program, args.iter().map(|arg| arg.to_string()).collect())
+}
+
+/// 运行命令并等待结束（最多 COMMAND_TIMEOUT），退出码非 0 时返回错误
+fn run_command(program: &str, args: &[String], cwd: Option<&str>) -> Result<(), String> {
+    let mut child = user_command(program, args, cwd)
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("{} exited with {}", program, status)),
+            Ok(None) if started.elapsed() >= COMMAND_TIMEOUT => {
+                warn!("[post_run] {} is still running, not waiting", program);
+                return Ok(());
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
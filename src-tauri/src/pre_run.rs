@@ -0,0 +1,442 @@
+//! 启动前的准备步骤
+//!
+//! 连接控制器前需要先运行模拟器或游戏。每个实例可以配置若干步骤：启动一个程序，
+//! 然后等待对应的 ADB 设备出现在设备扫描结果中（按地址匹配）或者匹配正则的窗口出现，
+//! 超时视为失败。目标已就绪时可以跳过启动。前端在自动连接前调用 `maa_run_pre_run`，
+//! 进度通过 `maa-pre-run` 事件通知，`maa_cancel_pre_run` 可以中止等待。
+//!
+//! 启动的程序不会被等待结束（模拟器会一直运行），由后台线程回收。
+
+use std::collections::HashMap;
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::maa_ffi::{current_backend, emit_event};
+use crate::user_command::user_command;
+
+/// 检查目标是否就绪的间隔（设备扫描较慢，不宜过于频繁）
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 等待中检查取消的间隔
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+fn default_timeout_secs() -> u32 {
+    120
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 判断目标已就绪的方式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReadyCheck {
+    /// 设备扫描结果中出现指定地址的 ADB 设备
+    AdbDevice { address: String },
+    /// 出现类名和标题都匹配正则的窗口（为空表示不限制）
+    Window {
+        #[serde(default)]
+        class_regex: Option<String>,
+        #[serde(default)]
+        window_regex: Option<String>,
+    },
+}
+
+/// 启动前的一个步骤
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreRunStep {
+    /// 要启动的程序
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 工作目录，为空时使用 MXU 的工作目录
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// 启动后等待目标就绪，None 表示启动后直接继续
+    #[serde(default)]
+    pub ready: Option<ReadyCheck>,
+    /// 等待就绪的超时（秒）
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u32,
+    /// 目标已就绪时不再启动程序
+    #[serde(default = "default_true")]
+    pub skip_if_ready: bool,
+    /// 就绪后额外等待的时间（秒），用于等待模拟器内系统或游戏加载完成
+    #[serde(default)]
+    pub settle_secs: u32,
+}
+
+/// 步骤执行阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreRunPhase {
+    /// 目标已就绪，跳过启动
+    Skipped,
+    /// 正在启动程序
+    Launching,
+    /// 等待目标就绪，deadline 为超时时间
+    Waiting,
+    /// 目标已就绪
+    Ready,
+    /// 步骤失败（启动失败、程序异常退出或等待超时），error 为原因
+    Failed,
+    /// 被 maa_cancel_pre_run 中止
+    Cancelled,
+    /// 所有步骤执行完毕
+    Completed,
+}
+
+/// maa-pre-run 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct PreRunEvent {
+    pub instance_id: String,
+    pub phase: PreRunPhase,
+    /// 当前步骤在步骤列表中的位置
+    pub step_index: Option<usize>,
+    /// phase 为 Waiting 时的超时时间（Unix 毫秒）
+    pub deadline: Option<i64>,
+    pub error: Option<String>,
+}
+
+fn event(instance_id: &str, phase: PreRunPhase, step_index: Option<usize>) -> PreRunEvent {
+    PreRunEvent {
+        instance_id: instance_id.to_string(),
+        phase,
+        step_index,
+        deadline: None,
+        error: None,
+    }
+}
+
+/// 启动前步骤的设置与执行状态
+#[derive(Default)]
+pub struct PreRunManager {
+    /// 各实例的步骤
+    steps: Mutex<HashMap<String, Vec<PreRunStep>>>,
+    /// 正在执行的实例及其取消标记
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl PreRunManager {
+    /// 设置实例的步骤（程序路径和正则在执行时检查，便于前端边编辑边同步）
+    pub fn set_steps(&self, instance_id: &str, steps: Vec<PreRunStep>) -> Result<(), String> {
+        self.steps
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(instance_id.to_string(), steps);
+        Ok(())
+    }
+
+    /// 取消实例正在执行的步骤，返回是否有正在执行的步骤
+    pub fn cancel(&self, instance_id: &str) -> Result<bool, String> {
+        let cancel = self
+            .running
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(instance_id)
+            .cloned();
+        match cancel {
+            Some(cancel) => {
+                cancel.store(true, Ordering::Release);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 依次执行实例的步骤（阻塞直到全部就绪、失败或被取消）
+    pub fn run(&self, instance_id: &str) -> Result<(), String> {
+        let steps = self
+            .steps
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(instance_id)
+            .cloned()
+            .unwrap_or_default();
+        if steps.is_empty() {
+            return Ok(());
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        {
+            let mut running = self.running.lock().map_err(|e| e.to_string())?;
+            if running.contains_key(instance_id) {
+                return Err(format!(
+                    "Pre-run of instance {} is already running",
+                    instance_id
+                ));
+            }
+            running.insert(instance_id.to_string(), cancel.clone());
+        }
+
+        info!("[pre_run] {}: running {} step(s)", instance_id, steps.len());
+        let result = steps
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, step)| run_step(instance_id, index, step, &cancel));
+
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(instance_id);
+        }
+
+        match &result {
+            Ok(()) => emit_event(
+                "maa-pre-run",
+                event(instance_id, PreRunPhase::Completed, None),
+            ),
+            Err(e) => warn!("[pre_run] {}: {}", instance_id, e),
+        }
+        result
+    }
+}
+
+/// 步骤失败时发送事件并返回错误
+fn fail(instance_id: &str, index: usize, phase: PreRunPhase, error: String) -> Result<(), String> {
+    let mut event = event(instance_id, phase, Some(index));
+    event.error = Some(error.clone());
+    emit_event("maa-pre-run", event);
+    Err(error)
+}
+
+fn run_step(
+    instance_id: &str,
+    index: usize,
+    step: &PreRunStep,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    if step.program.trim().is_empty() {
+        let error = "Program is empty".to_string();
+        return fail(instance_id, index, PreRunPhase::Failed, error);
+    }
+    let check = match step.ready.as_ref().map(ReadyCheck::compile).transpose() {
+        Ok(check) => check,
+        Err(e) => return fail(instance_id, index, PreRunPhase::Failed, e),
+    };
+
+    if step.skip_if_ready {
+        if let Some(check) = &check {
+            if check.is_ready() {
+                info!("[pre_run] {}: step {} already ready", instance_id, index);
+                emit_event(
+                    "maa-pre-run",
+                    event(instance_id, PreRunPhase::Skipped, Some(index)),
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    info!(
+        "[pre_run] {}: launching {} {:?}",
+        instance_id, step.program, step.args
+    );
+    emit_event(
+        "maa-pre-run",
+        event(instance_id, PreRunPhase::Launching, Some(index)),
+    );
+    let mut child = match user_command(&step.program, &step.args, step.cwd.as_deref()).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let error = format!("Failed to launch {}: {}", step.program, e);
+            return fail(instance_id, index, PreRunPhase::Failed, error);
+        }
+    };
+
+    let result = match &check {
+        Some(check) => wait_ready(instance_id, index, step, check, &mut child, cancel),
+        None => Ok(()),
+    };
+    reap(child);
+    result?;
+
+    // 就绪后额外等待
+    let settle = Duration::from_secs(step.settle_secs as u64);
+    let started = Instant::now();
+    while started.elapsed() < settle {
+        if cancel.load(Ordering::Acquire) {
+            return fail(
+                instance_id,
+                index,
+                PreRunPhase::Cancelled,
+                "Cancelled".to_string(),
+            );
+        }
+        thread::sleep(CANCEL_POLL_INTERVAL);
+    }
+
+    emit_event(
+        "maa-pre-run",
+        event(instance_id, PreRunPhase::Ready, Some(index)),
+    );
+    Ok(())
+}
+
+/// 等待目标就绪；启动的程序以非 0 退出码提前结束时视为失败
+/// （启动器类程序拉起模拟器后正常退出，不影响等待）
+fn wait_ready(
+    instance_id: &str,
+    index: usize,
+    step: &PreRunStep,
+    check: &CompiledCheck,
+    child: &mut Child,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    let timeout = Duration::from_secs(step.timeout_secs as u64);
+    let mut event = event(instance_id, PreRunPhase::Waiting, Some(index));
+    event.deadline = Some(chrono::Utc::now().timestamp_millis() + timeout.as_millis() as i64);
+    emit_event("maa-pre-run", event);
+
+    let started = Instant::now();
+    let mut exited = false;
+    loop {
+        if check.is_ready() {
+            info!(
+                "[pre_run] {}: step {} ready after {:?}",
+                instance_id,
+                index,
+                started.elapsed()
+            );
+            return Ok(());
+        }
+        if !exited {
+            if let Ok(Some(status)) = child.try_wait() {
+                exited = true;
+                if !status.success() {
+                    let error = format!("{} exited with {}", step.program, status);
+                    return fail(instance_id, index, PreRunPhase::Failed, error);
+                }
+            }
+        }
+        if started.elapsed() >= timeout {
+            let error = format!(
+                "Timed out after {}s waiting for {}",
+                step.timeout_secs, check
+            );
+            return fail(instance_id, index, PreRunPhase::Failed, error);
+        }
+
+        let next_poll = Instant::now() + POLL_INTERVAL;
+        while Instant::now() < next_poll {
+            if cancel.load(Ordering::Acquire) {
+                return fail(
+                    instance_id,
+                    index,
+                    PreRunPhase::Cancelled,
+                    "Cancelled".to_string(),
+                );
+            }
+            thread::sleep(CANCEL_POLL_INTERVAL);
+        }
+    }
+}
+
+/// 在后台线程等待程序退出，避免留下僵尸进程
+fn reap(mut child: Child) {
+    thread::spawn(move || match child.wait() {
+        Ok(status) => debug!("[pre_run] Launched process exited with {}", status),
+        Err(e) => debug!("[pre_run] Failed to wait for launched process: {}", e),
+    });
+}
+
+/// 编译好正则的就绪检查
+enum CompiledCheck {
+    AdbDevice(String),
+    Window {
+        class_re: Option<Regex>,
+        window_re: Option<Regex>,
+    },
+}
+
+impl ReadyCheck {
+    fn compile(&self) -> Result<CompiledCheck, String> {
+        let compile = |pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .filter(|pattern| !pattern.is_empty())
+                .map(Regex::new)
+                .transpose()
+                .map_err(|e| format!("Invalid regex: {}", e))
+        };
+        match self {
+            ReadyCheck::AdbDevice { address } => {
+                let address = address.trim();
+                if address.is_empty() {
+                    return Err("ADB address is empty".to_string());
+                }
+                Ok(CompiledCheck::AdbDevice(address.to_string()))
+            }
+            ReadyCheck::Window {
+                class_regex,
+                window_regex,
+            } => {
+                let class_re = compile(class_regex)?;
+                let window_re = compile(window_regex)?;
+                // 两个正则都为空时会匹配任意窗口，导致总是跳过启动
+                if class_re.is_none() && window_re.is_none() {
+                    return Err("Window check needs a class or window regex".to_string());
+                }
+                Ok(CompiledCheck::Window {
+                    class_re,
+                    window_re,
+                })
+            }
+        }
+    }
+}
+
+impl CompiledCheck {
+    fn is_ready(&self) -> bool {
+        let backend = match current_backend() {
+            Ok(backend) => backend,
+            Err(e) => {
+                debug!("[pre_run] {}", e);
+                return false;
+            }
+        };
+        match self {
+            CompiledCheck::AdbDevice(address) => match backend.find_adb_devices() {
+                Ok(devices) => devices
+                    .iter()
+                    .any(|device| device.address.eq_ignore_ascii_case(address)),
+                Err(e) => {
+                    debug!("[pre_run] Failed to find ADB devices: {}", e);
+                    false
+                }
+            },
+            CompiledCheck::Window {
+                class_re,
+                window_re,
+            } => match backend.find_desktop_windows() {
+                Ok(windows) => windows.iter().any(|window| {
+                    class_re
+                        .as_ref()
+                        .map_or(true, |re| re.is_match(&window.class_name))
+                        && window_re
+                            .as_ref()
+                            .map_or(true, |re| re.is_match(&window.window_name))
+                }),
+                Err(e) => {
+                    debug!("[pre_run] Failed to find windows: {}", e);
+                    false
+                }
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for CompiledCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompiledCheck::AdbDevice(address) => write!(f, "ADB device {}", address),
+            CompiledCheck::Window { .. } => write!(f, "window"),
+        }
+    }
+}
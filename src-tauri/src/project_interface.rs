@@ -0,0 +1,257 @@
+//! ProjectInterface V2（interface.json）的后端类型和加载
+//!
+//! 字段与前端 types/interface.ts 一致，未声明的字段解析时忽略。exe 目录下的 interface.json
+//! 加载后缓存在进程内，Pipeline 覆盖生成等后端功能通过 `current` 共用；
+//! `maa_validate_interface` 和无界面模式启动时重新加载，并返回或输出校验结果
+//! （见 `interface_validator`）。
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::jsonc::parse_jsonc;
+use crate::maa_commands::{get_exe_dir, AgentConfig};
+
+/// interface.json 的文件名（位于 exe 目录）
+pub const INTERFACE_FILE: &str = "interface.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectInterface {
+    pub interface_version: u32,
+    /// 语言代码 -> 翻译文件路径（相对 interface.json 所在目录）
+    #[serde(default)]
+    pub languages: HashMap<String, String>,
+    #[serde(default)]
+    pub name: String,
+    pub label: Option<String>,
+    pub title: Option<String>,
+    pub icon: Option<String>,
+    pub mirrorchyan_rid: Option<String>,
+    pub mirrorchyan_multiplatform: Option<bool>,
+    pub github: Option<String>,
+    pub version: Option<String>,
+    pub contact: Option<String>,
+    pub license: Option<String>,
+    pub welcome: Option<String>,
+    pub description: Option<String>,
+    pub agent: Option<AgentConfig>,
+    #[serde(default)]
+    pub controller: Vec<ControllerItem>,
+    #[serde(default)]
+    pub resource: Vec<ResourceItem>,
+    #[serde(default)]
+    pub task: Vec<TaskItem>,
+    #[serde(default)]
+    pub option: HashMap<String, OptionDefinition>,
+}
+
+/// 控制器类型，MXU 不支持的类型保留原名以便校验时报告
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControllerType {
+    Adb,
+    Win32,
+    PlayCover,
+    Gamepad,
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControllerItem {
+    pub name: String,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    #[serde(rename = "type")]
+    pub controller_type: ControllerType,
+    /// 原样保留，不是非负整数时由 interface_validator 报告，不影响加载
+    pub display_short_side: Option<Value>,
+    pub display_long_side: Option<Value>,
+    pub display_raw: Option<bool>,
+    pub permission_required: Option<bool>,
+    pub adb: Option<Value>,
+    pub win32: Option<Win32Config>,
+    pub playcover: Option<PlayCoverConfig>,
+    pub gamepad: Option<GamepadConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Win32Config {
+    pub class_regex: Option<String>,
+    pub window_regex: Option<String>,
+    pub mouse: Option<String>,
+    pub keyboard: Option<String>,
+    pub screencap: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayCoverConfig {
+    pub uuid: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GamepadConfig {
+    pub class_regex: Option<String>,
+    pub window_regex: Option<String>,
+    pub gamepad_type: Option<String>,
+    pub screencap: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceItem {
+    pub name: String,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    /// 资源目录（相对 interface.json 所在目录），按顺序加载
+    #[serde(default)]
+    pub path: Vec<String>,
+    /// 可用的控制器，未设置时不限
+    pub controller: Option<Vec<String>>,
+    #[serde(default)]
+    pub option: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskItem {
+    pub name: String,
+    pub label: Option<String>,
+    pub entry: String,
+    pub default_check: Option<bool>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    /// 可用的资源，未设置时不限
+    pub resource: Option<Vec<String>>,
+    /// 可用的控制器，未设置时不限
+    pub controller: Option<Vec<String>>,
+    pub pipeline_override: Option<Value>,
+    #[serde(default)]
+    pub option: Vec<String>,
+}
+
+/// 选项类型，未声明时为 select，不认识的类型保留为 Unknown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionType {
+    #[default]
+    Select,
+    Switch,
+    Input,
+    #[serde(other)]
+    Unknown,
+}
+
+/// 选项定义（select / switch 使用 cases，input 使用 inputs 和 pipeline_override）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionDefinition {
+    #[serde(rename = "type", default)]
+    pub option_type: OptionType,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub cases: Vec<CaseItem>,
+    pub default_case: Option<String>,
+    #[serde(default)]
+    pub inputs: Vec<InputItem>,
+    pub pipeline_override: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseItem {
+    pub name: String,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    /// 选中该 case 时显示的嵌套选项
+    #[serde(default)]
+    pub option: Vec<String>,
+    pub pipeline_override: Option<Value>,
+}
+
+/// 输入值替换到 pipeline_override 时的类型，不认识的类型保留为 Unknown（按 string 处理）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineType {
+    #[default]
+    String,
+    Int,
+    Bool,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputItem {
+    pub name: String,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub default: Option<String>,
+    #[serde(default)]
+    pub pipeline_type: PipelineType,
+    /// 输入值需要匹配的正则
+    pub verify: Option<String>,
+    /// 不匹配 verify 时的提示
+    pub pattern_msg: Option<String>,
+}
+
+/// 用户为选项选择或填写的值（与前端保存的格式一致）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OptionValue {
+    Select {
+        #[serde(rename = "caseName")]
+        case_name: String,
+    },
+    Switch {
+        value: bool,
+    },
+    Input {
+        #[serde(default)]
+        values: HashMap<String, String>,
+    },
+}
+
+impl ProjectInterface {
+    /// 读取目录下的 interface.json（只支持 version 2）
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(INTERFACE_FILE);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("读取文件失败 [{}]: {}", path.display(), e))?;
+        let pi: Self = parse_jsonc(&content, &path.to_string_lossy())?;
+        if pi.interface_version != 2 {
+            return Err(format!(
+                "不支持的 interface 版本: {}，仅支持 version 2",
+                pi.interface_version
+            ));
+        }
+        Ok(pi)
+    }
+
+    pub fn find_task(&self, name: &str) -> Option<&TaskItem> {
+        self.task.iter().find(|task| task.name == name)
+    }
+}
+
+/// 进程内缓存的 interface（exe 目录下的 interface.json）
+static CURRENT: Lazy<RwLock<Option<Arc<ProjectInterface>>>> = Lazy::new(|| RwLock::new(None));
+
+/// 缓存的 interface，尚未加载时从 exe 目录加载
+pub fn current() -> Result<Arc<ProjectInterface>, String> {
+    if let Some(pi) = CURRENT.read().map_err(|e| e.to_string())?.as_ref() {
+        return Ok(pi.clone());
+    }
+    reload()
+}
+
+/// 重新加载 exe 目录下的 interface.json 并更新缓存
+pub fn reload() -> Result<Arc<ProjectInterface>, String> {
+    let pi = Arc::new(ProjectInterface::load(Path::new(&get_exe_dir()?))?);
+    *CURRENT.write().map_err(|e| e.to_string())? = Some(pi.clone());
+    Ok(pi)
+}
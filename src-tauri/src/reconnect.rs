@@ -0,0 +1,458 @@
+//! 控制器自动重连
+//!
+//! 控制器动作（截图、点击等）在回调中失败，或定时检查时 MaaControllerConnected 返回 false，
+//! 视为连接断开：使用实例最近一次的 ControllerConfig 按指数退避重新连接。
+//! 断开时暂停实例的任务队列，中断的任务放回队列，重连成功后从该任务继续执行。
+//! 重连过程通过 `maa-reconnect` 事件通知前端，策略按实例配置。
+//!
+//! 回调观察者只把实例 ID 转发给看门狗线程；重连在独立线程中进行，
+//! 不阻塞 MaaFramework 回调，也不影响其他实例。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::instance_state::LifecycleState;
+use crate::maa_commands::{connect_controller_blocking, ControllerConfig, MaaState};
+use crate::maa_events::{EventPhase, MaaEvent};
+use crate::maa_ffi::{add_event_observer, emit_event, MaaCallbackEvent};
+use crate::task_queue::TaskQueue;
+
+/// 定时检查 MaaControllerConnected 的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 单次重连等待连接结果的超时时间
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 重连前等待剩余任务停止的超时时间
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 重连成功后忽略动作失败回调的时间（旧控制器的回调可能晚于重连结果到达）
+const RECONNECT_GRACE: Duration = Duration::from_secs(10);
+
+/// 自动重连策略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconnectPolicy {
+    /// 是否启用，默认关闭，由实例设置开启
+    pub enabled: bool,
+    /// 最大重连次数
+    pub max_attempts: u32,
+    /// 第一次重连前的等待时间（毫秒），之后每次翻倍
+    pub initial_backoff_ms: u64,
+    /// 等待时间上限（毫秒）
+    pub max_backoff_ms: u64,
+    /// 重连成功后是否从中断的任务继续执行
+    pub resume_tasks: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 5,
+            initial_backoff_ms: 2_000,
+            max_backoff_ms: 60_000,
+            resume_tasks: true,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// 第 attempt 次（从 1 开始）重连前的等待时间
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        let ms = self
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(self.max_backoff_ms);
+        Duration::from_millis(ms)
+    }
+}
+
+/// 重连阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconnectPhase {
+    /// 检测到连接断开，error 为断开原因
+    Disconnected,
+    /// 等待 delay_ms 后发起第 attempt 次重连
+    Attempting,
+    /// 第 attempt 次重连失败
+    AttemptFailed,
+    Succeeded,
+    /// 重连成功后恢复任务队列，resumed_tasks 为空表示未能继续（如用户已停止任务）
+    Resumed,
+    /// 达到最大重连次数仍未成功
+    GaveUp,
+    /// 实例被销毁、关闭了自动重连或已被手动重新连接
+    Cancelled,
+}
+
+/// maa-reconnect 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconnectEvent {
+    pub instance_id: String,
+    pub phase: ReconnectPhase,
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub delay_ms: u64,
+    pub error: Option<String>,
+    /// phase 为 Disconnected 时，重连成功后将继续执行的任务（队列项 ID）
+    pub interrupted_tasks: Vec<u64>,
+    /// phase 为 Resumed 时，将继续执行的任务（队列项 ID）
+    pub resumed_tasks: Vec<u64>,
+}
+
+impl ReconnectEvent {
+    fn new(instance_id: &str, phase: ReconnectPhase, policy: &ReconnectPolicy) -> Self {
+        Self {
+            instance_id: instance_id.to_string(),
+            phase,
+            attempt: 0,
+            max_attempts: policy.max_attempts,
+            delay_ms: 0,
+            error: None,
+            interrupted_tasks: Vec::new(),
+            resumed_tasks: Vec::new(),
+        }
+    }
+
+    fn emit(self) {
+        emit_event("maa-reconnect", self);
+    }
+}
+
+/// 断开时实例的状态
+struct Interrupted {
+    config: ControllerConfig,
+    /// 重连后将继续执行的任务（队列项 ID）
+    tasks: Vec<u64>,
+}
+
+/// 自动重连看门狗
+pub struct ReconnectWatchdog {
+    state: Arc<MaaState>,
+    /// 各实例的重连策略，未设置的实例使用默认策略
+    policies: Mutex<HashMap<String, ReconnectPolicy>>,
+    /// 正在重连的实例
+    active: Mutex<HashSet<String>>,
+    /// 各实例最近一次重连成功的时间
+    reconnected_at: Mutex<HashMap<String, Instant>>,
+    /// 回调中失败的控制器动作：(实例 ID, 原因)
+    sender: Mutex<Sender<(String, String)>>,
+}
+
+impl ReconnectWatchdog {
+    /// 注册回调观察者并启动看门狗线程
+    pub fn start(state: Arc<MaaState>) -> Arc<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let watchdog = Arc::new(Self {
+            state,
+            policies: Mutex::new(HashMap::new()),
+            active: Mutex::new(HashSet::new()),
+            reconnected_at: Mutex::new(HashMap::new()),
+            sender: Mutex::new(sender),
+        });
+
+        let observer = watchdog.clone();
+        add_event_observer(move |event| observer.observe(event));
+
+        let worker = watchdog.clone();
+        thread::spawn(move || worker.run(receiver));
+
+        watchdog
+    }
+
+    pub fn set_policy(&self, instance_id: &str, policy: ReconnectPolicy) -> Result<(), String> {
+        self.policies
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(instance_id.to_string(), policy);
+        Ok(())
+    }
+
+    fn policy(&self, instance_id: &str) -> ReconnectPolicy {
+        self.policies
+            .lock()
+            .ok()
+            .and_then(|policies| policies.get(instance_id).cloned())
+            .unwrap_or_default()
+    }
+
+    /// 回调观察者：只转发失败的非连接动作
+    fn observe(&self, event: &MaaCallbackEvent) {
+        if let MaaEvent::ControllerAction {
+            phase: EventPhase::Failed,
+            detail,
+        } = &event.event
+        {
+            if detail.is_connect() {
+                return;
+            }
+            if let Ok(sender) = self.sender.lock() {
+                let reason = format!("Controller action failed: {}", detail.action);
+                let _ = sender.send((event.instance_id.clone(), reason));
+            }
+        }
+    }
+
+    fn run(self: Arc<Self>, receiver: Receiver<(String, String)>) {
+        loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok((instance_id, reason)) => self.check(&instance_id, Some(reason)),
+                Err(RecvTimeoutError::Timeout) => {
+                    let instance_ids: Vec<String> = match self.state.instances.lock() {
+                        Ok(instances) => instances.keys().cloned().collect(),
+                        Err(_) => continue,
+                    };
+                    for instance_id in instance_ids {
+                        self.check(&instance_id, None);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    }
+
+    /// 检查实例是否断开，断开时启动重连线程
+    /// reason 为回调中失败的动作，为 None 时查询 MaaControllerConnected
+    fn check(self: &Arc<Self>, instance_id: &str, reason: Option<String>) {
+        if !self.policy(instance_id).enabled {
+            return;
+        }
+        // 只处理已连接的实例，正在连接或连接失败的实例由前端处理
+        if !self
+            .state
+            .lifecycles
+            .snapshot(instance_id)
+            .is_some_and(|snapshot| snapshot.connected)
+        {
+            return;
+        }
+        if self
+            .active
+            .lock()
+            .map_or(true, |active| active.contains(instance_id))
+        {
+            return;
+        }
+
+        let controller = match self.state.instance(instance_id) {
+            Ok(instance) => match instance.lock() {
+                Ok(instance) if instance.controller_config.is_some() => instance.controller.clone(),
+                _ => None,
+            },
+            Err(_) => None,
+        };
+        let Some(controller) = controller else {
+            return;
+        };
+
+        let reason = match reason {
+            Some(_) if self.recently_reconnected(instance_id) => return,
+            Some(reason) => reason,
+            None if controller.connected() => return,
+            None => "Controller disconnected".to_string(),
+        };
+        drop(controller);
+
+        // 标记后再启动线程，避免同一实例重复重连
+        let inserted = self
+            .active
+            .lock()
+            .is_ok_and(|mut active| active.insert(instance_id.to_string()));
+        if !inserted {
+            return;
+        }
+        let watchdog = self.clone();
+        let instance_id = instance_id.to_string();
+        thread::spawn(move || {
+            watchdog.reconnect(&instance_id, reason);
+            if let Ok(mut active) = watchdog.active.lock() {
+                active.remove(&instance_id);
+            }
+        });
+    }
+
+    fn recently_reconnected(&self, instance_id: &str) -> bool {
+        self.reconnected_at
+            .lock()
+            .ok()
+            .and_then(|times| times.get(instance_id).copied())
+            .is_some_and(|at| at.elapsed() < RECONNECT_GRACE)
+    }
+
+    fn reconnect(&self, instance_id: &str, reason: String) {
+        let policy = self.policy(instance_id);
+        warn!("[reconnect] {} disconnected: {}", instance_id, reason);
+
+        let Some(interrupted) = self.interrupt(instance_id, &policy, reason) else {
+            return;
+        };
+
+        for attempt in 1..=policy.max_attempts {
+            let delay = policy.backoff(attempt);
+            let mut event = ReconnectEvent::new(instance_id, ReconnectPhase::Attempting, &policy);
+            event.attempt = attempt;
+            event.delay_ms = delay.as_millis() as u64;
+            event.emit();
+
+            thread::sleep(delay);
+            if let Some(reason) = self.cancel_reason(instance_id) {
+                info!("[reconnect] {} cancelled: {}", instance_id, reason);
+                let mut event =
+                    ReconnectEvent::new(instance_id, ReconnectPhase::Cancelled, &policy);
+                event.attempt = attempt;
+                event.error = Some(reason.to_string());
+                event.emit();
+                self.abandon(instance_id);
+                return;
+            }
+
+            info!(
+                "[reconnect] {} attempt {}/{}",
+                instance_id, attempt, policy.max_attempts
+            );
+            match self.connect(instance_id, &interrupted.config) {
+                Ok(()) => {
+                    info!("[reconnect] {} reconnected", instance_id);
+                    if let Ok(mut times) = self.reconnected_at.lock() {
+                        times.insert(instance_id.to_string(), Instant::now());
+                    }
+                    let mut event =
+                        ReconnectEvent::new(instance_id, ReconnectPhase::Succeeded, &policy);
+                    event.attempt = attempt;
+                    event.emit();
+
+                    self.resume(instance_id, &policy, interrupted.tasks);
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        "[reconnect] {} attempt {} failed: {}",
+                        instance_id, attempt, e
+                    );
+                    let mut event =
+                        ReconnectEvent::new(instance_id, ReconnectPhase::AttemptFailed, &policy);
+                    event.attempt = attempt;
+                    event.error = Some(e);
+                    event.emit();
+                }
+            }
+        }
+
+        warn!(
+            "[reconnect] {} gave up after {} attempt(s)",
+            instance_id, policy.max_attempts
+        );
+        let mut event = ReconnectEvent::new(instance_id, ReconnectPhase::GaveUp, &policy);
+        event.attempt = policy.max_attempts;
+        event.emit();
+        self.abandon(instance_id);
+    }
+
+    /// 暂停任务队列并停止 Tasker，实例状态标记为连接失败
+    fn interrupt(
+        &self,
+        instance_id: &str,
+        policy: &ReconnectPolicy,
+        reason: String,
+    ) -> Option<Interrupted> {
+        let (config, tasker) = {
+            let instance = self.state.instance(instance_id).ok()?;
+            let instance = instance.lock().ok()?;
+            (instance.controller_config.clone()?, instance.tasker.clone())
+        };
+
+        // 运行中的任务放回队列，随后被停止时不会记为失败
+        let tasks = if policy.resume_tasks {
+            self.state
+                .update_queue(instance_id, TaskQueue::hold)
+                .unwrap_or_default()
+        } else {
+            let _ = self.state.update_queue(instance_id, TaskQueue::stop);
+            Vec::new()
+        };
+
+        let mut event = ReconnectEvent::new(instance_id, ReconnectPhase::Disconnected, policy);
+        event.interrupted_tasks = tasks.clone();
+        event.error = Some(reason.clone());
+        event.emit();
+
+        if let Some(tasker) = tasker.filter(|tasker| tasker.running()) {
+            // 停止剩余任务，避免它们在断开的控制器上逐个失败
+            self.state
+                .lifecycles
+                .update(instance_id, |lc| lc.stop_requested());
+            tasker.post_stop();
+            let deadline = Instant::now() + STOP_TIMEOUT;
+            while tasker.running() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(200));
+            }
+        }
+
+        self.state
+            .lifecycles
+            .update(instance_id, |lc| lc.connect_failed(&reason));
+        Some(Interrupted { config, tasks })
+    }
+
+    /// 放弃重连：取消暂停中的任务队列
+    fn abandon(&self, instance_id: &str) {
+        let _ = self.state.update_queue(instance_id, |queue| {
+            queue.stop();
+            queue.release();
+        });
+    }
+
+    /// 等待期间实例被销毁、关闭自动重连或已被手动连接时返回原因
+    fn cancel_reason(&self, instance_id: &str) -> Option<&'static str> {
+        if !self.policy(instance_id).enabled {
+            return Some("Auto reconnect disabled");
+        }
+        match self.state.lifecycles.snapshot(instance_id) {
+            None => Some("Instance destroyed"),
+            Some(snapshot) if snapshot.connected => Some("Already reconnected"),
+            Some(snapshot) if snapshot.lifecycle == LifecycleState::Connecting => {
+                Some("Connection in progress")
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// 发起连接并等待回调给出结果
+    fn connect(&self, instance_id: &str, config: &ControllerConfig) -> Result<(), String> {
+        connect_controller_blocking(&self.state, instance_id, config, CONNECT_TIMEOUT)
+    }
+
+    /// 恢复任务队列；有中断的任务时总会发送 Resumed，未能继续时 resumed_tasks 为空
+    fn resume(&self, instance_id: &str, policy: &ReconnectPolicy, tasks: Vec<u64>) {
+        // 重连期间用户停止了任务时，队列中已没有等待中的任务
+        let resumed_tasks = self
+            .state
+            .update_queue(instance_id, TaskQueue::release)
+            .unwrap_or_default();
+        if tasks.is_empty() {
+            return;
+        }
+
+        info!(
+            "[reconnect] {} resumed {}/{} task(s)",
+            instance_id,
+            resumed_tasks.len(),
+            tasks.len()
+        );
+
+        let mut event = ReconnectEvent::new(instance_id, ReconnectPhase::Resumed, policy);
+        event.resumed_tasks = resumed_tasks;
+        event.emit();
+    }
+}
@@ -0,0 +1,1004 @@
+//! 定时执行
+//!
+//! 前端通过 `maa_set_schedule` 同步每个实例的定时策略、启动任务的参数、设备和资源，
+//! 调度线程每分钟检查一次，策略命中时按自动连接的流程准备实例（启动前步骤、连接设备、
+//! 加载资源）后启动任务，窗口关闭或前端卡顿时也能按时执行。
+//!
+//! 策略可以按星期 + 小时 + 分钟设置，也可以使用 5 段 cron 表达式。时间按实例的时区计算
+//! （IANA 时区名，默认本机时区，见 `time_zone`）。调度按当地时间逐分钟推进且不会后退：
+//! 夏令时跳过的时段在跳过后立即补上，重复的时段只执行一次。
+//!
+//! 每次检查的时间保存在 state_dir 下，重启后会检查错过的时间点：开启了补跑的策略
+//! 补执行一次，其他策略发送跳过通知。执行过程通过 `maa-schedule` 事件通知前端。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveDateTime, Timelike, Utc};
+use log::{debug, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::data_dirs::data_dirs;
+use crate::maa_commands::{
+    connect_controller_blocking, create_instance, load_resource_blocking, start_tasks,
+    ControllerConfig, MaaState, StartRequest,
+};
+use crate::maa_ffi::{current_backend, emit_event};
+use crate::pre_run::PreRunManager;
+use crate::time_zone::Zone;
+
+/// 检查时间记录的文件名（位于 state_dir）
+const STATE_FILE: &str = "schedule_state.json";
+
+/// 启动时最多往前检查错过的时间点
+const MAX_CATCH_UP: chrono::Duration = chrono::Duration::days(7);
+
+/// 超过计划时间这么久才检查到的时间点视为错过
+const MISSED_AFTER: chrono::Duration = chrono::Duration::minutes(2);
+
+/// 预览下次执行时间时最多往后查找的范围（覆盖 2 月 29 日这类四年一次的表达式）
+const PREVIEW_RANGE: chrono::Duration = chrono::Duration::days(366 * 4 + 1);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const RESOURCE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 定时策略（字段名与前端保存的配置一致）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePolicy {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    /// 星期（0 = 周日）
+    #[serde(default)]
+    pub weekdays: Vec<u32>,
+    /// 小时（0-23）
+    #[serde(default)]
+    pub hours: Vec<u32>,
+    /// 整点后的分钟（0-59）
+    #[serde(default)]
+    pub minute: u32,
+    /// cron 表达式（分 时 日 月 周），设置后忽略星期、小时和分钟
+    #[serde(default)]
+    pub cron: Option<String>,
+    /// 错过的时间点（MXU 未运行或系统睡眠）在检查到时补执行一次
+    #[serde(default)]
+    pub catch_up: bool,
+}
+
+/// 定时执行时查找并连接的设备（对应前端保存的设备）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum ScheduleDevice {
+    /// 按设备名匹配 ADB 设备扫描结果
+    Adb {
+        name: String,
+    },
+    /// 按标题匹配窗口，正则用于筛选候选窗口
+    Win32 {
+        window_name: String,
+        #[serde(default)]
+        class_regex: Option<String>,
+        #[serde(default)]
+        window_regex: Option<String>,
+        screencap_method: u64,
+        mouse_method: u64,
+        keyboard_method: u64,
+    },
+    Gamepad {
+        window_name: String,
+        #[serde(default)]
+        class_regex: Option<String>,
+        #[serde(default)]
+        window_regex: Option<String>,
+    },
+    PlayCover {
+        address: String,
+    },
+}
+
+impl ScheduleDevice {
+    /// 扫描设备或窗口，生成连接配置
+    fn resolve(&self) -> Result<ControllerConfig, String> {
+        match self {
+            ScheduleDevice::Adb { name } => {
+                let device = current_backend()?
+                    .find_adb_devices()?
+                    .into_iter()
+                    .find(|device| &device.name == name)
+                    .ok_or_else(|| format!("ADB device not found: {}", name))?;
+                Ok(ControllerConfig::Adb {
+                    adb_path: device.adb_path,
+                    address: device.address,
+                    screencap_methods: device.screencap_methods.to_string(),
+                    input_methods: device.input_methods.to_string(),
+                    config: device.config,
+                })
+            }
+            ScheduleDevice::Win32 {
+                window_name,
+                class_regex,
+                window_regex,
+                screencap_method,
+                mouse_method,
+                keyboard_method,
+            } => Ok(ControllerConfig::Win32 {
+                handle: find_window(window_name, class_regex, window_regex)?,
+                screencap_method: *screencap_method,
+                mouse_method: *mouse_method,
+                keyboard_method: *keyboard_method,
+            }),
+            ScheduleDevice::Gamepad {
+                window_name,
+                class_regex,
+                window_regex,
+            } => Ok(ControllerConfig::Gamepad {
+                handle: find_window(window_name, class_regex, window_regex)?,
+                gamepad_type: None,
+                screencap_method: None,
+            }),
+            ScheduleDevice::PlayCover { address } => Ok(ControllerConfig::PlayCover {
+                address: address.clone(),
+            }),
+        }
+    }
+}
+
+fn find_window(
+    window_name: &str,
+    class_regex: &Option<String>,
+    window_regex: &Option<String>,
+) -> Result<u64, String> {
+    let compile = |regex: &Option<String>| {
+        regex
+            .as_deref()
+            .filter(|r| !r.is_empty())
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| e.to_string())
+    };
+    let class_re = compile(class_regex)?;
+    let window_re = compile(window_regex)?;
+    current_backend()?
+        .find_desktop_windows()?
+        .into_iter()
+        .filter(|w| {
+            class_re
+                .as_ref()
+                .map_or(true, |re| re.is_match(&w.class_name))
+        })
+        .filter(|w| {
+            window_re
+                .as_ref()
+                .map_or(true, |re| re.is_match(&w.window_name))
+        })
+        .find(|w| w.window_name == window_name)
+        .map(|w| w.handle)
+        .ok_or_else(|| format!("Window not found: {}", window_name))
+}
+
+/// 实例的定时执行设置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InstanceSchedule {
+    #[serde(default)]
+    pub policies: Vec<SchedulePolicy>,
+    /// 策略时间所在的时区，未设置时为本机时区
+    #[serde(default)]
+    pub timezone: Zone,
+    /// 启动任务的参数，没有启用的任务时为 None
+    #[serde(default)]
+    pub request: Option<StartRequest>,
+    /// 未连接时连接的设备，None 时使用本次运行中最后一次连接的配置
+    #[serde(default)]
+    pub device: Option<ScheduleDevice>,
+    /// 未加载资源时加载的资源路径
+    #[serde(default)]
+    pub resource_paths: Vec<String>,
+}
+
+/// 5 段 cron 表达式（分 时 日 月 周），支持 `*`、列表、范围和步长
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// 日和周都有限制时满足其一即可（与标准 cron 一致）
+    day_or_weekday: bool,
+}
+
+impl FromStr for CronExpr {
+    type Err = String;
+
+    fn from_str(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "Cron expression must have 5 fields (minute hour day month weekday): {}",
+                expr
+            ));
+        };
+        // 0 和 7 都表示周日
+        let weekdays = parse_field(weekday, 0, 7)?;
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            day_or_weekday: !day.starts_with('*') && !weekday.starts_with('*'),
+        })
+    }
+}
+
+/// 解析一段为位掩码（第 n 位表示值 n）
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("Invalid step in '{}'", part)),
+            },
+            None => (part, None),
+        };
+        let value = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| format!("Invalid value in '{}'", part))
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            // 单个值带步长时表示从该值到最大值
+            None if step.is_some() => (value(range)?, max),
+            None => (value(range)?, value(range)?),
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("'{}' is out of range {}-{}", part, min, max));
+        }
+        for v in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronExpr {
+    pub fn matches(&self, at: NaiveDateTime) -> bool {
+        let bit = |mask: u64, v: u32| mask & (1 << v) != 0;
+        let day = bit(self.days, at.day());
+        let weekday = bit(self.weekdays, at.weekday().num_days_from_sunday());
+        let day_matches = if self.day_or_weekday {
+            day || weekday
+        } else {
+            day && weekday
+        };
+        bit(self.minutes, at.minute())
+            && bit(self.hours, at.hour())
+            && bit(self.months, at.month())
+            && day_matches
+    }
+}
+
+/// 策略的触发时间
+#[derive(Debug, Clone)]
+enum Trigger {
+    Cron(CronExpr),
+    Weekly {
+        weekdays: Vec<u32>,
+        hours: Vec<u32>,
+        minute: u32,
+    },
+}
+
+impl Trigger {
+    fn new(policy: &SchedulePolicy) -> Result<Self, String> {
+        if let Some(cron) = policy.cron.as_deref().filter(|c| !c.trim().is_empty()) {
+            return cron.parse().map(Trigger::Cron);
+        }
+        if policy.minute > 59 {
+            return Err(format!("Invalid minute: {}", policy.minute));
+        }
+        Ok(Trigger::Weekly {
+            weekdays: policy.weekdays.clone(),
+            hours: policy.hours.clone(),
+            minute: policy.minute,
+        })
+    }
+
+    fn matches(&self, at: NaiveDateTime) -> bool {
+        match self {
+            Trigger::Cron(expr) => expr.matches(at),
+            Trigger::Weekly {
+                weekdays,
+                hours,
+                minute,
+            } => {
+                at.minute() == *minute
+                    && hours.contains(&at.hour())
+                    && weekdays.contains(&at.weekday().num_days_from_sunday())
+            }
+        }
+    }
+}
+
+/// 当地时间，精确到分钟
+fn minute_of(zone: &Zone, at: DateTime<Utc>) -> NaiveDateTime {
+    let local = zone.local_time(at);
+    local
+        .with_second(0)
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(local)
+}
+
+/// 下次执行的时间（Unix 毫秒），最多 count 个，供前端预览策略
+pub fn preview(policy: &SchedulePolicy, zone: Zone, count: usize) -> Result<Vec<i64>, String> {
+    let trigger = Trigger::new(policy)?;
+    Ok(upcoming(&trigger, &zone, Utc::now(), count))
+}
+
+/// from 之后的执行时间（Unix 毫秒），最多 count 个
+fn upcoming(trigger: &Trigger, zone: &Zone, from: DateTime<Utc>, count: usize) -> Vec<i64> {
+    let mut minute = minute_of(zone, from);
+    let end = minute + PREVIEW_RANGE;
+    let mut times = Vec::new();
+    while times.len() < count && minute < end {
+        minute += chrono::Duration::minutes(1);
+        if !trigger.matches(minute) {
+            continue;
+        }
+        // 落在夏令时跳过的时段时，在时段结束后执行
+        let mut at = minute;
+        let instant = loop {
+            match zone.instant(at) {
+                Some(instant) => break instant,
+                None => at += chrono::Duration::minutes(1),
+            }
+        };
+        if times.last() != Some(&instant.timestamp_millis()) {
+            times.push(instant.timestamp_millis());
+        }
+    }
+    times
+}
+
+/// 执行阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulePhase {
+    /// 策略命中，开始准备实例
+    Fired,
+    /// 未执行，skip_reason 为原因
+    Skipped,
+    /// 任务已启动
+    Started,
+    /// 准备实例或启动任务失败，error 为原因
+    Failed,
+}
+
+/// 未执行的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// 错过了计划时间且策略未开启补跑
+    Missed,
+    /// 实例正在运行任务或正在准备上一次定时执行
+    Running,
+    /// 没有启用的任务
+    NoTasks,
+}
+
+/// maa-schedule 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleEvent {
+    pub instance_id: String,
+    pub policy_id: String,
+    pub policy_name: String,
+    pub phase: SchedulePhase,
+    /// 计划执行的当地时间（"YYYY-MM-DD HH:MM"）
+    pub scheduled_for: String,
+    /// 是否为错过后的补跑
+    pub catch_up: bool,
+    pub skip_reason: Option<SkipReason>,
+    pub error: Option<String>,
+}
+
+/// 一次命中
+#[derive(Debug, Clone)]
+struct Occurrence {
+    policy: SchedulePolicy,
+    minute: NaiveDateTime,
+    catch_up: bool,
+}
+
+impl Occurrence {
+    fn event(&self, instance_id: &str, phase: SchedulePhase) -> ScheduleEvent {
+        ScheduleEvent {
+            instance_id: instance_id.to_string(),
+            policy_id: self.policy.id.clone(),
+            policy_name: self.policy.name.clone(),
+            phase,
+            scheduled_for: self.minute.format("%Y-%m-%d %H:%M").to_string(),
+            catch_up: self.catch_up,
+            skip_reason: None,
+            error: None,
+        }
+    }
+
+    fn skipped(&self, instance_id: &str, reason: SkipReason) {
+        info!(
+            "[scheduler] {}: skipped policy '{}' at {} ({:?})",
+            instance_id, self.policy.name, self.minute, reason
+        );
+        let mut event = self.event(instance_id, SchedulePhase::Skipped);
+        event.skip_reason = Some(reason);
+        emit_event("maa-schedule", event);
+    }
+}
+
+struct ScheduledInstance {
+    schedule: InstanceSchedule,
+    zone: Zone,
+    /// 已启用且有效的策略
+    triggers: Vec<(SchedulePolicy, Trigger)>,
+    /// 已检查到的当地时间（分钟），只会前进
+    cursor: NaiveDateTime,
+}
+
+/// 定时执行调度器
+pub struct Scheduler {
+    state: Arc<MaaState>,
+    pre_run: Arc<PreRunManager>,
+    instances: Mutex<HashMap<String, ScheduledInstance>>,
+    /// 各实例最近一次检查的时间（Unix 毫秒），启动时从文件读取
+    checked: Mutex<HashMap<String, i64>>,
+    /// 正在准备或启动任务的实例
+    firing: Mutex<HashSet<String>>,
+}
+
+fn state_path() -> PathBuf {
+    data_dirs().state_dir.join(STATE_FILE)
+}
+
+fn load_checked(path: &Path) -> HashMap<String, i64> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            warn!("[scheduler] Failed to read {}: {}", path.display(), e);
+            return HashMap::new();
+        }
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("[scheduler] Failed to parse {}: {}", path.display(), e);
+        HashMap::new()
+    })
+}
+
+impl Scheduler {
+    /// 启动调度线程
+    pub fn start(state: Arc<MaaState>, pre_run: Arc<PreRunManager>) -> Arc<Self> {
+        let scheduler = Arc::new(Self {
+            state,
+            pre_run,
+            instances: Mutex::new(HashMap::new()),
+            checked: Mutex::new(load_checked(&state_path())),
+            firing: Mutex::new(HashSet::new()),
+        });
+
+        let worker = scheduler.clone();
+        thread::spawn(move || loop {
+            // 在每分钟开始后稍等片刻检查，避免时钟误差导致检查落在上一分钟
+            let now = Utc::now();
+            let into_minute = now.timestamp_millis().rem_euclid(60_000) as u64;
+            thread::sleep(Duration::from_millis(60_000 - into_minute + 200));
+            worker.tick(Utc::now());
+        });
+
+        scheduler
+    }
+
+    /// 设置实例的定时执行（无效的策略会被忽略，便于前端边编辑边同步）
+    pub fn set_schedule(
+        &self,
+        instance_id: &str,
+        schedule: InstanceSchedule,
+    ) -> Result<(), String> {
+        let zone = schedule.timezone;
+        let triggers = schedule
+            .policies
+            .iter()
+            .filter(|policy| policy.enabled)
+            .filter_map(|policy| match Trigger::new(policy) {
+                Ok(trigger) => Some((policy.clone(), trigger)),
+                Err(e) => {
+                    warn!(
+                        "[scheduler] {}: ignoring policy '{}': {}",
+                        instance_id, policy.name, e
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        let now = Utc::now();
+        let mut instances = self.instances.lock().map_err(|e| e.to_string())?;
+        let cursor = match instances.get(instance_id) {
+            Some(existing) if existing.zone == zone => existing.cursor,
+            // 更换时区后从当前时间开始
+            Some(_) => minute_of(&zone, now),
+            None => {
+                // 首次设置时从上次检查的时间开始，以便补跑错过的时间点
+                let last_checked = self
+                    .checked
+                    .lock()
+                    .ok()
+                    .and_then(|checked| checked.get(instance_id).copied())
+                    .and_then(DateTime::from_timestamp_millis)
+                    .map(|at| at.max(now - MAX_CATCH_UP));
+                match last_checked {
+                    Some(at) => minute_of(&zone, at),
+                    // 没有记录时当前这一分钟也要检查
+                    None => minute_of(&zone, now) - chrono::Duration::minutes(1),
+                }
+            }
+        };
+        instances.insert(
+            instance_id.to_string(),
+            ScheduledInstance {
+                schedule,
+                zone,
+                triggers,
+                cursor,
+            },
+        );
+        Ok(())
+    }
+
+    /// 检查从上次检查到现在的每一分钟
+    fn tick(self: &Arc<Self>, now: DateTime<Utc>) {
+        let fired = self.due(now);
+        self.save_checked();
+
+        for (instance_id, schedule, occurrence) in fired {
+            self.fire(instance_id, schedule, occurrence);
+        }
+    }
+
+    /// 推进各实例的检查时间，返回要执行的时间点（错过且不补跑的发送跳过通知）
+    fn due(&self, now: DateTime<Utc>) -> Vec<(String, InstanceSchedule, Occurrence)> {
+        let mut fired = Vec::new();
+        let Ok(mut instances) = self.instances.lock() else {
+            return fired;
+        };
+        for (instance_id, instance) in instances.iter_mut() {
+            let current = minute_of(&instance.zone, now);
+            if current <= instance.cursor {
+                continue;
+            }
+            let mut minute = instance.cursor.max(current - MAX_CATCH_UP);
+            instance.cursor = current;
+
+            let mut on_time: Option<Occurrence> = None;
+            // 每个策略只保留最近一次错过的时间点
+            let mut missed: Vec<Occurrence> = Vec::new();
+            while minute < current {
+                minute += chrono::Duration::minutes(1);
+                for (policy, trigger) in &instance.triggers {
+                    if !trigger.matches(minute) {
+                        continue;
+                    }
+                    let late = instance
+                        .zone
+                        .instant(minute)
+                        .is_some_and(|at| now - at > MISSED_AFTER);
+                    let occurrence = Occurrence {
+                        policy: policy.clone(),
+                        minute,
+                        catch_up: late,
+                    };
+                    if !late {
+                        on_time.get_or_insert(occurrence);
+                    } else if let Some(existing) =
+                        missed.iter_mut().find(|o| o.policy.id == policy.id)
+                    {
+                        *existing = occurrence;
+                    } else {
+                        missed.push(occurrence);
+                    }
+                }
+            }
+
+            // 按时命中的优先；否则补跑最近一次错过的、开启了补跑的策略
+            let run = on_time.or_else(|| {
+                missed
+                    .iter()
+                    .filter(|o| o.policy.catch_up)
+                    .max_by_key(|o| o.minute)
+                    .cloned()
+            });
+            for occurrence in &missed {
+                let superseded = run.as_ref().is_some_and(|run| {
+                    run.policy.id == occurrence.policy.id && run.minute == occurrence.minute
+                });
+                if !superseded {
+                    occurrence.skipped(instance_id, SkipReason::Missed);
+                }
+            }
+            if let Some(occurrence) = run {
+                fired.push((instance_id.clone(), instance.schedule.clone(), occurrence));
+            }
+        }
+
+        if let Ok(mut checked) = self.checked.lock() {
+            for instance_id in instances.keys() {
+                checked.insert(instance_id.clone(), now.timestamp_millis());
+            }
+        }
+        fired
+    }
+
+    fn fire(
+        self: &Arc<Self>,
+        instance_id: String,
+        schedule: InstanceSchedule,
+        occurrence: Occurrence,
+    ) {
+        let Some(mut request) = schedule.request.clone().filter(|r| !r.tasks.is_empty()) else {
+            occurrence.skipped(&instance_id, SkipReason::NoTasks);
+            return;
+        };
+        // 执行条件按策略的时区判断
+        request.timezone = schedule.timezone;
+        let running = self
+            .state
+            .lifecycles
+            .snapshot(&instance_id)
+            .is_some_and(|snapshot| snapshot.is_running);
+        let Ok(mut firing) = self.firing.lock() else {
+            return;
+        };
+        if running || !firing.insert(instance_id.clone()) {
+            occurrence.skipped(&instance_id, SkipReason::Running);
+            return;
+        }
+        drop(firing);
+
+        info!(
+            "[scheduler] {}: policy '{}' fired for {}{}",
+            instance_id,
+            occurrence.policy.name,
+            occurrence.minute,
+            if occurrence.catch_up {
+                " (catch-up)"
+            } else {
+                ""
+            }
+        );
+        emit_event(
+            "maa-schedule",
+            occurrence.event(&instance_id, SchedulePhase::Fired),
+        );
+
+        let scheduler = self.clone();
+        thread::spawn(move || {
+            let result = launch(
+                &scheduler.state,
+                &scheduler.pre_run,
+                &instance_id,
+                schedule.device.as_ref(),
+                &schedule.resource_paths,
+                request,
+            );
+            if let Ok(mut firing) = scheduler.firing.lock() {
+                firing.remove(&instance_id);
+            }
+            let event = match result {
+                Ok(_) => occurrence.event(&instance_id, SchedulePhase::Started),
+                Err(e) => {
+                    warn!("[scheduler] {}: failed to start tasks: {}", instance_id, e);
+                    let mut event = occurrence.event(&instance_id, SchedulePhase::Failed);
+                    event.error = Some(e);
+                    event
+                }
+            };
+            emit_event("maa-schedule", event);
+        });
+    }
+
+    /// 写入各实例最近一次检查的时间（先写临时文件再替换）
+    fn save_checked(&self) {
+        let content = match self.checked.lock() {
+            Ok(checked) => serde_json::to_string_pretty(&*checked),
+            Err(_) => return,
+        };
+        let path = state_path();
+        let result = content.map_err(|e| e.to_string()).and_then(|content| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, content).map_err(|e| e.to_string())?;
+            std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            warn!("[scheduler] Failed to save {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// 按自动连接的流程准备实例（启动前步骤、连接设备、加载资源）后启动任务，
+/// device 为 None 时使用本次运行中最后一次连接的配置（无界面模式也通过这里启动）
+pub(crate) fn launch(
+    state: &MaaState,
+    pre_run: &PreRunManager,
+    instance_id: &str,
+    device: Option<&ScheduleDevice>,
+    resource_paths: &[String],
+    request: StartRequest,
+) -> Result<Vec<u64>, String> {
+    create_instance(state, instance_id)?;
+    let snapshot = state
+        .lifecycles
+        .snapshot(instance_id)
+        .ok_or("Instance not found")?;
+
+    if !snapshot.connected {
+        pre_run.run(instance_id)?;
+        let config = match device {
+            Some(device) => device.resolve()?,
+            None => state
+                .instance(instance_id)?
+                .lock()
+                .map_err(|e| e.to_string())?
+                .controller_config
+                .clone()
+                .ok_or("No saved device to connect")?,
+        };
+        debug!("[scheduler] {}: connecting {:?}", instance_id, config);
+        connect_controller_blocking(state, instance_id, &config, CONNECT_TIMEOUT)?;
+    }
+    if !snapshot.resource_loaded {
+        debug!("[scheduler] {}: loading resource", instance_id);
+        load_resource_blocking(state, instance_id, resource_paths, RESOURCE_TIMEOUT)?;
+    }
+
+    tauri::async_runtime::block_on(start_tasks(state, instance_id.to_string(), request))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Tz;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        at(s).and_utc()
+    }
+
+    fn cron(expr: &str) -> CronExpr {
+        expr.parse().unwrap()
+    }
+
+    fn policy(id: &str, cron: String, catch_up: bool) -> SchedulePolicy {
+        SchedulePolicy {
+            id: id.to_string(),
+            name: id.to_string(),
+            enabled: true,
+            weekdays: Vec::new(),
+            hours: Vec::new(),
+            minute: 0,
+            cron: Some(cron),
+            catch_up,
+        }
+    }
+
+    fn test_scheduler(checked: HashMap<String, i64>) -> Scheduler {
+        Scheduler {
+            state: Arc::new(MaaState::default()),
+            pre_run: Arc::new(PreRunManager::default()),
+            instances: Mutex::new(HashMap::new()),
+            checked: Mutex::new(checked),
+            firing: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// 从 from 开始每分钟检查一次，返回执行的时间点及检查时间
+    fn tick_every_minute(
+        policies: Vec<SchedulePolicy>,
+        zone: Zone,
+        from: DateTime<Utc>,
+        minutes: i64,
+    ) -> Vec<(DateTime<Utc>, Occurrence)> {
+        let scheduler = test_scheduler(HashMap::new());
+        let schedule = InstanceSchedule {
+            policies,
+            timezone: zone,
+            ..InstanceSchedule::default()
+        };
+        scheduler.set_schedule("test", schedule).unwrap();
+        if let Some(instance) = scheduler.instances.lock().unwrap().get_mut("test") {
+            instance.cursor = minute_of(&zone, from);
+        }
+        (1..=minutes)
+            .map(|i| from + chrono::Duration::minutes(i))
+            .flat_map(|now| {
+                scheduler
+                    .due(now)
+                    .into_iter()
+                    .map(move |(_, _, occurrence)| (now, occurrence))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cron_ranges_lists_and_steps() {
+        let expr = cron("*/15 9-11 * * *");
+        assert!(expr.matches(at("2026-10-17 09:00")));
+        assert!(expr.matches(at("2026-10-17 11:45")));
+        assert!(!expr.matches(at("2026-10-17 09:10")));
+        assert!(!expr.matches(at("2026-10-17 12:00")));
+
+        // 单个值带步长时从该值到最大值
+        let expr = cron("5/20 0 1-10/3 * *");
+        assert!(expr.matches(at("2026-10-04 00:25")));
+        assert!(expr.matches(at("2026-10-10 00:45")));
+        assert!(!expr.matches(at("2026-10-05 00:25")));
+        assert!(!expr.matches(at("2026-10-04 00:20")));
+
+        let expr = cron("0,30 8,20 * 1,7 *");
+        assert!(expr.matches(at("2026-07-01 20:30")));
+        assert!(!expr.matches(at("2026-08-01 20:30")));
+    }
+
+    #[test]
+    fn cron_rejects_invalid_fields() {
+        for expr in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(expr.parse::<CronExpr>().is_err(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn cron_seven_is_sunday() {
+        let sunday = at("2026-10-18 09:00");
+        assert!(cron("0 9 * * 7").matches(sunday));
+        assert_eq!(cron("0 9 * * 7"), cron("0 9 * * 0"));
+
+        let weekend = cron("0 9 * * 6-7");
+        assert!(weekend.matches(at("2026-10-17 09:00")));
+        assert!(weekend.matches(sunday));
+        assert!(!weekend.matches(at("2026-10-19 09:00")));
+    }
+
+    #[test]
+    fn cron_day_or_weekday() {
+        // 日和周都有限制时满足其一即可
+        let expr = cron("0 9 13 * 5");
+        assert!(expr.matches(at("2026-11-13 09:00")));
+        assert!(expr.matches(at("2026-10-13 09:00")));
+        assert!(expr.matches(at("2026-10-16 09:00")));
+        assert!(!expr.matches(at("2026-10-15 09:00")));
+
+        // 只限制其一时只看该字段
+        assert!(!cron("0 9 13 * *").matches(at("2026-10-16 09:00")));
+        assert!(!cron("0 9 * * 5").matches(at("2026-10-13 09:00")));
+    }
+
+    #[test]
+    fn dst_gap_runs_after_the_gap() {
+        // 2026-03-29 柏林 02:00 跳到 03:00
+        let zone = Zone::Named(Tz::Europe__Berlin);
+        let fired = tick_every_minute(
+            vec![policy("daily", "30 2 * * *".to_string(), false)],
+            zone,
+            utc("2026-03-29 00:50"),
+            30,
+        );
+        assert_eq!(fired.len(), 1);
+        let (now, occurrence) = &fired[0];
+        assert_eq!(*now, utc("2026-03-29 01:00"));
+        assert_eq!(occurrence.minute, at("2026-03-29 02:30"));
+        assert!(!occurrence.catch_up);
+
+        let trigger = Trigger::Cron(cron("0,30 2 * * *"));
+        assert_eq!(
+            upcoming(&trigger, &zone, utc("2026-03-28 12:00"), 2),
+            vec![
+                utc("2026-03-29 01:00").timestamp_millis(),
+                utc("2026-03-30 00:00").timestamp_millis(),
+            ]
+        );
+    }
+
+    #[test]
+    fn dst_overlap_runs_once() {
+        // 2026-10-25 柏林 03:00 回到 02:00，02:30 出现两次
+        let zone = Zone::Named(Tz::Europe__Berlin);
+        let fired = tick_every_minute(
+            vec![policy("daily", "30 2 * * *".to_string(), false)],
+            zone,
+            utc("2026-10-24 23:50"),
+            140,
+        );
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].0, utc("2026-10-25 00:30"));
+
+        let trigger = Trigger::Cron(cron("30 2 * * *"));
+        assert_eq!(
+            upcoming(&trigger, &zone, utc("2026-10-24 12:00"), 2),
+            vec![
+                utc("2026-10-25 00:30").timestamp_millis(),
+                utc("2026-10-26 01:30").timestamp_millis(),
+            ]
+        );
+    }
+
+    #[test]
+    fn catch_up_from_saved_state() {
+        let dir = std::env::temp_dir().join(format!("mxu-scheduler-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(STATE_FILE);
+        let now = Utc::now();
+        let last_checked = now - chrono::Duration::hours(3);
+        let saved = HashMap::from([("test".to_string(), last_checked.timestamp_millis())]);
+        std::fs::write(&path, serde_json::to_string(&saved).unwrap()).unwrap();
+
+        let zone = Zone::Named(Tz::UTC);
+        let missed = minute_of(&zone, now - chrono::Duration::hours(2));
+        let skipped = minute_of(&zone, now - chrono::Duration::hours(1));
+        let schedule = InstanceSchedule {
+            policies: vec![
+                policy(
+                    "catch-up",
+                    format!("{} {} * * *", missed.minute(), missed.hour()),
+                    true,
+                ),
+                policy(
+                    "no-catch-up",
+                    format!("{} {} * * *", skipped.minute(), skipped.hour()),
+                    false,
+                ),
+            ],
+            timezone: zone,
+            ..InstanceSchedule::default()
+        };
+
+        // 从保存的检查时间开始，只补跑开启了补跑的策略
+        let scheduler = test_scheduler(load_checked(&path));
+        scheduler.set_schedule("test", schedule.clone()).unwrap();
+        let fired = scheduler.due(now);
+        assert_eq!(fired.len(), 1);
+        let (instance_id, _, occurrence) = &fired[0];
+        assert_eq!(instance_id, "test");
+        assert_eq!(occurrence.policy.id, "catch-up");
+        assert_eq!(occurrence.minute, missed);
+        assert!(occurrence.catch_up);
+        assert!(scheduler.due(now).is_empty());
+
+        // 没有检查记录时从当前时间开始
+        let scheduler = test_scheduler(load_checked(&dir.join("missing.json")));
+        scheduler.set_schedule("test", schedule).unwrap();
+        assert!(scheduler.due(now).is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+//! 任务执行条件
+//!
+//! `TaskConfig` 可以声明若干条件，派发线程提交任务前逐个检查（使用启动时指定的时区，
+//! 与定时执行相同，未设置时为本机时区）：
+//! 前面的任务以指定结果结束、当前处于某个时间段内、当天是指定的星期几、
+//! 本周期（见 `task_ledger` 的每日重置时间）内尚未成功执行过。
+//! 任一条件不满足时任务被跳过，跳过原因记录在队列项的 `skip_reason` 中。
+//! 时间格式和星期几的取值在启动或插入任务时检查，无效时拒绝请求。
+
+use chrono::{DateTime, Datelike, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::task_queue::{QueuedTask, QueuedTaskStatus};
+use crate::time_zone::Zone;
+
+/// 任务执行条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskCondition {
+    /// 队列中前面的指定任务（前端任务 ID 或入口名）最近一次以 outcome 结束
+    AfterTask {
+        task: String,
+        outcome: QueuedTaskStatus,
+    },
+    /// 只在 start 到 end（"HH:MM"，不含 end）之间执行；end 早于 start 时跨越午夜
+    TimeWindow { start: String, end: String },
+    /// 只在指定的星期几执行（0 为周日）
+    Weekdays { days: Vec<u32> },
+    /// 本周期内尚未成功执行过（按完成记录的每日重置时间划分）
+    NotSucceededToday,
+}
+
+/// 检查条件所需的信息
+pub(crate) struct ConditionContext<'a> {
+    /// 队列中位于该任务之前的任务
+    pub earlier: &'a [QueuedTask],
+    /// 该任务在本周期内成功的时间（Unix 毫秒）
+    pub completed_at: Option<i64>,
+    /// 检查条件的时区
+    pub zone: Zone,
+    /// 该时区的当前时间
+    pub now: NaiveDateTime,
+}
+
+pub(crate) fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").map_err(|_| format!("Invalid time: {}", text))
+}
+
+impl TaskCondition {
+    /// 检查条件本身是否有效（时间格式、星期几的取值）
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        match self {
+            TaskCondition::TimeWindow { start, end } => {
+                parse_time(start)?;
+                parse_time(end)?;
+                Ok(())
+            }
+            TaskCondition::Weekdays { days } => match days.iter().find(|day| **day > 6) {
+                Some(day) => Err(format!("Invalid weekday: {} (expected 0-6)", day)),
+                None => Ok(()),
+            },
+            TaskCondition::AfterTask { .. } | TaskCondition::NotSucceededToday => Ok(()),
+        }
+    }
+
+    /// 满足条件时返回 Ok，否则返回跳过原因
+    pub(crate) fn check(&self, ctx: &ConditionContext) -> Result<(), String> {
+        match self {
+            TaskCondition::AfterTask { task, outcome } => {
+                let previous = ctx.earlier.iter().rev().find(|item| {
+                    item.source_id.as_deref() == Some(task.as_str()) || item.entry == *task
+                });
+                match previous.map(|item| item.status) {
+                    Some(status) if status == *outcome => Ok(()),
+                    Some(QueuedTaskStatus::Pending) | Some(QueuedTaskStatus::Running) | None => {
+                        Err(format!("Task {} has not finished", task))
+                    }
+                    Some(status) => Err(format!(
+                        "Task {} ended as {:?}, expected {:?}",
+                        task, status, outcome
+                    )),
+                }
+            }
+            TaskCondition::TimeWindow { start, end } => {
+                let start_time = parse_time(start)?;
+                let end_time = parse_time(end)?;
+                let now = NaiveTime::from_hms_opt(ctx.now.hour(), ctx.now.minute(), 0)
+                    .unwrap_or_default();
+                let inside = if start_time <= end_time {
+                    start_time <= now && now < end_time
+                } else {
+                    now >= start_time || now < end_time
+                };
+                if inside {
+                    Ok(())
+                } else {
+                    Err(format!("Outside time window {}-{}", start, end))
+                }
+            }
+            TaskCondition::Weekdays { days } => {
+                let weekday = ctx.now.weekday();
+                if days.contains(&weekday.num_days_from_sunday()) {
+                    Ok(())
+                } else {
+                    Err(format!("Not scheduled on {}", weekday))
+                }
+            }
+            TaskCondition::NotSucceededToday => match ctx.completed_at {
+                Some(at) => Err(format!(
+                    "Already completed in current period at {}",
+                    DateTime::from_timestamp_millis(at)
+                        .map(|at| ctx.zone.local_time(at).format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default()
+                )),
+                None => Ok(()),
+            },
+        }
+    }
+}
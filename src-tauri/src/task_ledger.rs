@@ -0,0 +1,261 @@
+//! 每日完成记录
+//!
+//! 按实例、任务名记录任务最近一次成功的时间，保存在 state_dir 下，重启后仍然有效。
+//! 每个实例可以设置每日重置时间和时区（IANA 时区名，默认本机时区，与定时执行相同），
+//! 两次重置之间为一个周期；`not_succeeded_today` 条件和 `maa_start_tasks` 的
+//! `skip_completed` 选项据此跳过本周期内已经成功过的任务。
+//!
+//! 回调线程只修改内存中的记录，写入文件在派发线程或命令中进行。
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::data_dirs::data_dirs;
+use crate::task_condition::parse_time;
+use crate::time_zone::Zone;
+
+/// 记录文件名（位于 state_dir）
+const LEDGER_FILE: &str = "task_ledger.json";
+
+fn default_reset_time() -> String {
+    "04:00".to_string()
+}
+
+/// 每日重置设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayReset {
+    /// 每日重置时间（"HH:MM"）
+    #[serde(default = "default_reset_time")]
+    pub reset_time: String,
+    /// 重置时间所在的时区，未设置时为本机时区
+    #[serde(default)]
+    pub timezone: Zone,
+}
+
+impl Default for DayReset {
+    fn default() -> Self {
+        Self {
+            reset_time: default_reset_time(),
+            timezone: Zone::Local,
+        }
+    }
+}
+
+impl DayReset {
+    fn validate(&self) -> Result<(), String> {
+        parse_time(&self.reset_time)?;
+        Ok(())
+    }
+
+    /// 包含 now 的周期的起止时间（Unix 毫秒）
+    pub fn period(&self, now: DateTime<Utc>) -> (i64, i64) {
+        let time = parse_time(&self.reset_time).unwrap_or(NaiveTime::MIN);
+        period_in(&self.timezone, time, now)
+    }
+}
+
+fn period_in(zone: &Zone, time: NaiveTime, now: DateTime<Utc>) -> (i64, i64) {
+    let local = zone.local_time(now);
+    let mut date = local.date();
+    if local.time() < time {
+        date = date.pred_opt().unwrap_or(date);
+    }
+    let reset_at = |date: NaiveDate| {
+        let naive = date.and_time(time);
+        // 重置时间落在夏令时跳过的时段时，顺延一小时
+        zone.instant(naive)
+            .or_else(|| zone.instant(naive + chrono::Duration::hours(1)))
+            .map_or_else(
+                || naive.and_utc().timestamp_millis(),
+                |at| at.timestamp_millis(),
+            )
+    };
+    (reset_at(date), reset_at(date.succ_opt().unwrap_or(date)))
+}
+
+/// 单个任务的记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// 最近一次成功的时间（Unix 毫秒）
+    pub last_success: i64,
+    /// 累计成功次数
+    pub total_successes: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InstanceLedger {
+    #[serde(default)]
+    reset: DayReset,
+    /// 任务名 -> 记录
+    #[serde(default)]
+    tasks: HashMap<String, LedgerEntry>,
+}
+
+/// 任务在当前周期的完成情况
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerTask {
+    #[serde(flatten)]
+    pub entry: LedgerEntry,
+    /// 本周期内已成功
+    pub completed: bool,
+}
+
+/// maa_get_task_ledger 的返回值
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskLedgerSnapshot {
+    pub reset: DayReset,
+    /// 当前周期的开始时间和下次重置时间（Unix 毫秒）
+    pub period_start: i64,
+    pub next_reset: i64,
+    pub tasks: HashMap<String, LedgerTask>,
+}
+
+/// 所有实例的完成记录
+pub struct TaskLedger {
+    instances: Mutex<HashMap<String, InstanceLedger>>,
+    /// 内存中的记录有尚未写入文件的修改
+    dirty: AtomicBool,
+}
+
+impl Default for TaskLedger {
+    fn default() -> Self {
+        Self {
+            instances: Mutex::new(load()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+}
+
+fn ledger_path() -> PathBuf {
+    data_dirs().state_dir.join(LEDGER_FILE)
+}
+
+fn load() -> HashMap<String, InstanceLedger> {
+    let path = ledger_path();
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            warn!("[task_ledger] Failed to read {}: {}", path.display(), e);
+            return HashMap::new();
+        }
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("[task_ledger] Failed to parse {}: {}", path.display(), e);
+        HashMap::new()
+    })
+}
+
+impl TaskLedger {
+    /// 记录任务成功（只修改内存，由 flush 写入文件）
+    pub fn record_success(&self, instance_id: &str, task_name: &str) {
+        let Ok(mut instances) = self.instances.lock() else {
+            return;
+        };
+        let entry = instances
+            .entry(instance_id.to_string())
+            .or_default()
+            .tasks
+            .entry(task_name.to_string())
+            .or_insert(LedgerEntry {
+                last_success: 0,
+                total_successes: 0,
+            });
+        entry.last_success = Utc::now().timestamp_millis();
+        entry.total_successes += 1;
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// 任务在当前周期内成功的时间，未成功时为 None
+    pub fn completed_at(&self, instance_id: &str, task_name: &str) -> Option<i64> {
+        let instances = self.instances.lock().ok()?;
+        let ledger = instances.get(instance_id)?;
+        let last_success = ledger.tasks.get(task_name)?.last_success;
+        let (period_start, _) = ledger.reset.period(Utc::now());
+        (last_success >= period_start).then_some(last_success)
+    }
+
+    /// 设置实例的每日重置时间和时区
+    pub fn set_reset(&self, instance_id: &str, reset: DayReset) -> Result<(), String> {
+        reset.validate()?;
+        let mut instances = self.instances.lock().map_err(|e| e.to_string())?;
+        let ledger = instances.entry(instance_id.to_string()).or_default();
+        if ledger.reset != reset {
+            ledger.reset = reset;
+            self.dirty.store(true, Ordering::Release);
+        }
+        Ok(())
+    }
+
+    /// 清除实例中指定任务（None 为全部任务）的记录
+    pub fn clear(&self, instance_id: &str, task_name: Option<&str>) -> Result<(), String> {
+        let mut instances = self.instances.lock().map_err(|e| e.to_string())?;
+        if let Some(ledger) = instances.get_mut(instance_id) {
+            match task_name {
+                Some(name) => {
+                    ledger.tasks.remove(name);
+                }
+                None => ledger.tasks.clear(),
+            }
+            self.dirty.store(true, Ordering::Release);
+        }
+        Ok(())
+    }
+
+    pub fn snapshot(&self, instance_id: &str) -> Result<TaskLedgerSnapshot, String> {
+        let instances = self.instances.lock().map_err(|e| e.to_string())?;
+        let default = InstanceLedger::default();
+        let ledger = instances.get(instance_id).unwrap_or(&default);
+        let (period_start, next_reset) = ledger.reset.period(Utc::now());
+        let tasks = ledger
+            .tasks
+            .iter()
+            .map(|(name, entry)| {
+                let task = LedgerTask {
+                    entry: entry.clone(),
+                    completed: entry.last_success >= period_start,
+                };
+                (name.clone(), task)
+            })
+            .collect();
+        Ok(TaskLedgerSnapshot {
+            reset: ledger.reset.clone(),
+            period_start,
+            next_reset,
+            tasks,
+        })
+    }
+
+    /// 有修改时写入文件（先写临时文件再替换，避免写入中断损坏记录）
+    pub fn flush(&self) {
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        let content = match self.instances.lock() {
+            Ok(instances) => serde_json::to_string_pretty(&*instances),
+            Err(_) => return,
+        };
+        let path = ledger_path();
+        let result = content.map_err(|e| e.to_string()).and_then(|content| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, content).map_err(|e| e.to_string())?;
+            std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+        });
+        match result {
+            Ok(()) => debug!("[task_ledger] Saved {}", path.display()),
+            Err(e) => {
+                warn!("[task_ledger] Failed to save {}: {}", path.display(), e);
+                self.dirty.store(true, Ordering::Release);
+            }
+        }
+    }
+}
@@ -0,0 +1,1147 @@
+//! 实例任务队列
+//!
+//! 每个实例有一个由后端持有的任务队列，记录每个任务的入口、名称、状态、起止时间和结果。
+//! 任务逐个提交：上一个任务结束后，派发线程才向 Tasker 提交下一个等待中的任务，
+//! 因此尚未开始的任务可以插入、删除、调整顺序或修改 Pipeline 覆盖。
+//! 队列每次变化都会发送 `maa-task-queue` 事件，`maa_get_all_states` 也返回完整队列，
+//! 刷新页面后可以看到等待中、运行中和已结束的任务。
+//!
+//! 任务可以设置失败重试次数、重试间隔和单次执行的最长时间：失败后按间隔重新提交，
+//! 超时则调用 MaaTaskerPostStop 并记为一次失败。每次提交都会记录在 `attempts` 中。
+//!
+//! 用户可以暂停队列：在当前任务结束后暂停，或立即停止当前任务并把它放回等待中；
+//! 恢复时按顺序继续提交剩余任务，可选择是否重新执行被中断的任务。
+//!
+//! `maa_start_tasks` 可以指定循环方式（执行 N 次、循环到截止时间、循环到指定任务成功或失败）：
+//! 一轮结束后由队列自己追加下一轮任务，前端不需要驱动循环。
+//!
+//! 任务可以声明执行条件（见 `task_condition`），提交前检查，不满足时记为跳过并附带原因。
+//! 任务成功时写入每日完成记录（见 `task_ledger`），用于跳过本周期内已完成的任务。
+//!
+//! `maa_start_tasks` 启动的一次运行（含所有循环轮次）开始和结束时发送 `maa-run` 事件，
+//! 结束时附带结果和各状态的任务数，运行结束后的动作（见 `post_run`）据此执行。
+//!
+//! 与生命周期状态机相同，回调观察者只做内存操作，提交任务在派发线程中进行。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::instance_state::LifecycleRegistry;
+use crate::maa_commands::{MaaState, TaskConfig};
+use crate::maa_events::{EventPhase, MaaEvent};
+use crate::maa_ffi::{add_event_observer, emit_event, MaaCallbackEvent, Tasker, MAA_INVALID_ID};
+use crate::task_condition::{ConditionContext, TaskCondition};
+use crate::task_ledger::TaskLedger;
+use crate::time_zone::Zone;
+
+/// 队列项 ID 计数器（进程内唯一）
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 派发线程检查超时和重试间隔的周期
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// 队列中任务的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueuedTaskStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+    /// 执行条件不满足，未提交
+    Skipped,
+}
+
+/// 任务列表的循环方式
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RunMode {
+    /// 只执行一次
+    #[default]
+    Once,
+    /// 共执行 times 轮
+    Repeat { times: u32 },
+    /// 循环到截止时间（Unix 毫秒），截止后不再开始新一轮，当前一轮会执行完
+    UntilDeadline { deadline: i64 },
+    /// 循环到指定任务（前端任务 ID 或入口名）以 outcome 结束的那一轮为止
+    UntilTask {
+        task: String,
+        outcome: QueuedTaskStatus,
+    },
+}
+
+/// maa_start_tasks 的循环设置
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RunLoop {
+    #[serde(default)]
+    pub mode: RunMode,
+    /// 两轮之间的等待时间（毫秒）
+    #[serde(default)]
+    pub iteration_delay_ms: u64,
+}
+
+/// 循环进度（随 maa-task-queue 事件和 maa_get_all_states 返回）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunProgress {
+    #[serde(flatten)]
+    pub config: RunLoop,
+    /// 当前轮次（从 1 开始）
+    pub iteration: u32,
+    /// 等待开始下一轮时，下一轮最早的开始时间（Unix 毫秒）
+    pub next_iteration_at: Option<i64>,
+}
+
+/// 一次运行的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunOutcome {
+    /// 所有任务成功或被跳过
+    Succeeded,
+    /// 有任务失败或被取消
+    Failed,
+    /// 用户停止或实例被销毁
+    Stopped,
+}
+
+/// 运行阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunPhase {
+    Started,
+    Finished,
+}
+
+/// 一次运行的记录，结束时 outcome 和各状态的任务数有效
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub phase: RunPhase,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    /// 已执行的轮数
+    pub iterations: u32,
+    pub outcome: Option<RunOutcome>,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub skipped: u32,
+    pub cancelled: u32,
+}
+
+/// maa-run 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct RunEvent {
+    pub instance_id: String,
+    #[serde(flatten)]
+    pub summary: RunSummary,
+}
+
+/// 正在进行的运行
+#[derive(Debug)]
+struct RunState {
+    progress: RunProgress,
+    /// 每轮要执行的任务
+    template: Vec<TaskConfig>,
+    summary: RunSummary,
+    /// 已请求停止，本轮结束后不再继续
+    stopped: bool,
+}
+
+impl RunState {
+    /// 统计本轮任务的结果
+    fn tally(&mut self, tasks: &[QueuedTask]) {
+        let iteration = self.progress.iteration;
+        let summary = &mut self.summary;
+        summary.iterations = iteration;
+        for task in tasks.iter().filter(|task| task.iteration == iteration) {
+            match task.status {
+                QueuedTaskStatus::Succeeded => summary.succeeded += 1,
+                QueuedTaskStatus::Failed => summary.failed += 1,
+                QueuedTaskStatus::Skipped => summary.skipped += 1,
+                QueuedTaskStatus::Cancelled => summary.cancelled += 1,
+                QueuedTaskStatus::Pending | QueuedTaskStatus::Running => {}
+            }
+        }
+    }
+
+    fn finish(mut self) -> RunSummary {
+        self.summary.phase = RunPhase::Finished;
+        self.summary.finished_at = Some(now_ms());
+        self.summary.outcome = Some(if self.stopped {
+            RunOutcome::Stopped
+        } else if self.summary.failed > 0 || self.summary.cancelled > 0 {
+            RunOutcome::Failed
+        } else {
+            RunOutcome::Succeeded
+        });
+        self.summary
+    }
+
+    /// 本轮结束后是否开始下一轮
+    fn should_continue(&self, tasks: &[QueuedTask]) -> bool {
+        let iteration = self.progress.iteration;
+        let current: Vec<&QueuedTask> = tasks
+            .iter()
+            .filter(|task| task.iteration == iteration)
+            .collect();
+        // 本轮没有任务真正提交（如 Tasker 不可用），不再继续，避免空转
+        if current.iter().all(|task| task.attempts.is_empty()) {
+            return false;
+        }
+        match &self.progress.config.mode {
+            RunMode::Once => false,
+            RunMode::Repeat { times } => iteration < *times,
+            RunMode::UntilDeadline { deadline } => {
+                now_ms() + (self.progress.config.iteration_delay_ms as i64) < *deadline
+            }
+            RunMode::UntilTask { task, outcome } => !current.iter().any(|item| {
+                (item.source_id.as_deref() == Some(task.as_str()) || item.entry == *task)
+                    && item.status == *outcome
+            }),
+        }
+    }
+}
+
+/// 任务的一次提交
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskAttempt {
+    pub task_id: i64,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    pub status: QueuedTaskStatus,
+    /// 因超时被停止
+    pub timed_out: bool,
+}
+
+/// 队列中的任务
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedTask {
+    /// 队列项 ID，不同于 MaaFramework 的任务 ID
+    pub id: u64,
+    pub entry: String,
+    /// interface 中的任务名，完成记录以此为键
+    pub task_name: Option<String>,
+    /// 显示名称
+    pub label: Option<String>,
+    /// 前端任务列表中对应的任务 ID
+    pub source_id: Option<String>,
+    pub pipeline_override: String,
+    pub status: QueuedTaskStatus,
+    /// 提交后 MaaFramework 返回的任务 ID
+    pub task_id: Option<i64>,
+    /// 加入队列、开始和结束的时间（Unix 毫秒）
+    pub enqueued_at: i64,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub error: Option<String>,
+    /// 失败后最多重试的次数
+    pub retry_count: u32,
+    /// 重试前的等待时间（毫秒）
+    pub retry_delay_ms: u64,
+    /// 单次执行的最长时间（毫秒），超时后停止并记为失败
+    pub timeout_ms: Option<u64>,
+    /// 等待重试时，最早可再次提交的时间（Unix 毫秒）
+    pub retry_at: Option<i64>,
+    /// 每次提交的记录
+    pub attempts: Vec<TaskAttempt>,
+    /// 所属的循环轮次（从 1 开始）
+    pub iteration: u32,
+    /// 执行条件，全部满足才提交
+    pub conditions: Vec<TaskCondition>,
+    /// 被跳过的原因
+    pub skip_reason: Option<String>,
+}
+
+impl QueuedTask {
+    fn new(config: &TaskConfig, iteration: u32) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            entry: config.entry.clone(),
+            task_name: config.task_name.clone(),
+            label: config.label.clone(),
+            source_id: config.source_id.clone(),
+            pipeline_override: config.pipeline_override.clone(),
+            status: QueuedTaskStatus::Pending,
+            task_id: None,
+            enqueued_at: now_ms(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            retry_count: config.retry_count.unwrap_or(0),
+            retry_delay_ms: config.retry_delay_ms.unwrap_or(0),
+            timeout_ms: config.timeout_ms.filter(|&ms| ms > 0),
+            retry_at: None,
+            attempts: Vec::new(),
+            iteration,
+            conditions: config.conditions.clone(),
+            skip_reason: None,
+        }
+    }
+
+    /// 完成记录的键：任务名，没有时使用入口名
+    fn ledger_key(&self) -> &str {
+        self.task_name.as_deref().unwrap_or(&self.entry)
+    }
+
+    fn finish(&mut self, status: QueuedTaskStatus, error: Option<String>) {
+        self.close_attempt(status);
+        self.status = status;
+        self.finished_at = Some(now_ms());
+        self.retry_at = None;
+        self.error = error;
+    }
+
+    /// 结束当前的提交记录
+    fn close_attempt(&mut self, status: QueuedTaskStatus) {
+        if let Some(attempt) = self
+            .attempts
+            .last_mut()
+            .filter(|attempt| attempt.status == QueuedTaskStatus::Running)
+        {
+            attempt.status = status;
+            attempt.finished_at = Some(now_ms());
+        }
+    }
+
+    fn failed_attempts(&self) -> usize {
+        self.attempts
+            .iter()
+            .filter(|attempt| attempt.status == QueuedTaskStatus::Failed)
+            .count()
+    }
+
+    /// 已开始派发但 MaaTaskerPostTask 尚未返回
+    fn is_posting(&self) -> bool {
+        self.status == QueuedTaskStatus::Running && self.task_id.is_none()
+    }
+}
+
+/// maa-task-queue 事件载荷
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskQueueEvent {
+    pub instance_id: String,
+    pub tasks: Vec<QueuedTask>,
+    /// 循环进度，未循环时为 None
+    pub run: Option<RunProgress>,
+}
+
+/// 单个实例的任务队列
+///
+/// 等待中的任务总是位于队列末尾，前面是已开始或已结束的任务
+#[derive(Debug, Default)]
+pub struct TaskQueue {
+    tasks: Vec<QueuedTask>,
+    /// 暂停派发（自动重连期间）
+    held: bool,
+    /// 用户暂停，不再提交新任务
+    paused: bool,
+    /// 立即暂停时被中断、放回等待中的任务
+    interrupted: Option<u64>,
+    /// 正在进行的运行
+    run: Option<RunState>,
+    /// 尚未发送的 maa-run 事件
+    run_events: Vec<RunSummary>,
+    /// 已请求停止，运行中的任务结束时记为取消
+    stopping: bool,
+    /// 已因超时调用 MaaTaskerPostStop，Tasker 空闲前不提交下一个任务
+    stop_pending: bool,
+    /// MaaTaskerPostTask 返回前就已结束的任务：MaaFramework 任务 ID -> 是否成功
+    early_results: HashMap<i64, bool>,
+    /// 已成功、尚未写入完成记录的任务（完成记录的键）
+    succeeded: Vec<String>,
+    /// 检查执行条件的时区（最近一次启动运行时指定）
+    zone: Zone,
+}
+
+impl TaskQueue {
+    /// 第一个等待中任务的位置
+    fn pending_start(&self) -> usize {
+        self.tasks
+            .iter()
+            .rposition(|task| task.status != QueuedTaskStatus::Pending)
+            .map_or(0, |idx| idx + 1)
+    }
+
+    fn pending_ids(&self) -> Vec<u64> {
+        self.tasks[self.pending_start()..]
+            .iter()
+            .map(|task| task.id)
+            .collect()
+    }
+
+    /// 尚未提交给 Tasker 的任务数（暂停派发时为 0）
+    fn waiting_count(&self) -> usize {
+        if self.held || self.paused {
+            return 0;
+        }
+        self.tasks
+            .iter()
+            .filter(|task| task.status == QueuedTaskStatus::Pending || task.is_posting())
+            .count()
+    }
+
+    /// 本轮任务全部结束时按循环设置开始下一轮；没有剩余任务时自动解除暂停
+    fn settle(&mut self) {
+        if self.tasks.iter().any(|task| {
+            task.status == QueuedTaskStatus::Pending || task.status == QueuedTaskStatus::Running
+        }) {
+            return;
+        }
+        if let Some(mut run) = self.run.take() {
+            run.tally(&self.tasks);
+            if !run.stopped && run.should_continue(&self.tasks) {
+                self.start_iteration(run);
+                return;
+            }
+            info!(
+                "[task_queue] Run finished after {} iteration(s)",
+                run.progress.iteration
+            );
+            self.run_events.push(run.finish());
+        }
+        self.paused = false;
+        self.interrupted = None;
+    }
+
+    /// 开始下一轮：只保留上一轮的记录，追加本轮任务
+    fn start_iteration(&mut self, mut run: RunState) {
+        let finished = run.progress.iteration;
+        let iteration = finished + 1;
+        info!("[task_queue] Starting iteration {}", iteration);
+        self.tasks.retain(|task| task.iteration >= finished);
+        self.tasks.extend(
+            run.template
+                .iter()
+                .map(|config| QueuedTask::new(config, iteration)),
+        );
+        let delay_ms = run.progress.config.iteration_delay_ms;
+        run.progress.iteration = iteration;
+        run.progress.next_iteration_at = (delay_ms > 0).then(|| now_ms() + delay_ms as i64);
+        self.run = Some(run);
+    }
+
+    /// 当前轮次（未循环时为 1）
+    fn iteration(&self) -> u32 {
+        self.run.as_ref().map_or(1, |run| run.progress.iteration)
+    }
+
+    /// 循环进度（只执行一次时为 None）
+    pub fn run_progress(&self) -> Option<RunProgress> {
+        self.run
+            .as_ref()
+            .filter(|run| run.progress.config.mode != RunMode::Once)
+            .map(|run| run.progress.clone())
+    }
+
+    /// 标记运行已停止，剩余任务结束后不再开始下一轮
+    fn stop_run(&mut self) {
+        if let Some(run) = &mut self.run {
+            run.stopped = true;
+        }
+    }
+
+    /// 把运行中的任务放回等待中，返回其 MaaFramework 任务 ID（尚未提交完成时为 None）
+    fn requeue_running(&mut self) -> Option<(u64, Option<i64>)> {
+        let task = self.running_mut()?;
+        task.close_attempt(QueuedTaskStatus::Cancelled);
+        task.status = QueuedTaskStatus::Pending;
+        let task_id = task.task_id.take();
+        Some((task.id, task_id))
+    }
+
+    fn running_mut(&mut self) -> Option<&mut QueuedTask> {
+        self.tasks
+            .iter_mut()
+            .find(|task| task.status == QueuedTaskStatus::Running)
+    }
+
+    fn pending_mut(&mut self, id: u64) -> Result<&mut QueuedTask, String> {
+        let task = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or("Task not found in queue")?;
+        if task.status != QueuedTaskStatus::Pending {
+            return Err("Task has already started".to_string());
+        }
+        Ok(task)
+    }
+
+    /// 加入任务，replace 为 true 时先清除除运行中任务外的所有任务；返回新任务的队列项 ID
+    /// 被替换的运行按已停止结束，保证每次运行都有 Finished 事件
+    pub fn enqueue(&mut self, tasks: &[TaskConfig], replace: bool) -> Vec<u64> {
+        if replace {
+            if let Some(mut run) = self.run.take() {
+                run.tally(&self.tasks);
+                run.stopped = true;
+                info!("[task_queue] Run replaced, finishing it as stopped");
+                self.run_events.push(run.finish());
+            }
+            self.tasks
+                .retain(|task| task.status == QueuedTaskStatus::Running);
+            self.paused = false;
+            self.interrupted = None;
+        }
+        let start = self.tasks.len();
+        self.insert(start, tasks)
+    }
+
+    /// 替换队列并按循环设置执行；返回第一轮任务的队列项 ID
+    pub fn start_run(&mut self, tasks: &[TaskConfig], run_loop: RunLoop, zone: Zone) -> Vec<u64> {
+        let ids = self.enqueue(tasks, true);
+        self.zone = zone;
+        let summary = RunSummary {
+            phase: RunPhase::Started,
+            started_at: now_ms(),
+            finished_at: None,
+            iterations: 1,
+            outcome: None,
+            succeeded: 0,
+            failed: 0,
+            skipped: 0,
+            cancelled: 0,
+        };
+        self.run_events.push(summary.clone());
+        self.run = Some(RunState {
+            progress: RunProgress {
+                config: run_loop,
+                iteration: 1,
+                next_iteration_at: None,
+            },
+            template: tasks.to_vec(),
+            summary,
+            stopped: false,
+        });
+        ids
+    }
+
+    /// 在 index 处插入任务（只能插入到等待中的任务之间）；返回新任务的队列项 ID
+    pub fn insert(&mut self, index: usize, tasks: &[TaskConfig]) -> Vec<u64> {
+        let index = index.clamp(self.pending_start(), self.tasks.len());
+        let iteration = self.iteration();
+        let new_tasks: Vec<QueuedTask> = tasks
+            .iter()
+            .map(|config| QueuedTask::new(config, iteration))
+            .collect();
+        let ids = new_tasks.iter().map(|task| task.id).collect();
+        self.tasks.splice(index..index, new_tasks);
+        ids
+    }
+
+    /// 删除尚未开始的任务
+    pub fn remove(&mut self, id: u64) -> Result<(), String> {
+        self.pending_mut(id)?;
+        self.tasks.retain(|task| task.id != id);
+        Ok(())
+    }
+
+    /// 按 ids 的顺序重排等待中的任务，ids 必须恰好包含所有等待中的任务
+    pub fn reorder(&mut self, ids: &[u64]) -> Result<(), String> {
+        let mut expected = self.pending_ids();
+        let mut given = ids.to_vec();
+        expected.sort_unstable();
+        given.sort_unstable();
+        if expected != given {
+            return Err("Task ids do not match the pending tasks".to_string());
+        }
+
+        let start = self.pending_start();
+        let mut pending: HashMap<u64, QueuedTask> = self
+            .tasks
+            .drain(start..)
+            .map(|task| (task.id, task))
+            .collect();
+        self.tasks
+            .extend(ids.iter().filter_map(|id| pending.remove(id)));
+        Ok(())
+    }
+
+    /// 修改任务的 Pipeline 覆盖；任务正在运行时返回其 MaaFramework 任务 ID，需同时覆盖到 Tasker
+    pub fn update_override(
+        &mut self,
+        id: u64,
+        pipeline_override: &str,
+    ) -> Result<Option<i64>, String> {
+        let task = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id)
+            .ok_or("Task not found in queue")?;
+        match task.status {
+            QueuedTaskStatus::Pending | QueuedTaskStatus::Running => {
+                task.pipeline_override = pipeline_override.to_string();
+                Ok(task.task_id)
+            }
+            _ => Err("Task has already finished".to_string()),
+        }
+    }
+
+    /// 取出下一个等待中的任务并标记为运行中；
+    /// 暂停派发、已有任务在运行、超时停止尚未完成或未到重试、下一轮的时间时返回 None
+    fn start_next(
+        &mut self,
+        tasker_idle: bool,
+        completed_at: impl Fn(&str) -> Option<i64>,
+    ) -> Option<QueuedTask> {
+        if self.held || self.paused || self.running_mut().is_some() {
+            return None;
+        }
+        if self.stop_pending {
+            if !tasker_idle {
+                return None;
+            }
+            self.stop_pending = false;
+        }
+        let now = now_ms();
+        if let Some(run) = &mut self.run {
+            match run.progress.next_iteration_at {
+                Some(at) if at > now => return None,
+                _ => run.progress.next_iteration_at = None,
+            }
+        }
+        let start = loop {
+            let start = self.pending_start();
+            let task = self.tasks.get(start)?;
+            if task.retry_at.is_some_and(|at| at > now) {
+                return None;
+            }
+            // 重试时不再检查条件
+            if !task.attempts.is_empty() {
+                break start;
+            }
+            match self.check_conditions(start, &completed_at) {
+                Ok(()) => break start,
+                Err(reason) => {
+                    info!("[task_queue] Skip task {}: {}", task.entry, reason);
+                    let task = &mut self.tasks[start];
+                    task.finish(QueuedTaskStatus::Skipped, None);
+                    task.skip_reason = Some(reason);
+                }
+            }
+        };
+        let task = &mut self.tasks[start];
+        task.status = QueuedTaskStatus::Running;
+        task.retry_at = None;
+        task.started_at.get_or_insert_with(now_ms);
+        Some(task.clone())
+    }
+
+    /// 检查队列中第 index 个任务的执行条件，不满足时返回原因
+    fn check_conditions(
+        &self,
+        index: usize,
+        completed_at: impl Fn(&str) -> Option<i64>,
+    ) -> Result<(), String> {
+        let task = &self.tasks[index];
+        let ctx = ConditionContext {
+            earlier: &self.tasks[..index],
+            completed_at: completed_at(task.ledger_key()),
+            zone: self.zone,
+            now: self.zone.local_time(chrono::Utc::now()),
+        };
+        task.conditions
+            .iter()
+            .try_for_each(|condition| condition.check(&ctx))
+    }
+
+    /// 是否需要派发线程定期检查（超时、重试间隔或等待超时停止完成）
+    fn needs_tick(&self) -> bool {
+        if self.held || self.paused {
+            return false;
+        }
+        self.stop_pending
+            || self
+                .run
+                .as_ref()
+                .is_some_and(|run| run.progress.next_iteration_at.is_some())
+            || self.tasks.iter().any(|task| {
+                (task.status == QueuedTaskStatus::Running && task.timeout_ms.is_some())
+                    || (task.status == QueuedTaskStatus::Pending && task.retry_at.is_some())
+            })
+    }
+
+    /// 检查运行中的任务是否超时；超时则标记并返回其 MaaFramework 任务 ID，需调用 MaaTaskerPostStop
+    fn check_timeout(&mut self) -> Option<i64> {
+        let now = now_ms();
+        let task = self.running_mut()?;
+        let timeout_ms = task.timeout_ms?;
+        let task_id = task.task_id?;
+        let attempt = task.attempts.last_mut()?;
+        if attempt.timed_out || now - attempt.started_at < timeout_ms as i64 {
+            return None;
+        }
+        attempt.timed_out = true;
+        self.stop_pending = true;
+        Some(task_id)
+    }
+
+    /// 记录提交得到的 MaaFramework 任务 ID；
+    /// 返回提交期间是否请求了停止，或任务已被暂停、重连放回等待中，需要停止 Tasker
+    fn assign(&mut self, id: u64, task_id: i64) -> bool {
+        let early_result = self.early_results.remove(&task_id);
+        self.early_results.clear();
+        let Some(task) = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id && task.status == QueuedTaskStatus::Running)
+        else {
+            return true;
+        };
+        task.task_id = Some(task_id);
+        task.attempts.push(TaskAttempt {
+            task_id,
+            started_at: now_ms(),
+            finished_at: None,
+            status: QueuedTaskStatus::Running,
+            timed_out: false,
+        });
+        let stopping = self.stopping;
+        if let Some(succeeded) = early_result {
+            self.task_finished(task_id, succeeded);
+        }
+        stopping
+    }
+
+    /// 提交失败（任务已被放回等待中时保持不变）
+    fn post_failed(&mut self, id: u64, reason: &str) {
+        if let Some(task) = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.id == id && task.status == QueuedTaskStatus::Running)
+        {
+            task.finish(QueuedTaskStatus::Failed, Some(reason.to_string()));
+        }
+        self.stopping = false;
+    }
+
+    /// 根据回调记录任务结束
+    fn task_finished(&mut self, task_id: i64, succeeded: bool) {
+        let stopping = self.stopping;
+        let running = self
+            .tasks
+            .iter_mut()
+            .find(|task| task.status == QueuedTaskStatus::Running && task.task_id == Some(task_id));
+        match running {
+            Some(task) => {
+                let status = match (stopping, succeeded) {
+                    (_, true) => QueuedTaskStatus::Succeeded,
+                    (true, false) => QueuedTaskStatus::Cancelled,
+                    (false, false) => QueuedTaskStatus::Failed,
+                };
+                let timed_out = task
+                    .attempts
+                    .last()
+                    .is_some_and(|attempt| attempt.timed_out);
+                let error = (status == QueuedTaskStatus::Failed && timed_out)
+                    .then(|| format!("Timed out after {} ms", task.timeout_ms.unwrap_or(0)));
+                task.close_attempt(status);
+                if status == QueuedTaskStatus::Failed
+                    && task.failed_attempts() <= task.retry_count as usize
+                {
+                    // 放回等待中的首位，到时间后重新提交
+                    info!(
+                        "[task_queue] Task {} failed, retry {}/{} in {} ms",
+                        task.entry,
+                        task.failed_attempts(),
+                        task.retry_count,
+                        task.retry_delay_ms
+                    );
+                    task.status = QueuedTaskStatus::Pending;
+                    task.task_id = None;
+                    task.error = error;
+                    task.retry_at = Some(now_ms() + task.retry_delay_ms as i64);
+                } else {
+                    task.finish(status, error);
+                }
+                if status == QueuedTaskStatus::Succeeded {
+                    self.succeeded.push(task.ledger_key().to_string());
+                }
+                self.stopping = false;
+            }
+            None => {
+                // 回调可能先于 MaaTaskerPostTask 返回到达
+                if self.tasks.iter().any(QueuedTask::is_posting) {
+                    self.early_results.insert(task_id, succeeded);
+                }
+            }
+        }
+    }
+
+    /// 用户停止：取消所有等待中的任务，运行中的任务结束时记为取消
+    pub fn stop(&mut self) {
+        for task in &mut self.tasks {
+            if task.status == QueuedTaskStatus::Pending {
+                task.finish(QueuedTaskStatus::Cancelled, None);
+            }
+        }
+        self.paused = false;
+        self.interrupted = None;
+        self.stop_run();
+        self.stopping = self.running_mut().is_some();
+    }
+
+    /// 用户暂停：immediate 为 false 时等当前任务结束，为 true 时把当前任务放回等待中；
+    /// 返回需要停止的 MaaFramework 任务 ID
+    pub fn pause(&mut self, immediate: bool) -> Result<Option<i64>, String> {
+        if self.paused {
+            return Err("Tasks are already paused".to_string());
+        }
+        if self.stopping {
+            return Err("Tasks are stopping".to_string());
+        }
+        if self.pending_start() == self.tasks.len() && self.running_mut().is_none() {
+            return Err("No tasks to pause".to_string());
+        }
+        self.paused = true;
+        if !immediate {
+            return Ok(None);
+        }
+        let Some((id, task_id)) = self.requeue_running() else {
+            return Ok(None);
+        };
+        self.interrupted = Some(id);
+        // 恢复时等待停止完成后再提交
+        self.stop_pending = true;
+        Ok(task_id)
+    }
+
+    /// 恢复派发；rerun_interrupted 为 false 时被中断的任务记为取消；返回将执行的任务
+    pub fn resume(&mut self, rerun_interrupted: bool) -> Result<Vec<u64>, String> {
+        if !self.paused {
+            return Err("Tasks are not paused".to_string());
+        }
+        self.paused = false;
+        if let Some(id) = self.interrupted.take().filter(|_| !rerun_interrupted) {
+            if let Some(pos) = self
+                .tasks
+                .iter()
+                .position(|task| task.id == id && task.status == QueuedTaskStatus::Pending)
+            {
+                // 移到等待中的任务之前，保持等待中的任务位于末尾
+                let mut task = self.tasks.remove(pos);
+                task.finish(QueuedTaskStatus::Cancelled, None);
+                let start = self.pending_start();
+                self.tasks.insert(start, task);
+            }
+        }
+        Ok(self.pending_ids())
+    }
+
+    /// 实例对象已被销毁，不会再收到回调：结束所有未完成的任务
+    pub fn abort(&mut self, reason: &str) {
+        for task in &mut self.tasks {
+            match task.status {
+                QueuedTaskStatus::Pending => task.finish(QueuedTaskStatus::Cancelled, None),
+                QueuedTaskStatus::Running => {
+                    task.finish(QueuedTaskStatus::Failed, Some(reason.to_string()))
+                }
+                _ => {}
+            }
+        }
+        self.held = false;
+        self.paused = false;
+        self.interrupted = None;
+        self.stop_run();
+        self.stopping = false;
+        self.stop_pending = false;
+        self.early_results.clear();
+    }
+
+    /// 暂停派发，并把运行中的任务放回等待中（随后会被停止，恢复后重新执行）；
+    /// 返回恢复后将执行的任务
+    pub fn hold(&mut self) -> Vec<u64> {
+        self.held = true;
+        self.requeue_running();
+        self.pending_ids()
+    }
+
+    /// 恢复派发，返回将执行的任务
+    pub fn release(&mut self) -> Vec<u64> {
+        self.held = false;
+        self.pending_ids()
+    }
+}
+
+/// 所有实例的任务队列
+#[derive(Default)]
+pub struct TaskQueueRegistry {
+    queues: Mutex<HashMap<String, TaskQueue>>,
+    /// 每日完成记录（独立加锁，可在持有队列锁时访问）
+    pub ledger: TaskLedger,
+    /// 通知派发线程检查实例的队列
+    dispatcher: Mutex<Option<Sender<String>>>,
+    /// 运行结束时通知（运行结束后的动作）
+    run_listener: Mutex<Option<Sender<RunEvent>>>,
+}
+
+impl TaskQueueRegistry {
+    /// 注册新实例（已存在时保持原队列）
+    pub fn insert(&self, instance_id: &str) {
+        if let Ok(mut queues) = self.queues.lock() {
+            queues.entry(instance_id.to_string()).or_default();
+        }
+    }
+
+    pub fn remove(&self, instance_id: &str) {
+        if let Ok(mut queues) = self.queues.lock() {
+            queues.remove(instance_id);
+        }
+    }
+
+    /// 修改实例的队列：任务有变化时发送 maa-task-queue 事件，
+    /// 同步生命周期中等待提交的任务数，并通知派发线程
+    pub fn update<F, R>(&self, lifecycles: &LifecycleRegistry, instance_id: &str, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut TaskQueue) -> R,
+    {
+        let Ok(mut queues) = self.queues.lock() else {
+            warn!("[task_queue] Failed to lock task queues");
+            return None;
+        };
+        let queue = queues.get_mut(instance_id)?;
+
+        let before = queue.tasks.clone();
+        let flags = (queue.held, queue.paused);
+        let result = f(queue);
+        for task_name in queue.succeeded.drain(..) {
+            self.ledger.record_success(instance_id, &task_name);
+        }
+        queue.settle();
+        let tasks_changed = queue.tasks != before;
+
+        if tasks_changed {
+            // 持锁发送，保证事件顺序与队列变化顺序一致
+            emit_queue(instance_id, queue.tasks.clone(), queue.run_progress());
+        }
+        for summary in queue.run_events.drain(..) {
+            let event = RunEvent {
+                instance_id: instance_id.to_string(),
+                summary,
+            };
+            emit_event("maa-run", event.clone());
+            if event.summary.phase == RunPhase::Finished {
+                if let Ok(Some(listener)) = self.run_listener.lock().as_deref() {
+                    let _ = listener.send(event);
+                }
+            }
+        }
+        if tasks_changed || (queue.held, queue.paused) != flags {
+            let waiting = queue.waiting_count();
+            let paused = queue.paused;
+            lifecycles.update(instance_id, |lc| {
+                lc.set_queued(waiting);
+                lc.set_paused(paused);
+            });
+            if let Ok(Some(sender)) = self.dispatcher.lock().as_deref() {
+                let _ = sender.send(instance_id.to_string());
+            }
+        }
+        Some(result)
+    }
+
+    /// 设置运行结束的接收端
+    pub fn set_run_listener(&self, sender: Sender<RunEvent>) {
+        if let Ok(mut listener) = self.run_listener.lock() {
+            *listener = Some(sender);
+        }
+    }
+
+    pub fn snapshot(&self, instance_id: &str) -> Option<Vec<QueuedTask>> {
+        let queues = self.queues.lock().ok()?;
+        queues.get(instance_id).map(|queue| queue.tasks.clone())
+    }
+
+    /// 需要定期检查的实例
+    fn ticking_instances(&self) -> Vec<String> {
+        match self.queues.lock() {
+            Ok(queues) => queues
+                .iter()
+                .filter(|(_, queue)| queue.needs_tick())
+                .map(|(id, _)| id.clone())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// 各实例的循环进度（只包含正在循环的实例）
+    pub fn run_progresses(&self) -> HashMap<String, RunProgress> {
+        match self.queues.lock() {
+            Ok(queues) => queues
+                .iter()
+                .filter_map(|(id, queue)| queue.run_progress().map(|run| (id.clone(), run)))
+                .collect(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    pub fn snapshots(&self) -> HashMap<String, Vec<QueuedTask>> {
+        match self.queues.lock() {
+            Ok(queues) => queues
+                .iter()
+                .map(|(id, queue)| (id.clone(), queue.tasks.clone()))
+                .collect(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    /// 回调观察者入口
+    fn handle_event(&self, lifecycles: &LifecycleRegistry, event: &MaaCallbackEvent) {
+        if let MaaEvent::TaskerTask { phase, detail } = &event.event {
+            if *phase == EventPhase::Starting || detail.is_stop() {
+                return;
+            }
+            let succeeded = *phase == EventPhase::Succeeded;
+            self.update(lifecycles, &event.instance_id, |queue| {
+                queue.task_finished(detail.task_id, succeeded)
+            });
+        }
+    }
+}
+
+/// 注册回调观察者并启动派发线程（需在生命周期观察者之后注册）
+pub fn start_dispatcher(state: Arc<MaaState>) {
+    let (sender, receiver) = mpsc::channel();
+    if let Ok(mut dispatcher) = state.queues.dispatcher.lock() {
+        *dispatcher = Some(sender);
+    }
+
+    let observer = state.clone();
+    add_event_observer(move |event| observer.queues.handle_event(&observer.lifecycles, event));
+
+    thread::spawn(move || run(state, receiver));
+}
+
+fn run(state: Arc<MaaState>, receiver: Receiver<String>) {
+    let mut last_tick = Instant::now();
+    loop {
+        match receiver.recv_timeout(TICK_INTERVAL.saturating_sub(last_tick.elapsed())) {
+            Ok(instance_id) => dispatch(&state, &instance_id),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if last_tick.elapsed() >= TICK_INTERVAL {
+            last_tick = Instant::now();
+            for instance_id in state.queues.ticking_instances() {
+                stop_if_timed_out(&state, &instance_id);
+                dispatch(&state, &instance_id);
+            }
+        }
+    }
+}
+
+fn instance_tasker(state: &MaaState, instance_id: &str) -> Option<Arc<Tasker>> {
+    state
+        .instance(instance_id)
+        .ok()
+        .and_then(|instance| instance.lock().ok().and_then(|i| i.tasker.clone()))
+}
+
+/// 运行中的任务超时则停止 Tasker，任务随后以失败结束并按设置重试
+fn stop_if_timed_out(state: &MaaState, instance_id: &str) {
+    let Some(task_id) = state
+        .queues
+        .update(&state.lifecycles, instance_id, TaskQueue::check_timeout)
+        .flatten()
+    else {
+        return;
+    };
+    warn!(
+        "[task_queue] {}: task {} timed out, stopping",
+        instance_id, task_id
+    );
+    if let Some(tasker) = instance_tasker(state, instance_id) {
+        tasker.post_stop();
+    }
+}
+
+/// 提交实例队列中的下一个任务
+fn dispatch(state: &MaaState, instance_id: &str) {
+    // 回调中记录的任务成功在这里写入文件
+    state.queues.ledger.flush();
+
+    let tasker = instance_tasker(state, instance_id);
+    let tasker_idle = !tasker.as_ref().is_some_and(|tasker| tasker.running());
+    let ledger = &state.queues.ledger;
+    let Some(task) = state
+        .queues
+        .update(&state.lifecycles, instance_id, |queue| {
+            queue.start_next(tasker_idle, |task_name| {
+                ledger.completed_at(instance_id, task_name)
+            })
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    let Some(tasker) = tasker else {
+        warn!("[task_queue] {}: tasker not created", instance_id);
+        state
+            .queues
+            .update(&state.lifecycles, instance_id, |queue| {
+                queue.post_failed(task.id, "Tasker not created")
+            });
+        return;
+    };
+
+    // 先记录待执行任务数量，避免回调先于记录到达
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.tasks_posting(1));
+    info!(
+        "[task_queue] Calling MaaTaskerPostTask: entry={}, override={}",
+        task.entry, task.pipeline_override
+    );
+    let task_id = tasker.post_task(&task.entry, &task.pipeline_override);
+    info!(
+        "[task_queue] MaaTaskerPostTask returned task_id: {}",
+        task_id
+    );
+
+    if task_id == MAA_INVALID_ID {
+        warn!("[task_queue] Failed to post task: {}", task.entry);
+        state
+            .lifecycles
+            .update(instance_id, |lc| lc.tasks_posted(1, &[], false));
+        state
+            .queues
+            .update(&state.lifecycles, instance_id, |queue| {
+                queue.post_failed(task.id, "Failed to post task")
+            });
+        return;
+    }
+
+    state
+        .lifecycles
+        .update(instance_id, |lc| lc.tasks_posted(1, &[task_id], false));
+    let stopping = state
+        .queues
+        .update(&state.lifecycles, instance_id, |queue| {
+            queue.assign(task.id, task_id)
+        })
+        .unwrap_or(false);
+    if stopping {
+        // 提交期间用户请求了停止，MaaTaskerPostStop 可能早于本任务提交
+        debug!("[task_queue] {}: stop requested while posting", instance_id);
+        tasker.post_stop();
+    }
+}
+
+fn emit_queue(instance_id: &str, tasks: Vec<QueuedTask>, run: Option<RunProgress>) {
+    emit_event(
+        "maa-task-queue",
+        TaskQueueEvent {
+            instance_id: instance_id.to_string(),
+            tasks,
+            run,
+        },
+    );
+}
@@ -0,0 +1,71 @@
+//! 定时执行和每日重置共用的时区
+//!
+//! 以 IANA 名称（如 `Europe/Berlin`）配置，未设置时为本机时区。两者都带夏令时规则：
+//! 当地时间落在夏令时跳过的时段时没有对应的时刻，落在重复的时段时取较早的一个。
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// 时区，序列化为 IANA 名称，本机时区为 null
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Zone {
+    #[default]
+    Local,
+    Named(Tz),
+}
+
+impl Zone {
+    /// 按 IANA 名称解析，空字符串为本机时区
+    pub fn parse(name: &str) -> Result<Self, String> {
+        if name.is_empty() {
+            return Ok(Zone::Local);
+        }
+        name.parse::<Tz>()
+            .map(Zone::Named)
+            .map_err(|_| format!("Unknown time zone: {}", name))
+    }
+
+    /// IANA 名称，本机时区为 None
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Zone::Local => None,
+            Zone::Named(tz) => Some(tz.name()),
+        }
+    }
+
+    /// 某一时刻的当地时间
+    pub fn local_time(&self, at: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Local => at.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => at.with_timezone(tz).naive_local(),
+        }
+    }
+
+    /// 当地时间对应的时刻，夏令时跳过的时段返回 None，重复的时段取较早的时刻
+    pub fn instant(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Zone::Local => Local
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+        }
+    }
+}
+
+impl Serialize for Zone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.name().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Zone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = Option::<String>::deserialize(deserializer)?;
+        Zone::parse(name.as_deref().unwrap_or_default()).map_err(serde::de::Error::custom)
+    }
+}
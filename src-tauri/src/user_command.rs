@@ -0,0 +1,27 @@
+//! 用户配置的外部命令
+//!
+//! 启动前的准备步骤和运行结束后的动作都会运行用户填写的程序，两者使用同样的方式构建进程。
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// 构建用户配置的命令（不继承标准输入输出）
+pub(crate) fn user_command(program: &str, args: &[String], cwd: Option<&str>) -> Command {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if let Some(cwd) = cwd.filter(|cwd| !cwd.is_empty()) {
+        command.current_dir(Path::new(cwd));
+    }
+    // Windows 平台使用 CREATE_NO_WINDOW 标志避免弹出控制台窗口
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
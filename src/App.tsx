@@ -40,9 +40,16 @@ import { Loader2, AlertCircle, RefreshCw } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
 import { loggers } from '@/utils/logger';
+import { buildInstanceSchedule, taskDisplayLabel } from '@/utils/schedule';
 import { useMaaCallbackLogger, useMaaAgentLogger } from '@/utils/useMaaCallbackLogger';
 import { getInterfaceLangKey } from '@/i18n';
 import { applyTheme, resolveThemeMode } from '@/themes';
+import {
+  defaultDayReset,
+  defaultPostRunConfig,
+  defaultReconnectPolicy,
+  type ConnectionStatus,
+} from '@/types/maa';
 
 const log = loggers.app;
 
@@ -184,6 +191,10 @@ function App() {
     rightPanelCollapsed,
     setRightPanelWidth: _setRightPanelWidth,
     setRightPanelCollapsed: _setRightPanelCollapsed,
+    instances,
+    selectedController,
+    selectedResource,
+    tcpCompatMode,
   } = useAppStore();
 
   // 带退出动画的设置页面关闭
@@ -447,6 +458,13 @@ function App() {
         log.warn('版本检查失败:', err);
       }
 
+      // 获取运行时能力，用于禁用旧版 MaaFramework 不支持的功能
+      try {
+        useAppStore.getState().setMaaCapabilities(await maaService.getCapabilities());
+      } catch (err) {
+        log.warn('获取 MaaFramework 能力失败:', err);
+      }
+
       log.info('加载完成, 项目:', result.interface.name);
       setLoadingState('success');
 
@@ -637,6 +655,379 @@ function App() {
     }
   }, [downloadStatus, setShowInstallConfirmModal]);
 
+  // MaaFramework 重新加载后刷新版本和能力（实例状态由 maa-instance-state 同步）
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onLibraryReloaded(async (version) => {
+        const store = useAppStore.getState();
+        store.setMaaInitialized(true, version);
+        try {
+          store.setMaaCapabilities(await maaService.getCapabilities());
+        } catch (err) {
+          log.warn('获取 MaaFramework 能力失败:', err);
+        }
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 监听后端推送的实例状态变化，同步连接与资源状态
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onInstanceState((instanceId, state) => {
+        const store = useAppStore.getState();
+        const { lifecycle } = state;
+        let connectionStatus: ConnectionStatus = state.connected ? 'Connected' : 'Disconnected';
+        if (lifecycle.state === 'Connecting') {
+          connectionStatus = 'Connecting';
+        } else if (lifecycle.state === 'Failed' && lifecycle.stage === 'Connection') {
+          connectionStatus = { Failed: lifecycle.reason };
+        }
+        store.setInstanceConnectionStatus(instanceId, connectionStatus);
+        store.setInstanceResourceLoaded(instanceId, state.resource_loaded);
+        store.setInstancePaused(instanceId, lifecycle.state === 'Paused');
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 同步后端任务队列；运行中的实例队列里没有等待中和运行中的任务时结束本次运行
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onTaskQueue((instanceId, tasks, run) => {
+        const store = useAppStore.getState();
+        store.setInstanceTaskQueue(instanceId, tasks, run);
+
+        const instance = store.instances.find((i) => i.id === instanceId);
+        if (!instance?.isRunning || tasks.length === 0) return;
+        if (tasks.some((task) => task.status === 'Pending' || task.status === 'Running')) return;
+
+        const succeeded = tasks.every(
+          (task) => task.status === 'Succeeded' || task.status === 'Skipped',
+        );
+        log.info(`实例 ${instance.name}: 任务队列执行完毕, 全部成功: ${succeeded}`);
+        if (store.projectInterface?.agent) {
+          maaService.stopAgent(instanceId).catch(() => {});
+        }
+        store.setInstanceTaskStatus(instanceId, succeeded ? 'Succeeded' : 'Failed');
+        store.updateInstance(instanceId, { isRunning: false });
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 后端启动的运行（如其他实例运行结束后的动作触发）同步为运行中
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onRun((event) => {
+        const store = useAppStore.getState();
+        if (event.phase === 'finished') {
+          store.clearScheduleExecution(event.instance_id);
+          return;
+        }
+        const instance = store.instances.find((i) => i.id === event.instance_id);
+        if (!instance || instance.isRunning) return;
+        log.info(`实例 ${instance.name}: 后端开始运行`);
+        store.updateInstance(instance.id, { isRunning: true });
+        store.setInstanceTaskStatus(instance.id, 'Running');
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 同步定时执行状态：触发时显示正在执行的策略，启动失败时清除
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onSchedule((event) => {
+        const store = useAppStore.getState();
+        if (event.phase === 'fired') {
+          const instance = store.instances.find((i) => i.id === event.instance_id);
+          if (instance) {
+            // 预注册 entry -> taskName 映射，确保回调时能找到任务名
+            const translations = store.interfaceTranslations[getInterfaceLangKey(store.language)];
+            for (const selectedTask of instance.selectedTasks) {
+              if (!selectedTask.enabled) continue;
+              const taskDef = store.projectInterface?.task.find(
+                (t) => t.name === selectedTask.taskName,
+              );
+              if (!taskDef) continue;
+              store.registerEntryTaskName(
+                taskDef.entry,
+                taskDisplayLabel(selectedTask, taskDef, translations),
+              );
+            }
+          }
+          store.setScheduleExecution(event.instance_id, {
+            policyName: event.policy_name,
+            startTime: Date.now(),
+          });
+        } else if (event.phase === 'failed') {
+          store.clearScheduleExecution(event.instance_id);
+        }
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 同步启动前准备步骤的进度（结束、失败或中止后清除）
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onPreRun((event) => {
+        const finished = ['completed', 'failed', 'cancelled'].includes(event.phase);
+        useAppStore.getState().setPreRunProgress(event.instance_id, finished ? null : event);
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 同步运行结束后动作的倒计时
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+
+    maaService
+      .onPostRun((event) => {
+        useAppStore
+          .getState()
+          .setPostRunCountdown(event.instance_id, event.phase === 'countdown' ? event : null);
+      })
+      .then((fn) => {
+        if (disposed) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 同步各实例的自动重连策略到后端（后端按实例 ID 保存，不要求实例已创建）
+  const syncedReconnectPoliciesRef = useRef<Record<string, string>>({});
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    for (const instance of instances) {
+      const policy = instance.reconnectPolicy ?? defaultReconnectPolicy;
+      const key = JSON.stringify(policy);
+      if (syncedReconnectPoliciesRef.current[instance.id] === key) continue;
+      syncedReconnectPoliciesRef.current[instance.id] = key;
+      maaService.setReconnectPolicy(instance.id, policy).catch((err) => {
+        delete syncedReconnectPoliciesRef.current[instance.id];
+        log.warn('同步自动重连策略失败:', err);
+      });
+    }
+  }, [instances]);
+
+  // 同步各实例的每日完成记录重置设置到后端（后端随完成记录一起保存）
+  const syncedDayResetsRef = useRef<Record<string, string>>({});
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    for (const instance of instances) {
+      const reset = instance.dayReset ?? defaultDayReset;
+      const key = JSON.stringify(reset);
+      if (syncedDayResetsRef.current[instance.id] === key) continue;
+      syncedDayResetsRef.current[instance.id] = key;
+      maaService.setDayReset(instance.id, reset).catch((err) => {
+        delete syncedDayResetsRef.current[instance.id];
+        log.warn('同步每日重置设置失败:', err);
+      });
+    }
+  }, [instances]);
+
+  // 同步各实例启动前的准备步骤到后端
+  const syncedPreRunsRef = useRef<Record<string, string>>({});
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    for (const instance of instances) {
+      const steps = instance.preRun ?? [];
+      const key = JSON.stringify(steps);
+      if (syncedPreRunsRef.current[instance.id] === key) continue;
+      syncedPreRunsRef.current[instance.id] = key;
+      maaService.setPreRunSteps(instance.id, steps).catch((err) => {
+        delete syncedPreRunsRef.current[instance.id];
+        log.warn('同步启动前准备步骤失败:', err);
+      });
+    }
+  }, [instances]);
+
+  // 同步各实例的定时执行到后端（后端按策略准备实例并启动任务，窗口关闭时也会执行）
+  const syncedSchedulesRef = useRef<Record<string, string>>({});
+  useEffect(() => {
+    if (!isTauri() || !projectInterface) return;
+
+    let disposed = false;
+    const translations = interfaceTranslations[getInterfaceLangKey(language)];
+    for (const instance of instances) {
+      const controllerName =
+        selectedController[instance.id] || projectInterface.controller[0]?.name;
+      const resourceName = selectedResource[instance.id] || projectInterface.resource[0]?.name;
+      buildInstanceSchedule(instance, {
+        projectInterface,
+        controller: projectInterface.controller.find((c) => c.name === controllerName),
+        resource: projectInterface.resource.find((r) => r.name === resourceName),
+        basePath,
+        tcpCompatMode,
+        translations,
+      })
+        .then((schedule) => {
+          // 生成期间设置又发生变化时，以新一轮的结果为准
+          if (disposed) return;
+          const key = JSON.stringify(schedule);
+          if (syncedSchedulesRef.current[instance.id] === key) return;
+          syncedSchedulesRef.current[instance.id] = key;
+          return maaService.setSchedule(instance.id, schedule).catch((err) => {
+            delete syncedSchedulesRef.current[instance.id];
+            throw err;
+          });
+        })
+        .catch((err) => {
+          log.warn('同步定时执行设置失败:', err);
+        });
+    }
+
+    return () => {
+      disposed = true;
+    };
+  }, [
+    instances,
+    projectInterface,
+    selectedController,
+    selectedResource,
+    basePath,
+    tcpCompatMode,
+    interfaceTranslations,
+    language,
+  ]);
+
+  // 同步各实例运行结束后的动作到后端
+  const syncedPostRunsRef = useRef<Record<string, string>>({});
+  useEffect(() => {
+    if (!isTauri()) return;
+
+    for (const instance of instances) {
+      const config = instance.postRun ?? defaultPostRunConfig;
+      const key = JSON.stringify(config);
+      if (syncedPostRunsRef.current[instance.id] === key) continue;
+      syncedPostRunsRef.current[instance.id] = key;
+      maaService.setPostRunActions(instance.id, config).catch((err) => {
+        delete syncedPostRunsRef.current[instance.id];
+        log.warn('同步运行结束后动作失败:', err);
+      });
+    }
+  }, [instances]);
+
   // 监听窗口大小变化
   useEffect(() => {
     if (!isTauri()) return;
@@ -4,7 +4,7 @@ import { Search, Plus, Sparkles, Loader2, AlertCircle } from 'lucide-react';
 import { useAppStore } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
 import { useResolvedContent } from '@/services/contentResolver';
-import { loggers, generateTaskPipelineOverride } from '@/utils';
+import { loggers } from '@/utils';
 import { getInterfaceLangKey } from '@/i18n';
 import { Tooltip } from './ui/Tooltip';
 import type { TaskItem } from '@/types/interface';
@@ -126,10 +126,6 @@ export function AddTaskPanel() {
     resolveI18nText,
     language,
     basePath,
-    // 任务运行状态管理
-    setTaskRunStatus,
-    registerMaaTaskMapping,
-    appendPendingTaskId,
     // 新增任务标记
     newTaskNames,
     removeNewTaskName,
@@ -209,7 +205,7 @@ export function AddTaskPanel() {
     // 先添加任务到列表
     addTaskToInstance(instance.id, task);
 
-    // 如果实例正在运行，立即调用 PostTask 追加到执行队列
+    // 如果实例正在运行，立即追加到后端任务队列
     if (instance.isRunning) {
       try {
         // 使用 getState() 获取最新状态（zustand 状态更新是同步的）
@@ -225,23 +221,24 @@ export function AddTaskPanel() {
         }
 
         // 构建 pipeline override
-        const pipelineOverride = generateTaskPipelineOverride(addedTask, projectInterface);
+        const pipelineOverride = await maaService.buildPipelineOverride(
+          addedTask.taskName,
+          addedTask.optionValues,
+        );
 
         log.info('运行中追加任务:', task.entry, ', pipelineOverride:', pipelineOverride);
 
-        // 调用 PostTask
-        const maaTaskId = await maaService.runTask(instance.id, task.entry, pipelineOverride);
-
-        log.info('任务已追加, maaTaskId:', maaTaskId);
-
-        // 注册映射关系
-        registerMaaTaskMapping(instance.id, maaTaskId, addedTask.id);
-
-        // 设置任务状态为 pending
-        setTaskRunStatus(instance.id, addedTask.id, 'pending');
-
-        // 追加到任务队列
-        appendPendingTaskId(instance.id, maaTaskId);
+        // 追加到队列末尾，任务状态由 maa-task-queue 事件同步
+        const taskDisplayName = resolveI18nText(task.label, langKey) || task.name;
+        const itemId = await maaService.runTask(
+          instance.id,
+          task.entry,
+          pipelineOverride,
+          taskDisplayName,
+          addedTask.id,
+        );
+
+        log.info('任务已追加, itemId:', itemId);
       } catch (err) {
         log.error('追加任务失败:', err);
       }
@@ -20,9 +20,13 @@ import clsx from 'clsx';
 import { maaService } from '@/services/maaService';
 import { useAppStore } from '@/stores/appStore';
 import { resolveI18nText } from '@/services/contentResolver';
-import type { AdbDevice, Win32Window, ControllerConfig } from '@/types/maa';
+import type { AdbDevice, Win32Window, ControllerConfig, ReconnectPolicy } from '@/types/maa';
 import type { ControllerItem, ResourceItem } from '@/types/interface';
-import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
+import {
+  parseWin32ScreencapMethod,
+  parseWin32InputMethod,
+  defaultReconnectPolicy,
+} from '@/types/maa';
 import { getInterfaceLangKey } from '@/i18n';
 
 // 检测当前操作系统
@@ -158,6 +162,7 @@ export function ConnectionPanel() {
     setInstanceConnectionStatus,
     setInstanceResourceLoaded,
     setInstanceSavedDevice,
+    updateInstance,
     connectionPanelExpanded,
     setConnectionPanelExpanded,
     registerCtrlIdName,
@@ -166,6 +171,13 @@ export function ConnectionPanel() {
 
   // 获取当前活动实例
   const activeInstance = instances.find((i) => i.id === activeInstanceId);
+  const reconnectPolicy = activeInstance?.reconnectPolicy ?? defaultReconnectPolicy;
+
+  // 修改当前实例的自动重连策略（由 App 同步到后端）
+  const updateReconnectPolicy = (patch: Partial<ReconnectPolicy>) => {
+    if (!activeInstanceId) return;
+    updateInstance(activeInstanceId, { reconnectPolicy: { ...reconnectPolicy, ...patch } });
+  };
 
   // 获取当前实例的连接和资源状态（从 store）
   const storedConnectionStatus = activeInstanceId
@@ -1529,6 +1541,64 @@ export function ConnectionPanel() {
                 <span>{resourceError}</span>
               </div>
             )}
+
+            {/* 分隔线 */}
+            <div className="border-t border-border" />
+
+            {/* 断线自动重连 */}
+            <div className="space-y-2">
+              <div className="flex items-center justify-between gap-2">
+                <div className="min-w-0">
+                  <div className="text-xs text-text-secondary">{t('connection.autoReconnect')}</div>
+                  <div className="text-xs text-text-muted truncate">
+                    {t('connection.autoReconnectHint')}
+                  </div>
+                </div>
+                <button
+                  onClick={() => updateReconnectPolicy({ enabled: !reconnectPolicy.enabled })}
+                  className={clsx(
+                    'relative w-11 h-6 rounded-full transition-colors flex-shrink-0',
+                    reconnectPolicy.enabled ? 'bg-accent' : 'bg-bg-active',
+                  )}
+                >
+                  <span
+                    className={clsx(
+                      'absolute top-1 left-1 w-4 h-4 rounded-full bg-white shadow-sm transition-transform duration-200',
+                      reconnectPolicy.enabled ? 'translate-x-5' : 'translate-x-0',
+                    )}
+                  />
+                </button>
+              </div>
+              {reconnectPolicy.enabled && (
+                <div className="flex items-center justify-between gap-2 text-xs text-text-secondary">
+                  <label className="flex items-center gap-1.5 cursor-pointer min-w-0">
+                    <input
+                      type="checkbox"
+                      checked={reconnectPolicy.resume_tasks}
+                      onChange={(e) => updateReconnectPolicy({ resume_tasks: e.target.checked })}
+                      className="w-3.5 h-3.5 rounded border-border-strong accent-accent"
+                    />
+                    <span className="truncate">{t('connection.resumeTasks')}</span>
+                  </label>
+                  <label className="flex items-center gap-1.5 flex-shrink-0">
+                    <span>{t('connection.maxAttempts')}</span>
+                    <input
+                      type="number"
+                      min={1}
+                      max={20}
+                      value={reconnectPolicy.max_attempts}
+                      onChange={(e) => {
+                        const value = parseInt(e.target.value, 10);
+                        if (!Number.isNaN(value)) {
+                          updateReconnectPolicy({ max_attempts: Math.min(20, Math.max(1, value)) });
+                        }
+                      }}
+                      className="w-12 px-1.5 py-0.5 rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60"
+                    />
+                  </label>
+                </div>
+              )}
+            </div>
           </div>
         </div>
       </div>
@@ -0,0 +1,203 @@
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { CalendarCheck, RotateCcw, X } from 'lucide-react';
+import { useAppStore } from '@/stores/appStore';
+import { maaService } from '@/services/maaService';
+import { getInterfaceLangKey } from '@/i18n';
+import { defaultDayReset, type DayReset, type TaskLedger } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+import { listTimeZones } from '@/utils/timeZone';
+import clsx from 'clsx';
+
+const log = loggers.task;
+
+interface DailyLedgerPanelProps {
+  instanceId: string;
+  onClose: () => void;
+}
+
+const inputClass =
+  'px-1.5 py-0.5 rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60 disabled:opacity-50';
+
+function formatTime(timestamp: number): string {
+  return new Date(timestamp).toLocaleString(undefined, {
+    month: '2-digit',
+    day: '2-digit',
+    hour: '2-digit',
+    minute: '2-digit',
+  });
+}
+
+export function DailyLedgerPanel({ instanceId, onClose }: DailyLedgerPanelProps) {
+  const { t } = useTranslation();
+  const panelRef = useRef<HTMLDivElement>(null);
+  const {
+    instances,
+    updateInstance,
+    projectInterface,
+    resolveI18nText,
+    language,
+    instanceTaskQueue,
+  } = useAppStore();
+  const [ledger, setLedger] = useState<TaskLedger | null>(null);
+
+  const instance = instances.find((i) => i.id === instanceId);
+  const dayReset = instance?.dayReset ?? defaultDayReset;
+  const langKey = getInterfaceLangKey(language);
+  const queue = instanceTaskQueue[instanceId];
+
+  // 点击外部关闭面板
+  useEffect(() => {
+    const handleClickOutside = (e: MouseEvent) => {
+      if (panelRef.current && !panelRef.current.contains(e.target as Node)) {
+        onClose();
+      }
+    };
+    document.addEventListener('mousedown', handleClickOutside);
+    return () => document.removeEventListener('mousedown', handleClickOutside);
+  }, [onClose]);
+
+  const refresh = useCallback(() => {
+    maaService
+      .getTaskLedger(instanceId)
+      .then(setLedger)
+      .catch((err) => log.warn('获取每日完成记录失败:', err));
+  }, [instanceId]);
+
+  // 打开面板和任务队列变化（任务成功后会写入记录）时刷新
+  useEffect(() => {
+    refresh();
+  }, [refresh, queue]);
+
+  const updateDayReset = (patch: Partial<DayReset>) => {
+    const reset = { ...dayReset, ...patch };
+    updateInstance(instanceId, { dayReset: reset });
+    maaService
+      .setDayReset(instanceId, reset)
+      .then(refresh)
+      .catch((err) => log.warn('同步每日重置设置失败:', err));
+  };
+
+  const clearLedger = (taskName?: string) => {
+    maaService
+      .clearTaskLedger(instanceId, taskName)
+      .then(refresh)
+      .catch((err) => log.warn('清除每日完成记录失败:', err));
+  };
+
+  // 任务列表中的任务（同名任务共用一条记录）
+  const taskNames = [...new Set(instance?.selectedTasks.map((task) => task.taskName) ?? [])];
+  const taskLabel = (name: string) => {
+    const taskDef = projectInterface?.task.find((item) => item.name === name);
+    return resolveI18nText(taskDef?.label, langKey) || name;
+  };
+
+  const timeZones = listTimeZones(dayReset.timezone);
+
+  return (
+    <div
+      ref={panelRef}
+      className={clsx(
+        'absolute bottom-full right-0 mb-2 w-80',
+        'bg-bg-primary border border-border rounded-lg shadow-lg',
+        'z-50',
+      )}
+    >
+      {/* 头部 */}
+      <div className="flex items-center justify-between px-4 py-3 border-b border-border">
+        <div className="flex items-center gap-2">
+          <CalendarCheck className="w-4 h-4 text-accent" />
+          <span className="text-sm font-medium text-text-primary">{t('dailyLedger.title')}</span>
+        </div>
+        <button onClick={onClose} className="p-1 rounded hover:bg-bg-hover">
+          <X className="w-4 h-4 text-text-muted" />
+        </button>
+      </div>
+
+      <div className="p-3 space-y-2 text-sm">
+        {/* 跳过已完成的任务 */}
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={instance?.skipCompleted ?? false}
+            disabled={instance?.isRunning}
+            onChange={(e) => updateInstance(instanceId, { skipCompleted: e.target.checked })}
+            className="accent-accent"
+          />
+          <span className="text-text-primary">{t('dailyLedger.skipCompleted')}</span>
+        </label>
+
+        {/* 每日重置时间和时区 */}
+        <div className="flex items-center gap-2 text-xs text-text-secondary">
+          <span>{t('dailyLedger.resetTime')}</span>
+          <input
+            type="time"
+            value={dayReset.reset_time}
+            onChange={(e) => e.target.value && updateDayReset({ reset_time: e.target.value })}
+            className={inputClass}
+          />
+          <select
+            value={dayReset.timezone ?? ''}
+            onChange={(e) => updateDayReset({ timezone: e.target.value || null })}
+            className={clsx(inputClass, 'flex-1 min-w-0')}
+          >
+            <option value="">{t('dailyLedger.localTime')}</option>
+            {timeZones.map((zone) => (
+              <option key={zone} value={zone}>
+                {zone}
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {/* 本周期的完成情况 */}
+        <div className="space-y-1 max-h-48 overflow-y-auto">
+          {taskNames.map((name) => {
+            const entry = ledger?.tasks[name];
+            const completed = entry?.completed ?? false;
+            return (
+              <div key={name} className="flex items-center gap-2 text-xs">
+                <span
+                  className={clsx(
+                    'w-1.5 h-1.5 rounded-full flex-shrink-0',
+                    completed ? 'bg-success' : 'bg-text-muted/50',
+                  )}
+                />
+                <span className="flex-1 truncate text-text-primary">{taskLabel(name)}</span>
+                <span className="text-text-muted">
+                  {completed && entry
+                    ? t('dailyLedger.completedAt', { time: formatTime(entry.last_success) })
+                    : t('dailyLedger.notCompleted')}
+                </span>
+                {entry && (
+                  <button
+                    onClick={() => clearLedger(name)}
+                    className="p-0.5 rounded hover:bg-bg-hover"
+                    title={t('dailyLedger.clear')}
+                  >
+                    <RotateCcw className="w-3 h-3 text-text-muted" />
+                  </button>
+                )}
+              </div>
+            );
+          })}
+        </div>
+      </div>
+
+      {/* 下次重置时间 */}
+      <div className="flex items-center justify-between px-4 py-2 border-t border-border bg-bg-tertiary rounded-b-lg">
+        <p className="text-xs text-text-muted">
+          {ledger
+            ? t('dailyLedger.nextReset', { time: formatTime(ledger.next_reset) })
+            : t('dailyLedger.hint')}
+        </p>
+        <button
+          onClick={() => clearLedger()}
+          className="text-xs text-text-muted hover:text-text-primary"
+        >
+          {t('dailyLedger.clearAll')}
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -28,7 +28,7 @@ import { maaService } from '@/services/maaService';
 import { ContextMenu, useContextMenu, type MenuItem } from './ContextMenu';
 import { FrameRateSelector, getFrameInterval } from './FrameRateSelector';
 import { resolveI18nText } from '@/services/contentResolver';
-import { loggers, generateTaskPipelineOverride } from '@/utils';
+import { loggers } from '@/utils';
 import type { TaskConfig, AgentConfig } from '@/types/maa';
 import { getInterfaceLangKey } from '@/i18n';
 
@@ -62,15 +62,8 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
     updateInstance,
     setInstanceTaskStatus,
     setInstanceCurrentTaskId,
-    setAllTasksRunStatus,
-    registerMaaTaskMapping,
-    setTaskRunStatus,
     clearTaskRunStatus,
-    setPendingTaskIds,
-    setCurrentTaskIndex,
-    clearPendingTasks,
     basePath,
-    registerTaskIdName,
     registerEntryTaskName,
     screenshotFrameRate,
     setShowAddTaskPanel,
@@ -89,7 +82,6 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
   const streamingRef = useRef(false);
   const lastFrameTimeRef = useRef(0);
   const frameIntervalRef = useRef(getFrameInterval(screenshotFrameRate));
-  const runningInstanceIdRef = useRef<string | null>(null);
 
   // 帧率配置变化时更新帧间隔
   useEffect(() => {
@@ -200,8 +192,6 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           setInstanceTaskStatus(instanceId, null);
           setInstanceCurrentTaskId(instanceId, null);
           clearTaskRunStatus(instanceId);
-          clearPendingTasks(instanceId);
-          runningInstanceIdRef.current = null;
         } catch (err) {
           log.error(`[${instanceName}] 停止任务失败:`, err);
         } finally {
@@ -222,15 +212,23 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
             const taskDef = projectInterface?.task.find((t) => t.name === selectedTask.taskName);
             if (!taskDef) continue;
 
-            taskConfigs.push({
-              entry: taskDef.entry,
-              pipeline_override: generateTaskPipelineOverride(selectedTask, projectInterface),
-            });
             const taskDisplayName =
               selectedTask.customName ||
               resolveI18nText(taskDef.label, translations) ||
               selectedTask.taskName;
             registerEntryTaskName(taskDef.entry, taskDisplayName);
+            taskConfigs.push({
+              entry: taskDef.entry,
+              pipeline_override: await maaService.buildPipelineOverride(
+                selectedTask.taskName,
+                selectedTask.optionValues,
+              ),
+              task_name: selectedTask.taskName,
+              label: taskDisplayName,
+              source_id: selectedTask.id,
+              ...selectedTask.runPolicy,
+              conditions: selectedTask.conditions,
+            });
           }
 
           if (taskConfigs.length === 0) {
@@ -254,46 +252,18 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           setInstanceTaskStatus(instanceId, 'Running');
           setShowAddTaskPanel(false);
 
-          // 启动任务
-          const taskIds = await maaService.startTasks(
+          // 启动任务（后端任务队列逐个提交，运行状态由 maa-task-queue 事件同步）
+          const itemIds = await maaService.startTasks(
             instanceId,
             taskConfigs,
             agentConfig,
             basePath,
             tcpCompatMode,
+            instance.runLoop,
+            instance.skipCompleted,
           );
 
-          log.info(`[${instanceName}] 任务已提交, task_ids:`, taskIds);
-
-          // 初始化任务运行状态
-          const enabledTaskIds = enabledTasks.map((t) => t.id);
-          setAllTasksRunStatus(instanceId, enabledTaskIds, 'pending');
-
-          // 记录映射关系
-          taskIds.forEach((maaTaskId, index) => {
-            if (enabledTasks[index]) {
-              registerMaaTaskMapping(instanceId, maaTaskId, enabledTasks[index].id);
-              const taskDef = projectInterface?.task.find(
-                (t) => t.name === enabledTasks[index].taskName,
-              );
-              const taskDisplayName =
-                enabledTasks[index].customName ||
-                resolveI18nText(taskDef?.label, translations) ||
-                enabledTasks[index].taskName;
-              registerTaskIdName(maaTaskId, taskDisplayName);
-            }
-          });
-
-          // 第一个任务设为 running
-          if (enabledTasks.length > 0) {
-            setTaskRunStatus(instanceId, enabledTasks[0].id, 'running');
-          }
-
-          // 设置任务队列
-          runningInstanceIdRef.current = instanceId;
-          setPendingTaskIds(instanceId, taskIds);
-          setCurrentTaskIndex(instanceId, 0);
-          setInstanceCurrentTaskId(instanceId, taskIds[0]);
+          log.info(`[${instanceName}] 任务已加入队列, item_ids:`, itemIds);
           setIsStarting(false);
         } catch (err) {
           log.error(`[${instanceName}] 任务启动异常:`, err);
@@ -307,7 +277,6 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
           updateInstance(instanceId, { isRunning: false });
           setInstanceTaskStatus(instanceId, 'Failed');
           clearTaskRunStatus(instanceId);
-          clearPendingTasks(instanceId);
           setIsStarting(false);
         }
       }
@@ -325,13 +294,6 @@ function InstanceCard({ instanceId, instanceName, isActive, onSelect }: Instance
       setInstanceTaskStatus,
       setInstanceCurrentTaskId,
       clearTaskRunStatus,
-      clearPendingTasks,
-      setAllTasksRunStatus,
-      registerMaaTaskMapping,
-      setTaskRunStatus,
-      setPendingTaskIds,
-      setCurrentTaskIndex,
-      registerTaskIdName,
       registerEntryTaskName,
       setShowAddTaskPanel,
       translations,
@@ -0,0 +1,306 @@
+import { useEffect, useRef, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Power, X } from 'lucide-react';
+import { useAppStore } from '@/stores/appStore';
+import { maaService } from '@/services/maaService';
+import { defaultPostRunConfig, type PostRunAction, type PostRunConfig } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+import clsx from 'clsx';
+
+const log = loggers.task;
+
+interface PostRunPanelProps {
+  instanceId: string;
+  onClose: () => void;
+}
+
+type PowerAction = 'none' | 'shutdown' | 'suspend';
+
+/** 面板中编辑的动作，按固定顺序组成 PostRunConfig.actions */
+interface PostRunDraft {
+  stopAgent: boolean;
+  runCommand: { program: string; args: string[]; cwd: string | null } | null;
+  startInstance: string | null;
+  power: PowerAction;
+  exitApp: boolean;
+}
+
+const inputClass =
+  'px-1.5 py-0.5 rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60 disabled:opacity-50';
+
+function toDraft(actions: PostRunAction[]): PostRunDraft {
+  const draft: PostRunDraft = {
+    stopAgent: false,
+    runCommand: null,
+    startInstance: null,
+    power: 'none',
+    exitApp: false,
+  };
+  for (const action of actions) {
+    switch (action.type) {
+      case 'stop_agent':
+        draft.stopAgent = true;
+        break;
+      case 'run_command':
+        draft.runCommand = { program: action.program, args: action.args, cwd: action.cwd };
+        break;
+      case 'start_instance':
+        draft.startInstance = action.instance_id;
+        break;
+      case 'shutdown':
+      case 'suspend':
+        draft.power = action.type;
+        break;
+      case 'exit_app':
+        draft.exitApp = true;
+        break;
+    }
+  }
+  return draft;
+}
+
+/** 先停止 Agent 和运行命令，再启动其他实例，最后关机/睡眠或退出 */
+function toActions(draft: PostRunDraft): PostRunAction[] {
+  const actions: PostRunAction[] = [];
+  if (draft.stopAgent) actions.push({ type: 'stop_agent' });
+  if (draft.runCommand) actions.push({ type: 'run_command', ...draft.runCommand });
+  if (draft.startInstance)
+    actions.push({ type: 'start_instance', instance_id: draft.startInstance });
+  if (draft.power !== 'none') actions.push({ type: draft.power });
+  if (draft.exitApp) actions.push({ type: 'exit_app' });
+  return actions;
+}
+
+/** 实例运行结束后动作的剩余倒计时（秒），未在倒计时时为 null */
+export function usePostRunCountdown(instanceId: string | undefined): number | null {
+  const countdown = useAppStore((state) =>
+    instanceId ? state.postRunCountdowns[instanceId] : undefined,
+  );
+  const [now, setNow] = useState(() => Date.now());
+
+  useEffect(() => {
+    if (!countdown) return;
+    const timer = setInterval(() => setNow(Date.now()), 500);
+    return () => clearInterval(timer);
+  }, [countdown]);
+
+  if (!countdown) return null;
+  return Math.max(0, Math.ceil((countdown.deadline - now) / 1000));
+}
+
+export function PostRunPanel({ instanceId, onClose }: PostRunPanelProps) {
+  const { t } = useTranslation();
+  const panelRef = useRef<HTMLDivElement>(null);
+  const { instances, updateInstance } = useAppStore();
+  const remaining = usePostRunCountdown(instanceId);
+
+  const instance = instances.find((i) => i.id === instanceId);
+  const config = instance?.postRun ?? defaultPostRunConfig;
+  const draft = toDraft(config.actions);
+  const otherInstances = instances.filter((i) => i.id !== instanceId);
+
+  // 点击外部关闭面板
+  useEffect(() => {
+    const handleClickOutside = (e: MouseEvent) => {
+      if (panelRef.current && !panelRef.current.contains(e.target as Node)) {
+        onClose();
+      }
+    };
+    document.addEventListener('mousedown', handleClickOutside);
+    return () => document.removeEventListener('mousedown', handleClickOutside);
+  }, [onClose]);
+
+  const updateConfig = (patch: Partial<PostRunConfig>) => {
+    updateInstance(instanceId, { postRun: { ...config, ...patch } });
+  };
+
+  const updateDraft = (patch: Partial<PostRunDraft>) => {
+    updateConfig({ actions: toActions({ ...draft, ...patch }) });
+  };
+
+  const cancelCountdown = () => {
+    maaService.cancelPostRun(instanceId).catch((err) => log.warn('取消运行结束后动作失败:', err));
+  };
+
+  return (
+    <div
+      ref={panelRef}
+      className={clsx(
+        'absolute bottom-full right-0 mb-2 w-80',
+        'bg-bg-primary border border-border rounded-lg shadow-lg',
+        'z-50',
+      )}
+    >
+      {/* 头部 */}
+      <div className="flex items-center justify-between px-4 py-3 border-b border-border">
+        <div className="flex items-center gap-2">
+          <Power className="w-4 h-4 text-accent" />
+          <span className="text-sm font-medium text-text-primary">{t('postRun.title')}</span>
+        </div>
+        <button onClick={onClose} className="p-1 rounded hover:bg-bg-hover">
+          <X className="w-4 h-4 text-text-muted" />
+        </button>
+      </div>
+
+      <div className="p-3 space-y-2 text-sm">
+        {/* 停止 Agent */}
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={draft.stopAgent}
+            onChange={(e) => updateDraft({ stopAgent: e.target.checked })}
+            className="accent-accent"
+          />
+          <span className="text-text-primary">{t('postRun.actions.stop_agent')}</span>
+        </label>
+
+        {/* 运行命令 */}
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={draft.runCommand !== null}
+            onChange={(e) =>
+              updateDraft({
+                runCommand: e.target.checked ? { program: '', args: [], cwd: null } : null,
+              })
+            }
+            className="accent-accent"
+          />
+          <span className="text-text-primary">{t('postRun.actions.run_command')}</span>
+        </label>
+        {draft.runCommand && (
+          <div className="pl-6 space-y-1 text-xs">
+            <input
+              type="text"
+              value={draft.runCommand.program}
+              placeholder={t('postRun.program')}
+              onChange={(e) =>
+                draft.runCommand &&
+                updateDraft({ runCommand: { ...draft.runCommand, program: e.target.value } })
+              }
+              className={clsx(inputClass, 'w-full')}
+            />
+            {/* 参数按空白分隔，失去焦点时保存 */}
+            <input
+              key={draft.runCommand.args.join(' ')}
+              type="text"
+              defaultValue={draft.runCommand.args.join(' ')}
+              placeholder={t('postRun.args')}
+              onBlur={(e) =>
+                draft.runCommand &&
+                updateDraft({
+                  runCommand: {
+                    ...draft.runCommand,
+                    args: e.target.value.split(/\s+/).filter(Boolean),
+                  },
+                })
+              }
+              className={clsx(inputClass, 'w-full')}
+            />
+            <input
+              type="text"
+              value={draft.runCommand.cwd ?? ''}
+              placeholder={t('postRun.cwd')}
+              onChange={(e) =>
+                draft.runCommand &&
+                updateDraft({ runCommand: { ...draft.runCommand, cwd: e.target.value || null } })
+              }
+              className={clsx(inputClass, 'w-full')}
+            />
+          </div>
+        )}
+
+        {/* 启动其他实例 */}
+        <div className="flex items-center gap-2">
+          <span className="text-text-primary flex-shrink-0">
+            {t('postRun.actions.start_instance')}
+          </span>
+          <select
+            value={draft.startInstance ?? ''}
+            onChange={(e) => updateDraft({ startInstance: e.target.value || null })}
+            className={clsx(inputClass, 'flex-1 min-w-0 text-xs')}
+          >
+            <option value="">{t('postRun.none')}</option>
+            {otherInstances.map((other) => (
+              <option key={other.id} value={other.id}>
+                {other.name}
+              </option>
+            ))}
+          </select>
+        </div>
+
+        {/* 关机 / 睡眠 */}
+        <div className="flex items-center gap-2">
+          <span className="text-text-primary flex-shrink-0">{t('postRun.power')}</span>
+          <select
+            value={draft.power}
+            onChange={(e) => updateDraft({ power: e.target.value as PowerAction })}
+            className={clsx(inputClass, 'flex-1 min-w-0 text-xs')}
+          >
+            <option value="none">{t('postRun.none')}</option>
+            <option value="shutdown">{t('postRun.actions.shutdown')}</option>
+            <option value="suspend">{t('postRun.actions.suspend')}</option>
+          </select>
+        </div>
+
+        {/* 退出 MXU */}
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={draft.exitApp}
+            onChange={(e) => updateDraft({ exitApp: e.target.checked })}
+            className="accent-accent"
+          />
+          <span className="text-text-primary">{t('postRun.actions.exit_app')}</span>
+        </label>
+
+        <div className="border-t border-border pt-2 space-y-2 text-xs text-text-secondary">
+          {/* 倒计时 */}
+          <div className="flex items-center gap-2">
+            <span>{t('postRun.countdown')}</span>
+            <input
+              type="number"
+              min={0}
+              max={600}
+              value={config.countdown_secs}
+              onChange={(e) => {
+                const value = parseInt(e.target.value, 10);
+                if (!isNaN(value)) {
+                  updateConfig({ countdown_secs: Math.min(600, Math.max(0, value)) });
+                }
+              }}
+              className={clsx(inputClass, 'w-16')}
+            />
+            <span>{t('postRun.seconds')}</span>
+          </div>
+
+          {/* 有任务失败时也执行 */}
+          <label className="flex items-center gap-2 cursor-pointer">
+            <input
+              type="checkbox"
+              checked={config.on_failure}
+              onChange={(e) => updateConfig({ on_failure: e.target.checked })}
+              className="accent-accent"
+            />
+            <span>{t('postRun.onFailure')}</span>
+          </label>
+        </div>
+      </div>
+
+      {/* 倒计时提示 */}
+      <div className="flex items-center justify-between px-4 py-2 border-t border-border bg-bg-tertiary rounded-b-lg">
+        <p className={clsx('text-xs', remaining !== null ? 'text-warning' : 'text-text-muted')}>
+          {remaining !== null ? t('postRun.pending', { seconds: remaining }) : t('postRun.hint')}
+        </p>
+        {remaining !== null && (
+          <button
+            onClick={cancelCountdown}
+            className="text-xs text-text-muted hover:text-text-primary"
+          >
+            {t('postRun.cancel')}
+          </button>
+        )}
+      </div>
+    </div>
+  );
+}
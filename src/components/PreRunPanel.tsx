@@ -0,0 +1,262 @@
+import { useEffect, useRef } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Plus, Rocket, Trash2, X } from 'lucide-react';
+import { useAppStore } from '@/stores/appStore';
+import { maaService } from '@/services/maaService';
+import { defaultPreRunStep, type PreRunStep, type ReadyCheck } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+import clsx from 'clsx';
+
+const log = loggers.task;
+
+interface PreRunPanelProps {
+  instanceId: string;
+  onClose: () => void;
+}
+
+const inputClass =
+  'px-1.5 py-0.5 rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60 disabled:opacity-50';
+
+/** 切换就绪检查方式时的初始值 */
+function defaultReadyCheck(type: string): ReadyCheck | null {
+  switch (type) {
+    case 'adb_device':
+      return { type: 'adb_device', address: '' };
+    case 'window':
+      return { type: 'window', class_regex: null, window_regex: null };
+    default:
+      return null;
+  }
+}
+
+export function PreRunPanel({ instanceId, onClose }: PreRunPanelProps) {
+  const { t } = useTranslation();
+  const panelRef = useRef<HTMLDivElement>(null);
+  const { instances, updateInstance, preRunProgress } = useAppStore();
+
+  const instance = instances.find((i) => i.id === instanceId);
+  const steps = instance?.preRun ?? [];
+  const progress = preRunProgress[instanceId];
+
+  // 点击外部关闭面板
+  useEffect(() => {
+    const handleClickOutside = (e: MouseEvent) => {
+      if (panelRef.current && !panelRef.current.contains(e.target as Node)) {
+        onClose();
+      }
+    };
+    document.addEventListener('mousedown', handleClickOutside);
+    return () => document.removeEventListener('mousedown', handleClickOutside);
+  }, [onClose]);
+
+  const updateSteps = (next: PreRunStep[]) => {
+    updateInstance(instanceId, { preRun: next });
+  };
+
+  const updateStep = (index: number, patch: Partial<PreRunStep>) => {
+    updateSteps(steps.map((step, i) => (i === index ? { ...step, ...patch } : step)));
+  };
+
+  const cancelPreRun = () => {
+    maaService.cancelPreRun(instanceId).catch((err) => log.warn('中止启动前准备步骤失败:', err));
+  };
+
+  const progressText = () => {
+    if (!progress) return t('preRun.hint');
+    const step = (progress.step_index ?? 0) + 1;
+    if (progress.phase === 'waiting' && progress.deadline) {
+      const time = new Date(progress.deadline).toLocaleTimeString();
+      return t('preRun.progress.waiting', { step, time });
+    }
+    return t(`preRun.progress.${progress.phase}`, { step });
+  };
+
+  return (
+    <div
+      ref={panelRef}
+      className={clsx(
+        'absolute bottom-full right-0 mb-2 w-96',
+        'bg-bg-primary border border-border rounded-lg shadow-lg',
+        'z-50',
+      )}
+    >
+      {/* 头部 */}
+      <div className="flex items-center justify-between px-4 py-3 border-b border-border">
+        <div className="flex items-center gap-2">
+          <Rocket className="w-4 h-4 text-accent" />
+          <span className="text-sm font-medium text-text-primary">{t('preRun.title')}</span>
+        </div>
+        <button onClick={onClose} className="p-1 rounded hover:bg-bg-hover">
+          <X className="w-4 h-4 text-text-muted" />
+        </button>
+      </div>
+
+      <div className="p-3 space-y-3 text-xs max-h-96 overflow-y-auto">
+        {steps.map((step, index) => (
+          <div key={index} className="space-y-1.5 p-2 rounded-md border border-border">
+            {/* 程序和参数 */}
+            <div className="flex items-center gap-2">
+              <span className="text-text-muted flex-shrink-0">#{index + 1}</span>
+              <input
+                type="text"
+                value={step.program}
+                placeholder={t('preRun.program')}
+                onChange={(e) => updateStep(index, { program: e.target.value })}
+                className={clsx(inputClass, 'flex-1 min-w-0')}
+              />
+              <button
+                onClick={() => updateSteps(steps.filter((_, i) => i !== index))}
+                className="p-1 rounded hover:bg-bg-hover"
+                title={t('preRun.removeStep')}
+              >
+                <Trash2 className="w-3.5 h-3.5 text-text-muted" />
+              </button>
+            </div>
+            {/* 参数按空白分隔，失去焦点时保存 */}
+            <input
+              key={step.args.join(' ')}
+              type="text"
+              defaultValue={step.args.join(' ')}
+              placeholder={t('preRun.args')}
+              onBlur={(e) =>
+                updateStep(index, { args: e.target.value.split(/\s+/).filter(Boolean) })
+              }
+              className={clsx(inputClass, 'w-full')}
+            />
+            <input
+              type="text"
+              value={step.cwd ?? ''}
+              placeholder={t('preRun.cwd')}
+              onChange={(e) => updateStep(index, { cwd: e.target.value || null })}
+              className={clsx(inputClass, 'w-full')}
+            />
+
+            {/* 就绪检查 */}
+            <div className="flex items-center gap-2 text-text-secondary">
+              <span className="flex-shrink-0">{t('preRun.waitFor')}</span>
+              <select
+                value={step.ready?.type ?? 'none'}
+                onChange={(e) => updateStep(index, { ready: defaultReadyCheck(e.target.value) })}
+                className={clsx(inputClass, 'flex-1 min-w-0')}
+              >
+                <option value="none">{t('preRun.ready.none')}</option>
+                <option value="adb_device">{t('preRun.ready.adb_device')}</option>
+                <option value="window">{t('preRun.ready.window')}</option>
+              </select>
+            </div>
+            {step.ready?.type === 'adb_device' && (
+              <input
+                type="text"
+                value={step.ready.address}
+                placeholder={t('preRun.address')}
+                onChange={(e) =>
+                  updateStep(index, { ready: { type: 'adb_device', address: e.target.value } })
+                }
+                className={clsx(inputClass, 'w-full')}
+              />
+            )}
+            {step.ready?.type === 'window' && (
+              <div className="flex items-center gap-2">
+                <input
+                  type="text"
+                  value={step.ready.class_regex ?? ''}
+                  placeholder={t('preRun.classRegex')}
+                  onChange={(e) =>
+                    step.ready?.type === 'window' &&
+                    updateStep(index, {
+                      ready: { ...step.ready, class_regex: e.target.value || null },
+                    })
+                  }
+                  className={clsx(inputClass, 'flex-1 min-w-0')}
+                />
+                <input
+                  type="text"
+                  value={step.ready.window_regex ?? ''}
+                  placeholder={t('preRun.windowRegex')}
+                  onChange={(e) =>
+                    step.ready?.type === 'window' &&
+                    updateStep(index, {
+                      ready: { ...step.ready, window_regex: e.target.value || null },
+                    })
+                  }
+                  className={clsx(inputClass, 'flex-1 min-w-0')}
+                />
+              </div>
+            )}
+
+            {step.ready && (
+              <div className="space-y-1.5 text-text-secondary">
+                {/* 超时和就绪后等待 */}
+                <div className="flex items-center gap-2">
+                  <span>{t('preRun.timeout')}</span>
+                  <input
+                    type="number"
+                    min={1}
+                    max={3600}
+                    value={step.timeout_secs}
+                    onChange={(e) => {
+                      const value = parseInt(e.target.value, 10);
+                      if (!isNaN(value)) {
+                        updateStep(index, { timeout_secs: Math.min(3600, Math.max(1, value)) });
+                      }
+                    }}
+                    className={clsx(inputClass, 'w-16')}
+                  />
+                  <span>{t('preRun.settle')}</span>
+                  <input
+                    type="number"
+                    min={0}
+                    max={600}
+                    value={step.settle_secs}
+                    onChange={(e) => {
+                      const value = parseInt(e.target.value, 10);
+                      if (!isNaN(value)) {
+                        updateStep(index, { settle_secs: Math.min(600, Math.max(0, value)) });
+                      }
+                    }}
+                    className={clsx(inputClass, 'w-16')}
+                  />
+                  <span>{t('preRun.seconds')}</span>
+                </div>
+
+                {/* 已就绪时跳过启动 */}
+                <label className="flex items-center gap-2 cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={step.skip_if_ready}
+                    onChange={(e) => updateStep(index, { skip_if_ready: e.target.checked })}
+                    className="accent-accent"
+                  />
+                  <span>{t('preRun.skipIfReady')}</span>
+                </label>
+              </div>
+            )}
+          </div>
+        ))}
+
+        <button
+          onClick={() => updateSteps([...steps, { ...defaultPreRunStep }])}
+          className="flex items-center gap-1 text-accent hover:underline"
+        >
+          <Plus className="w-3.5 h-3.5" />
+          {t('preRun.addStep')}
+        </button>
+      </div>
+
+      {/* 执行进度 */}
+      <div className="flex items-center justify-between px-4 py-2 border-t border-border bg-bg-tertiary rounded-b-lg">
+        <p className={clsx('text-xs', progress ? 'text-accent' : 'text-text-muted')}>
+          {progressText()}
+        </p>
+        {progress && (
+          <button
+            onClick={cancelPreRun}
+            className="text-xs text-text-muted hover:text-text-primary"
+          >
+            {t('preRun.cancel')}
+          </button>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,210 @@
+import { useEffect, useRef } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Repeat, X } from 'lucide-react';
+import { useAppStore } from '@/stores/appStore';
+import { getInterfaceLangKey } from '@/i18n';
+import { defaultRunLoop, type RunLoop, type RunMode } from '@/types/maa';
+import clsx from 'clsx';
+
+interface RunLoopPanelProps {
+  instanceId: string;
+  onClose: () => void;
+}
+
+const inputClass =
+  'px-1.5 py-0.5 rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60 disabled:opacity-50';
+
+/** 时间戳转换为 datetime-local 输入框的本地时间字符串 */
+function toLocalInput(timestamp: number): string {
+  const date = new Date(timestamp);
+  const pad = (n: number) => n.toString().padStart(2, '0');
+  const day = `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`;
+  return `${day}T${pad(date.getHours())}:${pad(date.getMinutes())}`;
+}
+
+export function RunLoopPanel({ instanceId, onClose }: RunLoopPanelProps) {
+  const { t } = useTranslation();
+  const panelRef = useRef<HTMLDivElement>(null);
+  const { instances, updateInstance, projectInterface, resolveI18nText, language } =
+    useAppStore();
+
+  const instance = instances.find((i) => i.id === instanceId);
+  const runLoop = instance?.runLoop ?? defaultRunLoop;
+  const mode = runLoop.mode;
+  const disabled = instance?.isRunning ?? false;
+  const langKey = getInterfaceLangKey(language);
+
+  // 点击外部关闭面板
+  useEffect(() => {
+    const handleClickOutside = (e: MouseEvent) => {
+      if (panelRef.current && !panelRef.current.contains(e.target as Node)) {
+        onClose();
+      }
+    };
+    document.addEventListener('mousedown', handleClickOutside);
+    return () => document.removeEventListener('mousedown', handleClickOutside);
+  }, [onClose]);
+
+  const updateRunLoop = (patch: Partial<RunLoop>) => {
+    updateInstance(instanceId, { runLoop: { ...runLoop, ...patch } });
+  };
+
+  // 切换循环方式时填入默认参数
+  const selectMode = (type: RunMode['type']) => {
+    if (type === mode.type) return;
+    const tasks = instance?.selectedTasks ?? [];
+    const modes: Record<RunMode['type'], RunMode> = {
+      once: { type: 'once' },
+      repeat: { type: 'repeat', times: 2 },
+      until_deadline: { type: 'until_deadline', deadline: Date.now() + 60 * 60 * 1000 },
+      until_task: {
+        type: 'until_task',
+        task: tasks.find((task) => task.enabled)?.id ?? tasks[0]?.id ?? '',
+        outcome: 'Succeeded',
+      },
+    };
+    updateRunLoop({ mode: modes[type] });
+  };
+
+  const taskName = (id: string) => {
+    const task = instance?.selectedTasks.find((item) => item.id === id);
+    if (!task) return id;
+    const taskDef = projectInterface?.task.find((item) => item.name === task.taskName);
+    return task.customName || resolveI18nText(taskDef?.label, langKey) || task.taskName;
+  };
+
+  const modeTypes: RunMode['type'][] = ['once', 'repeat', 'until_deadline', 'until_task'];
+
+  return (
+    <div
+      ref={panelRef}
+      className={clsx(
+        'absolute bottom-full right-0 mb-2 w-80',
+        'bg-bg-primary border border-border rounded-lg shadow-lg',
+        'z-50',
+      )}
+    >
+      {/* 头部 */}
+      <div className="flex items-center justify-between px-4 py-3 border-b border-border">
+        <div className="flex items-center gap-2">
+          <Repeat className="w-4 h-4 text-accent" />
+          <span className="text-sm font-medium text-text-primary">{t('runLoop.title')}</span>
+        </div>
+        <button onClick={onClose} className="p-1 rounded hover:bg-bg-hover">
+          <X className="w-4 h-4 text-text-muted" />
+        </button>
+      </div>
+
+      {/* 循环方式 */}
+      <div className="p-3 space-y-2 text-sm">
+        {modeTypes.map((type) => (
+          <label key={type} className="flex items-center gap-2 cursor-pointer">
+            <input
+              type="radio"
+              checked={mode.type === type}
+              disabled={disabled}
+              onChange={() => selectMode(type)}
+              className="accent-accent"
+            />
+            <span className="text-text-primary">{t(`runLoop.modes.${type}`)}</span>
+          </label>
+        ))}
+
+        {/* 循环参数 */}
+        {mode.type === 'repeat' && (
+          <label className="flex items-center gap-2 text-xs text-text-secondary pl-5">
+            <span>{t('runLoop.times')}</span>
+            <input
+              type="number"
+              min={1}
+              max={9999}
+              value={mode.times}
+              disabled={disabled}
+              onChange={(e) => {
+                const value = parseInt(e.target.value, 10);
+                if (!Number.isNaN(value)) {
+                  updateRunLoop({
+                    mode: { ...mode, times: Math.min(9999, Math.max(1, value)) },
+                  });
+                }
+              }}
+              className={clsx(inputClass, 'w-16')}
+            />
+          </label>
+        )}
+        {mode.type === 'until_deadline' && (
+          <label className="flex items-center gap-2 text-xs text-text-secondary pl-5">
+            <span>{t('runLoop.deadline')}</span>
+            <input
+              type="datetime-local"
+              value={toLocalInput(mode.deadline)}
+              disabled={disabled}
+              onChange={(e) => {
+                const deadline = new Date(e.target.value).getTime();
+                if (!Number.isNaN(deadline)) {
+                  updateRunLoop({ mode: { ...mode, deadline } });
+                }
+              }}
+              className={inputClass}
+            />
+          </label>
+        )}
+        {mode.type === 'until_task' && (
+          <div className="flex items-center gap-2 text-xs text-text-secondary pl-5">
+            <select
+              value={mode.task}
+              disabled={disabled}
+              onChange={(e) => updateRunLoop({ mode: { ...mode, task: e.target.value } })}
+              className={clsx(inputClass, 'flex-1 min-w-0')}
+            >
+              {instance?.selectedTasks.map((task) => (
+                <option key={task.id} value={task.id}>
+                  {taskName(task.id)}
+                </option>
+              ))}
+            </select>
+            <select
+              value={mode.outcome}
+              disabled={disabled}
+              onChange={(e) =>
+                updateRunLoop({
+                  mode: { ...mode, outcome: e.target.value as 'Succeeded' | 'Failed' },
+                })
+              }
+              className={inputClass}
+            >
+              <option value="Succeeded">{t('runLoop.succeeded')}</option>
+              <option value="Failed">{t('runLoop.failed')}</option>
+            </select>
+          </div>
+        )}
+
+        {/* 两轮之间的等待时间 */}
+        {mode.type !== 'once' && (
+          <label className="flex items-center gap-2 text-xs text-text-secondary">
+            <span>{t('runLoop.delay')}</span>
+            <input
+              type="number"
+              min={0}
+              max={86400}
+              value={Math.round(runLoop.iteration_delay_ms / 1000)}
+              disabled={disabled}
+              onChange={(e) => {
+                const value = parseInt(e.target.value, 10);
+                if (!Number.isNaN(value)) {
+                  updateRunLoop({ iteration_delay_ms: Math.min(86400, Math.max(0, value)) * 1000 });
+                }
+              }}
+              className={clsx(inputClass, 'w-20')}
+            />
+          </label>
+        )}
+      </div>
+
+      {/* 提示信息 */}
+      <div className="px-4 py-2 border-t border-border bg-bg-tertiary rounded-b-lg">
+        <p className="text-xs text-text-muted">{t('runLoop.hint')}</p>
+      </div>
+    </div>
+  );
+}
@@ -11,6 +11,8 @@ import {
   ToggleRight,
 } from 'lucide-react';
 import { useAppStore } from '@/stores/appStore';
+import { maaService } from '@/services/maaService';
+import { listTimeZones } from '@/utils/timeZone';
 import type { SchedulePolicy } from '@/types/interface';
 import clsx from 'clsx';
 import { ConfirmDialog } from './ConfirmDialog';
@@ -21,6 +23,11 @@ const generateId = () => Math.random().toString(36).substring(2, 9);
 // 小时选项 (0-23)
 const HOURS = Array.from({ length: 24 }, (_, i) => i);
 
+// 切换到 cron 表达式时的初始值：工作日 8:00
+const DEFAULT_CRON = '0 8 * * 1-5';
+
+const pad = (n: number) => n.toString().padStart(2, '0');
+
 interface SchedulePanelProps {
   instanceId: string;
   onClose: () => void;
@@ -29,12 +36,14 @@ interface SchedulePanelProps {
 /** 策略卡片组件 */
 function PolicyCard({
   policy,
+  timeZone,
   onUpdate,
   onDelete,
   isExpanded,
   onToggleExpand,
 }: {
   policy: SchedulePolicy;
+  timeZone?: string;
   onUpdate: (updates: Partial<SchedulePolicy>) => void;
   onDelete: () => void;
   isExpanded: boolean;
@@ -42,8 +51,32 @@ function PolicyCard({
 }) {
   const { t } = useTranslation();
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
+  const [nextRuns, setNextRuns] = useState<number[]>([]);
+  const [previewError, setPreviewError] = useState<string | null>(null);
 
   const weekdayLabels = t('schedule.weekdays', { returnObjects: true }) as string[];
+  const isCron = policy.cron !== undefined;
+  const minute = policy.minute ?? 0;
+
+  // 由后端计算接下来的执行时间，cron 表达式有误时显示错误
+  useEffect(() => {
+    let stale = false;
+    maaService
+      .previewSchedule(policy, timeZone)
+      .then((times) => {
+        if (stale) return;
+        setNextRuns(times);
+        setPreviewError(null);
+      })
+      .catch((err) => {
+        if (stale) return;
+        setNextRuns([]);
+        setPreviewError(String(err));
+      });
+    return () => {
+      stale = true;
+    };
+  }, [policy, timeZone]);
 
   const handleToggleWeekday = (day: number) => {
     const newWeekdays = policy.weekdays.includes(day)
@@ -89,11 +122,35 @@ function PolicyCard({
     if (policy.hours.length === 0) return t('schedule.noHours');
     if (policy.hours.length === 24) return t('schedule.everyHour');
     if (policy.hours.length <= 3) {
-      return policy.hours.map((h) => `${h.toString().padStart(2, '0')}:00`).join(', ');
+      return policy.hours.map((h) => `${pad(h)}:${pad(minute)}`).join(', ');
     }
     return `${policy.hours.length} ${t('schedule.hoursSelected')}`;
   };
 
+  const summary = isCron ? `cron: ${policy.cron}` : `${formatWeekdays()} · ${formatHours()}`;
+
+  const formatNextRuns = () => {
+    if (previewError) return previewError;
+    if (nextRuns.length === 0) return t('schedule.noNextRun');
+    const times = nextRuns.map((time) =>
+      new Date(time).toLocaleString(undefined, {
+        month: '2-digit',
+        day: '2-digit',
+        hour: '2-digit',
+        minute: '2-digit',
+      }),
+    );
+    return t('schedule.nextRuns', { times: times.join(', ') });
+  };
+
+  const modeButtonClass = (active: boolean) =>
+    clsx(
+      'flex-1 px-2 py-1 text-xs rounded border transition-colors',
+      active
+        ? 'bg-accent text-white border-accent'
+        : 'bg-bg-primary text-text-secondary border-border hover:border-accent hover:text-accent',
+    );
+
   return (
     <div
       className={clsx(
@@ -167,8 +224,41 @@ function PolicyCard({
             />
           </div>
 
+          {/* 按星期和时间 / cron 表达式 */}
+          <div className="flex gap-1">
+            <button
+              onClick={() => onUpdate({ cron: undefined })}
+              className={modeButtonClass(!isCron)}
+            >
+              {t('schedule.modeWeekly')}
+            </button>
+            <button
+              onClick={() => !isCron && onUpdate({ cron: DEFAULT_CRON })}
+              className={modeButtonClass(isCron)}
+            >
+              {t('schedule.modeCron')}
+            </button>
+          </div>
+
+          {isCron && (
+            <div className="space-y-1.5">
+              <input
+                type="text"
+                value={policy.cron}
+                placeholder={DEFAULT_CRON}
+                onChange={(e) => onUpdate({ cron: e.target.value })}
+                className={clsx(
+                  'w-full px-2 py-1.5 text-sm font-mono rounded border',
+                  'bg-bg-primary text-text-primary border-border',
+                  'focus:outline-none focus:border-accent focus:ring-1 focus:ring-accent/20',
+                )}
+              />
+              <p className="text-xs text-text-muted">{t('schedule.cronHint')}</p>
+            </div>
+          )}
+
           {/* 重复日期选择 */}
-          <div className="space-y-1.5">
+          <div className={clsx('space-y-1.5', isCron && 'hidden')}>
             <label className="text-xs font-medium text-text-secondary">
               {t('schedule.repeatDays')}
             </label>
@@ -203,7 +293,7 @@ function PolicyCard({
           </div>
 
           {/* 开始时间选择 */}
-          <div className="space-y-1.5">
+          <div className={clsx('space-y-1.5', isCron && 'hidden')}>
             <label className="text-xs font-medium text-text-secondary">
               {t('schedule.startTime')}
               <span className="text-text-muted font-normal ml-1">
@@ -238,13 +328,41 @@ function PolicyCard({
                 </button>
               ))}
             </div>
-            <p className="text-xs text-text-muted">{t('schedule.timeZoneHint')}</p>
+            {/* 整点后的分钟 */}
+            <div className="flex items-center gap-2 text-xs text-text-secondary">
+              <span>{t('schedule.minute')}</span>
+              <input
+                type="number"
+                min={0}
+                max={59}
+                value={minute}
+                onChange={(e) => {
+                  const value = parseInt(e.target.value, 10);
+                  if (!isNaN(value)) {
+                    onUpdate({ minute: Math.min(59, Math.max(0, value)) });
+                  }
+                }}
+                className="w-14 px-1.5 py-0.5 rounded border bg-bg-primary text-text-primary border-border focus:outline-none focus:border-accent"
+              />
+            </div>
           </div>
 
+          {/* 错过后补执行 */}
+          <label className="flex items-center gap-2 text-xs text-text-secondary cursor-pointer">
+            <input
+              type="checkbox"
+              checked={policy.catchUp ?? false}
+              onChange={(e) => onUpdate({ catchUp: e.target.checked })}
+              className="accent-accent"
+            />
+            <span>{t('schedule.catchUp')}</span>
+          </label>
+
           {/* 摘要显示 */}
-          <div className="pt-2 border-t border-border">
-            <p className="text-xs text-text-secondary">
-              {formatWeekdays()} · {formatHours()}
+          <div className="pt-2 border-t border-border space-y-1">
+            <p className="text-xs text-text-secondary">{summary}</p>
+            <p className={clsx('text-xs', previewError ? 'text-error' : 'text-text-muted')}>
+              {formatNextRuns()}
             </p>
           </div>
         </div>
@@ -253,8 +371,10 @@ function PolicyCard({
       {/* 未展开时显示简要信息 */}
       {!isExpanded && (
         <div className="px-3 pb-2">
-          <p className="text-xs text-text-muted truncate">
-            {formatWeekdays()} · {formatHours()}
+          <p
+            className={clsx('text-xs truncate', previewError ? 'text-error' : 'text-text-muted')}
+          >
+            {previewError ?? summary}
           </p>
         </div>
       )}
@@ -286,6 +406,8 @@ export function SchedulePanel({ instanceId, onClose }: SchedulePanelProps) {
   const instance = getActiveInstance();
   const policies = instance?.schedulePolicies || [];
   const anyEnabled = policies.some((p) => p.enabled);
+  const timeZone = instance?.scheduleTimeZone;
+  const timeZones = listTimeZones(timeZone);
 
   // 点击外部关闭面板
   useEffect(() => {
@@ -305,6 +427,7 @@ export function SchedulePanel({ instanceId, onClose }: SchedulePanelProps) {
       enabled: true,
       weekdays: [1, 2, 3, 4, 5], // 默认工作日
       hours: [8], // 默认早上8点
+      minute: 0,
     };
     updateInstance(instanceId, {
       schedulePolicies: [...policies, newPolicy],
@@ -389,6 +512,7 @@ export function SchedulePanel({ instanceId, onClose }: SchedulePanelProps) {
             <PolicyCard
               key={policy.id}
               policy={policy}
+              timeZone={timeZone}
               onUpdate={(updates) => handleUpdatePolicy(policy.id, updates)}
               onDelete={() => handleDeletePolicy(policy.id)}
               isExpanded={expandedPolicyId === policy.id}
@@ -417,6 +541,25 @@ export function SchedulePanel({ instanceId, onClose }: SchedulePanelProps) {
         </button>
       </div>
 
+      {/* 策略时间所在时区 */}
+      <div className="flex items-center gap-2 px-4 py-2 border-t border-border text-xs text-text-secondary">
+        <span>{t('schedule.timeZone')}</span>
+        <select
+          value={timeZone ?? ''}
+          onChange={(e) =>
+            updateInstance(instanceId, { scheduleTimeZone: e.target.value || undefined })
+          }
+          className="flex-1 min-w-0 px-1.5 py-0.5 rounded-md bg-bg-tertiary border border-border text-text-primary focus:outline-none focus:border-accent/60"
+        >
+          <option value="">{t('schedule.localTime')}</option>
+          {timeZones.map((zone) => (
+            <option key={zone} value={zone}>
+              {zone}
+            </option>
+          ))}
+        </select>
+      </div>
+
       {/* 提示信息 */}
       <div className="px-4 py-2 border-t border-border bg-bg-tertiary rounded-b-lg">
         <p className="text-xs text-text-muted">{t('schedule.hint')}</p>
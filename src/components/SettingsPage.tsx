@@ -55,6 +55,7 @@ import {
 import { maaService } from '@/services/maaService';
 import { ReleaseNotes, DownloadProgressBar } from './UpdateInfoCard';
 import { loggers } from '@/utils/logger';
+import { getDataDirs } from '@/utils/dataDirs';
 import { FrameRateSelector } from './FrameRateSelector';
 import { createProxySettings, shouldUseProxy } from '@/services/proxyService';
 import clsx from 'clsx';
@@ -124,6 +125,7 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
     setSaveDraw,
     tcpCompatMode,
     setTcpCompatMode,
+    maaCapabilities,
     downloadStatus,
     downloadProgress,
     setDownloadStatus,
@@ -139,6 +141,9 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
     setHotkeys,
   } = useAppStore();
 
+  // 旧版 MaaFramework 缺少 MaaAgentClientCreateTcp 时无法使用通信兼容模式
+  const tcpAgentUnsupported = maaCapabilities?.disabled_features.includes('tcp_agent') ?? false;
+
   // 获取强调色列表（包含自定义强调色）
   const accentColors = useMemo(
     () => getAccentInfoList(language, customAccents),
@@ -457,6 +462,8 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
   const [, setDebugLog] = useState<string[]>([]);
   const [mxuVersion, setMxuVersion] = useState<string | null>(null);
   const [maafwVersion, setMaafwVersion] = useState<string | null>(null);
+  const [reloadingMaafw, setReloadingMaafw] = useState(false);
+  const [maafwReloadError, setMaafwReloadError] = useState<string | null>(null);
   const [showCdk, setShowCdk] = useState(false);
   const [exeDir, setExeDir] = useState<string | null>(null);
   const [cwd, setCwd] = useState<string | null>(null);
@@ -872,7 +879,8 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
     try {
       const { openPath } = await import('@tauri-apps/plugin-opener');
       const { join } = await import('@tauri-apps/api/path');
-      const configPath = await join(basePath, 'config');
+      const dataDirs = await getDataDirs();
+      const configPath = dataDirs?.config_dir ?? (await join(basePath, 'config'));
       loggers.ui.info('打开配置目录:', configPath);
       await openPath(configPath);
     } catch (err) {
@@ -890,7 +898,8 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
     try {
       const { openPath } = await import('@tauri-apps/plugin-opener');
       const { join } = await import('@tauri-apps/api/path');
-      const logPath = await join(basePath, 'debug');
+      const dataDirs = await getDataDirs();
+      const logPath = dataDirs?.logs_dir ?? (await join(basePath, 'debug'));
       loggers.ui.info('打开日志目录:', logPath);
       await openPath(logPath);
     } catch (err) {
@@ -926,6 +935,24 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
     }
   };
 
+  // 重新加载 MaaFramework（替换 maafw 目录后无需重启）
+  const handleReloadMaafw = async () => {
+    if (!isTauri()) return;
+
+    setReloadingMaafw(true);
+    setMaafwReloadError(null);
+    try {
+      setMaafwVersion(await maaService.reloadLibrary());
+      addDebugLog('MaaFramework 已重新加载');
+    } catch (err) {
+      setMaafwVersion(null);
+      setMaafwReloadError(String(err));
+      addDebugLog(`重新加载 MaaFramework 失败: ${err}`);
+    } finally {
+      setReloadingMaafw(false);
+    }
+  };
+
   const projectName =
     resolveI18nText(projectInterface?.label, translations) || projectInterface?.name || 'MXU';
   const version = projectInterface?.version || '0.1.0';
@@ -1753,7 +1780,27 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
                       <span className="text-xs text-text-muted">({cacheEntryCount})</span>
                     )}
                   </button>
+                  {isTauri() && (
+                    <button
+                      onClick={handleReloadMaafw}
+                      disabled={reloadingMaafw}
+                      className="flex items-center gap-2 px-3 py-2 text-sm bg-bg-tertiary hover:bg-bg-hover rounded-lg transition-colors disabled:opacity-50"
+                      title={t('debug.reloadMaafwHint')}
+                    >
+                      {reloadingMaafw ? (
+                        <Loader2 className="w-4 h-4 animate-spin" />
+                      ) : (
+                        <RefreshCw className="w-4 h-4" />
+                      )}
+                      {t('debug.reloadMaafw')}
+                    </button>
+                  )}
                 </div>
+                {maafwReloadError && (
+                  <p className="text-xs text-error break-all">
+                    {t('debug.reloadMaafwFailed', { error: maafwReloadError })}
+                  </p>
+                )}
 
                 {/* 开发模式 */}
                 <div className="flex items-center justify-between pt-4 border-t border-border">
@@ -1811,14 +1858,20 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
                     <Network className="w-5 h-5 text-accent" />
                     <div>
                       <span className="font-medium text-text-primary">{t('debug.tcpCompatMode')}</span>
-                      <p className="text-xs text-text-muted mt-0.5">{t('debug.tcpCompatModeHint')}</p>
+                      <p className="text-xs text-text-muted mt-0.5">
+                        {tcpAgentUnsupported
+                          ? t('debug.tcpCompatModeUnsupported')
+                          : t('debug.tcpCompatModeHint')}
+                      </p>
                     </div>
                   </div>
                   <button
                     onClick={() => setTcpCompatMode(!tcpCompatMode)}
+                    disabled={tcpAgentUnsupported}
                     className={clsx(
                       'relative w-11 h-6 rounded-full transition-colors flex-shrink-0',
                       tcpCompatMode ? 'bg-accent' : 'bg-bg-active',
+                      tcpAgentUnsupported && 'opacity-50 cursor-not-allowed',
                     )}
                   >
                     <span
@@ -20,14 +20,15 @@ import {
   FileText,
   Link,
   AlertCircle,
+  Plus,
 } from 'lucide-react';
 import { useAppStore, type TaskRunStatus } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
 import { useResolvedContent } from '@/services/contentResolver';
-import { generateTaskPipelineOverride } from '@/utils';
 import { OptionEditor } from './OptionEditor';
 import { ContextMenu, useContextMenu, type MenuItem } from './ContextMenu';
 import type { SelectedTask } from '@/types/interface';
+import type { TaskCondition, TaskRunPolicy } from '@/types/maa';
 import { getInterfaceLangKey } from '@/i18n';
 import clsx from 'clsx';
 import { loggers } from '@/utils/logger';
@@ -78,6 +79,243 @@ function OptionPreviewTag({
   );
 }
 
+/** 重试与超时设置（界面以秒/分钟为单位，保存为毫秒） */
+function RunPolicyEditor({
+  policy,
+  disabled,
+  onChange,
+}: {
+  policy?: TaskRunPolicy;
+  disabled: boolean;
+  onChange: (policy: TaskRunPolicy) => void;
+}) {
+  const { t } = useTranslation();
+
+  const fields: {
+    key: keyof TaskRunPolicy;
+    label: string;
+    max: number;
+    scale: number;
+  }[] = [
+    { key: 'retry_count', label: t('taskItem.retryCount'), max: 10, scale: 1 },
+    { key: 'retry_delay_ms', label: t('taskItem.retryDelay'), max: 3600, scale: 1000 },
+    { key: 'timeout_ms', label: t('taskItem.timeout'), max: 1440, scale: 60000 },
+  ];
+
+  return (
+    <div className="space-y-1.5">
+      <div className="text-xs text-text-secondary">{t('taskItem.runPolicy')}</div>
+      <div className="flex flex-wrap items-center gap-x-4 gap-y-1.5 text-xs text-text-secondary">
+        {fields.map((field) => (
+          <label key={field.key} className="flex items-center gap-1.5">
+            <span>{field.label}</span>
+            <input
+              type="number"
+              min={0}
+              max={field.max}
+              value={Math.round((policy?.[field.key] ?? 0) / field.scale)}
+              disabled={disabled}
+              onChange={(e) => {
+                const value = parseInt(e.target.value, 10);
+                if (!Number.isNaN(value)) {
+                  const clamped = Math.min(field.max, Math.max(0, value));
+                  onChange({ ...policy, [field.key]: clamped * field.scale });
+                }
+              }}
+              className="w-14 px-1.5 py-0.5 rounded-md bg-bg-secondary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60 disabled:opacity-50 disabled:cursor-not-allowed"
+            />
+          </label>
+        ))}
+      </div>
+      <div className="text-[10px] text-text-muted">{t('taskItem.timeoutHint')}</div>
+    </div>
+  );
+}
+
+const conditionInputClass =
+  'px-1.5 py-0.5 rounded-md bg-bg-secondary border border-border text-text-primary focus:outline-none focus:ring-1 focus:ring-accent/60 focus:border-accent/60 disabled:opacity-50 disabled:cursor-not-allowed';
+
+/** 执行条件设置（由后端在提交任务前检查） */
+function TaskConditionEditor({
+  conditions,
+  tasks,
+  language,
+  disabled,
+  onChange,
+}: {
+  conditions: TaskCondition[];
+  /** 可作为前置任务的其他任务 */
+  tasks: { id: string; name: string }[];
+  language: string;
+  disabled: boolean;
+  onChange: (conditions: TaskCondition[]) => void;
+}) {
+  const { t } = useTranslation();
+
+  const conditionTypes: TaskCondition['type'][] = [
+    'after_task',
+    'time_window',
+    'weekdays',
+    'not_succeeded_today',
+  ];
+
+  // 2026-01-04 是周日，按 0-6 取得本地化的星期名称
+  const weekdayNames = useMemo(() => {
+    const format = new Intl.DateTimeFormat(language, { weekday: 'short' });
+    return Array.from({ length: 7 }, (_, day) => format.format(new Date(2026, 0, 4 + day)));
+  }, [language]);
+
+  const addCondition = (type: TaskCondition['type']) => {
+    const defaults: Record<TaskCondition['type'], TaskCondition> = {
+      after_task: { type: 'after_task', task: tasks[0]?.id ?? '', outcome: 'Succeeded' },
+      time_window: { type: 'time_window', start: '08:00', end: '12:00' },
+      weekdays: { type: 'weekdays', days: [1, 2, 3, 4, 5] },
+      not_succeeded_today: { type: 'not_succeeded_today' },
+    };
+    onChange([...conditions, defaults[type]]);
+  };
+
+  const updateCondition = (index: number, condition: TaskCondition) => {
+    onChange(conditions.map((item, i) => (i === index ? condition : item)));
+  };
+
+  const renderFields = (condition: TaskCondition, index: number) => {
+    switch (condition.type) {
+      case 'after_task':
+        return (
+          <>
+            <select
+              value={condition.task}
+              disabled={disabled}
+              onChange={(e) => updateCondition(index, { ...condition, task: e.target.value })}
+              className={clsx(conditionInputClass, 'flex-1 min-w-0')}
+            >
+              {tasks.map((task) => (
+                <option key={task.id} value={task.id}>
+                  {task.name}
+                </option>
+              ))}
+            </select>
+            <select
+              value={condition.outcome}
+              disabled={disabled}
+              onChange={(e) =>
+                updateCondition(index, {
+                  ...condition,
+                  outcome: e.target.value as 'Succeeded' | 'Failed',
+                })
+              }
+              className={conditionInputClass}
+            >
+              <option value="Succeeded">{t('taskItem.conditionSucceeded')}</option>
+              <option value="Failed">{t('taskItem.conditionFailed')}</option>
+            </select>
+          </>
+        );
+      case 'time_window':
+        return (
+          <>
+            <input
+              type="time"
+              value={condition.start}
+              disabled={disabled}
+              onChange={(e) =>
+                e.target.value && updateCondition(index, { ...condition, start: e.target.value })
+              }
+              className={conditionInputClass}
+            />
+            <span>-</span>
+            <input
+              type="time"
+              value={condition.end}
+              disabled={disabled}
+              onChange={(e) =>
+                e.target.value && updateCondition(index, { ...condition, end: e.target.value })
+              }
+              className={conditionInputClass}
+            />
+          </>
+        );
+      case 'weekdays':
+        return weekdayNames.map((name, day) => {
+          const selected = condition.days.includes(day);
+          return (
+            <button
+              key={day}
+              type="button"
+              disabled={disabled}
+              onClick={() =>
+                updateCondition(index, {
+                  ...condition,
+                  days: selected
+                    ? condition.days.filter((d) => d !== day)
+                    : [...condition.days, day].sort((a, b) => a - b),
+                })
+              }
+              className={clsx(
+                'px-1.5 py-0.5 rounded-md border text-[10px] disabled:opacity-50 disabled:cursor-not-allowed',
+                selected
+                  ? 'bg-accent/15 border-accent/60 text-accent'
+                  : 'border-border text-text-muted',
+              )}
+            >
+              {name}
+            </button>
+          );
+        });
+      default:
+        return null;
+    }
+  };
+
+  return (
+    <div className="space-y-1.5">
+      <div className="flex items-center justify-between">
+        <span className="text-xs text-text-secondary">{t('taskItem.conditions')}</span>
+        <label className="flex items-center gap-1 text-xs text-text-muted">
+          <Plus className="w-3 h-3" />
+          <select
+            value=""
+            disabled={disabled}
+            onChange={(e) => addCondition(e.target.value as TaskCondition['type'])}
+            className="bg-transparent text-text-muted focus:outline-none disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            <option value="" disabled>
+              {t('taskItem.conditionAdd')}
+            </option>
+            {conditionTypes.map((type) => (
+              <option key={type} value={type} disabled={type === 'after_task' && !tasks.length}>
+                {t(`taskItem.conditionTypes.${type}`)}
+              </option>
+            ))}
+          </select>
+        </label>
+      </div>
+      {conditions.map((condition, index) => (
+        <div
+          key={index}
+          className="flex flex-wrap items-center gap-1.5 text-xs text-text-secondary"
+        >
+          <span className="flex-shrink-0">{t(`taskItem.conditionTypes.${condition.type}`)}</span>
+          {renderFields(condition, index)}
+          <button
+            type="button"
+            disabled={disabled}
+            onClick={() => onChange(conditions.filter((_, i) => i !== index))}
+            className="ml-auto p-0.5 rounded hover:bg-bg-hover disabled:opacity-50 disabled:cursor-not-allowed"
+            title={t('common.delete')}
+          >
+            <X className="w-3 h-3 text-text-muted" />
+          </button>
+        </div>
+      ))}
+      {conditions.length > 0 && (
+        <div className="text-[10px] text-text-muted">{t('taskItem.conditionHint')}</div>
+      )}
+    </div>
+  );
+}
+
 interface TaskItemProps {
   instanceId: string;
   task: SelectedTask;
@@ -148,6 +386,8 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
     toggleTaskExpanded,
     removeTaskFromInstance,
     renameTask,
+    setTaskRunPolicy,
+    setTaskConditions,
     duplicateTask,
     moveTaskUp,
     moveTaskDown,
@@ -159,11 +399,12 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
     showOptionPreview,
     instanceTaskRunStatus,
     instances,
-    findMaaTaskIdBySelectedTaskId,
+    findQueuedTask,
     basePath,
     interfaceTranslations,
     animatingTaskIds,
     removeAnimatingTaskId,
+    maaCapabilities,
   } = useAppStore();
 
   // 获取任务运行状态
@@ -212,7 +453,11 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
   const isCompact = isInstanceRunning && !task.enabled;
 
   // 判断是否可以编辑选项（只有 pending 或 idle 状态的任务可以编辑）
-  const canEditOptions = taskRunStatus === 'idle' || taskRunStatus === 'pending';
+  // 缺少 MaaTaskerOverridePipeline 时，已提交的 pending 任务无法再修改
+  const overrideDisabled =
+    maaCapabilities?.disabled_features.includes('override_pipeline') ?? false;
+  const canEditOptions =
+    taskRunStatus === 'idle' || (taskRunStatus === 'pending' && !overrideDisabled);
 
   // 判断是否可以调整顺序/删除（实例运行时禁用）
   const canReorder = !isInstanceRunning;
@@ -225,7 +470,7 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
   const isAnimating = animatingTaskIds.includes(task.id);
   const animationElementRef = useRef<HTMLDivElement | null>(null);
 
-  // 当选项值变化且任务状态为 pending 时，调用 overridePipeline 更新任务配置
+  // 当选项值变化且任务状态为 pending 时，更新后端队列中该任务的 Pipeline 覆盖
   useEffect(() => {
     const currentOptionValues = JSON.stringify(task.optionValues);
 
@@ -243,23 +488,27 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
     // 更新 ref
     prevOptionValuesRef.current = currentOptionValues;
 
-    // 只有 pending 状态的任务才需要调用 overridePipeline
+    // 只有 pending 状态的任务才需要更新
     if (taskRunStatus !== 'pending') {
       return;
     }
 
-    // 获取对应的 maaTaskId
-    const maaTaskId = findMaaTaskIdBySelectedTaskId(instanceId, task.id);
-    if (maaTaskId === null) {
+    // 获取队列中对应的任务
+    const queuedTask = findQueuedTask(instanceId, task.id);
+    if (!queuedTask || queuedTask.status !== 'Pending') {
       return;
     }
 
     // 生成新的 pipeline override 并调用后端
-    const pipelineOverride = generateTaskPipelineOverride(task, projectInterface);
-    maaService.overridePipeline(instanceId, maaTaskId, pipelineOverride).catch((err) => {
-      loggers.task.error('Failed to override pipeline:', err);
-    });
-  }, [task.optionValues, taskRunStatus, instanceId, task.id, projectInterface]);
+    maaService
+      .buildPipelineOverride(task.taskName, task.optionValues)
+      .then((pipelineOverride) =>
+        maaService.updateQueuedTaskOverride(instanceId, queuedTask.id, pipelineOverride),
+      )
+      .catch((err) => {
+        loggers.task.error('Failed to override pipeline:', err);
+      });
+  }, [task.optionValues, taskRunStatus, instanceId, task.id, task.taskName]);
 
   const { state: menuState, show: showMenu, hide: hideMenu } = useContextMenu();
 
@@ -317,8 +566,23 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
   const hasOptions = taskDef.option && taskDef.option.length > 0;
   // 判断是否有描述内容（包括正在加载的情况）
   const hasDescription = !!resolvedDescription.html || resolvedDescription.loading;
-  // 有选项或有描述时都可以展开
-  const canExpand = hasOptions || hasDescription;
+  // 展开面板中总有重试与超时设置，这里只判断其上方是否还有内容
+  const hasPanelContent = hasOptions || hasDescription || isIncompatible;
+
+  // 被跳过时显示执行条件不满足的原因
+  const skipReason =
+    taskRunStatus === 'skipped' ? (findQueuedTask(instanceId, task.id)?.skip_reason ?? '') : '';
+
+  // 可作为前置条件的其他任务
+  const conditionTasks = (instance?.selectedTasks ?? [])
+    .filter((item) => item.id !== task.id)
+    .map((item) => {
+      const def = projectInterface?.task.find((d) => d.name === item.taskName);
+      return {
+        id: item.id,
+        name: item.customName || resolveI18nText(def?.label, langKey) || item.taskName,
+      };
+    });
 
   // 生成选项预览信息（最多显示3个）
   const optionPreviews = useMemo(() => {
@@ -456,18 +720,12 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
           disabled: isInstanceRunning,
           onClick: () => toggleTaskEnabled(instanceId, task.id),
         },
-        ...(canExpand
-          ? [
-              {
-                id: 'expand',
-                label: task.expanded
-                  ? t('contextMenu.collapseOptions')
-                  : t('contextMenu.expandOptions'),
-                icon: task.expanded ? ChevronUp : ChevronDown,
-                onClick: () => toggleTaskExpanded(instanceId, task.id),
-              },
-            ]
-          : []),
+        {
+          id: 'expand',
+          label: task.expanded ? t('contextMenu.collapseOptions') : t('contextMenu.expandOptions'),
+          icon: task.expanded ? ChevronUp : ChevronDown,
+          onClick: () => toggleTaskExpanded(instanceId, task.id),
+        },
         { id: 'divider-2', label: '', divider: true },
         {
           id: 'move-up',
@@ -514,7 +772,6 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
       t,
       task,
       instanceId,
-      canExpand,
       getActiveInstance,
       duplicateTask,
       toggleTaskEnabled,
@@ -542,6 +799,8 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
         return 'bg-success';
       case 'failed':
         return 'bg-error';
+      case 'skipped':
+        return 'bg-warning';
       default:
         return 'bg-transparent';
     }
@@ -603,7 +862,11 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
             'absolute left-0 top-0 bottom-0 w-1.5 rounded-l-lg transition-colors',
             getStatusIndicatorClass(),
           )}
-          title={t(`taskItem.status.${taskRunStatus}`)}
+          title={
+            taskRunStatus === 'skipped'
+              ? `${t('taskItem.status.skipped')}: ${skipReason}`
+              : t(`taskItem.status.${taskRunStatus}`)
+          }
         />
       )}
 
@@ -709,56 +972,44 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
                 )}
               </div>
 
-              {/* 不带选项的任务：直接显示不兼容警告 */}
-              {!canExpand && isIncompatible && (
-                <div className="flex-1 flex items-center gap-1.5 mx-2 overflow-hidden">
-                  <span className="inline-flex items-center gap-1 px-1.5 py-0.5 text-xs text-warning">
-                    <AlertCircle className="w-3 h-3" />
-                    {incompatibleReason}
-                  </span>
-                </div>
-              )}
-
               {/* 展开/折叠点击区域（包含选项预览） */}
-              {canExpand && (
-                <div
-                  onClick={() => toggleTaskExpanded(instanceId, task.id)}
-                  className="flex-1 flex items-center self-stretch min-h-[28px] cursor-pointer"
-                  title={task.expanded ? t('taskItem.collapse') : t('taskItem.expand')}
-                >
-                  {/* 选项预览标签 - 未展开时显示：不兼容时显示警告，否则显示选项预览 */}
-                  {!task.expanded && (
-                    <div className="flex-1 flex items-center gap-1.5 mx-2 overflow-hidden">
-                      {isIncompatible ? (
-                        <span className="inline-flex items-center gap-1 px-1.5 py-0.5 text-xs text-warning">
-                          <AlertCircle className="w-3 h-3" />
-                          {incompatibleReason}
-                        </span>
-                      ) : (
-                        showOptionPreview &&
-                        optionPreviews.length > 0 &&
-                        optionPreviews.map((preview) => (
-                          <OptionPreviewTag
-                            key={preview.key}
-                            label={preview.label}
-                            value={preview.value}
-                            type={preview.type}
-                          />
-                        ))
-                      )}
-                    </div>
-                  )}
-                  {/* 展开/折叠箭头 */}
-                  <div className="flex items-center justify-end pl-2 ml-auto">
-                    <ChevronRight
-                      className={clsx(
-                        'w-4 h-4 text-text-secondary transition-transform duration-150 ease-out',
-                        task.expanded && 'rotate-90',
-                      )}
-                    />
+              <div
+                onClick={() => toggleTaskExpanded(instanceId, task.id)}
+                className="flex-1 flex items-center self-stretch min-h-[28px] cursor-pointer"
+                title={task.expanded ? t('taskItem.collapse') : t('taskItem.expand')}
+              >
+                {/* 选项预览标签 - 未展开时显示：不兼容时显示警告，否则显示选项预览 */}
+                {!task.expanded && (
+                  <div className="flex-1 flex items-center gap-1.5 mx-2 overflow-hidden">
+                    {isIncompatible ? (
+                      <span className="inline-flex items-center gap-1 px-1.5 py-0.5 text-xs text-warning">
+                        <AlertCircle className="w-3 h-3" />
+                        {incompatibleReason}
+                      </span>
+                    ) : (
+                      showOptionPreview &&
+                      optionPreviews.length > 0 &&
+                      optionPreviews.map((preview) => (
+                        <OptionPreviewTag
+                          key={preview.key}
+                          label={preview.label}
+                          value={preview.value}
+                          type={preview.type}
+                        />
+                      ))
+                    )}
                   </div>
+                )}
+                {/* 展开/折叠箭头 */}
+                <div className="flex items-center justify-end pl-2 ml-auto">
+                  <ChevronRight
+                    className={clsx(
+                      'w-4 h-4 text-text-secondary transition-transform duration-150 ease-out',
+                      task.expanded && 'rotate-90',
+                    )}
+                  />
                 </div>
-              )}
+              </div>
             </>
           )}
         </div>
@@ -778,57 +1029,73 @@ export function TaskItem({ instanceId, task }: TaskItemProps) {
         )}
       </div>
 
-      {/* 展开面板（描述和/或选项）- 使用 grid 动画实现平滑展开/折叠 */}
-      {canExpand && (
-        <div
-          className="grid transition-[grid-template-rows] duration-150 ease-out"
-          style={{ gridTemplateRows: task.expanded ? '1fr' : '0fr' }}
-        >
-          <div className="overflow-hidden min-h-0">
-            <div className="border-t border-border bg-bg-tertiary p-3">
-              {/* 任务描述 */}
-              {hasDescription && (
-                <div className={hasOptions || isIncompatible ? 'mb-3' : ''}>
-                  <DescriptionContent
-                    html={resolvedDescription.html}
-                    loading={resolvedDescription.loading}
-                    type={resolvedDescription.type}
-                    loaded={resolvedDescription.loaded}
-                    error={resolvedDescription.error}
+      {/* 展开面板（描述、选项和重试设置）- 使用 grid 动画实现平滑展开/折叠 */}
+      <div
+        className="grid transition-[grid-template-rows] duration-150 ease-out"
+        style={{ gridTemplateRows: task.expanded ? '1fr' : '0fr' }}
+      >
+        <div className="overflow-hidden min-h-0">
+          <div className="border-t border-border bg-bg-tertiary p-3">
+            {/* 任务描述 */}
+            {hasDescription && (
+              <div className={hasOptions || isIncompatible ? 'mb-3' : ''}>
+                <DescriptionContent
+                  html={resolvedDescription.html}
+                  loading={resolvedDescription.loading}
+                  type={resolvedDescription.type}
+                  loaded={resolvedDescription.loaded}
+                  error={resolvedDescription.error}
+                />
+              </div>
+            )}
+            {/* 不兼容提示 - 独立于选项列表显示 */}
+            {isIncompatible && (
+              <div
+                className={clsx(
+                  'flex items-center gap-1.5 px-2 py-1.5 rounded-md bg-warning/10 text-warning text-xs',
+                  hasOptions && 'mb-3',
+                )}
+              >
+                <AlertCircle className="w-3.5 h-3.5 flex-shrink-0" />
+                <span>{incompatibleReason}</span>
+              </div>
+            )}
+            {/* 选项列表 - 仅在有选项时显示 */}
+            {hasOptions && (
+              <div className="space-y-3">
+                {taskDef.option?.map((optionKey) => (
+                  <OptionEditor
+                    key={optionKey}
+                    instanceId={instanceId}
+                    taskId={task.id}
+                    optionKey={optionKey}
+                    value={task.optionValues[optionKey]}
+                    disabled={!canEditOptions || isIncompatible}
                   />
-                </div>
-              )}
-              {/* 不兼容提示 - 独立于选项列表显示 */}
-              {isIncompatible && (
-                <div
-                  className={clsx(
-                    'flex items-center gap-1.5 px-2 py-1.5 rounded-md bg-warning/10 text-warning text-xs',
-                    hasOptions && 'mb-3',
-                  )}
-                >
-                  <AlertCircle className="w-3.5 h-3.5 flex-shrink-0" />
-                  <span>{incompatibleReason}</span>
-                </div>
-              )}
-              {/* 选项列表 - 仅在有选项时显示 */}
-              {hasOptions && (
-                <div className="space-y-3">
-                  {taskDef.option?.map((optionKey) => (
-                    <OptionEditor
-                      key={optionKey}
-                      instanceId={instanceId}
-                      taskId={task.id}
-                      optionKey={optionKey}
-                      value={task.optionValues[optionKey]}
-                      disabled={!canEditOptions || isIncompatible}
-                    />
-                  ))}
-                </div>
-              )}
+                ))}
+              </div>
+            )}
+            {/* 重试与超时设置 - 下次启动时生效 */}
+            <div className={clsx(hasPanelContent && 'mt-3 pt-3 border-t border-border')}>
+              <RunPolicyEditor
+                policy={task.runPolicy}
+                disabled={isInstanceRunning}
+                onChange={(policy) => setTaskRunPolicy(instanceId, task.id, policy)}
+              />
+            </div>
+            {/* 执行条件 - 下次启动时生效 */}
+            <div className="mt-3 pt-3 border-t border-border">
+              <TaskConditionEditor
+                conditions={task.conditions ?? []}
+                tasks={conditionTasks}
+                language={language}
+                disabled={isInstanceRunning}
+                onChange={(conditions) => setTaskConditions(instanceId, task.id, conditions)}
+              />
             </div>
           </div>
         </div>
-      )}
+      </div>
 
       {/* 右键菜单 */}
       {menuState.isOpen && (
@@ -1,4 +1,4 @@
-import { useState, useEffect, useRef, useCallback } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
 import {
   CheckSquare,
@@ -7,20 +7,27 @@ import {
   ChevronsDownUp,
   Plus,
   Play,
+  Pause,
   StopCircle,
   Loader2,
   Clock,
+  Repeat,
+  CalendarCheck,
+  Power,
+  Rocket,
   ShieldAlert,
 } from 'lucide-react';
 import { useAppStore } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
 import clsx from 'clsx';
-import { loggers, generateTaskPipelineOverride } from '@/utils';
-import type { TaskConfig, AgentConfig, ControllerConfig } from '@/types/maa';
+import { loggers, buildAgentConfig, buildTaskConfigs, resolveResourcePaths } from '@/utils';
+import type { ControllerConfig } from '@/types/maa';
 import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
 import { SchedulePanel } from './SchedulePanel';
-import type { Instance } from '@/types/interface';
-import { resolveI18nText } from '@/services/contentResolver';
+import { RunLoopPanel } from './RunLoopPanel';
+import { DailyLedgerPanel } from './DailyLedgerPanel';
+import { PostRunPanel, usePostRunCountdown } from './PostRunPanel';
+import { PreRunPanel } from './PreRunPanel';
 import { getInterfaceLangKey } from '@/i18n';
 
 const log = loggers.task;
@@ -31,12 +38,11 @@ interface ToolbarProps {
 }
 
 // 自动连接阶段
-type AutoConnectPhase = 'idle' | 'searching' | 'connecting' | 'loading_resource';
+type AutoConnectPhase = 'idle' | 'pre_run' | 'searching' | 'connecting' | 'loading_resource';
 
 export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   const { t } = useTranslation();
   const {
-    instances,
     getActiveInstance,
     selectAllTasks,
     collapseAllTasks,
@@ -45,36 +51,24 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     basePath,
     instanceConnectionStatus,
     instanceResourceLoaded,
+    instancePaused,
+    instanceTaskRun,
+    preRunProgress,
     setInstanceCurrentTaskId,
     setInstanceTaskStatus,
     setInstanceConnectionStatus,
     setInstanceResourceLoaded,
     selectedController,
     selectedResource,
-    // 任务运行状态管理
-    setTaskRunStatus,
-    setAllTasksRunStatus,
-    registerMaaTaskMapping,
-    findSelectedTaskIdByMaaTaskId,
+    // 任务运行状态管理（运行中的状态由后端任务队列推导）
     clearTaskRunStatus,
-    // 任务队列管理
-    instancePendingTaskIds,
-    instanceCurrentTaskIndex,
-    setPendingTaskIds,
-    setCurrentTaskIndex: setCurrentTaskIndexStore,
-    advanceCurrentTaskIndex,
-    clearPendingTasks,
     // 定时执行状态
     scheduleExecutions,
-    setScheduleExecution,
     clearScheduleExecution,
     // 回调 ID 映射
     registerCtrlIdName,
     registerResIdName,
-    registerTaskIdName,
     registerEntryTaskName,
-    // 日志
-    addLog,
     // 添加任务面板
     setShowAddTaskPanel,
     // 国际化
@@ -86,7 +80,12 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
 
   const [isStarting, setIsStarting] = useState(false);
   const [isStopping, setIsStopping] = useState(false);
+  const [isPausing, setIsPausing] = useState(false);
   const [showSchedulePanel, setShowSchedulePanel] = useState(false);
+  const [showRunLoopPanel, setShowRunLoopPanel] = useState(false);
+  const [showLedgerPanel, setShowLedgerPanel] = useState(false);
+  const [showPreRunPanel, setShowPreRunPanel] = useState(false);
+  const [showPostRunPanel, setShowPostRunPanel] = useState(false);
 
   // 自动连接状态
   const [autoConnectPhase, setAutoConnectPhase] = useState<AutoConnectPhase>('idle');
@@ -101,6 +100,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   const pendingResIdsRef = useRef<Set<number>>(new Set());
 
   const instance = getActiveInstance();
+  const postRunRemaining = usePostRunCountdown(instance?.id);
   const tasks = instance?.selectedTasks || [];
   const allEnabled = tasks.length > 0 && tasks.every((t) => t.enabled);
   const anyExpanded = tasks.some((t) => t.expanded);
@@ -112,12 +112,9 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   // 检查是否可以运行
   const instanceId = instance?.id || '';
 
-  // 任务队列状态（从 store 获取）
-  const pendingTaskIds = instancePendingTaskIds[instanceId] || [];
-  const currentTaskIndex = instanceCurrentTaskIndex[instanceId] || 0;
-  const runningInstanceIdRef = useRef<string | null>(null);
   const isConnected = instanceConnectionStatus[instanceId] === 'Connected';
   const isResourceLoaded = instanceResourceLoaded[instanceId] || false;
+  const isPaused = instancePaused[instanceId] || false;
 
   // 检查是否有保存的设备和资源配置
   const currentControllerName =
@@ -140,122 +137,6 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     (isConnected && isResourceLoaded && tasks.some((t) => t.enabled)) ||
     (hasSavedDeviceConfig && currentResource && tasks.some((t) => t.enabled));
 
-  // 监听任务完成回调
-  useEffect(() => {
-    if (pendingTaskIds.length === 0) return;
-
-    const currentTaskId = pendingTaskIds[currentTaskIndex];
-    if (currentTaskId === undefined) return;
-
-    let unlisten: (() => void) | null = null;
-
-    maaService
-      .onCallback((message, details) => {
-        if (details.task_id !== currentTaskId) return;
-
-        const runningInstanceId = runningInstanceIdRef.current;
-        if (!runningInstanceId) return;
-
-        if (message === 'Tasker.Task.Succeeded') {
-          log.info(`任务 ${currentTaskIndex + 1}/${pendingTaskIds.length} 完成`);
-
-          // 更新当前任务状态为成功
-          const selectedTaskId = findSelectedTaskIdByMaaTaskId(runningInstanceId, currentTaskId);
-          if (selectedTaskId) {
-            setTaskRunStatus(runningInstanceId, selectedTaskId, 'succeeded');
-          }
-
-          // 检查是否还有更多任务
-          if (currentTaskIndex + 1 < pendingTaskIds.length) {
-            // 移动到下一个任务
-            advanceCurrentTaskIndex(runningInstanceId);
-            const nextIndex = currentTaskIndex + 1;
-            setInstanceCurrentTaskId(runningInstanceId, pendingTaskIds[nextIndex]);
-
-            // 将下一个任务设为 running
-            const nextSelectedTaskId = findSelectedTaskIdByMaaTaskId(
-              runningInstanceId,
-              pendingTaskIds[nextIndex],
-            );
-            if (nextSelectedTaskId) {
-              setTaskRunStatus(runningInstanceId, nextSelectedTaskId, 'running');
-            }
-          } else {
-            // 所有任务完成
-            log.info('所有任务执行完成');
-
-            // 停止 Agent（如果有）
-            if (projectInterface?.agent) {
-              maaService.stopAgent(runningInstanceId).catch(() => {});
-            }
-
-            setInstanceTaskStatus(runningInstanceId, 'Succeeded');
-            updateInstance(runningInstanceId, { isRunning: false });
-            setInstanceCurrentTaskId(runningInstanceId, null);
-            clearPendingTasks(runningInstanceId);
-            runningInstanceIdRef.current = null;
-          }
-        } else if (message === 'Tasker.Task.Failed') {
-          log.error('任务执行失败, task_id:', currentTaskId);
-
-          // 更新当前任务状态为失败
-          const selectedTaskId = findSelectedTaskIdByMaaTaskId(runningInstanceId, currentTaskId);
-          if (selectedTaskId) {
-            setTaskRunStatus(runningInstanceId, selectedTaskId, 'failed');
-          }
-
-          // 检查是否还有更多任务（失败的任务不阻止后续任务执行）
-          if (currentTaskIndex + 1 < pendingTaskIds.length) {
-            // 移动到下一个任务
-            advanceCurrentTaskIndex(runningInstanceId);
-            const nextIndex = currentTaskIndex + 1;
-            setInstanceCurrentTaskId(runningInstanceId, pendingTaskIds[nextIndex]);
-
-            // 将下一个任务设为 running
-            const nextSelectedTaskId = findSelectedTaskIdByMaaTaskId(
-              runningInstanceId,
-              pendingTaskIds[nextIndex],
-            );
-            if (nextSelectedTaskId) {
-              setTaskRunStatus(runningInstanceId, nextSelectedTaskId, 'running');
-            }
-          } else {
-            // 所有任务执行完毕（至少有一个失败）
-            log.info('所有任务执行完毕（有任务失败）');
-
-            // 停止 Agent（如果有）
-            if (projectInterface?.agent) {
-              maaService.stopAgent(runningInstanceId).catch(() => {});
-            }
-
-            setInstanceTaskStatus(runningInstanceId, 'Failed');
-            updateInstance(runningInstanceId, { isRunning: false });
-            setInstanceCurrentTaskId(runningInstanceId, null);
-            clearPendingTasks(runningInstanceId);
-            runningInstanceIdRef.current = null;
-          }
-        }
-      })
-      .then((fn) => {
-        unlisten = fn;
-      });
-
-    return () => {
-      if (unlisten) unlisten();
-    };
-  }, [
-    pendingTaskIds,
-    currentTaskIndex,
-    projectInterface?.agent,
-    setInstanceCurrentTaskId,
-    setInstanceTaskStatus,
-    updateInstance,
-    findSelectedTaskIdByMaaTaskId,
-    setTaskRunStatus,
-    advanceCurrentTaskIndex,
-    clearPendingTasks,
-  ]);
-
   const handleSelectAll = () => {
     if (!instance) return;
     selectAllTasks(instance.id, !allEnabled);
@@ -422,10 +303,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     log.info('加载资源...');
 
     try {
-      const resourcePaths = currentResource.path.map((p) => {
-        const cleanPath = p.replace(/^\.\//, '').replace(/^\.\\/, '');
-        return `${basePath}/${cleanPath}`;
-      });
+      const resourcePaths = resolveResourcePaths(currentResource, basePath);
 
       const resIds = await maaService.loadResource(instanceId, resourcePaths);
       pendingResIdsRef.current = new Set(resIds);
@@ -473,423 +351,6 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     }
   };
 
-  /**
-   * 为指定实例启动任务（可由定时任务调用）
-   * @param targetInstance 目标实例
-   * @param schedulePolicyName 定时策略名称（可选，用于标记定时执行）
-   * @returns 是否成功启动
-   */
-  const startTasksForInstance = useCallback(
-    async (targetInstance: Instance, schedulePolicyName?: string): Promise<boolean> => {
-      const targetId = targetInstance.id;
-      const targetTasks = targetInstance.selectedTasks || [];
-
-      // 检查是否有启用的任务
-      const enabledTasks = targetTasks.filter((t) => t.enabled);
-      if (enabledTasks.length === 0) {
-        log.warn(`实例 ${targetInstance.name} 没有启用的任务`);
-        return false;
-      }
-
-      // 检查是否正在运行
-      if (targetInstance.isRunning) {
-        log.warn(`实例 ${targetInstance.name} 正在运行中`);
-        return false;
-      }
-
-      // 获取控制器和资源配置
-      const controllerName = selectedController[targetId] || projectInterface?.controller[0]?.name;
-      const resourceName = selectedResource[targetId] || projectInterface?.resource[0]?.name;
-      const controller = projectInterface?.controller.find((c) => c.name === controllerName);
-      const resource = projectInterface?.resource.find((r) => r.name === resourceName);
-      const savedDevice = targetInstance.savedDevice;
-
-      // 检查是否有保存的设备配置
-      const hasSavedDevice = Boolean(
-        savedDevice &&
-        (savedDevice.adbDeviceName || savedDevice.windowName || savedDevice.playcoverAddress),
-      );
-
-      const isTargetConnected = instanceConnectionStatus[targetId] === 'Connected';
-      const isTargetResourceLoaded = instanceResourceLoaded[targetId] || false;
-
-      // 判断是否可以运行
-      const canStartTask =
-        (isTargetConnected && isTargetResourceLoaded) || (hasSavedDevice && resource);
-
-      if (!canStartTask) {
-        log.warn(`实例 ${targetInstance.name} 无法启动：未连接且没有保存的设备配置`);
-        return false;
-      }
-
-      try {
-        // 如果未连接，尝试自动连接
-        if (!isTargetConnected && hasSavedDevice && controller && savedDevice) {
-          log.info(`实例 ${targetInstance.name}: 自动连接设备...`);
-
-          await ensureMaaInitialized();
-          await maaService.createInstance(targetId).catch(() => {});
-
-          let config: ControllerConfig | null = null;
-          const controllerType = controller.type;
-
-          if (controllerType === 'Adb' && savedDevice.adbDeviceName) {
-            const devices = await maaService.findAdbDevices();
-            const matchedDevice = devices.find((d) => d.name === savedDevice.adbDeviceName);
-            if (!matchedDevice) {
-              log.warn(`实例 ${targetInstance.name}: 未找到设备 ${savedDevice.adbDeviceName}`);
-              return false;
-            }
-            config = {
-              type: 'Adb',
-              adb_path: matchedDevice.adb_path,
-              address: matchedDevice.address,
-              screencap_methods: matchedDevice.screencap_methods,
-              input_methods: matchedDevice.input_methods,
-              config: matchedDevice.config,
-            };
-          } else if (
-            (controllerType === 'Win32' || controllerType === 'Gamepad') &&
-            savedDevice.windowName
-          ) {
-            const classRegex = controller.win32?.class_regex || controller.gamepad?.class_regex;
-            const windowRegex = controller.win32?.window_regex || controller.gamepad?.window_regex;
-            const windows = await maaService.findWin32Windows(classRegex, windowRegex);
-            const matchedWindow = windows.find((w) => w.window_name === savedDevice.windowName);
-            if (!matchedWindow) {
-              log.warn(`实例 ${targetInstance.name}: 未找到窗口 ${savedDevice.windowName}`);
-              return false;
-            }
-            if (controllerType === 'Win32') {
-              config = {
-                type: 'Win32',
-                handle: matchedWindow.handle,
-                screencap_method: parseWin32ScreencapMethod(controller.win32?.screencap || ''),
-                mouse_method: parseWin32InputMethod(controller.win32?.mouse || ''),
-                keyboard_method: parseWin32InputMethod(controller.win32?.keyboard || ''),
-              };
-            } else {
-              config = {
-                type: 'Gamepad',
-                handle: matchedWindow.handle,
-              };
-            }
-          } else if (controllerType === 'PlayCover' && savedDevice.playcoverAddress) {
-            config = {
-              type: 'PlayCover',
-              address: savedDevice.playcoverAddress,
-            };
-          }
-
-          if (!config) {
-            log.warn(`实例 ${targetInstance.name}: 无法构建控制器配置`);
-            return false;
-          }
-
-          const ctrlId = await maaService.connectController(targetId, config);
-
-          // 注册 ctrl_id 与设备名/类型的映射
-          let deviceName = '';
-          let targetType: 'device' | 'window' = 'device';
-          if (savedDevice?.adbDeviceName) {
-            deviceName = savedDevice.adbDeviceName;
-            targetType = 'device';
-          } else if (savedDevice?.windowName) {
-            deviceName = savedDevice.windowName;
-            targetType = 'window';
-          } else if (savedDevice?.playcoverAddress) {
-            deviceName = savedDevice.playcoverAddress;
-            targetType = 'device';
-          }
-          registerCtrlIdName(ctrlId, deviceName, targetType);
-
-          // 等待连接完成
-          const connectResult = await new Promise<boolean>((resolve) => {
-            const timeout = setTimeout(() => resolve(false), 30000);
-            maaService.onCallback((message, details) => {
-              if (details.ctrl_id !== ctrlId) return;
-              clearTimeout(timeout);
-              if (message === 'Controller.Action.Succeeded') {
-                setInstanceConnectionStatus(targetId, 'Connected');
-                resolve(true);
-              } else {
-                resolve(false);
-              }
-            });
-          });
-
-          if (!connectResult) {
-            log.warn(`实例 ${targetInstance.name}: 连接设备失败`);
-            return false;
-          }
-        }
-
-        // 如果资源未加载，尝试自动加载
-        if (!instanceResourceLoaded[targetId] && resource) {
-          log.info(`实例 ${targetInstance.name}: 加载资源...`);
-
-          const resourcePaths = resource.path.map((p) => {
-            const cleanPath = p.replace(/^\.\//, '').replace(/^\.\\/, '');
-            return `${basePath}/${cleanPath}`;
-          });
-
-          const resIds = await maaService.loadResource(targetId, resourcePaths);
-
-          // 注册 res_id 与资源名的映射
-          const resourceName = resource.label || resource.name;
-          resIds.forEach((resId) => {
-            registerResIdName(resId, resourceName);
-          });
-
-          // 等待资源加载完成
-          const loadResult = await new Promise<boolean>((resolve) => {
-            const timeout = setTimeout(() => resolve(false), 60000);
-            let remaining = new Set(resIds);
-
-            maaService.onCallback((message, details) => {
-              if (details.res_id === undefined || !remaining.has(details.res_id)) return;
-              if (message === 'Resource.Loading.Succeeded') {
-                remaining.delete(details.res_id);
-                if (remaining.size === 0) {
-                  clearTimeout(timeout);
-                  setInstanceResourceLoaded(targetId, true);
-                  resolve(true);
-                }
-              } else if (message === 'Resource.Loading.Failed') {
-                clearTimeout(timeout);
-                resolve(false);
-              }
-            });
-          });
-
-          if (!loadResult) {
-            log.warn(`实例 ${targetInstance.name}: 资源加载失败`);
-            return false;
-          }
-        }
-
-        log.info(`实例 ${targetInstance.name}: 开始执行任务, 数量:`, enabledTasks.length);
-
-        // 构建任务配置列表，同时预注册 entry -> taskName 映射（解决时序问题）
-        const taskConfigs: TaskConfig[] = [];
-        for (const selectedTask of enabledTasks) {
-          const taskDef = projectInterface?.task.find((t) => t.name === selectedTask.taskName);
-          if (!taskDef) continue;
-          taskConfigs.push({
-            entry: taskDef.entry,
-            pipeline_override: generateTaskPipelineOverride(selectedTask, projectInterface),
-          });
-          // 预注册 entry -> taskName 映射，确保回调时能找到任务名
-          const taskDisplayName =
-            selectedTask.customName ||
-            resolveI18nText(taskDef.label, translations) ||
-            selectedTask.taskName;
-          registerEntryTaskName(taskDef.entry, taskDisplayName);
-        }
-
-        if (taskConfigs.length === 0) {
-          log.warn(`实例 ${targetInstance.name}: 没有可执行的任务`);
-          return false;
-        }
-
-        // 准备 Agent 配置
-        let agentConfig: AgentConfig | undefined;
-        if (projectInterface?.agent) {
-          agentConfig = {
-            child_exec: projectInterface.agent.child_exec,
-            child_args: projectInterface.agent.child_args,
-            identifier: projectInterface.agent.identifier,
-            timeout: projectInterface.agent.timeout,
-          };
-        }
-
-        updateInstance(targetId, { isRunning: true });
-        setInstanceTaskStatus(targetId, 'Running');
-        setShowAddTaskPanel(false);
-
-        // 如果是定时执行，记录状态
-        if (schedulePolicyName) {
-          setScheduleExecution(targetId, {
-            policyName: schedulePolicyName,
-            startTime: Date.now(),
-          });
-        }
-
-        // 启动任务
-        const taskIds = await maaService.startTasks(targetId, taskConfigs, agentConfig, basePath, tcpCompatMode);
-
-        log.info(`实例 ${targetInstance.name}: 任务已提交, task_ids:`, taskIds);
-
-        // 初始化任务运行状态
-        const enabledTaskIds = enabledTasks.map((t) => t.id);
-        setAllTasksRunStatus(targetId, enabledTaskIds, 'pending');
-
-        // 开始任务时折叠所有任务
-        collapseAllTasks(targetId, false);
-
-        // 记录映射关系，并注册 task_id 与任务名的映射用于日志显示
-        taskIds.forEach((maaTaskId, index) => {
-          if (enabledTasks[index]) {
-            registerMaaTaskMapping(targetId, maaTaskId, enabledTasks[index].id);
-            // 注册 task_id 与任务名的映射（使用自定义名称或 label）
-            const taskDef = projectInterface?.task.find(
-              (t) => t.name === enabledTasks[index].taskName,
-            );
-            const taskDisplayName =
-              enabledTasks[index].customName ||
-              resolveI18nText(taskDef?.label, translations) ||
-              enabledTasks[index].taskName;
-            registerTaskIdName(maaTaskId, taskDisplayName);
-          }
-        });
-
-        // 第一个任务设为 running
-        if (enabledTasks.length > 0) {
-          setTaskRunStatus(targetId, enabledTasks[0].id, 'running');
-        }
-
-        // 设置任务队列
-        runningInstanceIdRef.current = targetId;
-        setPendingTaskIds(targetId, taskIds);
-        setCurrentTaskIndexStore(targetId, 0);
-        setInstanceCurrentTaskId(targetId, taskIds[0]);
-
-        return true;
-      } catch (err) {
-        log.error(`实例 ${targetInstance.name}: 任务启动异常:`, err);
-
-        if (projectInterface?.agent) {
-          try {
-            await maaService.stopAgent(targetId);
-          } catch {
-            // 忽略停止 agent 的错误
-          }
-        }
-
-        updateInstance(targetId, { isRunning: false });
-        setInstanceTaskStatus(targetId, 'Failed');
-        clearTaskRunStatus(targetId);
-        clearPendingTasks(targetId);
-        clearScheduleExecution(targetId);
-
-        return false;
-      }
-    },
-    [
-      projectInterface,
-      basePath,
-      selectedController,
-      selectedResource,
-      instanceConnectionStatus,
-      instanceResourceLoaded,
-      setInstanceConnectionStatus,
-      setInstanceResourceLoaded,
-      updateInstance,
-      setInstanceTaskStatus,
-      setInstanceCurrentTaskId,
-      setAllTasksRunStatus,
-      registerMaaTaskMapping,
-      setTaskRunStatus,
-      setPendingTaskIds,
-      setCurrentTaskIndexStore,
-      clearTaskRunStatus,
-      clearPendingTasks,
-      setScheduleExecution,
-      clearScheduleExecution,
-      setShowAddTaskPanel,
-    ],
-  );
-
-  /**
-   * 检查并执行定时任务
-   * 遍历所有实例的定时策略，如果当前时间匹配则启动任务
-   */
-  const checkAndExecuteScheduledTasks = useCallback(async () => {
-    const now = new Date();
-    const currentWeekday = now.getDay(); // 0-6, 0=周日
-    const currentHour = now.getHours(); // 0-23
-
-    log.info(`定时检查: 周${currentWeekday} ${currentHour}:00`);
-
-    for (const inst of instances) {
-      const policies = inst.schedulePolicies || [];
-
-      for (const policy of policies) {
-        if (!policy.enabled) continue;
-
-        // 检查是否匹配当前时间
-        const matchesWeekday = policy.weekdays.includes(currentWeekday);
-        const matchesHour = policy.hours.includes(currentHour);
-
-        if (matchesWeekday && matchesHour) {
-          log.info(`定时策略命中: 实例 "${inst.name}", 策略 "${policy.name}"`);
-
-          // 添加定时执行开始日志
-          const timeStr = `${currentHour.toString().padStart(2, '0')}:00`;
-          addLog(inst.id, {
-            type: 'info',
-            message: t('logs.messages.scheduleStarting', {
-              policy: policy.name,
-              time: timeStr,
-            }),
-          });
-
-          // 启动任务（复用启动函数）
-          const started = await startTasksForInstance(inst, policy.name);
-          if (started) {
-            log.info(`定时任务启动成功: 实例 "${inst.name}"`);
-          } else {
-            log.warn(`定时任务启动失败或跳过: 实例 "${inst.name}"`);
-          }
-
-          // 一个实例只执行第一个匹配的策略，避免重复启动
-          break;
-        }
-      }
-    }
-  }, [instances, startTasksForInstance, addLog, t]);
-
-  // 定时任务检查：每个整小时检查一次
-  useEffect(() => {
-    // 计算距离下一个整小时的时间
-    const now = new Date();
-    const msUntilNextHour =
-      (60 - now.getMinutes()) * 60 * 1000 - now.getSeconds() * 1000 - now.getMilliseconds();
-
-    // 立即检查一次（仅在整点时）
-    if (now.getMinutes() === 0 && now.getSeconds() < 5) {
-      checkAndExecuteScheduledTasks();
-    }
-
-    // 设置定时器，在下一个整小时执行
-    const initialTimeout = setTimeout(() => {
-      checkAndExecuteScheduledTasks();
-
-      // 然后每小时执行一次
-      const hourlyInterval = setInterval(
-        () => {
-          checkAndExecuteScheduledTasks();
-        },
-        60 * 60 * 1000,
-      );
-
-      // 保存 interval ID 以便清理
-      (
-        window as unknown as { __scheduleInterval?: ReturnType<typeof setInterval> }
-      ).__scheduleInterval = hourlyInterval;
-    }, msUntilNextHour);
-
-    return () => {
-      clearTimeout(initialTimeout);
-      const hourlyInterval = (
-        window as unknown as { __scheduleInterval?: ReturnType<typeof setInterval> }
-      ).__scheduleInterval;
-      if (hourlyInterval) {
-        clearInterval(hourlyInterval);
-      }
-    };
-  }, [checkAndExecuteScheduledTasks]);
-
   /**
    * 检查当前控制器是否需要管理员权限
    * @returns 如果需要权限且当前不是管理员返回 true
@@ -925,6 +386,26 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     }
   };
 
+  // 暂停时立即停止当前任务，继续时从该任务重新执行
+  const handlePauseResume = async () => {
+    if (!instance?.isRunning) return;
+
+    setIsPausing(true);
+    try {
+      if (isPaused) {
+        log.info('继续任务...');
+        await maaService.resumeTasks(instance.id, true);
+      } else {
+        log.info('暂停任务...');
+        await maaService.pauseTasks(instance.id, true);
+      }
+    } catch (err) {
+      log.error('暂停/继续任务失败:', err);
+    } finally {
+      setIsPausing(false);
+    }
+  };
+
   const handleStartStop = async () => {
     if (!instance) return;
 
@@ -943,9 +424,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         setInstanceCurrentTaskId(instance.id, null);
         // 清空任务运行状态和定时执行状态
         clearTaskRunStatus(instance.id);
-        clearPendingTasks(instance.id);
         clearScheduleExecution(instance.id);
-        runningInstanceIdRef.current = null;
       } catch (err) {
         log.error('停止任务失败:', err);
       } finally {
@@ -969,6 +448,14 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
       setAutoConnectError(null);
 
       try {
+        // 未连接时先执行启动前的准备步骤（启动模拟器或游戏并等待就绪）
+        if (!isConnected && instance.preRun?.length) {
+          setAutoConnectPhase('pre_run');
+          log.info('执行启动前的准备步骤...');
+          await ensureMaaInitialized();
+          await maaService.runPreRun(instance.id);
+        }
+
         // 如果未连接，尝试自动连接
         if (!isConnected && hasSavedDeviceConfig) {
           log.info('检测到保存的设备配置，尝试自动连接...');
@@ -993,22 +480,8 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         log.info('开始执行任务, 数量:', enabledTasks.length);
 
         // 构建任务配置列表，同时预注册 entry -> taskName 映射（解决时序问题）
-        const taskConfigs: TaskConfig[] = [];
-        for (const selectedTask of enabledTasks) {
-          const taskDef = projectInterface?.task.find((t) => t.name === selectedTask.taskName);
-          if (!taskDef) continue;
-
-          taskConfigs.push({
-            entry: taskDef.entry,
-            pipeline_override: generateTaskPipelineOverride(selectedTask, projectInterface),
-          });
-          // 预注册 entry -> taskName 映射，确保回调时能找到任务名
-          const taskDisplayName =
-            selectedTask.customName ||
-            resolveI18nText(taskDef.label, translations) ||
-            selectedTask.taskName;
-          registerEntryTaskName(taskDef.entry, taskDisplayName);
-        }
+        const taskConfigs = await buildTaskConfigs(tasks, projectInterface, translations);
+        taskConfigs.forEach((config) => registerEntryTaskName(config.entry, config.label));
 
         if (taskConfigs.length === 0) {
           log.warn('没有可执行的任务');
@@ -1017,64 +490,29 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         }
 
         // 准备 Agent 配置（如果有）
-        let agentConfig: AgentConfig | undefined;
-        if (projectInterface?.agent) {
-          agentConfig = {
-            child_exec: projectInterface.agent.child_exec,
-            child_args: projectInterface.agent.child_args,
-            identifier: projectInterface.agent.identifier,
-            timeout: projectInterface.agent.timeout,
-          };
-        }
+        const agentConfig = buildAgentConfig(projectInterface);
 
         updateInstance(instance.id, { isRunning: true });
         setInstanceTaskStatus(instance.id, 'Running');
         setShowAddTaskPanel(false);
 
-        // 启动任务（支持 Agent）
-        const taskIds = await maaService.startTasks(
+        // 启动任务（支持 Agent），后端任务队列逐个提交，运行状态由 maa-task-queue 事件同步
+        const itemIds = await maaService.startTasks(
           instance.id,
           taskConfigs,
           agentConfig,
           basePath,
           tcpCompatMode,
+          instance.runLoop,
+          instance.skipCompleted,
+          instance.scheduleTimeZone,
         );
 
-        log.info('任务已提交, task_ids:', taskIds);
-
-        // 初始化任务运行状态：所有启用的任务设为 pending
-        const enabledTaskIds = enabledTasks.map((t) => t.id);
-        setAllTasksRunStatus(instance.id, enabledTaskIds, 'pending');
+        log.info('任务已加入队列, item_ids:', itemIds);
 
         // 开始任务时折叠所有任务
         collapseAllTasks(instance.id, false);
 
-        // 记录 maaTaskId -> selectedTaskId 的映射关系，并注册 task_id 与任务名的映射
-        taskIds.forEach((maaTaskId, index) => {
-          if (enabledTasks[index]) {
-            registerMaaTaskMapping(instance.id, maaTaskId, enabledTasks[index].id);
-            // 注册 task_id 与任务名的映射（使用自定义名称或 label）
-            const taskDef = projectInterface?.task.find(
-              (t) => t.name === enabledTasks[index].taskName,
-            );
-            const taskDisplayName =
-              enabledTasks[index].customName ||
-              resolveI18nText(taskDef?.label, translations) ||
-              enabledTasks[index].taskName;
-            registerTaskIdName(maaTaskId, taskDisplayName);
-          }
-        });
-
-        // 第一个任务设为 running
-        if (enabledTasks.length > 0) {
-          setTaskRunStatus(instance.id, enabledTasks[0].id, 'running');
-        }
-
-        // 设置任务队列，由回调监听处理完成状态
-        runningInstanceIdRef.current = instance.id;
-        setPendingTaskIds(instance.id, taskIds);
-        setCurrentTaskIndexStore(instance.id, 0);
-        setInstanceCurrentTaskId(instance.id, taskIds[0]);
         setIsStarting(false);
       } catch (err) {
         log.error('任务启动异常:', err);
@@ -1092,7 +530,6 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         setInstanceTaskStatus(instance.id, 'Failed');
         // 清空任务运行状态
         clearTaskRunStatus(instance.id);
-        clearPendingTasks(instance.id);
         setIsStarting(false);
       }
     }
@@ -1128,6 +565,8 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   const getStartButtonText = () => {
     if (isStarting) {
       switch (autoConnectPhase) {
+        case 'pre_run':
+          return t('taskList.autoConnect.preRun');
         case 'searching':
           return t('taskList.autoConnect.searching');
         case 'connecting':
@@ -1287,6 +726,110 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
           <SchedulePanel instanceId={instance.id} onClose={() => setShowSchedulePanel(false)} />
         )}
 
+        {/* 循环执行按钮：设置了循环时高亮，循环中显示当前轮次 */}
+        {(() => {
+          const looping = (instance?.runLoop?.mode.type ?? 'once') !== 'once';
+          const run = instance ? instanceTaskRun[instance.id] : null;
+          const total = run?.mode.type === 'repeat' ? `/${run.mode.times}` : '';
+
+          return (
+            <button
+              onClick={() => setShowRunLoopPanel(!showRunLoopPanel)}
+              className={clsx(
+                'flex items-center gap-1.5 px-3 py-2 rounded-lg text-sm transition-colors relative',
+                showRunLoopPanel
+                  ? 'bg-accent text-white'
+                  : looping
+                    ? 'bg-accent/10 text-accent'
+                    : 'text-text-secondary hover:bg-bg-hover hover:text-text-primary',
+              )}
+              title={t('runLoop.title')}
+            >
+              <Repeat className="w-4 h-4" />
+              {run && !showRunLoopPanel && (
+                <span className="text-xs font-medium">
+                  {t('runLoop.iteration', { iteration: `${run.iteration}${total}` })}
+                </span>
+              )}
+            </button>
+          );
+        })()}
+
+        {/* 循环执行面板 */}
+        {showRunLoopPanel && instance && (
+          <RunLoopPanel instanceId={instance.id} onClose={() => setShowRunLoopPanel(false)} />
+        )}
+
+        {/* 每日完成记录按钮：开启跳过已完成任务时高亮 */}
+        <button
+          onClick={() => setShowLedgerPanel(!showLedgerPanel)}
+          className={clsx(
+            'flex items-center gap-1.5 px-3 py-2 rounded-lg text-sm transition-colors relative',
+            showLedgerPanel
+              ? 'bg-accent text-white'
+              : instance?.skipCompleted
+                ? 'bg-accent/10 text-accent'
+                : 'text-text-secondary hover:bg-bg-hover hover:text-text-primary',
+          )}
+          title={t('dailyLedger.title')}
+        >
+          <CalendarCheck className="w-4 h-4" />
+        </button>
+
+        {/* 每日完成记录面板 */}
+        {showLedgerPanel && instance && (
+          <DailyLedgerPanel instanceId={instance.id} onClose={() => setShowLedgerPanel(false)} />
+        )}
+
+        {/* 启动前准备步骤按钮：设置了步骤时高亮，执行中闪烁 */}
+        <button
+          onClick={() => setShowPreRunPanel(!showPreRunPanel)}
+          className={clsx(
+            'flex items-center gap-1.5 px-3 py-2 rounded-lg text-sm transition-colors relative',
+            showPreRunPanel
+              ? 'bg-accent text-white'
+              : instance?.preRun?.length
+                ? 'bg-accent/10 text-accent'
+                : 'text-text-secondary hover:bg-bg-hover hover:text-text-primary',
+          )}
+          title={t('preRun.title')}
+        >
+          <Rocket
+            className={clsx('w-4 h-4', instance && preRunProgress[instance.id] && 'animate-pulse')}
+          />
+        </button>
+
+        {/* 启动前准备步骤面板 */}
+        {showPreRunPanel && instance && (
+          <PreRunPanel instanceId={instance.id} onClose={() => setShowPreRunPanel(false)} />
+        )}
+
+        {/* 运行结束后动作按钮：设置了动作时高亮，倒计时中显示剩余秒数 */}
+        <button
+          onClick={() => setShowPostRunPanel(!showPostRunPanel)}
+          className={clsx(
+            'flex items-center gap-1.5 px-3 py-2 rounded-lg text-sm transition-colors relative',
+            showPostRunPanel
+              ? 'bg-accent text-white'
+              : postRunRemaining !== null
+                ? 'bg-warning/10 text-warning'
+                : instance?.postRun?.actions.length
+                  ? 'bg-accent/10 text-accent'
+                  : 'text-text-secondary hover:bg-bg-hover hover:text-text-primary',
+          )}
+          title={t('postRun.title')}
+        >
+          <Power className="w-4 h-4" />
+          {postRunRemaining !== null && !showPostRunPanel && (
+            <span className="text-xs font-medium">{postRunRemaining}s</span>
+          )}
+        </button>
+
+        {/* 运行结束后动作面板 */}
+        {showPostRunPanel && instance && (
+          <PostRunPanel instanceId={instance.id} onClose={() => setShowPostRunPanel(false)} />
+        )}
+
         {/* 权限提示弹窗 */}
         {showPermissionModal && (
           <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50 backdrop-blur-sm">
@@ -1341,6 +884,29 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
           </div>
         )}
 
+        {/* 暂停/继续按钮 - 仅运行时显示 */}
+        {instance?.isRunning && (
+          <button
+            onClick={handlePauseResume}
+            disabled={isPausing || isStopping}
+            className={clsx(
+              'flex items-center gap-2 px-4 py-2 rounded-lg text-sm font-medium transition-colors',
+              'bg-bg-tertiary hover:bg-bg-hover text-text-primary border border-border',
+              'disabled:opacity-50 disabled:cursor-not-allowed',
+            )}
+            title={isPaused ? t('taskList.resumeTasksHint') : t('taskList.pauseTasksHint')}
+          >
+            {isPausing ? (
+              <Loader2 className="w-4 h-4 animate-spin" />
+            ) : isPaused ? (
+              <Play className="w-4 h-4" />
+            ) : (
+              <Pause className="w-4 h-4" />
+            )}
+            <span>{isPaused ? t('taskList.resumeTasks') : t('taskList.pauseTasks')}</span>
+          </button>
+        )}
+
         {/* 开始/停止按钮 */}
         <button
           data-role="start-stop-button"
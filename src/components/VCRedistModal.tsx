@@ -5,6 +5,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { join } from '@tauri-apps/api/path';
 import { loggers } from '@/utils/logger';
+import { getDataDirs } from '@/utils/dataDirs';
 
 const log = loggers.app;
 
@@ -81,8 +82,11 @@ export function VCRedistModal({ show, onClose }: VCRedistModalProps) {
       const { url: vcredistUrl, filename: vcredistFilename } = getVCRedistInfo(architecture);
       log.info(`系统架构: ${architecture}, 下载: ${vcredistFilename}`);
 
-      const exeDir = await invoke<string>('get_exe_dir');
-      const downloadPath = await join(exeDir, 'cache', vcredistFilename);
+      const dataDirs = await getDataDirs();
+      if (!dataDirs) {
+        throw new Error('无法获取缓存目录');
+      }
+      const downloadPath = await join(dataDirs.cache_dir, vcredistFilename);
 
       log.info(`开始下载 VC++ 运行库: ${vcredistUrl} -> ${downloadPath}`);
 
@@ -88,6 +88,10 @@ export default {
     stopTasks: 'Stop Tasks',
     startingTasks: 'Starting...',
     stoppingTasks: 'Stopping...',
+    pauseTasks: 'Pause',
+    resumeTasks: 'Resume',
+    pauseTasksHint: 'Stop the current task and pause; resuming re-runs it',
+    resumeTasksHint: 'Continue the remaining tasks in order',
     // Auto connect
     autoConnect: {
       searching: 'Searching devices...',
@@ -95,6 +99,7 @@ export default {
       connecting: 'Connecting device...',
       connectingWindow: 'Connecting window...',
       loadingResource: 'Loading resource...',
+      preRun: 'Running pre-run steps...',
       deviceNotFound: 'Device not found: {{name}}',
       windowNotFound: 'Window not found: {{name}}',
       noSavedDevice: 'No saved device configuration',
@@ -132,10 +137,30 @@ export default {
       running: 'Running',
       succeeded: 'Completed',
       failed: 'Failed',
+      skipped: 'Skipped',
     },
     // Task compatibility
     incompatibleController: 'Not supported by current controller',
     incompatibleResource: 'Not supported by current resource',
+    // Retry & timeout
+    runPolicy: 'Retry & timeout',
+    retryCount: 'Retries',
+    retryDelay: 'Retry delay (s)',
+    timeout: 'Timeout (min)',
+    timeoutHint: 'Applies on next start; 0 means no retry / no limit',
+    // Run conditions
+    conditions: 'Run conditions',
+    conditionAdd: 'Add condition',
+    conditionTypes: {
+      after_task: 'After task',
+      time_window: 'Time window',
+      weekdays: 'Weekdays',
+      not_succeeded_today: 'Not yet succeeded today',
+    },
+    conditionSucceeded: 'succeeded',
+    conditionFailed: 'failed',
+    conditionHint: 
+      'Applies on next start; the task is skipped when a condition is not met. Times use the schedule time zone',
   },
 
   // Options
@@ -252,12 +277,33 @@ export default {
       stopTask: 'Stop Task',
       // Schedule messages
       scheduleStarting: 'Scheduled execution started [{{policy}}] {{time}}',
+      scheduleCatchUp: 'Catching up missed schedule [{{policy}}] {{time}}',
+      scheduleStarted: 'Scheduled tasks started [{{policy}}]',
+      scheduleFailed: 'Failed to start scheduled tasks [{{policy}}]: {{error}}',
+      scheduleSkipped: {
+        missed: 'Missed schedule [{{policy}}] {{time}} (catch-up disabled)',
+        running: 'Instance is running, skipped schedule [{{policy}}] {{time}}',
+        no_tasks: 'No enabled tasks, skipped schedule [{{policy}}] {{time}}',
+      },
       // Agent messages
       agentStarting: 'Agent starting...',
       agentStarted: 'Agent started',
       agentConnected: 'Agent connected',
       agentDisconnected: 'Agent disconnected',
       agentFailed: 'Agent start failed',
+      // Auto reconnect messages
+      reconnectDisconnected: 'Controller disconnected: {{reason}}',
+      reconnectAttempting: 'Reconnect attempt {{attempt}}/{{max}} in {{delay}}s...',
+      reconnectAttemptFailed: 'Reconnect attempt {{attempt}} failed: {{error}}',
+      reconnectSucceeded: 'Reconnected',
+      reconnectResumed: 'Resumed from the interrupted task ({{count}} tasks)',
+      reconnectGaveUp: 'Gave up after {{max}} reconnect attempts',
+      reconnectCancelled: 'Auto reconnect cancelled: {{reason}}',
+      postRunCountdown: 'Post-run actions in {{seconds}}s: {{actions}}',
+      postRunCancelled: 'Post-run actions cancelled: {{reason}}',
+      postRunExecuting: 'Running post-run action: {{action}}',
+      postRunActionFailed: '{{action}} failed: {{error}}',
+      postRunCompleted: 'Post-run actions completed',
     },
   },
 
@@ -300,6 +346,9 @@ export default {
     openConfigDir: 'Open Config Dir',
     openLogDir: 'Open Log Dir',
     clearCache: 'Clear Cache',
+    reloadMaafw: 'Reload MaaFramework',
+    reloadMaafwHint: 'Stops all tasks, disconnects all instances and reloads the library from the maafw folder',
+    reloadMaafwFailed: 'Reload failed: {{error}}',
     cacheCleared: 'Cache cleared',
     cacheStats: 'Cache entries: {{count}}',
     devMode: 'Developer Mode',
@@ -309,6 +358,7 @@ export default {
       'Save recognition and action debug images to log directory (auto-disabled on restart)',
     tcpCompatMode: 'Communication Compat Mode',
     tcpCompatModeHint: 'Try enabling this if the app crashes immediately after starting tasks. Only use in this case, as it may reduce performance',
+    tcpCompatModeUnsupported: 'The loaded MaaFramework does not support TCP communication',
   },
 
   // Welcome dialog
@@ -332,6 +382,10 @@ export default {
   // Connection panel
   connection: {
     title: 'Connection Settings',
+    autoReconnect: 'Auto reconnect',
+    autoReconnectHint: 'Reconnect with backoff after the connection drops',
+    resumeTasks: 'Resume tasks after reconnect',
+    maxAttempts: 'Max attempts',
   },
 
   // Dashboard
@@ -464,7 +518,15 @@ export default {
     everyHour: 'Every hour',
     all: 'All',
     hoursSelected: 'hours selected',
-    timeZoneHint: 'Using local timezone',
+    modeWeekly: 'Weekdays and hours',
+    modeCron: 'Cron expression',
+    cronHint: 'minute hour day month weekday; supports * , - / (Sunday is 0 or 7)',
+    minute: 'Minute',
+    catchUp: 'Run once afterwards if missed (app closed or system asleep)',
+    nextRuns: 'Next: {{times}}',
+    noNextRun: 'No upcoming runs',
+    timeZone: 'Time zone',
+    localTime: 'Local time zone',
     multiSelect: 'multi-select',
     enable: 'Enable schedule',
     disable: 'Disable schedule',
@@ -479,6 +541,92 @@ export default {
     weekdays: ['Sun', 'Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat'],
   },
 
+  // Loop
+  runLoop: {
+    title: 'Loop',
+    modes: {
+      once: 'Run once',
+      repeat: 'Repeat',
+      until_deadline: 'Loop until a deadline',
+      until_task: 'Loop until a task ends',
+    },
+    times: 'Times',
+    deadline: 'Deadline',
+    succeeded: 'Succeeded',
+    failed: 'Failed',
+    delay: 'Delay between runs (s)',
+    iteration: 'Run {{iteration}}',
+    hint: 'The next run starts automatically; stopping tasks ends the loop',
+  },
+
+  // Daily ledger
+  dailyLedger: {
+    title: 'Daily ledger',
+    skipCompleted: 'Skip tasks already completed in this period',
+    resetTime: 'Daily reset',
+    localTime: 'Local time',
+    completedAt: 'Done {{time}}',
+    notCompleted: 'Not done',
+    clear: 'Clear record',
+    clearAll: 'Clear all',
+    nextReset: 'Next reset: {{time}}',
+    hint: 'Successful tasks are recorded until the next daily reset',
+  },
+  preRun: {
+    title: 'Before run',
+    program: 'Program path',
+    args: 'Arguments (space separated)',
+    cwd: 'Working directory (optional)',
+    waitFor: 'Wait for',
+    ready: {
+      none: 'Nothing',
+      adb_device: 'ADB device',
+      window: 'Window',
+    },
+    address: 'ADB address, e.g. 127.0.0.1:16384',
+    classRegex: 'Window class regex',
+    windowRegex: 'Window title regex',
+    timeout: 'Timeout',
+    settle: 'Then wait',
+    seconds: 's',
+    skipIfReady: 'Skip launching when already ready',
+    addStep: 'Add step',
+    removeStep: 'Remove step',
+    cancel: 'Abort',
+    hint: 'Runs before connecting, e.g. to launch the emulator or game',
+    progress: {
+      skipped: 'Step {{step}}: already ready, launch skipped',
+      launching: 'Step {{step}}: launching program',
+      waiting: 'Step {{step}}: waiting until ready (timeout at {{time}})',
+      ready: 'Step {{step}}: ready',
+      failed: 'Step {{step}} failed: {{error}}',
+      cancelled: 'Pre-run steps aborted',
+      completed: 'Pre-run steps completed',
+    },
+  },
+  postRun: {
+    title: 'After run',
+    actions: {
+      stop_agent: 'Stop agent',
+      run_command: 'Run command',
+      start_instance: 'Start instance',
+      shutdown: 'Shut down',
+      suspend: 'Sleep',
+      exit_app: 'Exit MXU',
+    },
+    program: 'Program path',
+    args: 'Arguments (space separated)',
+    cwd: 'Working directory (optional)',
+    power: 'Power',
+    none: 'None',
+    countdown: 'Countdown',
+    seconds: 's',
+    onFailure: 'Also run when a task failed',
+    pending: 'Running in {{seconds}}s',
+    cancel: 'Cancel',
+    hint: 'Runs in order after the task list finishes, not when stopped manually',
+  },
+
   // Error messages
   errors: {
     loadInterfaceFailed: 'Failed to load interface.json',
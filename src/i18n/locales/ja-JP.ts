@@ -89,6 +89,10 @@ export default {
     stopTasks: '実行停止',
     startingTasks: '開始中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '一時停止',
+    resumeTasks: '再開',
+    pauseTasksHint: '現在のタスクを止めて一時停止し、再開時はそのタスクから実行',
+    resumeTasksHint: '残りのタスクを順番に再開',
     // 自動接続関連
     autoConnect: {
       searching: 'デバイスを検索中...',
@@ -96,6 +100,7 @@ export default {
       connecting: 'デバイスに接続中...',
       connectingWindow: 'ウィンドウに接続中...',
       loadingResource: 'リソースを読み込み中...',
+      preRun: '実行前の準備中...',
       deviceNotFound: 'デバイスが見つかりません: {{name}}',
       windowNotFound: 'ウィンドウが見つかりません: {{name}}',
       noSavedDevice: '保存されたデバイス設定がありません',
@@ -133,10 +138,30 @@ export default {
       running: '実行中',
       succeeded: '完了',
       failed: '失敗',
+      skipped: 'スキップ',
     },
     // タスクの互換性
     incompatibleController: '現在のコントローラーに対応していません',
     incompatibleResource: '現在のリソースに対応していません',
+    // リトライとタイムアウト
+    runPolicy: 'リトライとタイムアウト',
+    retryCount: 'リトライ回数',
+    retryDelay: 'リトライ間隔(秒)',
+    timeout: 'タイムアウト(分)',
+    timeoutHint: '次回開始時に反映。0 はリトライなし/無制限',
+    // 実行条件
+    conditions: '実行条件',
+    conditionAdd: '条件を追加',
+    conditionTypes: {
+      after_task: '前提タスク',
+      time_window: '時間帯',
+      weekdays: '曜日',
+      not_succeeded_today: '本日未成功',
+    },
+    conditionSucceeded: '成功後',
+    conditionFailed: '失敗後',
+    conditionHint: 
+      '次回開始時に反映。条件を満たさない場合はタスクをスキップします。時刻は定時実行のタイムゾーンで判定します',
   },
 
   // オプション
@@ -254,12 +279,33 @@ export default {
       stopTask: 'タスクを停止',
       // スケジュールメッセージ
       scheduleStarting: 'スケジュール実行を開始 [{{policy}}] {{time}}',
+      scheduleCatchUp: '見逃したスケジュールを実行 [{{policy}}] {{time}}',
+      scheduleStarted: 'スケジュールでタスクを開始しました [{{policy}}]',
+      scheduleFailed: 'スケジュール実行の開始に失敗 [{{policy}}]: {{error}}',
+      scheduleSkipped: {
+        missed: 'スケジュールを見逃しました [{{policy}}] {{time}}（後から実行は無効）',
+        running: 'インスタンス実行中のためスキップ [{{policy}}] {{time}}',
+        no_tasks: '有効なタスクがないためスキップ [{{policy}}] {{time}}',
+      },
       // Agent メッセージ
       agentStarting: 'Agent を起動中...',
       agentStarted: 'Agent が起動しました',
       agentConnected: 'Agent が接続しました',
       agentDisconnected: 'Agent が切断しました',
       agentFailed: 'Agent の起動に失敗しました',
+      // 自動再接続メッセージ
+      reconnectDisconnected: 'コントローラーの接続が切断されました: {{reason}}',
+      reconnectAttempting: '{{delay}} 秒後に再接続を試行します（{{attempt}}/{{max}}）...',
+      reconnectAttemptFailed: '再接続 {{attempt}} 回目に失敗しました: {{error}}',
+      reconnectSucceeded: '再接続しました',
+      reconnectResumed: '中断したタスクから再開しました（{{count}} 件）',
+      reconnectGaveUp: '{{max}} 回再接続に失敗したため中止しました',
+      reconnectCancelled: '自動再接続をキャンセルしました: {{reason}}',
+      postRunCountdown: '{{seconds}} 秒後に実行終了後のアクションを実行します: {{actions}}',
+      postRunCancelled: '実行終了後のアクションをキャンセルしました: {{reason}}',
+      postRunExecuting: '実行終了後のアクションを実行中: {{action}}',
+      postRunActionFailed: '{{action}}に失敗しました: {{error}}',
+      postRunCompleted: '実行終了後のアクションが完了しました',
     },
   },
 
@@ -302,6 +348,9 @@ export default {
     openConfigDir: '設定フォルダを開く',
     openLogDir: 'ログフォルダを開く',
     clearCache: 'キャッシュをクリア',
+    reloadMaafw: 'MaaFramework を再読み込み',
+    reloadMaafwHint: 'すべてのタスクを停止して切断し、maafw フォルダのライブラリを再読み込みします',
+    reloadMaafwFailed: '再読み込みに失敗しました：{{error}}',
     cacheCleared: 'キャッシュをクリアしました',
     cacheStats: 'キャッシュ項目: {{count}} 件',
     devMode: '開発者モード',
@@ -311,6 +360,7 @@ export default {
       '認識と操作のデバッグ画像をログフォルダに保存します（再起動後は自動的にオフになります）',
     tcpCompatMode: '通信互換モード',
     tcpCompatModeHint: 'タスク開始後にアプリがすぐにクラッシュする場合は有効にしてください。この場合のみ使用し、それ以外は性能に影響します',
+    tcpCompatModeUnsupported: '現在の MaaFramework は TCP 通信に対応していないため、有効にできません',
   },
 
   // ウェルカムダイアログ
@@ -333,6 +383,10 @@ export default {
   // 接続パネル
   connection: {
     title: '接続設定',
+    autoReconnect: '自動再接続',
+    autoReconnectHint: '切断後、間隔を空けて再接続します',
+    resumeTasks: '再接続後にタスクを再開',
+    maxAttempts: '最大試行回数',
   },
 
   // ダッシュボード
@@ -466,7 +520,15 @@ export default {
     everyHour: '毎時',
     all: 'すべて',
     hoursSelected: '件の時刻',
-    timeZoneHint: 'ローカルタイムゾーンを使用 (UTC+9)',
+    modeWeekly: '曜日と時刻',
+    modeCron: 'cron 式',
+    cronHint: '分 時 日 月 曜日、* , - / に対応（日曜は 0 または 7）',
+    minute: '分',
+    catchUp: '見逃した場合（未起動・スリープ中）復帰後に 1 回実行',
+    nextRuns: '次回：{{times}}',
+    noNextRun: '予定された実行はありません',
+    timeZone: 'タイムゾーン',
+    localTime: 'ローカルタイムゾーン',
     multiSelect: '複数選択可',
     enable: 'スケジュールを有効化',
     disable: 'スケジュールを無効化',
@@ -481,6 +543,92 @@ export default {
     weekdays: ['日', '月', '火', '水', '木', '金', '土'],
   },
 
+  // ループ実行
+  runLoop: {
+    title: 'ループ実行',
+    modes: {
+      once: '1 回実行',
+      repeat: '繰り返し実行',
+      until_deadline: '期限までループ',
+      until_task: '指定タスクの終了までループ',
+    },
+    times: '回数',
+    deadline: '期限',
+    succeeded: '成功',
+    failed: '失敗',
+    delay: 'ループ間隔(秒)',
+    iteration: '{{iteration}} 周目',
+    hint: '1 周終わると次の周を自動で開始します。停止するとループも終了します',
+  },
+
+  // デイリー完了記録
+  dailyLedger: {
+    title: 'デイリー完了記録',
+    skipCompleted: '開始時に今期間で完了済みのタスクをスキップ',
+    resetTime: '日次リセット',
+    localTime: 'ローカル時間',
+    completedAt: '{{time}} 完了',
+    notCompleted: '未完了',
+    clear: '記録を消去',
+    clearAll: 'すべて消去',
+    nextReset: '次回リセット：{{time}}',
+    hint: '成功したタスクは次の日次リセットまで記録されます',
+  },
+  preRun: {
+    title: '実行前の準備',
+    program: 'プログラムのパス',
+    args: '引数（スペース区切り）',
+    cwd: '作業ディレクトリ（任意）',
+    waitFor: '待機',
+    ready: {
+      none: '待機しない',
+      adb_device: 'ADB デバイス',
+      window: 'ウィンドウ',
+    },
+    address: 'ADB アドレス（例: 127.0.0.1:16384）',
+    classRegex: 'ウィンドウクラスの正規表現',
+    windowRegex: 'ウィンドウタイトルの正規表現',
+    timeout: 'タイムアウト',
+    settle: '準備完了後の待機',
+    seconds: '秒',
+    skipIfReady: '準備済みの場合は起動しない',
+    addStep: 'ステップを追加',
+    removeStep: 'ステップを削除',
+    cancel: '中止',
+    hint: '接続前に順番に実行します（エミュレーターやゲームの起動など）',
+    progress: {
+      skipped: 'ステップ {{step}}: 準備済みのため起動をスキップしました',
+      launching: 'ステップ {{step}}: プログラムを起動中',
+      waiting: 'ステップ {{step}}: 準備完了を待機中（{{time}} にタイムアウト）',
+      ready: 'ステップ {{step}}: 準備完了',
+      failed: 'ステップ {{step}} が失敗しました: {{error}}',
+      cancelled: '実行前の準備を中止しました',
+      completed: '実行前の準備が完了しました',
+    },
+  },
+  postRun: {
+    title: '実行終了後',
+    actions: {
+      stop_agent: 'Agent を停止',
+      run_command: 'コマンドを実行',
+      start_instance: 'インスタンスを開始',
+      shutdown: 'シャットダウン',
+      suspend: 'スリープ',
+      exit_app: 'MXU を終了',
+    },
+    program: 'プログラムのパス',
+    args: '引数（スペース区切り）',
+    cwd: '作業ディレクトリ（任意）',
+    power: '電源',
+    none: 'なし',
+    countdown: '実行前のカウントダウン',
+    seconds: '秒',
+    onFailure: '失敗したタスクがあっても実行',
+    pending: '{{seconds}} 秒後に実行',
+    cancel: 'キャンセル',
+    hint: 'タスクリストの完了後に順番に実行します（手動停止時は実行しません）',
+  },
+
   // エラーメッセージ
   errors: {
     loadInterfaceFailed: 'interface.json の読み込みに失敗しました',
@@ -88,6 +88,10 @@ export default {
     stopTasks: '실행 중지',
     startingTasks: '시작 중...',
     stoppingTasks: '중지 중...',
+    pauseTasks: '일시 정지',
+    resumeTasks: '계속',
+    pauseTasksHint: '현재 작업을 중지하고 일시 정지하며, 계속 시 해당 작업부터 다시 실행',
+    resumeTasksHint: '남은 작업을 순서대로 계속 실행',
     // 자동 연결 관련
     autoConnect: {
       searching: '기기 검색 중...',
@@ -95,6 +99,7 @@ export default {
       connecting: '기기 연결 중...',
       connectingWindow: '창 연결 중...',
       loadingResource: '리소스 로딩 중...',
+      preRun: '실행 전 준비 중...',
       deviceNotFound: '기기를 찾을 수 없습니다: {{name}}',
       windowNotFound: '창을 찾을 수 없습니다: {{name}}',
       noSavedDevice: '저장된 기기 설정이 없습니다',
@@ -131,10 +136,30 @@ export default {
       running: '실행 중',
       succeeded: '완료',
       failed: '실패',
+      skipped: '건너뜀',
     },
     // 작업 호환성
     incompatibleController: '현재 컨트롤러에서 지원되지 않음',
     incompatibleResource: '현재 리소스에서 지원되지 않음',
+    // 재시도 및 시간 제한
+    runPolicy: '재시도 및 시간 제한',
+    retryCount: '재시도 횟수',
+    retryDelay: '재시도 간격(초)',
+    timeout: '시간 제한(분)',
+    timeoutHint: '다음 시작 시 적용, 0은 재시도 없음/제한 없음',
+    // 실행 조건
+    conditions: '실행 조건',
+    conditionAdd: '조건 추가',
+    conditionTypes: {
+      after_task: '선행 작업',
+      time_window: '시간대',
+      weekdays: '요일',
+      not_succeeded_today: '오늘 아직 성공하지 않음',
+    },
+    conditionSucceeded: '성공 후',
+    conditionFailed: '실패 후',
+    conditionHint: 
+      '다음 시작 시 적용, 조건을 충족하지 않으면 작업을 건너뜁니다. 시간은 예약 실행의 시간대를 기준으로 합니다',
   },
 
   // 옵션
@@ -247,12 +272,33 @@ export default {
       stopTask: '작업 중지',
       // 예약 메시지
       scheduleStarting: '예약 실행 시작 [{{policy}}] {{time}}',
+      scheduleCatchUp: '놓친 예약 실행 [{{policy}}] {{time}}',
+      scheduleStarted: '예약 실행으로 작업 시작 [{{policy}}]',
+      scheduleFailed: '예약 실행 시작 실패 [{{policy}}]: {{error}}',
+      scheduleSkipped: {
+        missed: '예약을 놓침 [{{policy}}] {{time}} (보충 실행 꺼짐)',
+        running: '인스턴스 실행 중, 예약 건너뜀 [{{policy}}] {{time}}',
+        no_tasks: '활성화된 작업 없음, 예약 건너뜀 [{{policy}}] {{time}}',
+      },
       // Agent 메시지
       agentStarting: 'Agent 시작 중...',
       agentStarted: 'Agent가 시작되었습니다',
       agentConnected: 'Agent가 연결되었습니다',
       agentDisconnected: 'Agent 연결이 끊어졌습니다',
       agentFailed: 'Agent 시작에 실패했습니다',
+      // 자동 재연결 메시지
+      reconnectDisconnected: '컨트롤러 연결이 끊어졌습니다: {{reason}}',
+      reconnectAttempting: '{{delay}}초 후 재연결 시도 ({{attempt}}/{{max}})...',
+      reconnectAttemptFailed: '재연결 {{attempt}}회차 실패: {{error}}',
+      reconnectSucceeded: '재연결되었습니다',
+      reconnectResumed: '중단된 작업부터 재개했습니다 ({{count}}개 작업)',
+      reconnectGaveUp: '{{max}}회 재연결에 실패하여 중단했습니다',
+      reconnectCancelled: '자동 재연결이 취소되었습니다: {{reason}}',
+      postRunCountdown: '{{seconds}}초 후 실행 종료 후 동작을 실행합니다: {{actions}}',
+      postRunCancelled: '실행 종료 후 동작이 취소되었습니다: {{reason}}',
+      postRunExecuting: '실행 종료 후 동작 실행 중: {{action}}',
+      postRunActionFailed: '{{action}} 실패: {{error}}',
+      postRunCompleted: '실행 종료 후 동작이 완료되었습니다',
     },
   },
 
@@ -295,6 +341,9 @@ export default {
     openConfigDir: '설정 폴더 열기',
     openLogDir: '로그 폴더 열기',
     clearCache: '캐시 지우기',
+    reloadMaafw: 'MaaFramework 다시 불러오기',
+    reloadMaafwHint: '모든 작업을 중지하고 연결을 끊은 뒤 maafw 폴더의 라이브러리를 다시 불러옵니다',
+    reloadMaafwFailed: '다시 불러오기 실패: {{error}}',
     cacheCleared: '캐시가 지워졌습니다',
     cacheStats: '캐시 항목: {{count}}개',
     devMode: '개발자 모드',
@@ -304,6 +353,7 @@ export default {
       '인식 및 작업의 디버그 이미지를 로그 폴더에 저장합니다 (재시작 후 자동으로 비활성화됨)',
     tcpCompatMode: '통신 호환 모드',
     tcpCompatModeHint: '작업 시작 후 앱이 즉시 충돌하면 활성화해 보세요. 이 경우에만 사용하세요, 성능에 영향을 줄 수 있습니다',
+    tcpCompatModeUnsupported: '현재 MaaFramework 버전은 TCP 통신을 지원하지 않아 활성화할 수 없습니다',
   },
 
   // 환영 대화상자
@@ -326,6 +376,10 @@ export default {
   // 연결 패널
   connection: {
     title: '연결 설정',
+    autoReconnect: '자동 재연결',
+    autoReconnectHint: '연결이 끊기면 간격을 두고 다시 연결합니다',
+    resumeTasks: '재연결 후 작업 재개',
+    maxAttempts: '최대 시도 횟수',
   },
 
   // 대시보드
@@ -458,7 +512,15 @@ export default {
     everyHour: '매시',
     all: '전체',
     hoursSelected: '개의 시간',
-    timeZoneHint: '로컬 시간대 사용 (UTC+9)',
+    modeWeekly: '요일 및 시간',
+    modeCron: 'cron 표현식',
+    cronHint: '분 시 일 월 요일, * , - / 지원 (일요일은 0 또는 7)',
+    minute: '분',
+    catchUp: '놓친 경우(미실행 또는 절전) 복귀 후 한 번 실행',
+    nextRuns: '다음 실행: {{times}}',
+    noNextRun: '예정된 실행 없음',
+    timeZone: '시간대',
+    localTime: '로컬 시간대',
     multiSelect: '다중 선택',
     enable: '예약 활성화',
     disable: '예약 비활성화',
@@ -473,6 +535,92 @@ export default {
     weekdays: ['일', '월', '화', '수', '목', '금', '토'],
   },
 
+  // 반복 실행
+  runLoop: {
+    title: '반복 실행',
+    modes: {
+      once: '한 번 실행',
+      repeat: '반복 실행',
+      until_deadline: '마감 시간까지 반복',
+      until_task: '지정 작업이 끝날 때까지 반복',
+    },
+    times: '횟수',
+    deadline: '마감 시간',
+    succeeded: '성공',
+    failed: '실패',
+    delay: '회차 간격(초)',
+    iteration: '{{iteration}}회차',
+    hint: '한 회차가 끝나면 다음 회차가 자동으로 시작되며, 중지하면 반복도 종료됩니다',
+  },
+
+  // 일일 완료 기록
+  dailyLedger: {
+    title: '일일 완료 기록',
+    skipCompleted: '시작 시 이번 주기에 완료된 작업 건너뛰기',
+    resetTime: '일일 초기화',
+    localTime: '로컬 시간',
+    completedAt: '{{time}} 완료',
+    notCompleted: '미완료',
+    clear: '기록 삭제',
+    clearAll: '모두 삭제',
+    nextReset: '다음 초기화: {{time}}',
+    hint: '성공한 작업은 다음 일일 초기화까지 기록됩니다',
+  },
+  preRun: {
+    title: '실행 전 준비',
+    program: '프로그램 경로',
+    args: '인수 (공백으로 구분)',
+    cwd: '작업 디렉터리 (선택)',
+    waitFor: '대기',
+    ready: {
+      none: '대기 안 함',
+      adb_device: 'ADB 장치',
+      window: '창',
+    },
+    address: 'ADB 주소 (예: 127.0.0.1:16384)',
+    classRegex: '창 클래스 정규식',
+    windowRegex: '창 제목 정규식',
+    timeout: '시간 제한',
+    settle: '준비 후 대기',
+    seconds: '초',
+    skipIfReady: '이미 준비되면 실행하지 않음',
+    addStep: '단계 추가',
+    removeStep: '단계 삭제',
+    cancel: '중단',
+    hint: '연결 전에 순서대로 실행합니다 (에뮬레이터나 게임 실행 등)',
+    progress: {
+      skipped: '단계 {{step}}: 이미 준비되어 실행을 건너뜀',
+      launching: '단계 {{step}}: 프로그램 실행 중',
+      waiting: '단계 {{step}}: 준비 대기 중 ({{time}}에 시간 초과)',
+      ready: '단계 {{step}}: 준비됨',
+      failed: '단계 {{step}} 실패: {{error}}',
+      cancelled: '실행 전 준비가 중단되었습니다',
+      completed: '실행 전 준비가 완료되었습니다',
+    },
+  },
+  postRun: {
+    title: '실행 종료 후',
+    actions: {
+      stop_agent: 'Agent 중지',
+      run_command: '명령 실행',
+      start_instance: '인스턴스 시작',
+      shutdown: '시스템 종료',
+      suspend: '절전',
+      exit_app: 'MXU 종료',
+    },
+    program: '프로그램 경로',
+    args: '인수 (공백으로 구분)',
+    cwd: '작업 디렉터리 (선택)',
+    power: '전원',
+    none: '없음',
+    countdown: '실행 전 카운트다운',
+    seconds: '초',
+    onFailure: '실패한 작업이 있어도 실행',
+    pending: '{{seconds}}초 후 실행',
+    cancel: '취소',
+    hint: '작업 목록이 끝나면 순서대로 실행하며, 수동으로 중지하면 실행하지 않습니다',
+  },
+
   // 오류 메시지
   errors: {
     loadInterfaceFailed: 'interface.json 로딩에 실패했습니다',
@@ -88,6 +88,10 @@ export default {
     stopTasks: '停止任务',
     startingTasks: '启动中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '暂停任务',
+    resumeTasks: '继续任务',
+    pauseTasksHint: '停止当前任务并暂停，继续时从该任务重新执行',
+    resumeTasksHint: '按顺序继续执行剩余任务',
     // 自动连接相关
     autoConnect: {
       searching: '搜索设备...',
@@ -95,6 +99,7 @@ export default {
       connecting: '连接设备...',
       connectingWindow: '连接窗口...',
       loadingResource: '加载资源...',
+      preRun: '执行启动前准备...',
       deviceNotFound: '未找到设备: {{name}}',
       windowNotFound: '未找到窗口: {{name}}',
       noSavedDevice: '没有保存的设备配置',
@@ -131,10 +136,29 @@ export default {
       running: '执行中',
       succeeded: '已完成',
       failed: '执行失败',
+      skipped: '已跳过',
     },
     // 任务兼容性
     incompatibleController: '不支持当前控制器',
     incompatibleResource: '不支持当前资源',
+    // 重试与超时
+    runPolicy: '重试与超时',
+    retryCount: '失败重试次数',
+    retryDelay: '重试间隔(秒)',
+    timeout: '超时(分钟)',
+    timeoutHint: '下次启动时生效，0 表示不重试/不限时',
+    // 执行条件
+    conditions: '执行条件',
+    conditionAdd: '添加条件',
+    conditionTypes: {
+      after_task: '前置任务',
+      time_window: '时间段',
+      weekdays: '星期',
+      not_succeeded_today: '今天尚未成功',
+    },
+    conditionSucceeded: '成功后',
+    conditionFailed: '失败后',
+    conditionHint: '下次启动时生效，条件不满足时跳过该任务；时间按定时执行的时区判断',
   },
 
   // 选项
@@ -251,12 +275,33 @@ export default {
       stopTask: '停止任务',
       // 定时任务消息
       scheduleStarting: '定时执行开始 [{{policy}}] {{time}}',
+      scheduleCatchUp: '补执行错过的定时策略 [{{policy}}] {{time}}',
+      scheduleStarted: '定时执行已启动任务 [{{policy}}]',
+      scheduleFailed: '定时执行启动失败 [{{policy}}]: {{error}}',
+      scheduleSkipped: {
+        missed: '已错过定时策略 [{{policy}}] {{time}}，未开启补执行',
+        running: '实例正在运行，跳过定时策略 [{{policy}}] {{time}}',
+        no_tasks: '没有启用的任务，跳过定时策略 [{{policy}}] {{time}}',
+      },
       // Agent 消息
       agentStarting: 'Agent 正在启动...',
       agentStarted: 'Agent 已启动',
       agentConnected: 'Agent 已连接',
       agentDisconnected: 'Agent 已断开',
       agentFailed: 'Agent 启动失败',
+      // 自动重连消息
+      reconnectDisconnected: '控制器连接断开: {{reason}}',
+      reconnectAttempting: '{{delay}} 秒后进行第 {{attempt}}/{{max}} 次重连...',
+      reconnectAttemptFailed: '第 {{attempt}} 次重连失败: {{error}}',
+      reconnectSucceeded: '重连成功',
+      reconnectResumed: '已从中断的任务继续执行（{{count}} 个任务）',
+      reconnectGaveUp: '重连 {{max}} 次均失败，已放弃',
+      reconnectCancelled: '自动重连已取消: {{reason}}',
+      postRunCountdown: '{{seconds}} 秒后执行运行结束后的动作: {{actions}}',
+      postRunCancelled: '已取消运行结束后的动作: {{reason}}',
+      postRunExecuting: '执行运行结束后的动作: {{action}}',
+      postRunActionFailed: '{{action}}失败: {{error}}',
+      postRunCompleted: '运行结束后的动作已执行完毕',
     },
   },
 
@@ -299,6 +344,9 @@ export default {
     openConfigDir: '打开配置目录',
     openLogDir: '打开日志目录',
     clearCache: '清空缓存',
+    reloadMaafw: '重新加载 MaaFramework',
+    reloadMaafwHint: '停止所有任务并断开连接，重新加载 maafw 目录中的库',
+    reloadMaafwFailed: '重新加载失败：{{error}}',
     cacheCleared: '缓存已清空',
     cacheStats: '缓存条目: {{count}} 条',
     devMode: '开发模式',
@@ -307,6 +355,7 @@ export default {
     saveDrawHint: '保存识别和操作的调试图像到日志目录（重启软件后自动关闭）',
     tcpCompatMode: '通信兼容模式',
     tcpCompatModeHint: '若启动任务后软件立即闪退，可尝试开启。仅限此情况使用，否则会影响运行效率',
+    tcpCompatModeUnsupported: '当前 MaaFramework 版本不支持 TCP 通信，无法开启',
   },
 
   // 欢迎弹窗
@@ -329,6 +378,10 @@ export default {
   // 连接面板
   connection: {
     title: '连接设置',
+    autoReconnect: '自动重连',
+    autoReconnectHint: '断线后按退避间隔重新连接',
+    resumeTasks: '重连后继续任务',
+    maxAttempts: '最多重试',
   },
 
   // 中控台
@@ -472,7 +525,15 @@ export default {
     everyHour: '每小时',
     all: '全部',
     hoursSelected: '个时间点',
-    timeZoneHint: '使用本地时区 (UTC+8)',
+    modeWeekly: '按星期和时间',
+    modeCron: 'cron 表达式',
+    cronHint: '分 时 日 月 周，支持 * , - /，周日为 0 或 7',
+    minute: '分钟',
+    catchUp: '错过时（未运行或系统睡眠）恢复后补执行一次',
+    nextRuns: '下次执行：{{times}}',
+    noNextRun: '近期不会执行',
+    timeZone: '时区',
+    localTime: '本机时区',
     multiSelect: '可多选',
     enable: '启用策略',
     disable: '禁用策略',
@@ -487,6 +548,92 @@ export default {
     weekdays: ['周日', '周一', '周二', '周三', '周四', '周五', '周六'],
   },
 
+  // 循环执行
+  runLoop: {
+    title: '循环执行',
+    modes: {
+      once: '执行一次',
+      repeat: '重复执行',
+      until_deadline: '循环到截止时间',
+      until_task: '循环到指定任务结束',
+    },
+    times: '次数',
+    deadline: '截止时间',
+    succeeded: '成功',
+    failed: '失败',
+    delay: '每轮间隔(秒)',
+    iteration: '第 {{iteration}} 轮',
+    hint: '一轮结束后自动开始下一轮，停止任务会结束循环',
+  },
+
+  // 每日完成记录
+  dailyLedger: {
+    title: '每日完成记录',
+    skipCompleted: '启动时跳过本周期内已完成的任务',
+    resetTime: '每日重置',
+    localTime: '本机时区',
+    completedAt: '{{time}} 完成',
+    notCompleted: '未完成',
+    clear: '清除记录',
+    clearAll: '全部清除',
+    nextReset: '下次重置：{{time}}',
+    hint: '任务成功后记录完成时间，重置后重新计算',
+  },
+  preRun: {
+    title: '启动前准备',
+    program: '程序路径',
+    args: '参数（空格分隔）',
+    cwd: '工作目录（可选）',
+    waitFor: '等待',
+    ready: {
+      none: '不等待',
+      adb_device: 'ADB 设备出现',
+      window: '窗口出现',
+    },
+    address: 'ADB 地址，如 127.0.0.1:16384',
+    classRegex: '窗口类名正则',
+    windowRegex: '窗口标题正则',
+    timeout: '超时',
+    settle: '就绪后等待',
+    seconds: '秒',
+    skipIfReady: '已就绪时不再启动',
+    addStep: '添加步骤',
+    removeStep: '删除步骤',
+    cancel: '中止',
+    hint: '连接设备前依次执行，用于启动模拟器或游戏',
+    progress: {
+      skipped: '步骤 {{step}}：目标已就绪，跳过启动',
+      launching: '步骤 {{step}}：正在启动程序',
+      waiting: '步骤 {{step}}：等待就绪（{{time}} 超时）',
+      ready: '步骤 {{step}}：已就绪',
+      failed: '步骤 {{step}} 失败: {{error}}',
+      cancelled: '启动前准备已中止',
+      completed: '启动前准备已完成',
+    },
+  },
+  postRun: {
+    title: '运行结束后',
+    actions: {
+      stop_agent: '停止 Agent',
+      run_command: '运行命令',
+      start_instance: '启动实例',
+      shutdown: '关机',
+      suspend: '睡眠',
+      exit_app: '退出 MXU',
+    },
+    program: '程序路径',
+    args: '参数（空格分隔）',
+    cwd: '工作目录（可选）',
+    power: '电源',
+    none: '无',
+    countdown: '执行前倒计时',
+    seconds: '秒',
+    onFailure: '有任务失败时也执行',
+    pending: '{{seconds}} 秒后执行',
+    cancel: '取消',
+    hint: '任务列表全部执行完后按顺序执行，手动停止时不执行',
+  },
+
   // 错误消息
   errors: {
     loadInterfaceFailed: '加载 interface.json 失败',
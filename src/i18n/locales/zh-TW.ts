@@ -88,6 +88,10 @@ export default {
     stopTasks: '停止任務',
     startingTasks: '啟動中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '暫停任務',
+    resumeTasks: '繼續任務',
+    pauseTasksHint: '停止目前任務並暫停，繼續時從該任務重新執行',
+    resumeTasksHint: '依序繼續執行剩餘任務',
     // 自動連接相关
     autoConnect: {
       searching: '搜尋裝置...',
@@ -95,6 +99,7 @@ export default {
       connecting: '連接裝置...',
       connectingWindow: '連接視窗...',
       loadingResource: '載入資源...',
+      preRun: '執行啟動前準備...',
       deviceNotFound: '未找到裝置: {{name}}',
       windowNotFound: '未找到視窗: {{name}}',
       noSavedDevice: '沒有儲存的裝置設定',
@@ -131,10 +136,29 @@ export default {
       running: '執行中',
       succeeded: '已完成',
       failed: '執行失敗',
+      skipped: '已略過',
     },
     // 任務相容性
     incompatibleController: '不支援目前控制器',
     incompatibleResource: '不支援目前資源',
+    // 重試與逾時
+    runPolicy: '重試與逾時',
+    retryCount: '失敗重試次數',
+    retryDelay: '重試間隔(秒)',
+    timeout: '逾時(分鐘)',
+    timeoutHint: '下次啟動時生效，0 表示不重試/不限時',
+    // 執行條件
+    conditions: '執行條件',
+    conditionAdd: '新增條件',
+    conditionTypes: {
+      after_task: '前置任務',
+      time_window: '時間段',
+      weekdays: '星期',
+      not_succeeded_today: '今天尚未成功',
+    },
+    conditionSucceeded: '成功後',
+    conditionFailed: '失敗後',
+    conditionHint: '下次啟動時生效，條件不滿足時略過該任務；時間依排程執行的時區判斷',
   },
 
   // 選項
@@ -251,12 +275,33 @@ export default {
       stopTask: '停止任務',
       // 定時任務訊息
       scheduleStarting: '定時執行開始 [{{policy}}] {{time}}',
+      scheduleCatchUp: '補執行錯過的定時策略 [{{policy}}] {{time}}',
+      scheduleStarted: '定時執行已啟動任務 [{{policy}}]',
+      scheduleFailed: '定時執行啟動失敗 [{{policy}}]: {{error}}',
+      scheduleSkipped: {
+        missed: '已錯過定時策略 [{{policy}}] {{time}}，未開啟補執行',
+        running: '實例正在執行，跳過定時策略 [{{policy}}] {{time}}',
+        no_tasks: '沒有啟用的任務，跳過定時策略 [{{policy}}] {{time}}',
+      },
       // Agent 訊息
       agentStarting: 'Agent 正在啟動...',
       agentStarted: 'Agent 已啟動',
       agentConnected: 'Agent 已連接',
       agentDisconnected: 'Agent 已中斷',
       agentFailed: 'Agent 啟動失敗',
+      // 自動重連訊息
+      reconnectDisconnected: '控制器連接中斷: {{reason}}',
+      reconnectAttempting: '{{delay}} 秒後進行第 {{attempt}}/{{max}} 次重連...',
+      reconnectAttemptFailed: '第 {{attempt}} 次重連失敗: {{error}}',
+      reconnectSucceeded: '重連成功',
+      reconnectResumed: '已從中斷的任務繼續執行（{{count}} 個任務）',
+      reconnectGaveUp: '重連 {{max}} 次均失敗，已放棄',
+      reconnectCancelled: '自動重連已取消: {{reason}}',
+      postRunCountdown: '{{seconds}} 秒後執行執行結束後的動作: {{actions}}',
+      postRunCancelled: '已取消執行結束後的動作: {{reason}}',
+      postRunExecuting: '執行執行結束後的動作: {{action}}',
+      postRunActionFailed: '{{action}}失敗: {{error}}',
+      postRunCompleted: '執行結束後的動作已執行完畢',
     },
   },
 
@@ -299,6 +344,9 @@ export default {
     openConfigDir: '開啟設定目錄',
     openLogDir: '開啟日誌目錄',
     clearCache: '清空快取',
+    reloadMaafw: '重新載入 MaaFramework',
+    reloadMaafwHint: '停止所有任務並中斷連線，重新載入 maafw 目錄中的程式庫',
+    reloadMaafwFailed: '重新載入失敗：{{error}}',
     cacheCleared: '快取已清空',
     cacheStats: '快取項目: {{count}} 條',
     devMode: '開發模式',
@@ -307,6 +355,7 @@ export default {
     saveDrawHint: '儲存識別和操作的除錯圖像到日誌目錄（重啟軟體後自動關閉）',
     tcpCompatMode: '通訊相容模式',
     tcpCompatModeHint: '若啟動任務後軟體立即閃退，可嘗試開啟。僅限此情況使用，否則會影響運行效率',
+    tcpCompatModeUnsupported: '目前 MaaFramework 版本不支援 TCP 通訊，無法開啟',
   },
 
   // 欢迎彈窗
@@ -329,6 +378,10 @@ export default {
   // 連接面板
   connection: {
     title: '連接設定',
+    autoReconnect: '自動重連',
+    autoReconnectHint: '斷線後按退避間隔重新連接',
+    resumeTasks: '重連後繼續任務',
+    maxAttempts: '最多重試',
   },
 
   // 中控台
@@ -461,7 +514,15 @@ export default {
     everyHour: '每小時',
     all: '全部',
     hoursSelected: '個時間点',
-    timeZoneHint: '使用本地時區 (UTC+8)',
+    modeWeekly: '按星期和時間',
+    modeCron: 'cron 表達式',
+    cronHint: '分 時 日 月 週，支援 * , - /，週日為 0 或 7',
+    minute: '分鐘',
+    catchUp: '錯過時（未執行或系統睡眠）恢復後補執行一次',
+    nextRuns: '下次執行：{{times}}',
+    noNextRun: '近期不會執行',
+    timeZone: '時區',
+    localTime: '本機時區',
     multiSelect: '可多選',
     enable: '啟用策略',
     disable: '停用策略',
@@ -476,6 +537,92 @@ export default {
     weekdays: ['週日', '週一', '週二', '週三', '週四', '週五', '週六'],
   },
 
+  // 循環執行
+  runLoop: {
+    title: '循環執行',
+    modes: {
+      once: '執行一次',
+      repeat: '重複執行',
+      until_deadline: '循環到截止時間',
+      until_task: '循環到指定任務結束',
+    },
+    times: '次數',
+    deadline: '截止時間',
+    succeeded: '成功',
+    failed: '失敗',
+    delay: '每輪間隔(秒)',
+    iteration: '第 {{iteration}} 輪',
+    hint: '一輪結束後自動開始下一輪，停止任務會結束循環',
+  },
+
+  // 每日完成紀錄
+  dailyLedger: {
+    title: '每日完成紀錄',
+    skipCompleted: '啟動時略過本週期內已完成的任務',
+    resetTime: '每日重置',
+    localTime: '本機時區',
+    completedAt: '{{time}} 完成',
+    notCompleted: '未完成',
+    clear: '清除紀錄',
+    clearAll: '全部清除',
+    nextReset: '下次重置：{{time}}',
+    hint: '任務成功後記錄完成時間，重置後重新計算',
+  },
+  preRun: {
+    title: '啟動前準備',
+    program: '程式路徑',
+    args: '參數（空格分隔）',
+    cwd: '工作目錄（選填）',
+    waitFor: '等待',
+    ready: {
+      none: '不等待',
+      adb_device: 'ADB 裝置出現',
+      window: '視窗出現',
+    },
+    address: 'ADB 位址，如 127.0.0.1:16384',
+    classRegex: '視窗類別名稱正規表示式',
+    windowRegex: '視窗標題正規表示式',
+    timeout: '逾時',
+    settle: '就緒後等待',
+    seconds: '秒',
+    skipIfReady: '已就緒時不再啟動',
+    addStep: '新增步驟',
+    removeStep: '刪除步驟',
+    cancel: '中止',
+    hint: '連接裝置前依序執行，用於啟動模擬器或遊戲',
+    progress: {
+      skipped: '步驟 {{step}}：目標已就緒，略過啟動',
+      launching: '步驟 {{step}}：正在啟動程式',
+      waiting: '步驟 {{step}}：等待就緒（{{time}} 逾時）',
+      ready: '步驟 {{step}}：已就緒',
+      failed: '步驟 {{step}} 失敗: {{error}}',
+      cancelled: '啟動前準備已中止',
+      completed: '啟動前準備已完成',
+    },
+  },
+  postRun: {
+    title: '執行結束後',
+    actions: {
+      stop_agent: '停止 Agent',
+      run_command: '執行命令',
+      start_instance: '啟動實例',
+      shutdown: '關機',
+      suspend: '睡眠',
+      exit_app: '退出 MXU',
+    },
+    program: '程式路徑',
+    args: '參數（空格分隔）',
+    cwd: '工作目錄（選填）',
+    power: '電源',
+    none: '無',
+    countdown: '執行前倒數',
+    seconds: '秒',
+    onFailure: '有任務失敗時也執行',
+    pending: '{{seconds}} 秒後執行',
+    cancel: '取消',
+    hint: '任務清單全部執行完後依序執行，手動停止時不執行',
+  },
+
   // 錯誤訊息
   errors: {
     loadInterfaceFailed: '載入 interface.json 失敗',
@@ -10,6 +10,7 @@
  */
 
 import { loggers } from '@/utils/logger';
+import { getDataDirs } from '@/utils/dataDirs';
 
 const log = loggers.app;
 
@@ -65,9 +66,13 @@ function urlToFilename(url: string): string {
 }
 
 /**
- * 获取缓存目录路径
+ * 获取缓存目录路径（便携模式为 exe同目录/cache，安装模式为用户缓存目录）
  */
-function getCacheDir(basePath: string): string {
+async function getCacheDir(basePath: string): Promise<string> {
+  const dataDirs = await getDataDirs();
+  if (dataDirs) {
+    return dataDirs.cache_dir;
+  }
   if (basePath === '' || basePath === '.') {
     return `./${CACHE_DIR}`;
   }
@@ -78,15 +83,15 @@ function getCacheDir(basePath: string): string {
 /**
  * 获取缓存索引文件路径
  */
-function getCacheIndexPath(basePath: string): string {
-  return `${getCacheDir(basePath)}/${CACHE_INDEX_FILE}`;
+async function getCacheIndexPath(basePath: string): Promise<string> {
+  return `${await getCacheDir(basePath)}/${CACHE_INDEX_FILE}`;
 }
 
 /**
  * 获取缓存数据文件路径
  */
-function getCacheDataPath(basePath: string, filename: string): string {
-  return `${getCacheDir(basePath)}/${filename}`;
+async function getCacheDataPath(basePath: string, filename: string): Promise<string> {
+  return `${await getCacheDir(basePath)}/${filename}`;
 }
 
 /**
@@ -107,7 +112,7 @@ async function loadCacheIndex(basePath: string): Promise<CacheIndex> {
 
   try {
     const { readTextFile, exists } = await import('@tauri-apps/plugin-fs');
-    const indexPath = getCacheIndexPath(basePath);
+    const indexPath = await getCacheIndexPath(basePath);
 
     if (await exists(indexPath)) {
       const content = await readTextFile(indexPath);
@@ -133,8 +138,8 @@ async function saveCacheIndex(basePath: string): Promise<void> {
 
   try {
     const { writeTextFile, mkdir, exists } = await import('@tauri-apps/plugin-fs');
-    const cacheDir = getCacheDir(basePath);
-    const indexPath = getCacheIndexPath(basePath);
+    const cacheDir = await getCacheDir(basePath);
+    const indexPath = await getCacheIndexPath(basePath);
 
     if (!(await exists(cacheDir))) {
       await mkdir(cacheDir, { recursive: true });
@@ -154,7 +159,7 @@ async function readCacheData(basePath: string, filename: string): Promise<string
 
   try {
     const { readTextFile, exists } = await import('@tauri-apps/plugin-fs');
-    const dataPath = getCacheDataPath(basePath, filename);
+    const dataPath = await getCacheDataPath(basePath, filename);
 
     if (await exists(dataPath)) {
       return await readTextFile(dataPath);
@@ -173,13 +178,13 @@ async function writeCacheData(basePath: string, filename: string, data: string):
 
   try {
     const { writeTextFile, mkdir, exists } = await import('@tauri-apps/plugin-fs');
-    const cacheDir = getCacheDir(basePath);
+    const cacheDir = await getCacheDir(basePath);
 
     if (!(await exists(cacheDir))) {
       await mkdir(cacheDir, { recursive: true });
     }
 
-    const dataPath = getCacheDataPath(basePath, filename);
+    const dataPath = await getCacheDataPath(basePath, filename);
     await writeTextFile(dataPath, data);
   } catch (err) {
     log.warn('写入缓存数据失败:', err);
@@ -343,7 +348,7 @@ export async function cleanExpiredCache(
     // 删除过期的缓存文件和索引条目
     for (const url of expiredUrls) {
       const entry = index.entries[url];
-      const dataPath = getCacheDataPath(basePath, entry.filename);
+      const dataPath = await getCacheDataPath(basePath, entry.filename);
 
       if (await exists(dataPath)) {
         await remove(dataPath);
@@ -366,7 +371,7 @@ export async function clearAllCache(basePath: string = '.'): Promise<void> {
 
   try {
     const { remove, exists } = await import('@tauri-apps/plugin-fs');
-    const cacheDir = getCacheDir(basePath);
+    const cacheDir = await getCacheDir(basePath);
 
     if (await exists(cacheDir)) {
       await remove(cacheDir, { recursive: true });
@@ -2,6 +2,7 @@ import type { MxuConfig } from '@/types/config';
 import { defaultConfig } from '@/types/config';
 import { loggers } from '@/utils/logger';
 import { parseJsonc } from '@/utils/jsonc';
+import { getDataDirs } from '@/utils/dataDirs';
 
 const log = loggers.config;
 
@@ -25,9 +26,13 @@ const isTauri = () => {
 };
 
 /**
- * 获取配置目录路径（exe同目录/config）
+ * 获取配置目录路径（便携模式为 exe同目录/config，安装模式为用户配置目录）
  */
-function getConfigDir(basePath: string): string {
+async function getConfigDir(basePath: string): Promise<string> {
+  const dataDirs = await getDataDirs();
+  if (dataDirs) {
+    return dataDirs.config_dir;
+  }
   if (basePath === '' || basePath === '.') {
     return `./${CONFIG_DIR}`;
   }
@@ -39,8 +44,8 @@ function getConfigDir(basePath: string): string {
 /**
  * 获取配置文件完整路径
  */
-function getConfigPath(basePath: string, projectName?: string): string {
-  const configDir = getConfigDir(basePath);
+async function getConfigPath(basePath: string, projectName?: string): Promise<string> {
+  const configDir = await getConfigDir(basePath);
   const fileName = getConfigFileName(projectName);
   return `${configDir}/${fileName}`;
 }
@@ -52,7 +57,7 @@ function getConfigPath(basePath: string, projectName?: string): string {
  */
 export async function loadConfig(basePath: string, projectName?: string): Promise<MxuConfig> {
   if (isTauri()) {
-    const configPath = getConfigPath(basePath, projectName);
+    const configPath = await getConfigPath(basePath, projectName);
 
     log.debug('加载配置, 路径:', configPath);
 
@@ -118,8 +123,8 @@ export async function saveConfig(
     }
   }
 
-  const configDir = getConfigDir(basePath);
-  const configPath = getConfigPath(basePath, projectName);
+  const configDir = await getConfigDir(basePath);
+  const configPath = await getConfigPath(basePath, projectName);
 
   log.debug('保存配置, 路径:', configPath);
 
@@ -1,5 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { ProjectInterface } from '@/types/interface';
+import type { InterfaceIssue, ProjectInterface } from '@/types/interface';
 import { loggers } from '@/utils/logger';
 import { parseJsonc } from '@/utils/jsonc';
 
@@ -160,6 +160,25 @@ function getDirectoryFromPath(filePath: string): string {
   return filePath.substring(0, lastSlash);
 }
 
+/**
+ * 由后端校验 interface.json 并输出发现的问题（Tauri 环境），校验失败不影响加载
+ */
+async function reportInterfaceIssues(): Promise<void> {
+  try {
+    const issues = await invoke<InterfaceIssue[]>('maa_validate_interface');
+    for (const issue of issues) {
+      const text = `interface.json ${issue.path}: ${issue.message}`;
+      if (issue.severity === 'error') {
+        log.error(text);
+      } else {
+        log.warn(text);
+      }
+    }
+  } catch (err) {
+    log.warn('校验 interface.json 失败:', err);
+  }
+}
+
 /**
  * 加载 interface.json
  *
@@ -183,6 +202,7 @@ export async function autoLoadInterface(): Promise<LoadResult> {
     log.info('basePath (绝对路径):', basePath);
 
     const pi = await loadInterfaceFromLocal(interfacePath);
+    await reportInterfaceIssues();
     const translations = await loadTranslationsFromLocal(pi, relativeBasePath);
     return { interface: pi, translations, basePath };
   }
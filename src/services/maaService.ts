@@ -12,19 +12,92 @@ import type {
   AgentConfig,
   TaskConfig,
   InstanceRuntimeInfo,
+  InstanceLifecycle,
+  BackendInstanceState,
+  MaaCapabilities,
+  MaaInitResult,
+  ReconnectPolicy,
+  ReconnectEvent,
+  QueuedTask,
+  RunLoop,
+  RunProgress,
+  DayReset,
+  TaskLedger,
+  RunEvent,
+  PostRunConfig,
+  PostRunEvent,
+  PreRunStep,
+  PreRunEvent,
+  InstanceSchedule,
+  ScheduleEvent,
+  PipelineOverrideError,
+  PipelineInspection,
 } from '@/types/maa';
+import type { OptionValue, SchedulePolicy } from '@/types/interface';
 import { loggers } from '@/utils/logger';
 
 const log = loggers.maa;
 
+/** 回调来源对象类型 */
+export type MaaCallbackSource = 'resource' | 'controller' | 'tasker' | 'context';
+
 /** MaaFramework 回调事件载荷 */
 export interface MaaCallbackEvent {
+  /** 事件所属实例 ID */
+  instance_id: string;
+  /** 事件来源对象类型 */
+  source: MaaCallbackSource;
   /** 消息类型，如 "Resource.Loading.Succeeded", "Controller.Action.Succeeded", "Tasker.Task.Succeeded" */
   message: string;
-  /** 详细数据 JSON 字符串 */
-  details: string;
+  /** 详细数据（后端已解析的 JSON，无法解析时为 null） */
+  details: MaaCallbackDetails | null;
+  /** 后端解析的强类型消息 */
+  event: MaaEvent;
+}
+
+/** maa-instance-state 事件载荷 */
+export interface InstanceStateEvent {
+  instance_id: string;
+  /** 变化前的生命周期状态 */
+  previous: InstanceLifecycle;
+  state: BackendInstanceState;
+}
+
+/** maa-task-queue 事件载荷 */
+export interface TaskQueueEvent {
+  instance_id: string;
+  tasks: QueuedTask[];
+  /** 循环进度，未循环时为 null */
+  run: RunProgress | null;
+}
+
+/** maa-library-reloaded 事件载荷 */
+export interface LibraryReloadedEvent {
+  lib_dir: string;
+  version: string;
 }
 
+/** 消息阶段 */
+export type MaaEventPhase = 'Starting' | 'Succeeded' | 'Failed';
+
+/** 后端解析的强类型消息（kind 区分消息族，未识别的消息为 Unknown） */
+export type MaaEvent =
+  | {
+      kind:
+        | 'ResourceLoading'
+        | 'ControllerAction'
+        | 'TaskerTask'
+        | 'PipelineNode'
+        | 'RecognitionNode'
+        | 'ActionNode'
+        | 'NextList'
+        | 'Recognition'
+        | 'Action';
+      phase: MaaEventPhase;
+      detail: MaaCallbackDetails & Record<string, unknown>;
+    }
+  | { kind: 'Unknown'; message: string; details: unknown };
+
 /** 回调消息详情（通用字段） */
 export interface MaaCallbackDetails {
   res_id?: number;
@@ -49,14 +122,17 @@ const isTauri = () => {
 export const maaService = {
   /**
    * 初始化 MaaFramework
-   * @param libDir MaaFramework 库目录（可选，默认从 exe 目录/maafw 加载）
-   * @returns 版本号
+   * @param libDir MaaFramework 库目录（可选，默认按搜索顺序查找）
+   * @returns 版本号、实际使用的目录和搜索结果
    */
-  async init(libDir?: string): Promise<string> {
+  async init(libDir?: string): Promise<MaaInitResult> {
     log.info('初始化 MaaFramework, libDir:', libDir || '(默认)');
-    const version = await invoke<string>('maa_init', { libDir: libDir || null });
-    log.info('MaaFramework 版本:', version);
-    return version;
+    const result = await invoke<MaaInitResult>('maa_init', { libDir: libDir || null });
+    for (const candidate of result.search?.rejected ?? []) {
+      log.debug(`跳过 MaaFramework 目录 ${candidate.path} (${candidate.source}): ${candidate.reason}`);
+    }
+    log.info('MaaFramework 目录:', result.lib_dir, ', 版本:', result.version);
+    return result;
   },
 
   /**
@@ -92,6 +168,28 @@ export const maaService = {
     return result;
   },
 
+  /**
+   * 重新加载 MaaFramework（停止任务并销毁所有实例的连接和资源，之后需重新连接）
+   * @param libDir 新的库目录，为空时重新加载当前目录
+   * @returns 新的版本号
+   */
+  async reloadLibrary(libDir?: string): Promise<string> {
+    log.info('重新加载 MaaFramework:', libDir || '(当前目录)');
+    const version = await invoke<string>('maa_reload_library', { libDir: libDir || null });
+    log.info('MaaFramework 重新加载完成, 版本:', version);
+    return version;
+  },
+
+  /**
+   * 获取 MaaFramework 运行时能力（加载路径、可选函数、禁用的功能）
+   */
+  async getCapabilities(): Promise<MaaCapabilities> {
+    log.debug('获取 MaaFramework 能力...');
+    const capabilities = await invoke<MaaCapabilities>('maa_get_capabilities');
+    log.info('MaaFramework 能力:', capabilities);
+    return capabilities;
+  },
+
   /**
    * 查找 ADB 设备
    */
@@ -237,16 +335,20 @@ export const maaService = {
   },
 
   /**
-   * 运行任务
+   * 运行任务（追加到后端任务队列末尾）
    * @param instanceId 实例 ID
    * @param entry 任务入口
    * @param pipelineOverride Pipeline 覆盖 JSON
-   * @returns 任务 ID
+   * @param label 显示名称
+   * @param sourceId 前端任务列表中对应的任务 ID
+   * @returns 队列项 ID
    */
   async runTask(
     instanceId: string,
     entry: string,
     pipelineOverride: string = '{}',
+    label?: string,
+    sourceId?: string,
   ): Promise<number> {
     log.info(
       '运行任务, 实例:',
@@ -259,13 +361,15 @@ export const maaService = {
     if (!isTauri()) {
       return Math.floor(Math.random() * 10000);
     }
-    const taskId = await invoke<number>('maa_run_task', {
+    const itemId = await invoke<number>('maa_run_task', {
       instanceId,
       entry,
       pipelineOverride,
+      label: label ?? null,
+      sourceId: sourceId ?? null,
     });
-    log.info('任务已提交, taskId:', taskId);
-    return taskId;
+    log.info('任务已加入队列, itemId:', itemId);
+    return itemId;
   },
 
   /**
@@ -292,6 +396,73 @@ export const maaService = {
     log.info('停止任务请求已发送');
   },
 
+  /**
+   * 暂停任务队列
+   * @param instanceId 实例 ID
+   * @param immediate 为 true 时立即停止当前任务（恢复后可重新执行），否则等当前任务结束
+   */
+  async pauseTasks(instanceId: string, immediate: boolean): Promise<void> {
+    log.info('暂停任务, 实例:', instanceId, ', 立即:', immediate);
+    if (!isTauri()) return;
+    await invoke('maa_pause_tasks', { instanceId, immediate });
+  },
+
+  /**
+   * 恢复暂停的任务队列
+   * @param instanceId 实例 ID
+   * @param rerunInterrupted 是否重新执行暂停时被中断的任务
+   * @returns 将执行的任务的队列项 ID
+   */
+  async resumeTasks(instanceId: string, rerunInterrupted: boolean): Promise<number[]> {
+    log.info('恢复任务, 实例:', instanceId, ', 重新执行中断的任务:', rerunInterrupted);
+    if (!isTauri()) return [];
+    return await invoke<number[]>('maa_resume_tasks', { instanceId, rerunInterrupted });
+  },
+
+  /**
+   * 由后端根据 interface 中的任务和选项定义生成 Pipeline 覆盖
+   * @param taskName interface 中的任务名
+   * @param optionValues 任务的选项值
+   * @returns Pipeline 覆盖 JSON（数组格式）
+   * @throws 输入值不符合 verify 等错误时，错误信息为各项错误的合并
+   */
+  async buildPipelineOverride(
+    taskName: string,
+    optionValues: Record<string, OptionValue>,
+  ): Promise<string> {
+    if (!isTauri()) return '[]';
+    try {
+      return await invoke<string>('maa_build_pipeline_override', {
+        task: taskName,
+        optionValues,
+      });
+    } catch (err) {
+      if (Array.isArray(err)) {
+        const errors = err as PipelineOverrideError[];
+        throw new Error(errors.map((e) => e.message).join('; '));
+      }
+      throw err;
+    }
+  },
+
+  /**
+   * 查看合并 Pipeline 覆盖后入口及其可达节点的最终定义，以及各字段来自哪一层
+   * @param entry 入口节点名
+   * @param pipelineOverride Pipeline 覆盖 JSON（对象或对象数组）
+   * @param resourcePaths 资源目录（按加载顺序）
+   */
+  async inspectPipeline(
+    entry: string,
+    pipelineOverride: string,
+    resourcePaths: string[],
+  ): Promise<PipelineInspection> {
+    return await invoke<PipelineInspection>('maa_inspect_pipeline', {
+      entry,
+      pipelineOverride,
+      resourcePaths,
+    });
+  },
+
   /**
    * 覆盖已提交任务的 Pipeline 配置（用于运行中修改尚未执行的任务选项）
    * @param instanceId 实例 ID
@@ -322,6 +493,118 @@ export const maaService = {
     return success;
   },
 
+  /**
+   * 获取实例的后端任务队列
+   * @param instanceId 实例 ID
+   */
+  async getTaskQueue(instanceId: string): Promise<QueuedTask[]> {
+    if (!isTauri()) return [];
+    return await invoke<QueuedTask[]>('maa_get_task_queue', { instanceId });
+  },
+
+  /**
+   * 在任务队列中插入任务（只能插入到尚未开始的任务之间）
+   * @param instanceId 实例 ID
+   * @param tasks 任务列表
+   * @param index 插入位置，省略时追加到末尾
+   * @returns 新任务的队列项 ID
+   */
+  async insertQueuedTasks(
+    instanceId: string,
+    tasks: TaskConfig[],
+    index?: number,
+  ): Promise<number[]> {
+    log.info(
+      '插入队列任务, 实例:',
+      instanceId,
+      ', 位置:',
+      index ?? 'end',
+      ', 任务数:',
+      tasks.length,
+    );
+    if (!isTauri()) return [];
+    return await invoke<number[]>('maa_queue_insert', {
+      instanceId,
+      index: index ?? null,
+      tasks,
+    });
+  },
+
+  /**
+   * 从任务队列中删除尚未开始的任务
+   * @param instanceId 实例 ID
+   * @param itemId 队列项 ID
+   */
+  async removeQueuedTask(instanceId: string, itemId: number): Promise<void> {
+    log.info('删除队列任务, 实例:', instanceId, ', itemId:', itemId);
+    if (!isTauri()) return;
+    await invoke('maa_queue_remove', { instanceId, itemId });
+  },
+
+  /**
+   * 调整尚未开始的任务的顺序
+   * @param instanceId 实例 ID
+   * @param itemIds 所有尚未开始的任务的队列项 ID（新顺序）
+   */
+  async reorderQueuedTasks(instanceId: string, itemIds: number[]): Promise<void> {
+    log.info('调整队列顺序, 实例:', instanceId, ', itemIds:', itemIds);
+    if (!isTauri()) return;
+    await invoke('maa_queue_reorder', { instanceId, itemIds });
+  },
+
+  /**
+   * 修改队列中任务的 Pipeline 覆盖（尚未开始或正在运行的任务）
+   * @param instanceId 实例 ID
+   * @param itemId 队列项 ID
+   * @param pipelineOverride Pipeline 覆盖 JSON
+   */
+  async updateQueuedTaskOverride(
+    instanceId: string,
+    itemId: number,
+    pipelineOverride: string,
+  ): Promise<void> {
+    log.info(
+      '修改队列任务 Pipeline 覆盖, 实例:',
+      instanceId,
+      ', itemId:',
+      itemId,
+      ', override:',
+      pipelineOverride,
+    );
+    if (!isTauri()) return;
+    await invoke('maa_queue_update_override', { instanceId, itemId, pipelineOverride });
+  },
+
+  /**
+   * 监听后端任务队列变化
+   * @param callback 回调函数，接收实例 ID 和完整的任务队列
+   */
+  async onTaskQueue(
+    callback: (instanceId: string, tasks: QueuedTask[], run: RunProgress | null) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<TaskQueueEvent>('maa-task-queue', (event) => {
+      callback(event.payload.instance_id, event.payload.tasks, event.payload.run);
+    });
+  },
+
+  /**
+   * 监听实例一次运行（含所有循环轮次）的开始和结束
+   * @param callback 回调函数，参数为 maa-run 事件载荷
+   */
+  async onRun(callback: (event: RunEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<RunEvent>('maa-run', (event) => {
+      callback(event.payload);
+    });
+  },
+
   /**
    * 检查是否正在运行
    * @param instanceId 实例 ID
@@ -363,7 +646,10 @@ export const maaService = {
    * @param agentConfig Agent 配置（可选）
    * @param cwd 工作目录（Agent 子进程的 CWD）
    * @param tcpCompatMode 通信兼容模式（强制使用 TCP）
-   * @returns 任务 ID 列表
+   * @param runLoop 循环设置（可选，默认只执行一次）
+   * @param skipCompleted 跳过本周期内已完成的任务（按每日完成记录）
+   * @param timeZone 执行条件中时间段和星期几所在的时区（IANA 名称），undefined 表示本机时区
+   * @returns 第一轮的队列项 ID 列表（任务由后端队列逐个提交）
    */
   async startTasks(
     instanceId: string,
@@ -371,6 +657,9 @@ export const maaService = {
     agentConfig?: AgentConfig,
     cwd?: string,
     tcpCompatMode?: boolean,
+    runLoop?: RunLoop,
+    skipCompleted?: boolean,
+    timeZone?: string,
   ): Promise<number[]> {
     log.info('启动任务, 实例:', instanceId, ', 任务数:', tasks.length, ', cwd:', cwd || '.');
     tasks.forEach((task, i) => {
@@ -382,15 +671,18 @@ export const maaService = {
     if (!isTauri()) {
       return tasks.map((_, i) => i + 1);
     }
-    const taskIds = await invoke<number[]>('maa_start_tasks', {
+    const itemIds = await invoke<number[]>('maa_start_tasks', {
       instanceId,
       tasks,
       agentConfig: agentConfig || null,
       cwd: cwd || '.',
       tcpCompatMode: tcpCompatMode || false,
+      runLoop: runLoop || null,
+      skipCompleted: skipCompleted || false,
+      timezone: timeZone ?? null,
     });
-    log.info('任务已提交, taskIds:', taskIds);
-    return taskIds;
+    log.info('任务已加入队列, itemIds:', itemIds);
+    return itemIds;
   },
 
   /**
@@ -406,7 +698,7 @@ export const maaService = {
 
   /**
    * 监听 MaaFramework 回调事件
-   * @param callback 回调函数，接收消息类型和详情
+   * @param callback 回调函数，接收消息类型、详情和事件元信息（所属实例、来源）
    * @returns 取消监听的函数
    *
    * 常见消息类型：
@@ -417,7 +709,11 @@ export const maaService = {
    * - Node.Action.Starting/Succeeded/Failed - 节点动作状态
    */
   async onCallback(
-    callback: (message: string, details: MaaCallbackDetails) => void,
+    callback: (
+      message: string,
+      details: MaaCallbackDetails,
+      meta: { instanceId: string; source: MaaCallbackSource },
+    ) => void,
   ): Promise<UnlistenFn> {
     if (!isTauri()) {
       // 非 Tauri 环境返回空函数
@@ -425,16 +721,14 @@ export const maaService = {
     }
 
     return await listen<MaaCallbackEvent>('maa-callback', (event) => {
-      const { message, details } = event.payload;
-      //   log.debug('MaaCallback:', message, details);
-
-      try {
-        const parsedDetails = JSON.parse(details) as MaaCallbackDetails;
-        callback(message, parsedDetails);
-      } catch {
-        log.warn('Failed to parse callback details:', details);
-        callback(message, {});
+      const { instance_id, source, message, details } = event.payload;
+      //   log.debug('MaaCallback:', instance_id, source, message, details);
+      const meta = { instanceId: instance_id, source };
+
+      if (!details) {
+        log.warn('Callback details unavailable:', message);
       }
+      callback(message, details ?? {}, meta);
     });
   },
 
@@ -486,20 +780,221 @@ export const maaService = {
   },
 
   /**
-   * 获取单个实例的运行时状态（通过 Maa API 实时查询）
+   * 监听后端实例生命周期状态变化（替代轮询 getInstanceState）
+   * @param callback 回调函数，接收实例 ID、新状态和变化前的生命周期状态
+   * @returns 取消监听的函数
+   */
+  async onInstanceState(
+    callback: (
+      instanceId: string,
+      state: BackendInstanceState,
+      previous: InstanceLifecycle,
+    ) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<InstanceStateEvent>('maa-instance-state', (event) => {
+      const { instance_id, state, previous } = event.payload;
+      callback(instance_id, state, previous);
+    });
+  },
+
+  /**
+   * 监听 MaaFramework 重新加载完成
+   * @param callback 回调函数，参数为新的版本号和库目录
+   */
+  async onLibraryReloaded(
+    callback: (version: string, libDir: string) => void,
+  ): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<LibraryReloadedEvent>('maa-library-reloaded', (event) => {
+      callback(event.payload.version, event.payload.lib_dir);
+    });
+  },
+
+  /**
+   * 设置实例的控制器自动重连策略（实例尚未创建时也可设置）
+   * @param instanceId 实例 ID
+   * @param policy 重连策略
+   */
+  async setReconnectPolicy(instanceId: string, policy: ReconnectPolicy): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_set_reconnect_policy', { instanceId, policy });
+  },
+
+  /**
+   * 设置实例每日完成记录的重置时间和时区（实例尚未创建时也可设置）
+   * @param instanceId 实例 ID
+   * @param reset 重置设置
+   */
+  async setDayReset(instanceId: string, reset: DayReset): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_set_day_reset', { instanceId, reset });
+  },
+
+  /**
+   * 获取实例的每日完成记录
+   * @param instanceId 实例 ID
+   */
+  async getTaskLedger(instanceId: string): Promise<TaskLedger | null> {
+    if (!isTauri()) return null;
+    return await invoke<TaskLedger>('maa_get_task_ledger', { instanceId });
+  },
+
+  /**
+   * 清除实例的每日完成记录
+   * @param instanceId 实例 ID
+   * @param taskName 任务名（为空时清除全部任务）
+   */
+  async clearTaskLedger(instanceId: string, taskName?: string): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_clear_task_ledger', { instanceId, taskName: taskName ?? null });
+  },
+
+  /**
+   * 设置实例启动前的准备步骤（实例尚未创建时也可设置）
+   * @param instanceId 实例 ID
+   * @param steps 准备步骤
+   */
+  async setPreRunSteps(instanceId: string, steps: PreRunStep[]): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_set_pre_run_steps', { instanceId, steps });
+  },
+
+  /**
+   * 执行实例启动前的准备步骤，全部就绪后返回，失败或被中止时抛出错误
+   * @param instanceId 实例 ID
+   */
+  async runPreRun(instanceId: string): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_run_pre_run', { instanceId });
+  },
+
+  /**
+   * 中止实例正在执行的启动前准备步骤
+   * @param instanceId 实例 ID
+   * @returns 是否有正在执行的步骤
+   */
+  async cancelPreRun(instanceId: string): Promise<boolean> {
+    if (!isTauri()) return false;
+    return await invoke<boolean>('maa_cancel_pre_run', { instanceId });
+  },
+
+  /**
+   * 监听启动前准备步骤的进度
+   * @param callback 回调函数，参数为 maa-pre-run 事件载荷
+   */
+  async onPreRun(callback: (event: PreRunEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<PreRunEvent>('maa-pre-run', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 设置实例运行结束后的动作（实例尚未创建时也可设置）
+   * @param instanceId 实例 ID
+   * @param config 动作设置
+   */
+  async setPostRunActions(instanceId: string, config: PostRunConfig): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_set_post_run_actions', { instanceId, config });
+  },
+
+  /**
+   * 取消实例正在倒计时的运行结束后动作
+   * @param instanceId 实例 ID
+   * @returns 是否有需要取消的动作
+   */
+  async cancelPostRun(instanceId: string): Promise<boolean> {
+    if (!isTauri()) return false;
+    return await invoke<boolean>('maa_cancel_post_run', { instanceId });
+  },
+
+  /**
+   * 监听运行结束后动作的倒计时和执行进度
+   * @param callback 回调函数，参数为 maa-post-run 事件载荷
+   */
+  async onPostRun(callback: (event: PostRunEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<PostRunEvent>('maa-post-run', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 设置实例的定时执行，由后端按策略准备实例并启动任务（实例尚未创建时也可设置）
+   * @param instanceId 实例 ID
+   * @param schedule 定时策略、启动参数、设备和资源
+   */
+  async setSchedule(instanceId: string, schedule: InstanceSchedule): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('maa_set_schedule', { instanceId, schedule });
+  },
+
+  /**
+   * 预览定时策略接下来的执行时间，cron 表达式有误等无效策略会抛出错误
+   * @param policy 定时策略
+   * @param timeZone 时区（IANA 名称），undefined 表示本机时区
+   * @returns 执行时间（Unix 毫秒）
+   */
+  async previewSchedule(policy: SchedulePolicy, timeZone?: string): Promise<number[]> {
+    if (!isTauri()) return [];
+    return await invoke<number[]>('maa_preview_schedule', {
+      policy,
+      timezone: timeZone ?? null,
+    });
+  },
+
+  /**
+   * 监听定时执行的触发、跳过和启动结果
+   * @param callback 回调函数，参数为 maa-schedule 事件载荷
+   */
+  async onSchedule(callback: (event: ScheduleEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<ScheduleEvent>('maa-schedule', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 监听控制器自动重连进度
+   * @param callback 回调函数，参数为 maa-reconnect 事件载荷
+   */
+  async onReconnect(callback: (event: ReconnectEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) {
+      return () => {};
+    }
+
+    return await listen<ReconnectEvent>('maa-reconnect', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 获取单个实例的运行时状态（与 maa-instance-state 事件使用同一模型）
    * @param instanceId 实例 ID
    */
   async getInstanceState(instanceId: string): Promise<InstanceRuntimeInfo | null> {
     if (!isTauri()) return null;
     try {
-      const state = await invoke<{
-        connected: boolean;
-        resource_loaded: boolean;
-        tasker_inited: boolean;
-        is_running: boolean;
-        task_ids: number[];
-      }>('maa_get_instance_state', { instanceId });
+      const state = await invoke<BackendInstanceState>('maa_get_instance_state', { instanceId });
       return {
+        lifecycle: state.lifecycle,
         connectionStatus: state.connected ? 'Connected' : 'Disconnected',
         resourceLoaded: state.resource_loaded,
         isRunning: state.is_running,
@@ -512,12 +1007,13 @@ export const maaService = {
   },
 
   /**
-   * 获取所有实例的状态快照（通过 Maa API 实时查询，用于启动时恢复状态）
+   * 获取所有实例的状态快照（用于启动时恢复状态）
    */
   async getAllStates(): Promise<{
     instances: Record<
       string,
       {
+        lifecycle: InstanceLifecycle;
         connected: boolean;
         resourceLoaded: boolean;
         taskerInited: boolean;
@@ -525,22 +1021,17 @@ export const maaService = {
         taskIds: number[];
       }
     >;
+    taskQueues: Record<string, QueuedTask[]>;
+    taskRuns: Record<string, RunProgress>;
     cachedAdbDevices: AdbDevice[];
     cachedWin32Windows: Win32Window[];
   } | null> {
     if (!isTauri()) return null;
     try {
       const states = await invoke<{
-        instances: Record<
-          string,
-          {
-            connected: boolean;
-            resource_loaded: boolean;
-            tasker_inited: boolean;
-            is_running: boolean;
-            task_ids: number[];
-          }
-        >;
+        instances: Record<string, BackendInstanceState>;
+        task_queues: Record<string, QueuedTask[]>;
+        task_runs: Record<string, RunProgress>;
         cached_adb_devices: AdbDevice[];
         cached_win32_windows: Win32Window[];
       }>('maa_get_all_states');
@@ -549,6 +1040,7 @@ export const maaService = {
       const instances: Record<
         string,
         {
+          lifecycle: InstanceLifecycle;
           connected: boolean;
           resourceLoaded: boolean;
           taskerInited: boolean;
@@ -559,6 +1051,7 @@ export const maaService = {
 
       for (const [id, state] of Object.entries(states.instances)) {
         instances[id] = {
+          lifecycle: state.lifecycle,
           connected: state.connected,
           resourceLoaded: state.resource_loaded,
           taskerInited: state.tasker_inited,
@@ -569,6 +1062,8 @@ export const maaService = {
 
       return {
         instances,
+        taskQueues: states.task_queues,
+        taskRuns: states.task_runs,
         cachedAdbDevices: states.cached_adb_devices,
         cachedWin32Windows: states.cached_win32_windows,
       };
@@ -4,6 +4,7 @@
 import type { UpdateChannel, ProxySettings } from '@/types/config';
 import type { UpdateInfo, DownloadProgress } from '@/stores/appStore';
 import { loggers } from '@/utils/logger';
+import { getDataDirs } from '@/utils/dataDirs';
 import { fetch as tauriFetch } from '@tauri-apps/plugin-http';
 import { openUrl } from '@tauri-apps/plugin-opener';
 import { exists } from '@tauri-apps/plugin-fs';
@@ -698,12 +699,16 @@ export async function checkAndPrepareDownload(
 }
 
 /**
- * 获取更新包保存路径
+ * 获取更新包保存路径（缓存目录下，安装模式为用户缓存目录）
  */
 export async function getUpdateSavePath(basePath: string, filename?: string): Promise<string> {
   const os = getOS();
   const ext = os === 'windows' ? '.zip' : '.tar.gz';
   const name = filename || `update${ext}`;
+  const dataDirs = await getDataDirs();
+  if (dataDirs) {
+    return await join(dataDirs.cache_dir, name);
+  }
   return await join(basePath, 'cache', name);
 }
 
@@ -25,10 +25,25 @@ import {
   defaultScreenshotFrameRate,
 } from '@/types/config';
 import { findSwitchCase } from '@/utils/optionHelpers';
+import { timeZoneFromOffset } from '@/utils/timeZone';
 
 // 最近关闭列表最大条目数
 const MAX_RECENTLY_CLOSED = 30;
-import type { ConnectionStatus, TaskStatus, AdbDevice, Win32Window } from '@/types/maa';
+import type {
+  ConnectionStatus,
+  TaskStatus,
+  AdbDevice,
+  Win32Window,
+  InstanceLifecycle,
+  MaaCapabilities,
+  QueuedTask,
+  QueuedTaskStatus,
+  PostRunEvent,
+  PreRunEvent,
+  RunProgress,
+  TaskCondition,
+  TaskRunPolicy,
+} from '@/types/maa';
 import { saveConfig } from '@/services/configService';
 import i18n, { getInterfaceLangKey } from '@/i18n';
 import {
@@ -43,7 +58,17 @@ import {
 import { loggers } from '@/utils/logger';
 
 /** 单个任务的运行状态 */
-export type TaskRunStatus = 'idle' | 'pending' | 'running' | 'succeeded' | 'failed';
+export type TaskRunStatus = 'idle' | 'pending' | 'running' | 'succeeded' | 'failed' | 'skipped';
+
+/** 后端队列状态对应的任务运行状态（取消的任务显示为未运行） */
+const queuedTaskRunStatus: Record<QueuedTaskStatus, TaskRunStatus> = {
+  Pending: 'pending',
+  Running: 'running',
+  Succeeded: 'succeeded',
+  Failed: 'failed',
+  Cancelled: 'idle',
+  Skipped: 'skipped',
+};
 
 /** 日志条目类型 */
 export type LogType = 'info' | 'success' | 'warning' | 'error' | 'agent' | 'focus';
@@ -121,6 +146,8 @@ interface AppState {
   selectAllTasks: (instanceId: string, enabled: boolean) => void;
   collapseAllTasks: (instanceId: string, expanded: boolean) => void;
   renameTask: (instanceId: string, taskId: string, newName: string) => void;
+  setTaskRunPolicy: (instanceId: string, taskId: string, policy: TaskRunPolicy) => void;
+  setTaskConditions: (instanceId: string, taskId: string, conditions: TaskCondition[]) => void;
 
   // 任务右键菜单操作
   duplicateTask: (instanceId: string, taskId: string) => void;
@@ -166,15 +193,20 @@ interface AppState {
   maaInitialized: boolean;
   maaVersion: string | null;
   setMaaInitialized: (initialized: boolean, version?: string) => void;
+  maaCapabilities: MaaCapabilities | null;
+  setMaaCapabilities: (capabilities: MaaCapabilities | null) => void;
 
   // 实例运行时状态
   instanceConnectionStatus: Record<string, ConnectionStatus>;
   instanceResourceLoaded: Record<string, boolean>;
   instanceCurrentTaskId: Record<string, number | null>;
   instanceTaskStatus: Record<string, TaskStatus | null>;
+  /** 任务队列被用户暂停（生命周期为 Paused） */
+  instancePaused: Record<string, boolean>;
 
   setInstanceConnectionStatus: (instanceId: string, status: ConnectionStatus) => void;
   setInstanceResourceLoaded: (instanceId: string, loaded: boolean) => void;
+  setInstancePaused: (instanceId: string, paused: boolean) => void;
   setInstanceCurrentTaskId: (instanceId: string, taskId: number | null) => void;
   setInstanceTaskStatus: (instanceId: string, status: TaskStatus | null) => void;
 
@@ -198,6 +230,7 @@ interface AppState {
     instances: Record<
       string,
       {
+        lifecycle: InstanceLifecycle;
         connected: boolean;
         resourceLoaded: boolean;
         taskerInited: boolean;
@@ -205,6 +238,8 @@ interface AppState {
         taskIds: number[];
       }
     >;
+    taskQueues: Record<string, QueuedTask[]>;
+    taskRuns: Record<string, RunProgress>;
     cachedAdbDevices: AdbDevice[];
     cachedWin32Windows: Win32Window[];
   }) => void;
@@ -323,25 +358,30 @@ interface AppState {
   registerMaaTaskMapping: (instanceId: string, maaTaskId: number, selectedTaskId: string) => void;
   // 根据 maaTaskId 查找 selectedTaskId
   findSelectedTaskIdByMaaTaskId: (instanceId: string, maaTaskId: number) => string | null;
-  // 根据 selectedTaskId 查找 maaTaskId（反向查找）
-  findMaaTaskIdBySelectedTaskId: (instanceId: string, selectedTaskId: string) => number | null;
   // 清空实例的任务运行状态
   clearTaskRunStatus: (instanceId: string) => void;
 
-  // 运行中任务队列管理
-  instancePendingTaskIds: Record<string, number[]>;
-  instanceCurrentTaskIndex: Record<string, number>;
-  setPendingTaskIds: (instanceId: string, taskIds: number[]) => void;
-  appendPendingTaskId: (instanceId: string, taskId: number) => void;
-  setCurrentTaskIndex: (instanceId: string, index: number) => void;
-  advanceCurrentTaskIndex: (instanceId: string) => void;
-  clearPendingTasks: (instanceId: string) => void;
+  // 后端任务队列（由 maa-task-queue 事件同步，任务运行状态和当前任务 ID 据此推导）
+  instanceTaskQueue: Record<string, QueuedTask[]>;
+  // 循环进度（未循环时为 null）
+  instanceTaskRun: Record<string, RunProgress | null>;
+  setInstanceTaskQueue: (instanceId: string, tasks: QueuedTask[], run: RunProgress | null) => void;
+  // 根据 selectedTaskId 查找队列中最近的对应任务
+  findQueuedTask: (instanceId: string, selectedTaskId: string) => QueuedTask | null;
 
   // 定时执行状态
   scheduleExecutions: Record<string, ScheduleExecutionInfo>;
   setScheduleExecution: (instanceId: string, info: ScheduleExecutionInfo | null) => void;
   clearScheduleExecution: (instanceId: string) => void;
 
+  // 正在执行的启动前准备步骤（由 maa-pre-run 事件同步）
+  preRunProgress: Record<string, PreRunEvent>;
+  setPreRunProgress: (instanceId: string, event: PreRunEvent | null) => void;
+
+  // 正在倒计时的运行结束后动作（由 maa-post-run 事件同步）
+  postRunCountdowns: Record<string, PostRunEvent>;
+  setPostRunCountdown: (instanceId: string, event: PostRunEvent | null) => void;
+
   // 日志管理
   instanceLogs: Record<string, LogEntry[]>;
   addLog: (instanceId: string, log: Omit<LogEntry, 'id' | 'timestamp'>) => void;
@@ -477,6 +517,47 @@ const initializeAllOptionValues = (
   return result;
 };
 
+/**
+ * 根据后端任务队列推导任务运行状态、maaTaskId 映射和当前任务 ID
+ * 任务运行状态只由队列决定；映射只追加，已结束任务的迟到回调仍能找到任务名
+ */
+function applyTaskQueue(
+  state: AppState,
+  instanceId: string,
+  tasks: QueuedTask[],
+  run: RunProgress | null,
+): Partial<AppState> {
+  const runStatus: Record<string, TaskRunStatus> = {};
+  const mapping = { ...state.maaTaskIdMapping[instanceId] };
+  const taskIdToName = { ...state.taskIdToName };
+  let currentTaskId: number | null = null;
+
+  for (const task of tasks) {
+    if (task.source_id) {
+      runStatus[task.source_id] = queuedTaskRunStatus[task.status];
+    }
+    if (task.task_id === null) continue;
+    if (task.source_id) {
+      mapping[task.task_id] = task.source_id;
+    }
+    if (task.label) {
+      taskIdToName[task.task_id] = task.label;
+    }
+    if (task.status === 'Running') {
+      currentTaskId = task.task_id;
+    }
+  }
+
+  return {
+    instanceTaskQueue: { ...state.instanceTaskQueue, [instanceId]: tasks },
+    instanceTaskRun: { ...state.instanceTaskRun, [instanceId]: run },
+    instanceTaskRunStatus: { ...state.instanceTaskRunStatus, [instanceId]: runStatus },
+    maaTaskIdMapping: { ...state.maaTaskIdMapping, [instanceId]: mapping },
+    instanceCurrentTaskId: { ...state.instanceCurrentTaskId, [instanceId]: currentTaskId },
+    taskIdToName,
+  };
+}
+
 export const useAppStore = create<AppState>()(
   subscribeWithSelector((set, get) => ({
     // 主题和语言
@@ -682,8 +763,17 @@ export const useAppStore = create<AppState>()(
               customName: t.customName,
               enabled: t.enabled,
               optionValues: t.optionValues,
+              runPolicy: t.runPolicy,
+              conditions: t.conditions,
             })),
             schedulePolicies: instanceToClose.schedulePolicies,
+            scheduleTimeZone: instanceToClose.scheduleTimeZone,
+            reconnectPolicy: instanceToClose.reconnectPolicy,
+            runLoop: instanceToClose.runLoop,
+            dayReset: instanceToClose.dayReset,
+            skipCompleted: instanceToClose.skipCompleted,
+            postRun: instanceToClose.postRun,
+            preRun: instanceToClose.preRun,
           };
           // 添加到列表头部，并限制最大条目数
           newRecentlyClosed = [closedRecord, ...state.recentlyClosed].slice(0, MAX_RECENTLY_CLOSED);
@@ -895,6 +985,36 @@ export const useAppStore = create<AppState>()(
         ),
       })),
 
+    setTaskRunPolicy: (instanceId, taskId, policy) =>
+      set((state) => ({
+        instances: state.instances.map((i) =>
+          i.id === instanceId
+            ? {
+                ...i,
+                selectedTasks: i.selectedTasks.map((t) =>
+                  t.id === taskId ? { ...t, runPolicy: policy } : t,
+                ),
+              }
+            : i,
+        ),
+      })),
+
+    setTaskConditions: (instanceId, taskId, conditions) =>
+      set((state) => ({
+        instances: state.instances.map((i) =>
+          i.id === instanceId
+            ? {
+                ...i,
+                selectedTasks: i.selectedTasks.map((t) =>
+                  t.id === taskId
+                    ? { ...t, conditions: conditions.length > 0 ? conditions : undefined }
+                    : t,
+                ),
+              }
+            : i,
+        ),
+      })),
+
     // 复制任务
     duplicateTask: (instanceId, taskId) => {
       const state = get();
@@ -1122,6 +1242,8 @@ export const useAppStore = create<AppState>()(
           customName: t.customName,
           enabled: t.enabled,
           optionValues: t.optionValues,
+          runPolicy: t.runPolicy,
+          conditions: t.conditions,
           expanded: false,
         }));
 
@@ -1136,6 +1258,19 @@ export const useAppStore = create<AppState>()(
           selectedTasks: savedTasks,
           isRunning: false,
           schedulePolicies: inst.schedulePolicies,
+          scheduleTimeZone: inst.scheduleTimeZone ?? timeZoneFromOffset(inst.scheduleUtcOffset),
+          reconnectPolicy: inst.reconnectPolicy,
+          runLoop: inst.runLoop,
+          dayReset: inst.dayReset && {
+            reset_time: inst.dayReset.reset_time,
+            timezone:
+              inst.dayReset.timezone ??
+              timeZoneFromOffset(inst.dayReset.utc_offset_minutes) ??
+              null,
+          },
+          skipCompleted: inst.skipCompleted,
+          postRun: inst.postRun,
+          preRun: inst.preRun,
         };
       });
 
@@ -1217,12 +1352,15 @@ export const useAppStore = create<AppState>()(
         maaInitialized: initialized,
         maaVersion: version || null,
       }),
+    maaCapabilities: null,
+    setMaaCapabilities: (capabilities) => set({ maaCapabilities: capabilities }),
 
     // 实例运行时状态
     instanceConnectionStatus: {},
     instanceResourceLoaded: {},
     instanceCurrentTaskId: {},
     instanceTaskStatus: {},
+    instancePaused: {},
 
     setInstanceConnectionStatus: (instanceId, status) =>
       set((state) => ({
@@ -1240,6 +1378,14 @@ export const useAppStore = create<AppState>()(
         },
       })),
 
+    setInstancePaused: (instanceId, paused) =>
+      set((state) => ({
+        instancePaused: {
+          ...state.instancePaused,
+          [instanceId]: paused,
+        },
+      })),
+
     setInstanceCurrentTaskId: (instanceId, taskId) =>
       set((state) => ({
         instanceCurrentTaskId: {
@@ -1340,6 +1486,7 @@ export const useAppStore = create<AppState>()(
         const connectionStatus: Record<string, ConnectionStatus> = {};
         const resourceLoaded: Record<string, boolean> = {};
         const taskStatus: Record<string, TaskStatus | null> = {};
+        const paused: Record<string, boolean> = {};
 
         // 更新实例的 isRunning 状态
         const updatedInstances = currentState.instances.map((instance) => {
@@ -1348,7 +1495,10 @@ export const useAppStore = create<AppState>()(
             // 只有当后端有正在运行的任务时，才恢复 isRunning 状态
             // taskIds 为空表示用户已停止任务（MaaTaskerPostStop 清空了 task_ids，
             // 但 MaaTaskerRunning 可能在回调完成前仍返回 true）
-            const isRunning = backendState.isRunning && backendState.taskIds.length > 0;
+            // 暂停的实例仍有剩余任务，同样视为运行中
+            const isRunning =
+              (backendState.isRunning && backendState.taskIds.length > 0) ||
+              backendState.lifecycle.state === 'Paused';
             return {
               ...instance,
               isRunning,
@@ -1360,17 +1510,32 @@ export const useAppStore = create<AppState>()(
         for (const [instanceId, state] of Object.entries(states.instances)) {
           connectionStatus[instanceId] = state.connected ? 'Connected' : 'Disconnected';
           resourceLoaded[instanceId] = state.resourceLoaded;
+          paused[instanceId] = state.lifecycle.state === 'Paused';
           // 同样检查 taskIds，避免显示错误的运行状态
           if (state.isRunning && state.taskIds.length > 0) {
             taskStatus[instanceId] = 'Running';
           }
         }
 
+        // 恢复任务队列，刷新后仍能看到等待中、运行中和已结束的任务
+        let queueState: AppState = currentState;
+        for (const [instanceId, tasks] of Object.entries(states.taskQueues)) {
+          const run = states.taskRuns[instanceId] ?? null;
+          queueState = { ...queueState, ...applyTaskQueue(queueState, instanceId, tasks, run) };
+        }
+
         return {
           instances: updatedInstances,
           instanceConnectionStatus: connectionStatus,
           instanceResourceLoaded: resourceLoaded,
           instanceTaskStatus: taskStatus,
+          instancePaused: paused,
+          instanceTaskQueue: queueState.instanceTaskQueue,
+          instanceTaskRun: queueState.instanceTaskRun,
+          instanceTaskRunStatus: queueState.instanceTaskRunStatus,
+          maaTaskIdMapping: queueState.maaTaskIdMapping,
+          instanceCurrentTaskId: queueState.instanceCurrentTaskId,
+          taskIdToName: queueState.taskIdToName,
           cachedAdbDevices: states.cachedAdbDevices,
           cachedWin32Windows: states.cachedWin32Windows,
         };
@@ -1517,10 +1682,19 @@ export const useAppStore = create<AppState>()(
           customName: t.customName,
           enabled: t.enabled,
           optionValues: t.optionValues,
+          runPolicy: t.runPolicy,
+          conditions: t.conditions,
           expanded: false,
         })),
         isRunning: false,
         schedulePolicies: closedInstance.schedulePolicies,
+        scheduleTimeZone: closedInstance.scheduleTimeZone,
+        reconnectPolicy: closedInstance.reconnectPolicy,
+        runLoop: closedInstance.runLoop,
+        dayReset: closedInstance.dayReset,
+        skipCompleted: closedInstance.skipCompleted,
+        postRun: closedInstance.postRun,
+        preRun: closedInstance.preRun,
       };
 
       // 恢复选中的控制器和资源状态
@@ -1597,19 +1771,6 @@ export const useAppStore = create<AppState>()(
       return mapping?.[maaTaskId] || null;
     },
 
-    findMaaTaskIdBySelectedTaskId: (instanceId, selectedTaskId) => {
-      const state = get();
-      const mapping = state.maaTaskIdMapping[instanceId];
-      if (!mapping) return null;
-      // 反向查找：遍历 mapping 找到 value 等于 selectedTaskId 的 key
-      for (const [maaTaskIdStr, taskId] of Object.entries(mapping)) {
-        if (taskId === selectedTaskId) {
-          return parseInt(maaTaskIdStr, 10);
-        }
-      }
-      return null;
-    },
-
     clearTaskRunStatus: (instanceId) =>
       set((state) => ({
         instanceTaskRunStatus: {
@@ -1622,53 +1783,20 @@ export const useAppStore = create<AppState>()(
         },
       })),
 
-    // 运行中任务队列管理
-    instancePendingTaskIds: {},
-    instanceCurrentTaskIndex: {},
-
-    setPendingTaskIds: (instanceId, taskIds) =>
-      set((state) => ({
-        instancePendingTaskIds: {
-          ...state.instancePendingTaskIds,
-          [instanceId]: taskIds,
-        },
-      })),
-
-    appendPendingTaskId: (instanceId, taskId) =>
-      set((state) => ({
-        instancePendingTaskIds: {
-          ...state.instancePendingTaskIds,
-          [instanceId]: [...(state.instancePendingTaskIds[instanceId] || []), taskId],
-        },
-      })),
+    // 后端任务队列
+    instanceTaskQueue: {},
+    instanceTaskRun: {},
 
-    setCurrentTaskIndex: (instanceId, index) =>
-      set((state) => ({
-        instanceCurrentTaskIndex: {
-          ...state.instanceCurrentTaskIndex,
-          [instanceId]: index,
-        },
-      })),
-
-    advanceCurrentTaskIndex: (instanceId) =>
-      set((state) => ({
-        instanceCurrentTaskIndex: {
-          ...state.instanceCurrentTaskIndex,
-          [instanceId]: (state.instanceCurrentTaskIndex[instanceId] || 0) + 1,
-        },
-      })),
+    setInstanceTaskQueue: (instanceId, tasks, run) =>
+      set((state) => applyTaskQueue(state, instanceId, tasks, run)),
 
-    clearPendingTasks: (instanceId) =>
-      set((state) => ({
-        instancePendingTaskIds: {
-          ...state.instancePendingTaskIds,
-          [instanceId]: [],
-        },
-        instanceCurrentTaskIndex: {
-          ...state.instanceCurrentTaskIndex,
-          [instanceId]: 0,
-        },
-      })),
+    findQueuedTask: (instanceId, selectedTaskId) => {
+      const queue = get().instanceTaskQueue[instanceId] || [];
+      for (let i = queue.length - 1; i >= 0; i--) {
+        if (queue[i].source_id === selectedTaskId) return queue[i];
+      }
+      return null;
+    },
 
     // 定时执行状态
     scheduleExecutions: {},
@@ -1689,6 +1817,30 @@ export const useAppStore = create<AppState>()(
         ),
       })),
 
+    // 启动前准备步骤的进度
+    preRunProgress: {},
+
+    setPreRunProgress: (instanceId, event) =>
+      set((state) => ({
+        preRunProgress: event
+          ? { ...state.preRunProgress, [instanceId]: event }
+          : Object.fromEntries(
+              Object.entries(state.preRunProgress).filter(([id]) => id !== instanceId),
+            ),
+      })),
+
+    // 运行结束后动作的倒计时
+    postRunCountdowns: {},
+
+    setPostRunCountdown: (instanceId, event) =>
+      set((state) => ({
+        postRunCountdowns: event
+          ? { ...state.postRunCountdowns, [instanceId]: event }
+          : Object.fromEntries(
+              Object.entries(state.postRunCountdowns).filter(([id]) => id !== instanceId),
+            ),
+      })),
+
     // 日志管理
     instanceLogs: {},
 
@@ -1773,8 +1925,17 @@ function generateConfig(): MxuConfig {
         customName: t.customName,
         enabled: t.enabled,
         optionValues: t.optionValues,
+        runPolicy: t.runPolicy,
+        conditions: t.conditions,
       })),
       schedulePolicies: inst.schedulePolicies,
+      scheduleTimeZone: inst.scheduleTimeZone,
+      reconnectPolicy: inst.reconnectPolicy,
+      runLoop: inst.runLoop,
+      dayReset: inst.dayReset,
+      skipCompleted: inst.skipCompleted,
+      postRun: inst.postRun,
+      preRun: inst.preRun,
     })),
     settings: {
       theme: state.theme,
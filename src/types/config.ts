@@ -2,6 +2,15 @@
 
 import type { OptionValue } from './interface';
 import type { AccentColor, CustomAccent } from '@/themes/types';
+import type {
+  DayReset,
+  PostRunConfig,
+  PreRunStep,
+  ReconnectPolicy,
+  RunLoop,
+  TaskCondition,
+  TaskRunPolicy,
+} from './maa';
 
 // 定时执行策略
 export interface SchedulePolicy {
@@ -10,6 +19,9 @@ export interface SchedulePolicy {
   enabled: boolean; // 是否启用
   weekdays: number[]; // 重复日期 (0-6, 0=周日)
   hours: number[]; // 开始时间 (0-23)
+  minute?: number; // 整点后的分钟 (0-59)，未设置时为整点
+  cron?: string; // cron 表达式（分 时 日 月 周），设置后忽略星期、小时和分钟
+  catchUp?: boolean; // 错过的时间点（MXU 未运行或系统睡眠）在检查到时补执行一次
 }
 
 // 保存的任务配置
@@ -19,6 +31,8 @@ export interface SavedTask {
   customName?: string; // 用户自定义名称
   enabled: boolean;
   optionValues: Record<string, OptionValue>;
+  runPolicy?: TaskRunPolicy; // 重试与超时设置
+  conditions?: TaskCondition[]; // 执行条件
 }
 
 // 保存的设备信息
@@ -45,6 +59,23 @@ export interface SavedInstance {
   tasks: SavedTask[];
   // 定时执行策略列表
   schedulePolicies?: SchedulePolicy[];
+  // 定时执行策略的时区（IANA 名称），未设置时使用本机时区
+  scheduleTimeZone?: string;
+  // 旧版本保存的时区（相对 UTC 的偏移分钟数），加载时转换为 scheduleTimeZone
+  scheduleUtcOffset?: number;
+  // 控制器自动重连策略，未设置时使用默认策略
+  reconnectPolicy?: ReconnectPolicy;
+  // 任务列表循环设置，未设置时只执行一次
+  runLoop?: RunLoop;
+  // 每日完成记录的重置设置，未设置时使用默认设置
+  // （旧版本保存的是相对 UTC 的偏移分钟数 utc_offset_minutes，加载时转换为 timezone）
+  dayReset?: DayReset & { utc_offset_minutes?: number | null };
+  // 启动时跳过本周期内已完成的任务
+  skipCompleted?: boolean;
+  // 运行结束后的动作，未设置时不执行
+  postRun?: PostRunConfig;
+  // 启动前的准备步骤（启动模拟器或游戏并等待就绪）
+  preRun?: PreRunStep[];
 }
 
 // 窗口大小配置
@@ -65,6 +96,13 @@ export interface RecentlyClosedInstance {
   savedDevice?: SavedDeviceInfo;
   tasks: SavedTask[]; // 保存的任务配置
   schedulePolicies?: SchedulePolicy[]; // 定时执行策略
+  scheduleTimeZone?: string; // 定时执行策略的时区
+  reconnectPolicy?: ReconnectPolicy; // 控制器自动重连策略
+  runLoop?: RunLoop; // 任务列表循环设置
+  dayReset?: DayReset; // 每日完成记录的重置设置
+  skipCompleted?: boolean; // 跳过本周期内已完成的任务
+  postRun?: PostRunConfig; // 运行结束后的动作
+  preRun?: PreRunStep[]; // 启动前的准备步骤
 }
 
 // MirrorChyan 更新频道
@@ -1,5 +1,15 @@
 // MaaFramework ProjectInterface V2 协议类型定义
 
+import type {
+  DayReset,
+  PostRunConfig,
+  PreRunStep,
+  ReconnectPolicy,
+  RunLoop,
+  TaskCondition,
+  TaskRunPolicy,
+} from './maa';
+
 export interface ProjectInterface {
   interface_version: 2;
   languages?: Record<string, string>;
@@ -141,6 +151,14 @@ export interface InputOption {
 
 export type OptionDefinition = SelectOption | SwitchOption | InputOption;
 
+// interface.json 校验结果（后端 maa_validate_interface 返回）
+export interface InterfaceIssue {
+  severity: 'error' | 'warning';
+  kind: string;
+  path: string; // JSON 路径，如 $.task[2].option[0]
+  message: string;
+}
+
 // 运行时状态类型
 export interface SelectedTask {
   id: string;
@@ -148,6 +166,8 @@ export interface SelectedTask {
   customName?: string; // 用户自定义名称
   enabled: boolean;
   optionValues: Record<string, OptionValue>;
+  runPolicy?: TaskRunPolicy; // 重试与超时设置
+  conditions?: TaskCondition[]; // 执行条件
   expanded: boolean;
 }
 
@@ -170,6 +190,9 @@ export interface SchedulePolicy {
   enabled: boolean; // 是否启用
   weekdays: number[]; // 重复日期 (0-6, 0=周日)
   hours: number[]; // 开始时间 (0-23)
+  minute?: number; // 整点后的分钟 (0-59)，未设置时为整点
+  cron?: string; // cron 表达式（分 时 日 月 周），设置后忽略星期、小时和分钟
+  catchUp?: boolean; // 错过的时间点（MXU 未运行或系统睡眠）在检查到时补执行一次
 }
 
 // 多开实例状态
@@ -187,6 +210,20 @@ export interface Instance {
   isRunning: boolean;
   // 定时执行策略列表
   schedulePolicies?: SchedulePolicy[];
+  // 定时执行策略的时区（IANA 名称），未设置时使用本机时区
+  scheduleTimeZone?: string;
+  // 控制器自动重连策略
+  reconnectPolicy?: ReconnectPolicy;
+  // 任务列表循环设置
+  runLoop?: RunLoop;
+  // 每日完成记录的重置设置
+  dayReset?: DayReset;
+  // 启动时跳过本周期内已完成的任务
+  skipCompleted?: boolean;
+  // 运行结束后的动作
+  postRun?: PostRunConfig;
+  // 启动前的准备步骤
+  preRun?: PreRunStep[];
 }
 
 // 翻译文件类型
@@ -1,5 +1,7 @@
 // MaaFramework 类型定义
 
+import type { SchedulePolicy } from './interface';
+
 /** ADB 设备信息 */
 export interface AdbDevice {
   name: string;
@@ -68,8 +70,113 @@ export interface MaaInitState {
   error: string | null;
 }
 
+/** MaaFramework 运行库候选目录（maa_init 的搜索结果） */
+export interface MaafwCandidate {
+  path: string;
+  source: 'cli_flag' | 'env_var' | 'exe_dir' | 'xdg_data' | 'system';
+  /** 被跳过的原因，选中的目录为 null */
+  reason: string | null;
+}
+
+/** maa_init 返回结果 */
+export interface MaaInitResult {
+  version: string;
+  /** 实际使用的库目录 */
+  lib_dir: string;
+  /** 自动搜索结果，指定 libDir 时为 null */
+  search: { chosen: MaafwCandidate | null; rejected: MaafwCandidate[] } | null;
+}
+
+/** 依赖可选函数的功能（旧版 MaaFramework 可能缺失） */
+export type MaaFeature = 'override_pipeline' | 'tcp_agent';
+
+/** MaaFramework 运行时能力报告（maa_get_capabilities） */
+export interface MaaCapabilities {
+  backend: 'library' | 'simulator' | null;
+  lib_dir: string | null;
+  library_paths: string[];
+  version: string | null;
+  parsed_version: { major: number; minor: number; patch: number; pre: string } | null;
+  optional_symbols: { name: string; feature: MaaFeature; available: boolean }[];
+  missing_symbols: string[];
+  load_error: string | null;
+  disabled_features: MaaFeature[];
+}
+
+/** 控制器自动重连策略（按实例配置） */
+export interface ReconnectPolicy {
+  enabled: boolean;
+  /** 最大重连次数 */
+  max_attempts: number;
+  /** 第一次重连前的等待时间（毫秒），之后每次翻倍 */
+  initial_backoff_ms: number;
+  /** 等待时间上限（毫秒） */
+  max_backoff_ms: number;
+  /** 重连成功后是否从中断的任务继续执行 */
+  resume_tasks: boolean;
+}
+
+/** 默认自动重连策略（与后端默认值一致） */
+export const defaultReconnectPolicy: ReconnectPolicy = {
+  enabled: false,
+  max_attempts: 5,
+  initial_backoff_ms: 2000,
+  max_backoff_ms: 60000,
+  resume_tasks: true,
+};
+
+/** 自动重连阶段 */
+export type ReconnectPhase =
+  | 'disconnected'
+  | 'attempting'
+  | 'attempt_failed'
+  | 'succeeded'
+  | 'resumed'
+  | 'gave_up'
+  | 'cancelled';
+
+/** maa-reconnect 事件载荷 */
+export interface ReconnectEvent {
+  instance_id: string;
+  phase: ReconnectPhase;
+  attempt: number;
+  max_attempts: number;
+  delay_ms: number;
+  error: string | null;
+  /** phase 为 disconnected 时，重连成功后将继续执行的任务（队列项 ID） */
+  interrupted_tasks: number[];
+  /** phase 为 resumed 时将继续执行的任务（队列项 ID），为空表示未能继续 */
+  resumed_tasks: number[];
+}
+
 /** 实例运行时信息 */
+/** 实例生命周期状态（后端状态机） */
+export type InstanceLifecycle =
+  | {
+      state:
+        | 'Idle'
+        | 'Connecting'
+        | 'Connected'
+        | 'LoadingResource'
+        | 'Ready'
+        | 'Running'
+        | 'Paused'
+        | 'Stopping';
+    }
+  | { state: 'Failed'; stage: 'Connection' | 'Resource' | 'Tasker'; reason: string };
+
+/** 后端实例状态（maa_get_instance_state 与 maa-instance-state 事件共用） */
+export interface BackendInstanceState {
+  lifecycle: InstanceLifecycle;
+  connected: boolean;
+  resource_loaded: boolean;
+  tasker_inited: boolean;
+  is_running: boolean;
+  task_ids: number[];
+}
+
 export interface InstanceRuntimeInfo {
+  lifecycle: InstanceLifecycle;
   connectionStatus: ConnectionStatus;
   resourceLoaded: boolean;
   isRunning: boolean;
@@ -155,4 +262,362 @@ export interface AgentConfig {
 export interface TaskConfig {
   entry: string;
   pipeline_override: string;
+  /** interface 中的任务名，每日完成记录以此为键 */
+  task_name?: string;
+  /** 显示名称 */
+  label?: string;
+  /** 前端任务列表中对应的任务 ID（SelectedTask.id） */
+  source_id?: string;
+  /** 失败后最多重试的次数 */
+  retry_count?: number;
+  /** 重试前的等待时间（毫秒） */
+  retry_delay_ms?: number;
+  /** 单次执行的最长时间（毫秒），超时后停止任务 */
+  timeout_ms?: number;
+  /** 执行条件，提交前检查，不满足时跳过 */
+  conditions?: TaskCondition[];
+}
+
+/** 生成 Pipeline 覆盖时的错误（maa_build_pipeline_override 返回） */
+export interface PipelineOverrideError {
+  kind: 'interface' | 'unknown_task' | 'verify_failed' | 'invalid_number' | 'cyclic_option';
+  /** 出错的选项名 */
+  option: string | null;
+  /** 出错的输入名 */
+  input: string | null;
+  message: string;
+}
+
+/** Pipeline 合并的一层（资源目录或 pipeline_override 数组中的一项） */
+export interface PipelineLayer {
+  kind: 'resource' | 'override';
+  /** 资源目录，或覆盖项在数组中的位置（如 $[2]） */
+  source: string;
+}
+
+/** 合并后的节点 */
+export interface InspectedNode {
+  name: string;
+  definition: Record<string, unknown>;
+  /** 字段 -> 最后设置该字段的层（layers 的下标），param 中的字段为 recognition.param.xxx */
+  field_layers: Record<string, number>;
+  /** 最后定义该节点的 pipeline 文件（只在覆盖中出现的节点为 null） */
+  file: string | null;
+}
+
+/** maa_inspect_pipeline 返回的合并结果 */
+export interface PipelineInspection {
+  entry: string;
+  layers: PipelineLayer[];
+  /** 从入口开始按广度优先顺序 */
+  nodes: InspectedNode[];
+  /** 被引用但没有定义的节点 */
+  missing: string[];
+}
+
+/** 任务的重试与超时设置（按任务配置，原样传入 TaskConfig） */
+export type TaskRunPolicy = Pick<TaskConfig, 'retry_count' | 'retry_delay_ms' | 'timeout_ms'>;
+
+/** 任务执行条件（按本地时间检查） */
+export type TaskCondition =
+  /** 前面的指定任务（SelectedTask.id 或入口名）最近一次以 outcome 结束 */
+  | { type: 'after_task'; task: string; outcome: 'Succeeded' | 'Failed' }
+  /** 只在 start 到 end（"HH:MM"）之间执行，end 早于 start 时跨越午夜 */
+  | { type: 'time_window'; start: string; end: string }
+  /** 只在指定的星期几执行（0 为周日） */
+  | { type: 'weekdays'; days: number[] }
+  /** 今天尚未成功执行过 */
+  | { type: 'not_succeeded_today' };
+
+/** 后端任务队列中任务的状态 */
+export type QueuedTaskStatus =
+  | 'Pending'
+  | 'Running'
+  | 'Succeeded'
+  | 'Failed'
+  | 'Cancelled'
+  | 'Skipped';
+
+/** 队列任务的一次提交 */
+export interface TaskAttempt {
+  task_id: number;
+  started_at: number;
+  finished_at: number | null;
+  status: QueuedTaskStatus;
+  /** 因超时被停止 */
+  timed_out: boolean;
+}
+
+/** 后端任务队列中的任务（maa_get_task_queue 与 maa-task-queue 事件共用） */
+export interface QueuedTask {
+  /** 队列项 ID，不同于 MaaFramework 的任务 ID */
+  id: number;
+  entry: string;
+  task_name: string | null;
+  label: string | null;
+  source_id: string | null;
+  pipeline_override: string;
+  status: QueuedTaskStatus;
+  /** 提交后 MaaFramework 返回的任务 ID */
+  task_id: number | null;
+  /** 加入队列、开始和结束的时间（Unix 毫秒） */
+  enqueued_at: number;
+  started_at: number | null;
+  finished_at: number | null;
+  error: string | null;
+  retry_count: number;
+  retry_delay_ms: number;
+  timeout_ms: number | null;
+  /** 等待重试时，最早可再次提交的时间（Unix 毫秒） */
+  retry_at: number | null;
+  /** 每次提交的记录 */
+  attempts: TaskAttempt[];
+  /** 所属的循环轮次（从 1 开始） */
+  iteration: number;
+  conditions: TaskCondition[];
+  /** 执行条件不满足时的跳过原因 */
+  skip_reason: string | null;
+}
+
+/** 任务列表的循环方式 */
+export type RunMode =
+  | { type: 'once' }
+  | { type: 'repeat'; times: number }
+  /** 循环到截止时间（Unix 毫秒），截止后不再开始新一轮 */
+  | { type: 'until_deadline'; deadline: number }
+  /** 循环到指定任务（SelectedTask.id 或入口名）以 outcome 结束的那一轮为止 */
+  | { type: 'until_task'; task: string; outcome: 'Succeeded' | 'Failed' };
+
+/** 循环设置（按实例配置，随 maa_start_tasks 传入） */
+export interface RunLoop {
+  mode: RunMode;
+  /** 两轮之间的等待时间（毫秒） */
+  iteration_delay_ms: number;
+}
+
+/** 默认只执行一次 */
+export const defaultRunLoop: RunLoop = {
+  mode: { type: 'once' },
+  iteration_delay_ms: 0,
+};
+
+/** 循环进度（maa-task-queue 事件与 maa_get_all_states 共用） */
+export interface RunProgress extends RunLoop {
+  /** 当前轮次（从 1 开始） */
+  iteration: number;
+  /** 等待开始下一轮时，下一轮最早的开始时间（Unix 毫秒） */
+  next_iteration_at: number | null;
+}
+
+/** 每日完成记录的重置设置（按实例配置，同步到后端） */
+export interface DayReset {
+  /** 每日重置时间（"HH:MM"） */
+  reset_time: string;
+  /** 重置时间所在的时区（IANA 名称），null 表示本机时区 */
+  timezone: string | null;
+}
+
+/** 默认每天本机时间 04:00 重置（与后端默认值一致） */
+export const defaultDayReset: DayReset = {
+  reset_time: '04:00',
+  timezone: null,
+};
+
+/** 单个任务的完成记录 */
+export interface TaskLedgerEntry {
+  /** 最近一次成功的时间（Unix 毫秒） */
+  last_success: number;
+  total_successes: number;
+  /** 本周期内已成功 */
+  completed: boolean;
+}
+
+/** maa_get_task_ledger 返回的每日完成记录 */
+export interface TaskLedger {
+  reset: DayReset;
+  /** 当前周期的开始时间和下次重置时间（Unix 毫秒） */
+  period_start: number;
+  next_reset: number;
+  /** 任务名 -> 记录 */
+  tasks: Record<string, TaskLedgerEntry>;
+}
+
+/** 一次运行的结果 */
+export type RunOutcome = 'Succeeded' | 'Failed' | 'Stopped';
+
+/** maa-run 事件载荷：实例的一次运行（含所有循环轮次）开始或结束 */
+export interface RunEvent {
+  instance_id: string;
+  phase: 'started' | 'finished';
+  started_at: number;
+  finished_at: number | null;
+  /** 已执行的轮数 */
+  iterations: number;
+  /** 结束时的运行结果 */
+  outcome: RunOutcome | null;
+  succeeded: number;
+  failed: number;
+  skipped: number;
+  cancelled: number;
+}
+
+/** 运行结束后的动作 */
+export type PostRunAction =
+  | { type: 'exit_app' }
+  | { type: 'run_command'; program: string; args: string[]; cwd: string | null }
+  | { type: 'stop_agent' }
+  | { type: 'shutdown' }
+  | { type: 'suspend' }
+  /** 按上一次的参数启动另一个实例的任务 */
+  | { type: 'start_instance'; instance_id: string };
+
+/** 运行结束后动作设置（按实例配置，同步到后端） */
+export interface PostRunConfig {
+  /** 按顺序执行的动作 */
+  actions: PostRunAction[];
+  /** 执行前的倒计时（秒） */
+  countdown_secs: number;
+  /** 有任务失败时也执行 */
+  on_failure: boolean;
+}
+
+/** 默认不执行任何动作（与后端默认值一致） */
+export const defaultPostRunConfig: PostRunConfig = {
+  actions: [],
+  countdown_secs: 30,
+  on_failure: false,
+};
+
+/** 运行结束后动作的执行阶段 */
+export type PostRunPhase = 'countdown' | 'cancelled' | 'executing' | 'action_failed' | 'completed';
+
+/** maa-post-run 事件载荷 */
+export interface PostRunEvent {
+  instance_id: string;
+  phase: PostRunPhase;
+  actions: PostRunAction[];
+  /** 触发动作的运行结果 */
+  outcome: RunOutcome;
+  /** 倒计时结束的时间（Unix 毫秒） */
+  deadline: number;
+  /** phase 为 executing / action_failed 时的动作序号 */
+  action_index: number | null;
+  error: string | null;
+}
+
+/** 判断启动前步骤的目标已就绪的方式 */
+export type ReadyCheck =
+  /** 设备扫描结果中出现指定地址的 ADB 设备 */
+  | { type: 'adb_device'; address: string }
+  /** 出现类名和标题都匹配正则的窗口（为空表示不限制） */
+  | { type: 'window'; class_regex: string | null; window_regex: string | null };
+
+/** 启动前的准备步骤：启动程序并等待目标就绪（按实例配置，同步到后端） */
+export interface PreRunStep {
+  program: string;
+  args: string[];
+  cwd: string | null;
+  /** 启动后等待目标就绪，null 表示启动后直接继续 */
+  ready: ReadyCheck | null;
+  /** 等待就绪的超时（秒） */
+  timeout_secs: number;
+  /** 目标已就绪时不再启动程序 */
+  skip_if_ready: boolean;
+  /** 就绪后额外等待的时间（秒） */
+  settle_secs: number;
+}
+
+/** 新建步骤的默认值（与后端默认值一致） */
+export const defaultPreRunStep: PreRunStep = {
+  program: '',
+  args: [],
+  cwd: null,
+  ready: null,
+  timeout_secs: 120,
+  skip_if_ready: true,
+  settle_secs: 0,
+};
+
+/** 启动前步骤的执行阶段 */
+export type PreRunPhase =
+  | 'skipped'
+  | 'launching'
+  | 'waiting'
+  | 'ready'
+  | 'failed'
+  | 'cancelled'
+  | 'completed';
+
+/** maa-pre-run 事件载荷 */
+export interface PreRunEvent {
+  instance_id: string;
+  phase: PreRunPhase;
+  /** 当前步骤的序号 */
+  step_index: number | null;
+  /** phase 为 waiting 时的超时时间（Unix 毫秒） */
+  deadline: number | null;
+  error: string | null;
+}
+
+/** 定时执行时查找并连接的设备（由保存的设备和控制器配置生成） */
+export type ScheduleDevice =
+  | { type: 'Adb'; name: string }
+  | {
+      type: 'Win32';
+      window_name: string;
+      class_regex: string | null;
+      window_regex: string | null;
+      screencap_method: number;
+      mouse_method: number;
+      keyboard_method: number;
+    }
+  | {
+      type: 'Gamepad';
+      window_name: string;
+      class_regex: string | null;
+      window_regex: string | null;
+    }
+  | { type: 'PlayCover'; address: string };
+
+/** 定时执行启动任务的参数（与 maa_start_tasks 的参数一致） */
+export interface ScheduleStartRequest {
+  tasks: TaskConfig[];
+  agent_config: AgentConfig | null;
+  cwd: string;
+  tcp_compat_mode: boolean;
+  run_loop: RunLoop;
+  skip_completed: boolean;
+}
+
+/** 实例的定时执行设置（同步到后端，由后端调度执行） */
+export interface InstanceSchedule {
+  policies: SchedulePolicy[];
+  /** 策略时间所在的时区（IANA 名称），null 表示本机时区 */
+  timezone: string | null;
+  /** 没有启用的任务时为 null */
+  request: ScheduleStartRequest | null;
+  /** 未连接时连接的设备，null 时使用最后一次连接的配置 */
+  device: ScheduleDevice | null;
+  /** 未加载资源时加载的资源路径 */
+  resource_paths: string[];
+}
+
+/** 定时执行的阶段 */
+export type SchedulePhase = 'fired' | 'skipped' | 'started' | 'failed';
+
+/** 定时执行未执行的原因 */
+export type ScheduleSkipReason = 'missed' | 'running' | 'no_tasks';
+
+/** maa-schedule 事件载荷 */
+export interface ScheduleEvent {
+  instance_id: string;
+  policy_id: string;
+  policy_name: string;
+  phase: SchedulePhase;
+  /** 计划执行的当地时间（"YYYY-MM-DD HH:MM"） */
+  scheduled_for: string;
+  /** 是否为错过后的补跑 */
+  catch_up: boolean;
+  skip_reason: ScheduleSkipReason | null;
+  error: string | null;
 }
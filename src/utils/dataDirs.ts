@@ -0,0 +1,51 @@
+/**
+ * 数据目录
+ * 便携模式下配置、缓存、日志位于 exe 目录；安装模式（exe 目录只读）下位于用户目录（XDG 等）
+ */
+
+/** get_data_dirs 返回的目录信息 */
+export interface DataDirs {
+  mode: 'portable' | 'installed';
+  exe_dir: string;
+  config_dir: string;
+  cache_dir: string;
+  state_dir: string;
+  logs_dir: string;
+}
+
+// 检测是否在 Tauri 环境中
+const isTauri = () => typeof window !== 'undefined' && '__TAURI__' in window;
+
+// 目录在进程生命周期内不变，只查询一次
+let dataDirsPromise: Promise<DataDirs | null> | null = null;
+
+/**
+ * 获取数据目录（非 Tauri 环境或查询失败时返回 null）
+ * 返回的路径统一使用 `/` 分隔且不带末尾分隔符
+ */
+export function getDataDirs(): Promise<DataDirs | null> {
+  if (!isTauri()) return Promise.resolve(null);
+
+  if (!dataDirsPromise) {
+    dataDirsPromise = (async () => {
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        const dirs = await invoke<DataDirs>('get_data_dirs');
+        const normalize = (p: string) => p.replace(/\\/g, '/').replace(/\/$/, '');
+        return {
+          ...dirs,
+          exe_dir: normalize(dirs.exe_dir),
+          config_dir: normalize(dirs.config_dir),
+          cache_dir: normalize(dirs.cache_dir),
+          state_dir: normalize(dirs.state_dir),
+          logs_dir: normalize(dirs.logs_dir),
+        };
+      } catch (err) {
+        console.warn('[DataDirs] Failed to get data dirs:', err);
+        dataDirsPromise = null;
+        return null;
+      }
+    })();
+  }
+  return dataDirsPromise;
+}
@@ -1,5 +1,6 @@
 export * from './logger';
 export { default as log } from './logger';
-export * from './pipelineOverride';
 export * from './jsonc';
 export * from './optionHelpers';
+export * from './schedule';
+export * from './timeZone';
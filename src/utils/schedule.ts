@@ -0,0 +1,151 @@
+/**
+ * 启动任务参数的构建工具
+ * 手动启动和后端定时执行使用同一份任务配置、Agent 配置、设备和资源路径
+ */
+
+import type {
+  ControllerItem,
+  Instance,
+  ProjectInterface,
+  ResourceItem,
+  SavedDeviceInfo,
+  SelectedTask,
+  TaskItem,
+} from '@/types/interface';
+import type { AgentConfig, InstanceSchedule, ScheduleDevice, TaskConfig } from '@/types/maa';
+import { defaultRunLoop, parseWin32InputMethod, parseWin32ScreencapMethod } from '@/types/maa';
+import { resolveI18nText } from '@/services/contentResolver';
+import { maaService } from '@/services/maaService';
+
+/** 任务的显示名称（自定义名称优先） */
+export function taskDisplayLabel(
+  selectedTask: SelectedTask,
+  taskDef: TaskItem,
+  translations?: Record<string, string>,
+): string {
+  return (
+    selectedTask.customName ||
+    resolveI18nText(taskDef.label, translations) ||
+    selectedTask.taskName
+  );
+}
+
+/**
+ * 已启用任务的配置列表（interface 中不存在的任务会被忽略）
+ * Pipeline 覆盖由后端生成，输入值不符合要求时抛出错误
+ */
+export async function buildTaskConfigs(
+  selectedTasks: SelectedTask[],
+  projectInterface: ProjectInterface | null,
+  translations?: Record<string, string>,
+): Promise<TaskConfig[]> {
+  const taskConfigs: TaskConfig[] = [];
+  for (const selectedTask of selectedTasks) {
+    if (!selectedTask.enabled) continue;
+    const taskDef = projectInterface?.task.find((t) => t.name === selectedTask.taskName);
+    if (!taskDef) continue;
+    taskConfigs.push({
+      entry: taskDef.entry,
+      pipeline_override: await maaService.buildPipelineOverride(
+        selectedTask.taskName,
+        selectedTask.optionValues,
+      ),
+      task_name: selectedTask.taskName,
+      label: taskDisplayLabel(selectedTask, taskDef, translations),
+      source_id: selectedTask.id,
+      ...selectedTask.runPolicy,
+      conditions: selectedTask.conditions,
+    });
+  }
+  return taskConfigs;
+}
+
+/** interface 中声明的 Agent 配置 */
+export function buildAgentConfig(
+  projectInterface: ProjectInterface | null,
+): AgentConfig | undefined {
+  if (!projectInterface?.agent) return undefined;
+  return {
+    child_exec: projectInterface.agent.child_exec,
+    child_args: projectInterface.agent.child_args,
+    identifier: projectInterface.agent.identifier,
+    timeout: projectInterface.agent.timeout,
+  };
+}
+
+/** 资源包的完整路径 */
+export function resolveResourcePaths(resource: ResourceItem, basePath: string): string[] {
+  return resource.path.map((p) => {
+    const cleanPath = p.replace(/^\.\//, '').replace(/^\.\\/, '');
+    return `${basePath}/${cleanPath}`;
+  });
+}
+
+/** 由保存的设备和控制器配置生成后端查找设备的条件，没有保存设备时为 null */
+export function buildScheduleDevice(
+  controller: ControllerItem | undefined,
+  savedDevice: SavedDeviceInfo | undefined,
+): ScheduleDevice | null {
+  if (!controller || !savedDevice) return null;
+
+  if (controller.type === 'Adb' && savedDevice.adbDeviceName) {
+    return { type: 'Adb', name: savedDevice.adbDeviceName };
+  }
+  if (controller.type === 'Win32' && savedDevice.windowName) {
+    return {
+      type: 'Win32',
+      window_name: savedDevice.windowName,
+      class_regex: controller.win32?.class_regex ?? null,
+      window_regex: controller.win32?.window_regex ?? null,
+      screencap_method: parseWin32ScreencapMethod(controller.win32?.screencap || ''),
+      mouse_method: parseWin32InputMethod(controller.win32?.mouse || ''),
+      keyboard_method: parseWin32InputMethod(controller.win32?.keyboard || ''),
+    };
+  }
+  if (controller.type === 'Gamepad' && savedDevice.windowName) {
+    return {
+      type: 'Gamepad',
+      window_name: savedDevice.windowName,
+      class_regex: controller.gamepad?.class_regex ?? null,
+      window_regex: controller.gamepad?.window_regex ?? null,
+    };
+  }
+  if (controller.type === 'PlayCover' && savedDevice.playcoverAddress) {
+    return { type: 'PlayCover', address: savedDevice.playcoverAddress };
+  }
+  return null;
+}
+
+/** 实例的定时执行设置，由后端按策略准备实例并启动任务 */
+export async function buildInstanceSchedule(
+  instance: Instance,
+  options: {
+    projectInterface: ProjectInterface | null;
+    controller: ControllerItem | undefined;
+    resource: ResourceItem | undefined;
+    basePath: string;
+    tcpCompatMode: boolean;
+    translations?: Record<string, string>;
+  },
+): Promise<InstanceSchedule> {
+  const { projectInterface, controller, resource, basePath, tcpCompatMode, translations } =
+    options;
+  const tasks = await buildTaskConfigs(instance.selectedTasks, projectInterface, translations);
+  return {
+    policies: instance.schedulePolicies ?? [],
+    timezone: instance.scheduleTimeZone ?? null,
+    request:
+      tasks.length > 0
+        ? {
+            tasks,
+            agent_config: buildAgentConfig(projectInterface) ?? null,
+            cwd: basePath,
+            tcp_compat_mode: tcpCompatMode,
+            run_loop: instance.runLoop ?? defaultRunLoop,
+            skip_completed: instance.skipCompleted ?? false,
+          }
+        : null,
+    device: buildScheduleDevice(controller, instance.savedDevice),
+    resource_paths: resource ? resolveResourcePaths(resource, basePath) : [],
+  };
+}
@@ -0,0 +1,43 @@
+/**
+ * 定时执行和每日重置使用的时区（IANA 名称，如 Europe/Berlin）
+ */
+
+/** 运行环境不支持列出全部时区时使用的常用时区 */
+const COMMON_TIME_ZONES = [
+  'UTC',
+  'America/Los_Angeles',
+  'America/New_York',
+  'America/Sao_Paulo',
+  'Europe/London',
+  'Europe/Berlin',
+  'Europe/Moscow',
+  'Asia/Kolkata',
+  'Asia/Shanghai',
+  'Asia/Taipei',
+  'Asia/Tokyo',
+  'Asia/Seoul',
+  'Australia/Sydney',
+];
+
+/** 可选的时区，当前设置不在其中时额外列出 */
+export function listTimeZones(current?: string | null): string[] {
+  // supportedValuesOf 不在 ES2020 的类型定义中
+  const intl = Intl as unknown as { supportedValuesOf?: (key: string) => string[] };
+  const zones = intl.supportedValuesOf?.('timeZone') ?? COMMON_TIME_ZONES;
+  if (current && !zones.includes(current)) {
+    return [...zones, current].sort();
+  }
+  return zones;
+}
+
+/**
+ * 旧版本保存的 UTC 偏移（分钟）转换为时区名称
+ * 整点偏移对应 Etc/GMT±h（符号与偏移相反），其他偏移回退到本机时区
+ */
+export function timeZoneFromOffset(minutes: number | null | undefined): string | undefined {
+  if (minutes === null || minutes === undefined || minutes % 60 !== 0) return undefined;
+  const hours = minutes / 60;
+  if (hours === 0) return 'UTC';
+  if (hours < -12 || hours > 14) return undefined;
+  return `Etc/GMT${hours > 0 ? '-' : '+'}${Math.abs(hours)}`;
+}
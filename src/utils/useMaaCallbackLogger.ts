@@ -6,6 +6,13 @@
 import { useEffect, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
 import { maaService, type MaaCallbackDetails } from '@/services/maaService';
+import type {
+  PostRunAction,
+  PostRunEvent,
+  PreRunEvent,
+  ReconnectEvent,
+  ScheduleEvent,
+} from '@/types/maa';
 import { useAppStore, type LogType } from '@/stores/appStore';
 import { loggers } from '@/utils/logger';
 import { getInterfaceLangKey } from '@/i18n';
@@ -148,17 +155,13 @@ export function useMaaCallbackLogger() {
     // 设置回调监听
     const setupListener = async () => {
       try {
-        const unlisten = await maaService.onCallback((message, details) => {
+        const unlisten = await maaService.onCallback((message, details, { instanceId }) => {
           // 组件已卸载则忽略
           if (cancelled) return;
 
-          // 获取当前活动实例 ID
-          const currentActiveId = useAppStore.getState().activeInstanceId;
-          if (!currentActiveId) return;
-
-          // 根据消息类型处理
+          // 根据消息类型处理（日志写入事件所属的实例）
           handleCallback(
-            currentActiveId,
+            instanceId,
             message,
             details as MaaCallbackDetails & Record<string, unknown>,
             t,
@@ -187,6 +190,165 @@ export function useMaaCallbackLogger() {
       }
     };
   }, [t, addLog]);
+
+  // 控制器自动重连进度
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+
+    maaService
+      .onReconnect((event) => {
+        if (cancelled) return;
+        addLog(event.instance_id, formatReconnectLog(event, t));
+      })
+      .then((fn) => {
+        if (cancelled) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      })
+      .catch((err) => {
+        log.error('Failed to setup reconnect listener:', err);
+      });
+
+    return () => {
+      cancelled = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [t, addLog]);
+
+  // 定时执行的触发、跳过和启动结果
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+
+    maaService
+      .onSchedule((event) => {
+        if (cancelled) return;
+        addLog(event.instance_id, formatScheduleLog(event, t));
+      })
+      .then((fn) => {
+        if (cancelled) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      })
+      .catch((err) => {
+        log.error('Failed to setup schedule listener:', err);
+      });
+
+    return () => {
+      cancelled = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [t, addLog]);
+
+  // 启动前准备步骤的进度
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+
+    maaService
+      .onPreRun((event) => {
+        if (cancelled) return;
+        addLog(event.instance_id, formatPreRunLog(event, t));
+      })
+      .then((fn) => {
+        if (cancelled) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      })
+      .catch((err) => {
+        log.error('Failed to setup pre-run listener:', err);
+      });
+
+    return () => {
+      cancelled = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [t, addLog]);
+
+  // 运行结束后动作的倒计时和执行进度
+  useEffect(() => {
+    let cancelled = false;
+    let unlisten: (() => void) | null = null;
+
+    maaService
+      .onPostRun((event) => {
+        if (cancelled) return;
+        addLog(event.instance_id, formatPostRunLog(event, t));
+      })
+      .then((fn) => {
+        if (cancelled) {
+          fn();
+        } else {
+          unlisten = fn;
+        }
+      })
+      .catch((err) => {
+        log.error('Failed to setup post-run listener:', err);
+      });
+
+    return () => {
+      cancelled = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [t, addLog]);
+}
+
+/**
+ * 将 maa-reconnect 事件格式化为日志
+ */
+function formatReconnectLog(
+  event: ReconnectEvent,
+  t: (key: string, options?: Record<string, unknown>) => string,
+): { type: LogType; message: string } {
+  const { attempt, max_attempts: max } = event;
+  const error = event.error ?? '';
+  switch (event.phase) {
+    case 'disconnected':
+      return {
+        type: 'warning',
+        message: t('logs.messages.reconnectDisconnected', { reason: error }),
+      };
+    case 'attempting':
+      return {
+        type: 'info',
+        message: t('logs.messages.reconnectAttempting', {
+          attempt,
+          max,
+          delay: Math.round(event.delay_ms / 100) / 10,
+        }),
+      };
+    case 'attempt_failed':
+      return {
+        type: 'warning',
+        message: t('logs.messages.reconnectAttemptFailed', { attempt, error }),
+      };
+    case 'succeeded':
+      return { type: 'success', message: t('logs.messages.reconnectSucceeded') };
+    case 'resumed':
+      return {
+        type: 'info',
+        message: t('logs.messages.reconnectResumed', { count: event.resumed_tasks.length }),
+      };
+    case 'gave_up':
+      return { type: 'error', message: t('logs.messages.reconnectGaveUp', { max }) };
+    case 'cancelled':
+      return { type: 'info', message: t('logs.messages.reconnectCancelled', { reason: error }) };
+  }
 }
 
 /**
@@ -505,3 +667,97 @@ export function useMaaAgentLogger() {
     };
   }, [addLog]);
 }
+
+/**
+ * 将 maa-schedule 事件格式化为日志
+ */
+function formatScheduleLog(
+  event: ScheduleEvent,
+  t: (key: string, options?: Record<string, unknown>) => string,
+): { type: LogType; message: string } {
+  const params = {
+    policy: event.policy_name,
+    time: event.scheduled_for,
+    error: event.error ?? '',
+  };
+  switch (event.phase) {
+    case 'fired':
+      return {
+        type: 'info',
+        message: t(
+          event.catch_up ? 'logs.messages.scheduleCatchUp' : 'logs.messages.scheduleStarting',
+          params,
+        ),
+      };
+    case 'started':
+      return { type: 'success', message: t('logs.messages.scheduleStarted', params) };
+    case 'failed':
+      return { type: 'error', message: t('logs.messages.scheduleFailed', params) };
+    case 'skipped':
+      return {
+        type: 'warning',
+        message: t(`logs.messages.scheduleSkipped.${event.skip_reason ?? 'missed'}`, params),
+      };
+  }
+}
+
+/**
+ * 将 maa-pre-run 事件格式化为日志
+ */
+function formatPreRunLog(
+  event: PreRunEvent,
+  t: (key: string, options?: Record<string, unknown>) => string,
+): { type: LogType; message: string } {
+  const message = t(`preRun.progress.${event.phase}`, {
+    step: (event.step_index ?? 0) + 1,
+    time: event.deadline ? new Date(event.deadline).toLocaleTimeString() : '',
+    error: event.error ?? '',
+  });
+  switch (event.phase) {
+    case 'failed':
+      return { type: 'error', message };
+    case 'cancelled':
+      return { type: 'warning', message };
+    case 'ready':
+    case 'completed':
+      return { type: 'success', message };
+    default:
+      return { type: 'info', message };
+  }
+}
+
+/**
+ * 将 maa-post-run 事件格式化为日志
+ */
+function formatPostRunLog(
+  event: PostRunEvent,
+  t: (key: string, options?: Record<string, unknown>) => string,
+): { type: LogType; message: string } {
+  const actionName = (action: PostRunAction | undefined) =>
+    action ? t(`postRun.actions.${action.type}`) : '';
+  const action = actionName(event.actions[event.action_index ?? -1]);
+  switch (event.phase) {
+    case 'countdown':
+      return {
+        type: 'info',
+        message: t('logs.messages.postRunCountdown', {
+          seconds: Math.max(0, Math.round((event.deadline - Date.now()) / 1000)),
+          actions: event.actions.map(actionName).join(', '),
+        }),
+      };
+    case 'cancelled':
+      return {
+        type: 'info',
+        message: t('logs.messages.postRunCancelled', { reason: event.error ?? '' }),
+      };
+    case 'executing':
+      return { type: 'info', message: t('logs.messages.postRunExecuting', { action }) };
+    case 'action_failed':
+      return {
+        type: 'error',
+        message: t('logs.messages.postRunActionFailed', { action, error: event.error ?? '' }),
+      };
+    case 'completed':
+      return { type: 'success', message: t('logs.messages.postRunCompleted') };
+  }
+}